@@ -10,7 +10,7 @@ use lodviz_core::core::csv::parse_csv;
 use lodviz_core::core::data::DataPoint;
 use lodviz_core::core::data::WaterfallKind;
 use lodviz_core::core::data::{BarDataset, Dataset, OhlcBar, Series, WaterfallBar};
-use lodviz_core::core::field_value::{DataTable, FieldValue};
+use lodviz_core::core::field_value::DataTable;
 
 /// Fetch a CSV file from `url` and parse it into a [`DataTable`].
 pub async fn fetch_csv(url: &str) -> Result<DataTable, String> {
@@ -37,28 +37,15 @@ use lodviz_core::core::table_data::{ColumnDef, ColumnType, TableData};
 pub async fn fetch_table_data(url: &str) -> Result<TableData, String> {
     let dt = fetch_csv(url).await?;
 
-    // Infer columns from the keys of the first row
-    let mut columns = Vec::new();
-    if let Some(first_row) = dt.rows().first() {
-        for (k, _) in first_row.iter() {
-            columns.push(ColumnDef::new(k, k, ColumnType::Text));
-        }
-    }
+    // Columns in CSV header order
+    let columns: Vec<ColumnDef> = dt
+        .column_names()
+        .into_iter()
+        .map(|k| ColumnDef::new(k, k, ColumnType::Text))
+        .collect();
 
-    let rows = dt
-        .rows()
-        .iter()
-        .map(|row| {
-            let mut row_values = Vec::new();
-            for col in &columns {
-                row_values.push(
-                    row.get(&col.key)
-                        .cloned()
-                        .unwrap_or(lodviz_core::core::field_value::FieldValue::Null),
-                );
-            }
-            row_values
-        })
+    let rows = (0..dt.len())
+        .map(|i| dt.columns().iter().map(|col| col.get(i)).collect())
         .collect();
 
     Ok(TableData::new(columns, rows))
@@ -70,10 +57,9 @@ pub async fn fetch_table_data(url: &str) -> Result<TableData, String> {
 pub fn to_xy_dataset(table: &DataTable, x_col: &str, y_col: &str, name: &str) -> Dataset {
     let points: Vec<DataPoint> = table
         .rows()
-        .iter()
         .filter_map(|row| {
-            let x = row.get(x_col)?.as_f64()?;
-            let y = row.get(y_col)?.as_f64()?;
+            let x = row.get_f64(x_col)?;
+            let y = row.get_f64(y_col)?;
             Some(DataPoint::new(x, y))
         })
         .collect();
@@ -88,10 +74,9 @@ pub fn to_multi_dataset(table: &DataTable, x_col: &str, series_names: &[(&str, &
     for (y_col, name) in series_names {
         let points: Vec<DataPoint> = table
             .rows()
-            .iter()
             .filter_map(|row| {
-                let x = row.get(x_col)?.as_f64()?;
-                let y = row.get(*y_col)?.as_f64()?;
+                let x = row.get_f64(x_col)?;
+                let y = row.get_f64(y_col)?;
                 Some(DataPoint::new(x, y))
             })
             .collect();
@@ -122,10 +107,9 @@ pub fn to_grouped_bar(
 pub fn to_pie_entries(table: &DataTable, label_col: &str, value_col: &str) -> Vec<PieEntry> {
     table
         .rows()
-        .iter()
         .filter_map(|row| {
-            let label = row.get(label_col)?.as_str()?.to_owned();
-            let value = row.get(value_col)?.as_f64()?;
+            let label = row.get_str(label_col)?.to_owned();
+            let value = row.get_f64(value_col)?;
             Some(PieEntry { label, value })
         })
         .collect()
@@ -159,13 +143,12 @@ pub fn to_ohlc(
 ) -> Vec<OhlcBar> {
     table
         .rows()
-        .iter()
         .filter_map(|row| {
-            let idx = row.get(idx_col)?.as_f64()?;
-            let open = row.get(open_col)?.as_f64()?;
-            let high = row.get(high_col)?.as_f64()?;
-            let low = row.get(low_col)?.as_f64()?;
-            let close = row.get(close_col)?.as_f64()?;
+            let idx = row.get_f64(idx_col)?;
+            let open = row.get_f64(open_col)?;
+            let high = row.get_f64(high_col)?;
+            let low = row.get_f64(low_col)?;
+            let close = row.get_f64(close_col)?;
             Some(OhlcBar::new(idx, open, high, low, close))
         })
         .collect()
@@ -178,12 +161,11 @@ pub fn to_ohlc(
 pub fn to_radar_series(table: &DataTable, name_col: &str, axis_cols: &[&str]) -> Vec<RadarSeries> {
     table
         .rows()
-        .iter()
         .filter_map(|row| {
-            let name = row.get(name_col)?.as_str()?.to_owned();
+            let name = row.get_str(name_col)?.to_owned();
             let values: Vec<f64> = axis_cols
                 .iter()
-                .map(|col| row.get(*col).and_then(FieldValue::as_f64).unwrap_or(0.0))
+                .map(|col| row.get_f64(col).unwrap_or(0.0))
                 .collect();
             if values.is_empty() {
                 None
@@ -205,11 +187,10 @@ pub fn to_waterfall_bars(
 ) -> Vec<WaterfallBar> {
     table
         .rows()
-        .iter()
         .filter_map(|row| {
-            let label = row.get(label_col)?.as_str()?.to_owned();
-            let value = row.get(value_col)?.as_f64()?;
-            let kind = match row.get(kind_col)?.as_str()? {
+            let label = row.get_str(label_col)?.to_owned();
+            let value = row.get_f64(value_col)?;
+            let kind = match row.get_str(kind_col)? {
                 "start" => WaterfallKind::Start,
                 "total" => WaterfallKind::Total,
                 _ => WaterfallKind::Delta,
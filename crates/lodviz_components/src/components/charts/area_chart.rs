@@ -11,14 +11,14 @@ use lodviz_core::algorithms::lttb::lttb_downsample;
 use lodviz_core::core::a11y;
 use lodviz_core::core::data::{DataPoint, Dataset};
 use lodviz_core::core::mark::Mark;
-use lodviz_core::core::scale::{LinearScale, Scale};
+use lodviz_core::core::scale::{AxisScale, Scale, ScaleType};
 use lodviz_core::core::theme::{ChartConfig, GridStyle};
 
 /// Generate SVG path `d` attribute for line + closed area fill
 fn generate_area_path(
     points: &[DataPoint],
-    x_scale: &LinearScale,
-    y_scale: &LinearScale,
+    x_scale: &AxisScale,
+    y_scale: &AxisScale,
     baseline_y: f64,
 ) -> String {
     if points.is_empty() {
@@ -49,11 +49,7 @@ fn generate_area_path(
 }
 
 /// Generate SVG path `d` for just the line (no fill close)
-fn generate_line_path(
-    points: &[DataPoint],
-    x_scale: &LinearScale,
-    y_scale: &LinearScale,
-) -> String {
+fn generate_line_path(points: &[DataPoint], x_scale: &AxisScale, y_scale: &AxisScale) -> String {
     if points.is_empty() {
         return String::from("M 0 0");
     }
@@ -189,6 +185,8 @@ pub fn AreaChart(
         Memo::new(move |_| chart_height.get() as f64 - margin.get().top - margin.get().bottom);
 
     let final_title = Memo::new(move |_| config.get().title.or(title.clone()));
+    let x_format = Memo::new(move |_| config.get().x_number_format());
+    let y_format = Memo::new(move |_| config.get().y_number_format());
     let grid_style = Memo::new(move |_| {
         config.get().grid.unwrap_or_else(|| {
             let th = theme.get();
@@ -212,19 +210,19 @@ pub fn AreaChart(
         let mut y_min = f64::INFINITY;
         let mut y_max = f64::NEG_INFINITY;
 
+        let cfg = config.get();
+        let (x_kind, y_kind) = (cfg.x_scale_type(), cfg.y_scale_type());
+
+        // Log axes cannot show values <= 0: leave them out of the extent
         let all_points = series.iter().flat_map(|(_, pts)| pts.iter());
         for p in all_points {
-            if p.x < x_min {
-                x_min = p.x;
+            if x_kind != ScaleType::Log || p.x > 0.0 {
+                x_min = x_min.min(p.x);
+                x_max = x_max.max(p.x);
             }
-            if p.x > x_max {
-                x_max = p.x;
-            }
-            if p.y < y_min {
-                y_min = p.y;
-            }
-            if p.y > y_max {
-                y_max = p.y;
+            if y_kind != ScaleType::Log || p.y > 0.0 {
+                y_min = y_min.min(p.y);
+                y_max = y_max.max(p.y);
             }
         }
 
@@ -237,10 +235,21 @@ pub fn AreaChart(
             y_max = 1.0;
         }
 
-        // Add minimal padding to Y to avoid cutting off peaks
-        let y_pad = (y_max - y_min) * 0.05;
+        // Add minimal padding to Y to avoid cutting off peaks (log axes
+        // get room from niceing to whole decades instead)
+        let y_pad = if y_kind == ScaleType::Log {
+            0.0
+        } else {
+            (y_max - y_min) * 0.05
+        };
+
+        // Fixed domains from config (e.g. shared across facets) win
+        let (x_min, x_max) = cfg.x_domain.unwrap_or((x_min, x_max));
+        let (y_min, y_max) = cfg
+            .y_domain
+            .unwrap_or_else(|| y_kind.valid_domain((y_min - y_pad, y_max + y_pad)));
 
-        ZoomTransform::from_domain(x_min, x_max, y_min - y_pad, y_max + y_pad)
+        ZoomTransform::from_domain(x_min, x_max, y_min, y_max)
     });
 
     // Zoom state
@@ -256,13 +265,19 @@ pub fn AreaChart(
     let x_scale = Memo::new(move |_| {
         let t = zoom_transform.get();
         let w = inner_width.get();
-        LinearScale::new((t.x_min, t.x_max), (0.0, w))
+        config.get().x_axis_scale((t.x_min, t.x_max), (0.0, w))
     });
 
     let y_scale = Memo::new(move |_| {
         let t = zoom_transform.get();
         let h = inner_height.get();
-        LinearScale::new((t.y_min, t.y_max), (h, 0.0))
+        config.get().y_axis_scale((t.y_min, t.y_max), (h, 0.0))
+    });
+
+    // Axis scale kinds, so box zoom follows log / sqrt / symlog axes
+    let scale_types = Signal::derive(move || {
+        let cfg = config.get();
+        (cfg.x_scale_type(), cfg.y_scale_type())
     });
 
     let x_tick_count = Memo::new(move |_| (inner_width.get() / 100.0).max(2.0) as usize);
@@ -344,10 +359,9 @@ pub fn AreaChart(
         let Some(hover_signal) = dash_hover_x else {
             return;
         };
-        let domain_x = cursor_norm.get().map(|(norm_x, _)| {
-            let t = zoom_transform.get();
-            t.x_min + norm_x * (t.x_max - t.x_min)
-        });
+        let domain_x = cursor_norm
+            .get()
+            .map(|(norm_x, _)| x_scale.get().inverse(norm_x * inner_width.get()));
         hover_signal.set(domain_x);
     });
 
@@ -604,6 +618,7 @@ pub fn AreaChart(
                                         stroke=theme.get().axis_color
                                         font_size=theme.get().axis_font_size
                                         label=x_label_clone.clone()
+                                        format=x_format.get()
                                     />
                                 }
                             }}
@@ -619,6 +634,7 @@ pub fn AreaChart(
                                     stroke=theme.get().axis_color
                                     font_size=theme.get().axis_font_size
                                     label=y_label_clone.clone()
+                                    format=y_format.get()
                                 />
                             }
                         }}
@@ -651,6 +667,8 @@ pub fn AreaChart(
                             inner_width=inner_width
                             inner_height=inner_height
                             cursor_normalized_x=cursor_x
+                            x_format=x_format
+                            y_format=y_format
                         />
 
                         // Zoom/Pan overlay
@@ -660,6 +678,7 @@ pub fn AreaChart(
                             inner_width=inner_width
                             inner_height=inner_height
                             set_cursor=set_cursor_norm
+                            scale_types=scale_types
                         />
 
                         // SVG Legend overlay (must be last to render on top)
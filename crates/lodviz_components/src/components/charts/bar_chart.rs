@@ -9,7 +9,7 @@ use lodviz_core::algorithms::stack::stack_series;
 use lodviz_core::core::a11y;
 use lodviz_core::core::data::{BarDataset, BarSeries};
 use lodviz_core::core::mark::Mark;
-use lodviz_core::core::scale::{BandScale, LinearScale, Scale};
+use lodviz_core::core::scale::{AxisScale, BandScale, LinearScale, Scale, ScaleType, NICE_TICKS};
 use lodviz_core::core::theme::{ChartConfig, GridStyle};
 
 /// Bar orientation
@@ -134,6 +134,7 @@ pub fn BarChart(
         Memo::new(move |_| chart_height.get() as f64 - margin.get().top - margin.get().bottom);
 
     let final_title = Memo::new(move |_| config.get().title.or(title.clone()));
+    let y_format = Memo::new(move |_| config.get().y_number_format());
     let grid_style = Memo::new(move |_| {
         config.get().grid.unwrap_or_else(|| {
             let th = theme.get();
@@ -159,7 +160,7 @@ pub fn BarChart(
         BandScale::new(d.categories.clone(), range, 0.2)
     });
 
-    // Value scale (linear) computed from all visible series
+    // Value scale (`ChartConfig::y_scale` kind) computed from all visible series
     let value_scale = Memo::new(move |_| {
         let d = data.get();
         let vis = series_visibility.get();
@@ -193,9 +194,27 @@ pub fn BarChart(
 
         let max_val = if max_val <= 0.0 { 1.0 } else { max_val * 1.1 }; // 10% padding
 
+        // Fixed value domain from config (e.g. shared across facets) wins; a
+        // log axis starts at the decade below the smallest bar instead of 0
+        let cfg = config.get();
+        let kind = cfg.y_scale_type();
+        let (min_val, max_val) = cfg.y_domain.unwrap_or_else(|| {
+            if kind == ScaleType::Log {
+                let min_positive = d
+                    .series
+                    .iter()
+                    .flat_map(|s| s.values.iter().copied())
+                    .filter(|&v| v > 0.0)
+                    .fold(f64::INFINITY, f64::min);
+                kind.nice_domain((min_positive, max_val), NICE_TICKS)
+            } else {
+                (0.0, max_val)
+            }
+        });
+
         match orientation {
-            BarOrientation::Vertical => LinearScale::new((0.0, max_val), (range_size, 0.0)),
-            BarOrientation::Horizontal => LinearScale::new((0.0, max_val), (0.0, range_size)),
+            BarOrientation::Vertical => cfg.y_axis_scale((min_val, max_val), (range_size, 0.0)),
+            BarOrientation::Horizontal => cfg.y_axis_scale((min_val, max_val), (0.0, range_size)),
         }
     });
 
@@ -308,9 +327,11 @@ pub fn BarChart(
                                 let (x_s, y_s) = match orientation {
                                     BarOrientation::Vertical => {
                                         (
-                                            LinearScale::new(
-                                                (0.0, inner_width.get()),
-                                                (0.0, inner_width.get()),
+                                            AxisScale::Linear(
+                                                LinearScale::new(
+                                                    (0.0, inner_width.get()),
+                                                    (0.0, inner_width.get()),
+                                                ),
                                             ),
                                             vs,
                                         )
@@ -318,9 +339,11 @@ pub fn BarChart(
                                     BarOrientation::Horizontal => {
                                         (
                                             vs,
-                                            LinearScale::new(
-                                                (0.0, inner_height.get()),
-                                                (0.0, inner_height.get()),
+                                            AxisScale::Linear(
+                                                LinearScale::new(
+                                                    (0.0, inner_height.get()),
+                                                    (0.0, inner_height.get()),
+                                                ),
                                             ),
                                         )
                                     }
@@ -347,6 +370,11 @@ pub fn BarChart(
                             let bs = band_scale.get();
                             let vs = value_scale.get();
                             let th = theme.get();
+                            let value_format = y_format.get();
+                            let format_value = |v: f64| match &value_format {
+                                Some(format) => format.format(v),
+                                None => format!("{v:.1}"),
+                            };
                             let visible_series: Vec<(usize, &BarSeries)> = d
                                 .series
                                 .iter()
@@ -370,7 +398,7 @@ pub fn BarChart(
                                                 .get(ci)
                                                 .map(|s| s.as_str())
                                                 .unwrap_or("");
-                                            let label = format!("{cat}: {} = {val:.1}", series.name);
+                                            let label = format!("{cat}: {} = {}", series.name, format_value(val));
                                             let (rx, ry, rw, rh) = match orientation {
                                                 BarOrientation::Vertical => {
                                                     let x = bs.map_index(ci) + vi as f64 * sub_band_width;
@@ -413,7 +441,7 @@ pub fn BarChart(
                                                 .map(|s| s.as_str())
                                                 .unwrap_or("");
                                             let val = sv.y1 - sv.y0;
-                                            let label = format!("{cat}: {series_name} = {val:.1}");
+                                            let label = format!("{cat}: {series_name} = {}", format_value(val));
                                             let (rx, ry, rw, rh) = match orientation {
                                                 BarOrientation::Vertical => {
                                                     let x = bs.map_index(ci);
@@ -509,6 +537,7 @@ pub fn BarChart(
                                             stroke=theme.get().axis_color
                                             font_size=theme.get().axis_font_size
                                             label=y_label_clone.clone()
+                                            format=y_format.get()
                                         />
                                     }
                                         .into_any()
@@ -527,6 +556,7 @@ pub fn BarChart(
                                                 stroke=theme.get().axis_color
                                                 font_size=theme.get().axis_font_size
                                                 label=x_label_clone.clone()
+                                                format=y_format.get()
                                             />
                                         </g>
                                     }
@@ -544,6 +574,7 @@ pub fn BarChart(
                             orientation=orientation
                             mode=mode
                             margin=margin
+                            value_format=y_format
                         /> // SVG Legend overlay (must be last to render on top)
                         {move || {
                             show_legend
@@ -469,6 +469,7 @@ pub fn BoxPlot(
                                     stroke=theme.get().axis_color
                                     font_size=theme.get().axis_font_size
                                     label=y_label_clone.clone()
+                                    format=config.get().y_number_format()
                                 />
                             }
                         }} // Tooltip overlay (last = captures mouse events above chart content)
@@ -892,6 +893,7 @@ pub fn ViolinChart(
                                     stroke=theme.get().axis_color
                                     font_size=theme.get().axis_font_size
                                     label=y_label_clone.clone()
+                                    format=config.get().y_number_format()
                                 />
                             }
                         }} // Tooltip overlay (last = captures mouse events above chart content)
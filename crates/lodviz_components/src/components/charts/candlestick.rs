@@ -254,6 +254,7 @@ pub fn CandlestickChart(
                                         stroke=theme.get().axis_color
                                         font_size=theme.get().axis_font_size
                                         label=x_label_clone.clone()
+                                        format=config.get().x_number_format()
                                     />
                                 }
                             }}
@@ -269,6 +270,7 @@ pub fn CandlestickChart(
                                     stroke=theme.get().axis_color
                                     font_size=theme.get().axis_font_size
                                     label=y_label_clone.clone()
+                                    format=config.get().y_number_format()
                                 />
                             }
                         }} // Tooltip overlay (last = captures mouse events above chart content)
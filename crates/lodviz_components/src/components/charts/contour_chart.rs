@@ -274,6 +274,7 @@ pub fn ContourChart(
                                             tick_count=5
                                             text_color=th.text_color.clone()
                                             font_size=th.axis_font_size
+                                            format=config.get().y_number_format()
                                         />
                                     </g>
                                 },
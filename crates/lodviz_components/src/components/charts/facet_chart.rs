@@ -0,0 +1,192 @@
+/// FacetChart — small multiples of one chart spec.
+///
+/// Renders a `FacetSpec`: the table is split by the `row` / `column` fields in
+/// `lodviz_core` and every panel is drawn by `SmartChart` on a CSS grid, with
+/// a header naming its facet values. Panels share their x/y domains unless
+/// the spec resolves a scale as independent, and a single legend above the
+/// grid replaces the per-panel legends (series colors match across panels).
+use crate::components::charts::smart_chart::{ChartError, SmartChart};
+use leptos::prelude::*;
+use lodviz_core::core::facet::FacetSpec;
+use lodviz_core::core::theme::ChartConfig;
+
+/// Grid of facet panels with per-panel headers and a shared legend.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let spec = FacetSpec::new(
+///     ChartSpec::builder()
+///         .from_table(sales)
+///         .mark(Mark::Line)
+///         .x(Field::temporal("date"))
+///         .y(Field::quantitative("amount"))
+///         .color(Field::nominal("product"))
+///         .build(),
+/// )
+/// .with_column(Field::nominal("region"))
+/// .with_wrap(3);
+///
+/// view! { <FacetChart spec=Signal::derive(move || spec.clone()) /> }
+/// ```
+///
+/// An invalid spec renders a `ChartError` panel with the `SpecError` message.
+#[component]
+pub fn FacetChart(
+    /// Reactive facet specification
+    spec: Signal<FacetSpec>,
+    /// Height of each panel in pixels
+    #[prop(default = 240)]
+    panel_height: u32,
+    /// Override chart config (uses the inner spec's config when default)
+    #[prop(default = Signal::derive(|| ChartConfig::default()), into)]
+    config: Signal<ChartConfig>,
+) -> impl IntoView {
+    let resolved_config = Signal::derive(move || {
+        let external = config.get();
+        if external.title.is_some() || external.theme.is_some() {
+            external
+        } else {
+            spec.get().spec.config
+        }
+    });
+    let theme = Memo::new(move |_| resolved_config.get().theme.unwrap_or_default());
+    // Resolve once per spec change. Panel specs are not comparable, so every
+    // recomputation counts as a change.
+    let grid = Memo::new_with_compare(move |_| spec.get().resolve(), |_, _| true);
+    // Memo on the message so data updates don't rebuild a valid grid
+    let error = Memo::new(move |_| grid.get().err().map(|e| e.to_string()));
+
+    let legend_view = move || {
+        let Ok(g) = grid.get() else {
+            return ().into_any();
+        };
+        let show = resolved_config
+            .get()
+            .show_legend
+            .unwrap_or(g.series.len() > 1);
+        if !show {
+            return ().into_any();
+        }
+        let th = theme.get();
+        let items = g
+            .series
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let color = th.palette[i % th.palette.len()].clone();
+                view! {
+                    <span style="display: inline-flex; align-items: center; gap: 4px;">
+                        <span style=format!(
+                            "width: 10px; height: 10px; border-radius: 2px; background-color: {color};",
+                        )></span>
+                        {name.clone()}
+                    </span>
+                }
+            })
+            .collect_view();
+        view! {
+            <div
+                class="facet-legend"
+                style=format!(
+                    "display: flex; flex-wrap: wrap; justify-content: center; gap: 12px; padding: 4px 0; font-size: {}px; font-family: {}; color: {};",
+                    th.axis_font_size,
+                    th.font_family,
+                    th.text_color,
+                )
+            >
+                {items}
+            </div>
+        }
+        .into_any()
+    };
+
+    let panels_view = move || {
+        let Ok(g) = grid.get() else {
+            return ().into_any();
+        };
+        let th = theme.get();
+        let cells = g
+            .panels
+            .into_iter()
+            .map(|panel| {
+                let header = panel.header();
+                let mut panel_spec = panel.spec;
+                panel_spec.config.theme = Some(th.clone());
+                panel_spec.config.height = Some(panel_height);
+                let panel_spec = Signal::derive(move || panel_spec.clone());
+                view! {
+                    <div style=format!(
+                        "grid-row: {}; grid-column: {}; display: flex; flex-direction: column; min-width: 0;",
+                        panel.row + 1,
+                        panel.column + 1,
+                    )>
+                        <div style=format!(
+                            "text-align: center; font-size: {}px; font-family: {}; color: {}; font-weight: 600; padding: 2px 0;",
+                            th.axis_font_size,
+                            th.font_family,
+                            th.text_color,
+                        )>{header}</div>
+                        <div style=format!("height: {panel_height}px; position: relative;")>
+                            <SmartChart spec=panel_spec height=panel_height />
+                        </div>
+                    </div>
+                }
+            })
+            .collect_view();
+        view! {
+            <div
+                class="facet-grid"
+                style=format!(
+                    "display: grid; grid-template-columns: repeat({}, minmax(0, 1fr)); gap: 8px;",
+                    g.columns.max(1),
+                )
+            >
+                {cells}
+            </div>
+        }
+        .into_any()
+    };
+
+    view! {
+        {move || {
+            if let Some(message) = error.get() {
+                return view! { <ChartError message=message config=resolved_config /> }.into_any();
+            }
+            view! {
+                <div
+                    class="facet-chart"
+                    style=move || {
+                        format!(
+                            "width: 100%; display: flex; flex-direction: column; background-color: {};",
+                            theme.get().background_color,
+                        )
+                    }
+                >
+                    {move || {
+                        resolved_config
+                            .get()
+                            .title
+                            .map(|t| {
+                                let th = theme.get();
+                                view! {
+                                    <h3 style=format!(
+                                        "text-align: center; margin: 0; padding-top: {}px; padding-bottom: {}px; font-size: {}px; font-family: {}; color: {}; font-weight: {};",
+                                        th.title_padding_top,
+                                        th.title_padding_bottom,
+                                        th.title_font_size,
+                                        th.font_family,
+                                        th.text_color,
+                                        th.title_font_weight,
+                                    )>{t}</h3>
+                                }
+                            })
+                    }}
+                    {legend_view}
+                    {panels_view}
+                </div>
+            }
+                .into_any()
+        }}
+    }
+}
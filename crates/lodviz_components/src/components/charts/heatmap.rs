@@ -5,12 +5,14 @@ use crate::hooks::use_container_size;
 use leptos::prelude::*;
 use lodviz_core::core::color_map::{ColorMap, SequentialColorMap};
 use lodviz_core::core::data::GridData;
+use lodviz_core::core::scale::ThresholdScale;
 use lodviz_core::core::theme::ChartConfig;
 
 /// HeatmapChart: renders a 2-D grid as colored rectangles using a continuous ColorMap.
 ///
 /// Features:
-/// - Configurable color map (any `ColorMap` variant)
+/// - Configurable color map (any `ColorMap` variant), or discrete color
+///   classes from a `ThresholdScale` (choropleth style)
 /// - Optional cell value labels
 /// - Optional ColorBar legend
 /// - Hover tooltip (row, col, value)
@@ -22,6 +24,10 @@ pub fn HeatmapChart(
     /// Color map for encoding values as colors
     #[prop(default = ColorMap::Sequential(SequentialColorMap::Viridis))]
     color_map: ColorMap,
+    /// Discrete color classes (e.g. from a `QuantizeScale` or
+    /// `QuantileScale`); replaces `color_map` and the color bar
+    #[prop(optional)]
+    color_bins: Option<ThresholdScale<String>>,
     /// Show numeric value labels inside each cell
     #[prop(default = false)]
     show_values: bool,
@@ -57,6 +63,7 @@ pub fn HeatmapChart(
     });
 
     let final_title = Memo::new(move |_| config.get().title);
+    let value_format = Memo::new(move |_| config.get().y_number_format());
 
     // Colorbar width in margin right
     let colorbar_w = if show_colorbar { 60.0 } else { 0.0 };
@@ -119,6 +126,7 @@ pub fn HeatmapChart(
 
     let color_map_clone = color_map.clone();
     let color_map_colorbar = color_map.clone();
+    let bins_cells = color_bins.clone();
     let clip_id = format!("heatmap-clip-{}", uuid::Uuid::new_v4().simple());
 
     let iw_signal = Signal::derive(move || inner_width.get());
@@ -192,18 +200,23 @@ pub fn HeatmapChart(
                             let cell_h = ih / nrows as f64;
                             let th = theme.get();
                             let cm = color_map_clone.clone();
+                            let bins = bins_cells.clone();
                             g.values
                                 .iter()
                                 .enumerate()
                                 .flat_map(|(row, row_vals)| {
                                     let cm_row = cm.clone();
+                                    let bins_row = bins.clone();
                                     let tc_row = th.text_color.clone();
                                     row_vals
                                         .iter()
                                         .enumerate()
                                         .map(move |(col, &val)| {
                                             let t = (val - min) / range;
-                                            let fill = cm_row.map(t);
+                                            let fill = bins_row
+                                                .as_ref()
+                                                .and_then(|b| b.map(val).cloned())
+                                                .unwrap_or_else(|| cm_row.map(t));
                                             let x = col as f64 * cell_w;
                                             let y = row as f64 * cell_h;
                                             let label = if show_values {
@@ -336,6 +349,21 @@ pub fn HeatmapChart(
                             let ih = inner_height.get();
                             let iw = inner_width.get();
                             let th = theme.get();
+                            if let Some(bins) = color_bins.clone() {
+                                return Some(
+                                    view! {
+                                        <g transform=format!("translate({:.2}, 0)", iw + 10.0)>
+                                            <BinLegend
+                                                bins=bins
+                                                height=ih
+                                                text_color=th.text_color.clone()
+                                                font_size=th.axis_font_size
+                                            />
+                                        </g>
+                                    }
+                                        .into_any(),
+                                );
+                            }
                             Some(
                                 view! {
                                     <g transform=format!("translate({:.2}, 0)", iw + 10.0)>
@@ -348,9 +376,11 @@ pub fn HeatmapChart(
                                             tick_count=5
                                             text_color=th.text_color.clone()
                                             font_size=th.axis_font_size
+                                            format=value_format.get()
                                         />
                                     </g>
-                                },
+                                }
+                                    .into_any(),
                             )
                         }}
 
@@ -364,6 +394,7 @@ pub fn HeatmapChart(
                             inner_width=iw_signal
                             inner_height=ih_signal
                             text_color="white".to_string()
+                            value_format=value_format
                         />
                     </g>
                 </svg>
@@ -371,3 +402,54 @@ pub fn HeatmapChart(
         </div>
     }
 }
+
+/// Stacked swatches for discrete color classes, highest class on top,
+/// labelled with the thresholds between them
+#[component]
+fn BinLegend(
+    bins: ThresholdScale<String>,
+    height: f64,
+    text_color: String,
+    font_size: f64,
+) -> impl IntoView {
+    let n = bins.range().len().max(1);
+    let swatch_h = height / n as f64;
+    let swatches = bins
+        .range()
+        .iter()
+        .enumerate()
+        .map(|(i, color)| {
+            let y = (n - 1 - i) as f64 * swatch_h;
+            view! {
+                <rect
+                    x="0"
+                    y=format!("{y:.2}")
+                    width="14"
+                    height=format!("{swatch_h:.2}")
+                    fill=color.clone()
+                />
+            }
+        })
+        .collect_view();
+    let labels = bins
+        .thresholds()
+        .iter()
+        .take(n - 1)
+        .enumerate()
+        .map(|(i, t)| {
+            let y = (n - 1 - i) as f64 * swatch_h;
+            view! {
+                <text
+                    x="18"
+                    y=format!("{y:.2}")
+                    dominant-baseline="middle"
+                    font-size=font_size
+                    fill=text_color.clone()
+                >
+                    {format!("{t:.2}")}
+                </text>
+            }
+        })
+        .collect_view();
+    view! { <g class="heatmap-bins">{swatches} {labels}</g> }
+}
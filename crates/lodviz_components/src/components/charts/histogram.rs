@@ -4,7 +4,7 @@ use crate::components::svg::grid::Grid;
 use crate::hooks::use_container_size;
 use leptos::prelude::*;
 use lodviz_core::algorithms::statistics::{histogram_bins, BinRule};
-use lodviz_core::core::scale::{LinearScale, Scale};
+use lodviz_core::core::scale::{Scale, ScaleType, NICE_TICKS};
 use lodviz_core::core::theme::ChartConfig;
 
 /// Histogram chart for continuous data
@@ -60,6 +60,8 @@ pub fn Histogram(
         Memo::new(move |_| chart_height.get() as f64 - margin.get().top - margin.get().bottom);
 
     let final_title = Memo::new(move |_| config.get().title.clone());
+    let x_format = Memo::new(move |_| config.get().x_number_format());
+    let y_format = Memo::new(move |_| config.get().y_number_format());
 
     let a11y_title_id = format!("chart-title-{}", uuid::Uuid::new_v4().as_simple());
     let a11y_desc_id = format!("chart-desc-{}", uuid::Uuid::new_v4().as_simple());
@@ -75,13 +77,21 @@ pub fn Histogram(
         } else {
             (bs[0].x0, bs[bs.len() - 1].x1)
         };
-        LinearScale::new((x0, x1), (0.0, inner_width.get()))
+        config
+            .get()
+            .x_axis_scale((x0, x1), (0.0, inner_width.get()))
     });
 
     let y_scale = Memo::new(move |_| {
+        let cfg = config.get();
         let max_count = bins.get().iter().map(|b| b.count).max().unwrap_or(1);
         let max_val = (max_count as f64 * 1.1).max(1.0);
-        LinearScale::new((0.0, max_val), (inner_height.get(), 0.0))
+        // Counts on a log axis start at 1 (empty bins stay flat)
+        let domain = match cfg.y_scale_type() {
+            ScaleType::Log => ScaleType::Log.nice_domain((1.0, max_val), NICE_TICKS),
+            _ => (0.0, max_val),
+        };
+        cfg.y_axis_scale(domain, (inner_height.get(), 0.0))
     });
 
     let x_tick_count = Memo::new(move |_| (inner_width.get() / 80.0).max(2.0) as usize);
@@ -163,6 +173,7 @@ pub fn Histogram(
                                 .cloned()
                                 .unwrap_or_else(|| "#5470c6".to_string());
                             let ih = inner_height.get();
+                            let bound_format = x_format.get();
                             bs.iter()
                                 .map(|b| {
                                     let x0_px = xs.map(b.x0);
@@ -170,10 +181,14 @@ pub fn Histogram(
                                     let bar_w = (x1_px - x0_px - 1.0).max(0.5);
                                     let bar_h = ih - ys.map(b.count as f64);
                                     let bar_y = ys.map(b.count as f64);
+                                    let bound = |v: f64| match &bound_format {
+                                        Some(format) => format.format(v),
+                                        None => format!("{v:.2}"),
+                                    };
                                     let tooltip_text = format!(
-                                        "[{:.2}, {:.2}) — {} points",
-                                        b.x0,
-                                        b.x1,
+                                        "[{}, {}) — {} points",
+                                        bound(b.x0),
+                                        bound(b.x1),
                                         b.count,
                                     );
                                     let tt = tooltip_text.clone();
@@ -238,6 +253,7 @@ pub fn Histogram(
                                         stroke=theme.get().axis_color
                                         font_size=theme.get().axis_font_size
                                         label=x_label_clone.clone()
+                                        format=x_format.get()
                                     />
                                 }
                             }}
@@ -253,6 +269,7 @@ pub fn Histogram(
                                     stroke=theme.get().axis_color
                                     font_size=theme.get().axis_font_size
                                     label=y_label_clone.clone()
+                                    format=y_format.get()
                                 />
                             }
                         }}
@@ -0,0 +1,437 @@
+/// LayeredChart — several marks drawn over one pair of shared scales.
+///
+/// Renders a `LayerSpec`: each layer (line, area, points, rules, text
+/// labels) is resolved in `lodviz_core` and drawn in order against the
+/// combined x/y domain, so e.g. a threshold rule lines up with the series
+/// without duplicating any scale logic. Series with the same name share a
+/// color across layers and toggle together from the legend.
+use crate::components::charts::smart_chart::ChartError;
+use crate::components::svg::axis::{Axis, AxisOrientation};
+use crate::components::svg::grid::Grid;
+use crate::components::svg::legend::{estimate_legend_width, Legend, LegendItem, LegendPosition};
+use crate::hooks::use_container_size;
+use leptos::prelude::*;
+use lodviz_core::core::data::DataPoint;
+use lodviz_core::core::layer::{LayerGeometry, LayerSpec, RuleAxis};
+use lodviz_core::core::mark::Mark;
+use lodviz_core::core::scale::{LinearScale, Scale};
+use lodviz_core::core::theme::ChartConfig;
+
+/// SVG path through `points`; closed down to `baseline` for areas
+fn path_data(
+    points: &[DataPoint],
+    xs: &LinearScale,
+    ys: &LinearScale,
+    baseline: Option<f64>,
+) -> String {
+    let mut d = String::with_capacity(points.len() * 16);
+    for (i, p) in points.iter().enumerate() {
+        let cmd = if i == 0 { 'M' } else { 'L' };
+        d.push_str(&format!("{cmd} {:.2} {:.2} ", xs.map(p.x), ys.map(p.y)));
+    }
+    if let (Some(base), Some(first), Some(last)) = (baseline, points.first(), points.last()) {
+        let y0 = ys.map(base);
+        d.push_str(&format!(
+            "L {:.2} {y0:.2} L {:.2} {y0:.2} Z",
+            xs.map(last.x),
+            xs.map(first.x)
+        ));
+    }
+    d
+}
+
+/// Layered chart over shared x/y scales and axes.
+///
+/// # Usage
+///
+/// ```rust,ignore
+/// let spec = LayerSpec::new()
+///     .with_table(sales)
+///     .with_layer(Layer::new(Mark::Line).with_x(Field::quantitative("day")).with_y(Field::quantitative("amount")))
+///     .with_layer(Layer::new(Mark::Rule).with_table(targets).with_y(Field::quantitative("goal")));
+///
+/// view! { <LayeredChart spec=Signal::derive(move || spec.clone()) /> }
+/// ```
+///
+/// An invalid spec renders a `ChartError` panel naming the failing layer.
+#[component]
+pub fn LayeredChart(
+    /// Reactive layered specification
+    spec: Signal<LayerSpec>,
+    /// X axis label (optional)
+    #[prop(optional, into)]
+    x_label: Option<String>,
+    /// Y axis label (optional)
+    #[prop(optional, into)]
+    y_label: Option<String>,
+    /// Override chart config (uses spec.config when default)
+    #[prop(default = Signal::derive(|| ChartConfig::default()), into)]
+    config: Signal<ChartConfig>,
+) -> impl IntoView {
+    let resolved_config = Signal::derive(move || {
+        let external = config.get();
+        if external.title.is_some() || external.theme.is_some() {
+            external
+        } else {
+            spec.get().config
+        }
+    });
+    let theme = Memo::new(move |_| resolved_config.get().theme.unwrap_or_default());
+    let resolved = Memo::new(move |_| spec.get().resolve());
+    let error = Memo::new(move |_| resolved.get().err().map(|e| e.to_string()));
+
+    let (container_width, container_height, container_ref) = use_container_size();
+    let chart_width = Memo::new(move |_| {
+        let measured = container_width.get();
+        if measured > 0.0 {
+            return measured;
+        }
+        resolved_config.get().width.unwrap_or(800) as f64
+    });
+    let chart_height = Memo::new(move |_| {
+        let measured = container_height.get();
+        if measured > 0.0 {
+            return measured;
+        }
+        resolved_config.get().height.unwrap_or(400) as f64
+    });
+
+    // Distinct series names across series layers, in first-seen order
+    let series_names = Memo::new(move |_| {
+        let mut names: Vec<String> = Vec::new();
+        if let Ok(r) = resolved.get() {
+            for layer in &r.layers {
+                if let LayerGeometry::Series(ds) = &layer.geometry {
+                    for s in &ds.series {
+                        if !names.contains(&s.name) {
+                            names.push(s.name.clone());
+                        }
+                    }
+                }
+            }
+        }
+        names
+    });
+    let (hidden, set_hidden) = signal(Vec::<String>::new());
+
+    let legend_items = Signal::derive(move || {
+        let th = theme.get();
+        let hidden = hidden.get();
+        series_names
+            .get()
+            .iter()
+            .enumerate()
+            .map(|(i, name)| LegendItem {
+                name: name.clone(),
+                color: th.palette[i % th.palette.len()].clone(),
+                visible: !hidden.contains(name),
+            })
+            .collect::<Vec<_>>()
+    });
+    let show_legend = Memo::new(move |_| {
+        resolved_config
+            .get()
+            .show_legend
+            .unwrap_or_else(|| series_names.get().len() > 1)
+    });
+    let on_legend_toggle = Callback::new(move |idx: usize| {
+        let Some(name) = series_names.get().get(idx).cloned() else {
+            return;
+        };
+        let mut h = hidden.get();
+        match h.iter().position(|n| *n == name) {
+            Some(pos) => {
+                h.remove(pos);
+            }
+            None => h.push(name),
+        }
+        set_hidden.set(h);
+    });
+
+    let legend_outside = Memo::new(move |_| resolved_config.get().legend_outside.unwrap_or(false));
+    let margin = Memo::new(move |_| {
+        let mut m = resolved_config.get().margin.unwrap_or_default();
+        if legend_outside.get() && show_legend.get() {
+            m.right += estimate_legend_width(&legend_items.get()) + 16.0;
+        }
+        m
+    });
+    let inner_width =
+        Memo::new(move |_| chart_width.get() - margin.get().left - margin.get().right);
+    let inner_height =
+        Memo::new(move |_| chart_height.get() - margin.get().top - margin.get().bottom);
+
+    // Shared scales over the combined domain of every layer, unless fixed in config
+    let x_scale = Memo::new(move |_| {
+        let domain = resolved_config
+            .get()
+            .x_domain
+            .unwrap_or_else(|| resolved.get().map_or((0.0, 1.0), |r| r.x_domain));
+        LinearScale::new(domain, (0.0, inner_width.get()))
+    });
+    let y_scale = Memo::new(move |_| {
+        let domain = resolved_config.get().y_domain.unwrap_or_else(|| {
+            let (lo, hi) = resolved.get().map_or((0.0, 1.0), |r| r.y_domain);
+            let pad = (hi - lo) * 0.05;
+            (lo - pad, hi + pad)
+        });
+        LinearScale::new(domain, (inner_height.get(), 0.0))
+    });
+    let x_tick_count = Memo::new(move |_| (inner_width.get() / 100.0).max(2.0) as usize);
+    let y_tick_count = Memo::new(move |_| (inner_height.get() / 50.0).max(2.0) as usize);
+
+    let clip_id = format!("clip-{}", uuid::Uuid::new_v4());
+
+    let layers_view = move || {
+        let Ok(r) = resolved.get() else {
+            return Vec::new();
+        };
+        let (xs, ys) = (x_scale.get(), y_scale.get());
+        let (w, h) = (inner_width.get(), inner_height.get());
+        let th = theme.get();
+        let names = series_names.get();
+        let hidden = hidden.get();
+        let color_of = |name: &str| {
+            let i = names.iter().position(|n| n == name).unwrap_or(0);
+            th.palette[i % th.palette.len()].clone()
+        };
+        let baseline = ys.domain().0.max(0.0).min(ys.domain().1);
+
+        r.layers
+            .iter()
+            .map(|layer| match &layer.geometry {
+                LayerGeometry::Series(ds) => ds
+                    .series
+                    .iter()
+                    .filter(|s| !hidden.contains(&s.name))
+                    .map(|s| {
+                        let color = color_of(&s.name);
+                        match layer.mark {
+                            Mark::Area => view! {
+                                <path
+                                    d=path_data(&s.data, &xs, &ys, Some(baseline))
+                                    fill=color
+                                    fill-opacity=th.area_opacity
+                                    stroke="none"
+                                />
+                            }
+                            .into_any(),
+                            Mark::Point | Mark::Circle => s
+                                .data
+                                .iter()
+                                .map(|p| {
+                                    view! {
+                                        <circle
+                                            cx=format!("{:.2}", xs.map(p.x))
+                                            cy=format!("{:.2}", ys.map(p.y))
+                                            r=th.point_radius
+                                            fill=color.clone()
+                                            fill-opacity=th.point_opacity
+                                        />
+                                    }
+                                })
+                                .collect_view()
+                                .into_any(),
+                            _ => view! {
+                                <path
+                                    d=path_data(&s.data, &xs, &ys, None)
+                                    fill="none"
+                                    stroke=color
+                                    stroke-width=th.stroke_width
+                                    stroke-linejoin="round"
+                                    stroke-linecap="round"
+                                    opacity=th.line_opacity
+                                />
+                            }
+                            .into_any(),
+                        }
+                    })
+                    .collect_view()
+                    .into_any(),
+                LayerGeometry::Rules { axis, values } => values
+                    .iter()
+                    .map(|&v| {
+                        let (x1, y1, x2, y2) = match axis {
+                            RuleAxis::X => (xs.map(v), 0.0, xs.map(v), h),
+                            RuleAxis::Y => (0.0, ys.map(v), w, ys.map(v)),
+                        };
+                        view! {
+                            <line
+                                x1=x1
+                                y1=y1
+                                x2=x2
+                                y2=y2
+                                stroke=th.text_color.clone()
+                                stroke-width="1.5"
+                                stroke-dasharray="6,4"
+                            />
+                        }
+                    })
+                    .collect_view()
+                    .into_any(),
+                LayerGeometry::Labels(labels) => labels
+                    .iter()
+                    .map(|l| {
+                        view! {
+                            <text
+                                x=format!("{:.2}", xs.map(l.x))
+                                y=format!("{:.2}", ys.map(l.y) - 6.0)
+                                text-anchor="middle"
+                                font-size=th.axis_font_size
+                                font-family=th.font_family.clone()
+                                fill=th.text_color.clone()
+                            >
+                                {l.text.clone()}
+                            </text>
+                        }
+                    })
+                    .collect_view()
+                    .into_any(),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    view! {
+        {move || {
+            if let Some(message) = error.get() {
+                return view! { <ChartError message=message config=resolved_config /> }.into_any();
+            }
+            let clip_id = clip_id.clone();
+            let x_label = x_label.clone();
+            let y_label = y_label.clone();
+            view! {
+                <div
+                    class="layered-chart"
+                    style=move || {
+                        format!(
+                            "width: 100%; height: 100%; display: flex; flex-direction: column; background-color: {};",
+                            theme.get().background_color,
+                        )
+                    }
+                >
+                    {move || {
+                        resolved_config
+                            .get()
+                            .title
+                            .map(|t| {
+                                let th = theme.get();
+                                view! {
+                                    <h3 style=format!(
+                                        "text-align: center; margin: 0; padding-top: {}px; padding-bottom: {}px; font-size: {}px; font-family: {}; color: {}; font-weight: {};",
+                                        th.title_padding_top,
+                                        th.title_padding_bottom,
+                                        th.title_font_size,
+                                        th.font_family,
+                                        th.text_color,
+                                        th.title_font_weight,
+                                    )>{t}</h3>
+                                }
+                            })
+                    }}
+                    <div node_ref=container_ref style="flex: 1; min-height: 0; position: relative;">
+                        <svg
+                            role="img"
+                            aria-label=move || {
+                                resolved_config
+                                    .get()
+                                    .title
+                                    .unwrap_or_else(|| "Layered chart".to_string())
+                            }
+                            viewBox=move || format!("0 0 {} {}", chart_width.get(), chart_height.get())
+                            style="width: 100%; height: 100%; display: block;"
+                        >
+                            <g transform=move || {
+                                format!("translate({}, {})", margin.get().left, margin.get().top)
+                            }>
+                                <defs>
+                                    <clipPath id=clip_id.clone()>
+                                        <rect
+                                            x="0"
+                                            y="0"
+                                            width=move || inner_width.get()
+                                            height=move || inner_height.get()
+                                        ></rect>
+                                    </clipPath>
+                                </defs>
+                                {move || {
+                                    let gs = resolved_config
+                                        .get()
+                                        .grid
+                                        .unwrap_or_else(|| theme.get().grid.clone());
+                                    (gs.show_x || gs.show_y)
+                                        .then(|| {
+                                            view! {
+                                                <Grid
+                                                    x_scale=x_scale.get()
+                                                    y_scale=y_scale.get()
+                                                    tick_count=x_tick_count.get()
+                                                    width=inner_width.get()
+                                                    height=inner_height.get()
+                                                    style=gs
+                                                />
+                                            }
+                                        })
+                                }}
+                                <g clip-path=format!("url(#{})", clip_id)>{layers_view}</g>
+                                <g transform=move || {
+                                    format!("translate(0, {})", inner_height.get())
+                                }>
+                                    {move || {
+                                        view! {
+                                            <Axis
+                                                orientation=AxisOrientation::Bottom
+                                                scale=x_scale.get()
+                                                tick_count=x_tick_count.get()
+                                                _dimension=inner_width.get()
+                                                stroke=theme.get().axis_color
+                                                font_size=theme.get().axis_font_size
+                                                label=x_label.clone()
+                                                format=resolved_config.get().x_number_format()
+                                            />
+                                        }
+                                    }}
+                                </g>
+                                {move || {
+                                    view! {
+                                        <Axis
+                                            orientation=AxisOrientation::Left
+                                            scale=y_scale.get()
+                                            tick_count=y_tick_count.get()
+                                            _dimension=inner_height.get()
+                                            stroke=theme.get().axis_color
+                                            font_size=theme.get().axis_font_size
+                                            label=y_label.clone()
+                                            format=resolved_config.get().y_number_format()
+                                        />
+                                    }
+                                }}
+                                {move || {
+                                    show_legend
+                                        .get()
+                                        .then(|| {
+                                            let position = if legend_outside.get() {
+                                                LegendPosition::ExternalRight
+                                            } else {
+                                                LegendPosition::TopRight
+                                            };
+                                            view! {
+                                                <Legend
+                                                    items=legend_items
+                                                    position=position
+                                                    inner_width=inner_width
+                                                    inner_height=inner_height
+                                                    on_toggle=on_legend_toggle
+                                                    text_color=theme.get().text_color
+                                                />
+                                            }
+                                        })
+                                }}
+                            </g>
+                        </svg>
+                    </div>
+                </div>
+            }
+                .into_any()
+        }}
+    }
+}
@@ -9,19 +9,14 @@ use crate::components::svg::legend::{estimate_legend_width, Legend, LegendItem,
 use crate::components::svg::tooltip::Tooltip;
 use crate::hooks::use_container_size;
 use leptos::prelude::*;
-use lodviz_core::algorithms::lttb::lttb_downsample;
 use lodviz_core::core::a11y;
 use lodviz_core::core::data::{DataPoint, Dataset};
 use lodviz_core::core::mark::Mark;
-use lodviz_core::core::scale::{LinearScale, Scale};
-use lodviz_core::core::theme::{ChartConfig, GridStyle};
+use lodviz_core::core::scale::{AxisScale, Scale, ScaleType, NICE_TICKS};
+use lodviz_core::core::theme::{ChartConfig, ChartTheme, GridStyle};
 
 /// Generate SVG path `d` attribute from data points and scales
-fn generate_path_data(
-    points: &[DataPoint],
-    x_scale: &LinearScale,
-    y_scale: &LinearScale,
-) -> String {
+fn generate_path_data(points: &[DataPoint], x_scale: &AxisScale, y_scale: &AxisScale) -> String {
     if points.is_empty() {
         return String::from("M 0 0");
     }
@@ -125,23 +120,44 @@ pub fn LineChart(
         }
     });
 
-    // Processed data: LTTB downsample per series (defined early — needed by legend_items before margin)
-    let processed_data = Memo::new(move |_| {
-        let dataset = data.get();
-        dataset
+    // LTTB downsample per series; attributes (and source rows) stay aligned
+    let downsampled = Memo::new(move |_| {
+        data.get()
             .series
             .iter()
             .map(|s| {
-                let points = if s.data.len() > 1000 {
-                    lttb_downsample(&s.data, 1000)
+                if s.data.len() > 1000 {
+                    s.downsample_lttb(1000)
                 } else {
-                    s.data.clone()
-                };
-                (s.name.clone(), points)
+                    s.clone()
+                }
             })
             .collect::<Vec<_>>()
     });
 
+    // Processed data (defined early — needed by legend_items before margin)
+    let processed_data = Memo::new(move |_| {
+        downsampled
+            .get()
+            .into_iter()
+            .map(|s| (s.name, s.data))
+            .collect::<Vec<_>>()
+    });
+
+    // Palette slot and dash pattern per series (detail parts share their group's color)
+    let series_styles = Memo::new(move |_| {
+        let dataset = data.get();
+        dataset
+            .color_indices()
+            .into_iter()
+            .zip(dataset.series.iter().map(|s| s.stroke_dash.clone()))
+            .collect::<Vec<_>>()
+    });
+    let series_color = move |th: &ChartTheme, i: usize| {
+        let slot = series_styles.with(|s| s.get(i).map_or(i, |(slot, _)| *slot));
+        th.palette[slot % th.palette.len()].clone()
+    };
+
     // Legend items — defined early so margin can adapt when legend_outside is enabled
     let legend_items = Signal::derive(move || {
         let series = processed_data.get();
@@ -152,7 +168,7 @@ pub fn LineChart(
             .enumerate()
             .map(|(i, (name, _))| LegendItem {
                 name: name.clone(),
-                color: th.palette[i % th.palette.len()].clone(),
+                color: series_color(&th, i),
                 visible: vis.get(i).copied().unwrap_or(true),
             })
             .collect::<Vec<_>>()
@@ -174,6 +190,8 @@ pub fn LineChart(
         Memo::new(move |_| chart_height.get() as f64 - margin.get().top - margin.get().bottom);
 
     let final_title = Memo::new(move |_| config.get().title.or(title.clone()));
+    let x_format = Memo::new(move |_| config.get().x_number_format());
+    let y_format = Memo::new(move |_| config.get().y_number_format());
     let grid_style = Memo::new(move |_| {
         config.get().grid.unwrap_or_else(|| {
             let th = theme.get();
@@ -198,19 +216,19 @@ pub fn LineChart(
         let mut y_min = f64::INFINITY;
         let mut y_max = f64::NEG_INFINITY;
 
+        let cfg = config.get();
+        let (x_kind, y_kind) = (cfg.x_scale_type(), cfg.y_scale_type());
+
+        // Log axes cannot show values <= 0: leave them out of the extent
         let all_points = series.iter().flat_map(|(_, pts)| pts.iter());
         for p in all_points {
-            if p.x < x_min {
-                x_min = p.x;
+            if x_kind != ScaleType::Log || p.x > 0.0 {
+                x_min = x_min.min(p.x);
+                x_max = x_max.max(p.x);
             }
-            if p.x > x_max {
-                x_max = p.x;
-            }
-            if p.y < y_min {
-                y_min = p.y;
-            }
-            if p.y > y_max {
-                y_max = p.y;
+            if y_kind != ScaleType::Log || p.y > 0.0 {
+                y_min = y_min.min(p.y);
+                y_max = y_max.max(p.y);
             }
         }
 
@@ -223,10 +241,22 @@ pub fn LineChart(
             y_max = 1.0;
         }
 
-        // Add minimal padding to Y to avoid cutting off peaks
-        let y_pad = (y_max - y_min) * 0.05;
+        // Add minimal padding to Y to avoid cutting off peaks (log axes
+        // get room from niceing to whole decades instead)
+        let y_pad = if y_kind == ScaleType::Log {
+            0.0
+        } else {
+            (y_max - y_min) * 0.05
+        };
+
+        // Fixed domains from config (e.g. shared across facets) win; otherwise
+        // Y starts and ends on round values
+        let (x_min, x_max) = cfg.x_domain.unwrap_or((x_min, x_max));
+        let (y_min, y_max) = cfg
+            .y_domain
+            .unwrap_or_else(|| y_kind.nice_domain((y_min - y_pad, y_max + y_pad), NICE_TICKS));
 
-        ZoomTransform::from_domain(x_min, x_max, y_min - y_pad, y_max + y_pad)
+        ZoomTransform::from_domain(x_min, x_max, y_min, y_max)
     });
 
     // Zoom state
@@ -245,13 +275,19 @@ pub fn LineChart(
     let x_scale = Memo::new(move |_| {
         let t = zoom_transform.get();
         let w = inner_width.get();
-        LinearScale::new((t.x_min, t.x_max), (0.0, w))
+        config.get().x_axis_scale((t.x_min, t.x_max), (0.0, w))
     });
 
     let y_scale = Memo::new(move |_| {
         let t = zoom_transform.get();
         let h = inner_height.get();
-        LinearScale::new((t.y_min, t.y_max), (h, 0.0))
+        config.get().y_axis_scale((t.y_min, t.y_max), (h, 0.0))
+    });
+
+    // Axis scale kinds, so box zoom follows log / sqrt / symlog axes
+    let scale_types = Signal::derive(move || {
+        let cfg = config.get();
+        (cfg.x_scale_type(), cfg.y_scale_type())
     });
 
     // Dynamic tick counts
@@ -310,6 +346,18 @@ pub fn LineChart(
             .collect::<Vec<_>>()
     });
 
+    // Per-point attributes of the visible series, parallel to tooltip_series
+    let tooltip_attributes = Signal::derive(move || {
+        let vis = series_visibility.get();
+        downsampled
+            .get()
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| vis.get(*i).copied().unwrap_or(true))
+            .map(|(_, s)| s.attributes)
+            .collect::<Vec<_>>()
+    });
+
     let tooltip_colors = Memo::new(move |_| {
         let series = processed_data.get();
         let vis = series_visibility.get();
@@ -318,7 +366,7 @@ pub fn LineChart(
             .iter()
             .enumerate()
             .filter(|(i, _)| vis.get(*i).copied().unwrap_or(true))
-            .map(|(i, _)| th.palette[i % th.palette.len()].clone())
+            .map(|(i, _)| series_color(&th, i))
             .collect::<Vec<_>>()
     });
 
@@ -340,16 +388,17 @@ pub fn LineChart(
     // --- Linked dashboard crosshair ---
     // Extract the shared hover_x signal from DashboardContext (if wrapped in LinkedDashboard)
     let dash_hover_x = use_context::<DashboardContext>().map(|ctx| ctx.hover_x);
+    // Hovered record from a linked chart
+    let dash_hover_row = use_context::<DashboardContext>().map(|ctx| ctx.hover_row);
 
     // Emit our cursor position as domain-X to the shared context
     Effect::new(move |_| {
         let Some(hover_signal) = dash_hover_x else {
             return;
         };
-        let domain_x = cursor_norm.get().map(|(norm_x, _)| {
-            let t = zoom_transform.get();
-            t.x_min + norm_x * (t.x_max - t.x_min)
-        });
+        let domain_x = cursor_norm
+            .get()
+            .map(|(norm_x, _)| x_scale.get().inverse(norm_x * inner_width.get()));
         hover_signal.set(domain_x);
     });
 
@@ -478,12 +527,14 @@ pub fn LineChart(
                             let xs = x_scale.get();
                             let ys = y_scale.get();
                             let th = theme.get();
+                            let styles = series_styles.get();
                             series
                                 .iter()
                                 .enumerate()
                                 .map(|(i, (_, points))| {
                                     let visible = vis.get(i).copied().unwrap_or(true);
-                                    let color = th.palette[i % th.palette.len()].clone();
+                                    let color = series_color(&th, i);
+                                    let dash = styles.get(i).and_then(|(_, d)| d.clone());
                                     let d = generate_path_data(points, &xs, &ys);
                                     let display_style = if visible { "inline" } else { "none" };
                                     // Default to true if index not found
@@ -500,6 +551,7 @@ pub fn LineChart(
                                                 fill="none"
                                                 stroke=color
                                                 stroke-width=th.stroke_width
+                                                stroke-dasharray=dash
                                                 stroke-linejoin="round"
                                                 stroke-linecap="round"
                                                 opacity=th.line_opacity
@@ -526,7 +578,7 @@ pub fn LineChart(
                                     let cx = x_scale.get().map(point.x);
                                     let cy = y_scale.get().map(point.y);
                                     let desc = a11y::describe_point(point, idx, points.len());
-                                    let color = th.palette[si % th.palette.len()].clone();
+                                    let color = series_color(&th, si);
                                     Some(
                                         // Use first visible series
                                         view! {
@@ -569,6 +621,7 @@ pub fn LineChart(
                                         stroke=theme.get().axis_color
                                         font_size=theme.get().axis_font_size
                                         label=x_label_clone.clone()
+                                        format=x_format.get()
                                     />
                                 }
                             }}
@@ -584,6 +637,7 @@ pub fn LineChart(
                                     stroke=theme.get().axis_color
                                     font_size=theme.get().axis_font_size
                                     label=y_label_clone.clone()
+                                    format=y_format.get()
                                 />
                             }
                         }}
@@ -607,6 +661,43 @@ pub fn LineChart(
                             })
                         }}
 
+                        // Linked highlight: every point of the hovered record
+                        {move || {
+                            let row = dash_hover_row?.get()?;
+                            let vis = series_visibility.get();
+                            let xs = x_scale.get();
+                            let ys = y_scale.get();
+                            let th = theme.get();
+                            let rings = downsampled
+                                .get()
+                                .iter()
+                                .enumerate()
+                                .filter(|(i, _)| vis.get(*i).copied().unwrap_or(true))
+                                .flat_map(|(_, s)| {
+                                    s.data
+                                        .iter()
+                                        .enumerate()
+                                        .filter(|(j, _)| s.attributes.row_of(*j) == Some(row))
+                                        .map(|(_, p)| (xs.map(p.x), ys.map(p.y)))
+                                        .collect::<Vec<_>>()
+                                })
+                                .map(|(cx, cy)| {
+                                    view! {
+                                        <circle
+                                            cx=format!("{cx:.2}")
+                                            cy=format!("{cy:.2}")
+                                            r=th.point_radius + 4.0
+                                            fill="none"
+                                            stroke=th.text_color.clone()
+                                            stroke-width=2
+                                            style="pointer-events: none;"
+                                        />
+                                    }
+                                })
+                                .collect_view();
+                            Some(view! { <g class="linked-highlight">{rings}</g> })
+                        }}
+
                         // Tooltip overlay (must be last to receive mouse events)
                         <Tooltip
                             series_data=tooltip_series
@@ -616,6 +707,9 @@ pub fn LineChart(
                             inner_width=inner_width
                             inner_height=inner_height
                             cursor_normalized_x=cursor_x
+                            series_attributes=tooltip_attributes
+                            x_format=x_format
+                            y_format=y_format
                         />
 
                         // ZoomPan overlay
@@ -625,6 +719,7 @@ pub fn LineChart(
                             inner_width=inner_width
                             inner_height=inner_height
                             set_cursor=set_cursor_norm
+                            scale_types=scale_types
                         />
 
                         // SVG Legend overlay (must be last to render on top)
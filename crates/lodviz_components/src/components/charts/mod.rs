@@ -11,10 +11,14 @@ pub mod candlestick;
 pub mod chord_chart;
 /// Contour chart component (marching squares)
 pub mod contour_chart;
+/// Facet chart component (small multiples with a shared legend)
+pub mod facet_chart;
 /// Heatmap chart component
 pub mod heatmap;
 /// Histogram component
 pub mod histogram;
+/// Layered chart component (several marks over shared scales)
+pub mod layered_chart;
 /// Line chart component
 pub mod line_chart;
 /// Pie and donut chart component
@@ -1,4 +1,5 @@
 /// ScatterChart component with LTTB downsampling and multi-series support
+use crate::components::interaction::linked_context::DashboardContext;
 use crate::components::interaction::zoom_pan::{ZoomPan, ZoomTransform};
 use crate::components::svg::axis::{Axis, AxisOrientation};
 use crate::components::svg::grid::Grid;
@@ -6,12 +7,11 @@ use crate::components::svg::legend::{estimate_legend_width, Legend, LegendItem,
 use crate::components::svg::tooltip::{Tooltip, TooltipMode};
 use crate::hooks::use_container_size;
 use leptos::prelude::*;
-use lodviz_core::algorithms::lttb::lttb_downsample;
 use lodviz_core::core::a11y;
-use lodviz_core::core::data::Dataset;
+use lodviz_core::core::data::{Dataset, PointAttributes};
 use lodviz_core::core::mark::Mark;
-use lodviz_core::core::scale::{LinearScale, Scale};
-use lodviz_core::core::theme::{ChartConfig, GridStyle};
+use lodviz_core::core::scale::{Scale, ScaleType, NICE_TICKS};
+use lodviz_core::core::theme::{ChartConfig, ChartTheme, GridStyle};
 
 /// ScatterChart component for rendering scatter plots with automatic downsampling
 ///
@@ -21,6 +21,9 @@ use lodviz_core::core::theme::{ChartConfig, GridStyle};
 /// - Interactive legend with click-to-toggle
 /// - Optional axis labels
 /// - Point opacity from theme (configurable via ChartTheme.point_opacity)
+/// - Per-point size, opacity, shape, color, text label and tooltip fields
+///   from `Series::attributes`, kept aligned through downsampling
+/// - Linked highlighting of the hovered record inside a `LinkedDashboard`
 #[component]
 pub fn ScatterChart(
     /// Dataset containing one or more series
@@ -77,23 +80,49 @@ pub fn ScatterChart(
         }
     });
 
-    // Processed data: LTTB downsample per series (defined early — needed by legend_items before margin)
-    let processed_data = Memo::new(move |_| {
-        let dataset = data.get();
-        dataset
+    // LTTB downsample per series; attributes (and source rows) stay aligned
+    let downsampled = Memo::new(move |_| {
+        data.get()
             .series
             .iter()
             .map(|s| {
-                let points = if s.data.len() > 1000 {
-                    lttb_downsample(&s.data, 1000)
+                if s.data.len() > 1000 {
+                    s.downsample_lttb(1000)
                 } else {
-                    s.data.clone()
-                };
-                (s.name.clone(), points)
+                    s.clone()
+                }
             })
             .collect::<Vec<_>>()
     });
 
+    // Processed data (defined early — needed by legend_items before margin)
+    let processed_data = Memo::new(move |_| {
+        downsampled
+            .get()
+            .into_iter()
+            .map(|s| (s.name, s.data))
+            .collect::<Vec<_>>()
+    });
+
+    // Per-point attributes, parallel to processed_data
+    let series_attributes = Memo::new(move |_| {
+        downsampled
+            .get()
+            .into_iter()
+            .map(|s| s.attributes)
+            .collect::<Vec<PointAttributes>>()
+    });
+
+    // Hovered record from a linked chart (if wrapped in LinkedDashboard)
+    let dash_hover_row = use_context::<DashboardContext>().map(|ctx| ctx.hover_row);
+
+    // Palette slot per series (detail parts share their group's color)
+    let color_slots = Memo::new(move |_| data.get().color_indices());
+    let series_color = move |th: &ChartTheme, i: usize| {
+        let slot = color_slots.with(|s| s.get(i).copied().unwrap_or(i));
+        th.palette[slot % th.palette.len()].clone()
+    };
+
     // Legend items — defined early so margin can adapt when legend_outside is enabled
     let legend_items = Signal::derive(move || {
         let series = processed_data.get();
@@ -104,7 +133,7 @@ pub fn ScatterChart(
             .enumerate()
             .map(|(i, (name, _))| LegendItem {
                 name: name.clone(),
-                color: th.palette[i % th.palette.len()].clone(),
+                color: series_color(&th, i),
                 visible: vis.get(i).copied().unwrap_or(true),
             })
             .collect::<Vec<_>>()
@@ -126,6 +155,8 @@ pub fn ScatterChart(
         Memo::new(move |_| chart_height.get() as f64 - margin.get().top - margin.get().bottom);
 
     let final_title = Memo::new(move |_| config.get().title.or(title.clone()));
+    let x_format = Memo::new(move |_| config.get().x_number_format());
+    let y_format = Memo::new(move |_| config.get().y_number_format());
     let grid_style = Memo::new(move |_| {
         config.get().grid.unwrap_or_else(|| {
             let th = theme.get();
@@ -150,19 +181,19 @@ pub fn ScatterChart(
         let mut y_min = f64::INFINITY;
         let mut y_max = f64::NEG_INFINITY;
 
+        let cfg = config.get();
+        let (x_kind, y_kind) = (cfg.x_scale_type(), cfg.y_scale_type());
+
+        // Log axes cannot show values <= 0: leave them out of the extent
         let all_points = series.iter().flat_map(|(_, pts)| pts.iter());
         for p in all_points {
-            if p.x < x_min {
-                x_min = p.x;
-            }
-            if p.x > x_max {
-                x_max = p.x;
-            }
-            if p.y < y_min {
-                y_min = p.y;
+            if x_kind != ScaleType::Log || p.x > 0.0 {
+                x_min = x_min.min(p.x);
+                x_max = x_max.max(p.x);
             }
-            if p.y > y_max {
-                y_max = p.y;
+            if y_kind != ScaleType::Log || p.y > 0.0 {
+                y_min = y_min.min(p.y);
+                y_max = y_max.max(p.y);
             }
         }
 
@@ -175,6 +206,15 @@ pub fn ScatterChart(
             y_max = 1.0;
         }
 
+        // Fixed domains from config (e.g. shared across facets) win; otherwise
+        // both axes start and end on round values
+        let (x_min, x_max) = cfg
+            .x_domain
+            .unwrap_or_else(|| x_kind.nice_domain((x_min, x_max), NICE_TICKS));
+        let (y_min, y_max) = cfg
+            .y_domain
+            .unwrap_or_else(|| y_kind.nice_domain((y_min, y_max), NICE_TICKS));
+
         ZoomTransform::from_domain(x_min, x_max, y_min, y_max)
     });
 
@@ -191,13 +231,19 @@ pub fn ScatterChart(
     let x_scale = Memo::new(move |_| {
         let t = zoom_transform.get();
         let w = inner_width.get();
-        LinearScale::new((t.x_min, t.x_max), (0.0, w))
+        config.get().x_axis_scale((t.x_min, t.x_max), (0.0, w))
     });
 
     let y_scale = Memo::new(move |_| {
         let t = zoom_transform.get();
         let h = inner_height.get();
-        LinearScale::new((t.y_min, t.y_max), (h, 0.0))
+        config.get().y_axis_scale((t.y_min, t.y_max), (h, 0.0))
+    });
+
+    // Axis scale kinds, so box zoom follows log / sqrt / symlog axes
+    let scale_types = Signal::derive(move || {
+        let cfg = config.get();
+        (cfg.x_scale_type(), cfg.y_scale_type())
     });
 
     let x_tick_count = Memo::new(move |_| (inner_width.get() / 100.0).max(2.0) as usize);
@@ -256,6 +302,17 @@ pub fn ScatterChart(
             .collect::<Vec<_>>()
     });
 
+    let tooltip_attributes = Signal::derive(move || {
+        let attributes = series_attributes.get();
+        let vis = series_visibility.get();
+        attributes
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| vis.get(*i).copied().unwrap_or(true))
+            .map(|(_, a)| a)
+            .collect::<Vec<_>>()
+    });
+
     let tooltip_colors = Memo::new(move |_| {
         let series = processed_data.get();
         let vis = series_visibility.get();
@@ -264,7 +321,7 @@ pub fn ScatterChart(
             .iter()
             .enumerate()
             .filter(|(i, _)| vis.get(*i).copied().unwrap_or(true))
-            .map(|(i, _)| th.palette[i % th.palette.len()].clone())
+            .map(|(i, _)| series_color(&th, i))
             .collect::<Vec<_>>()
     });
 
@@ -395,26 +452,76 @@ pub fn ScatterChart(
                             let xs = x_scale.get();
                             let ys = y_scale.get();
                             let th = theme.get();
+                            let attributes = series_attributes.get();
                             series
                                 .iter()
                                 .enumerate()
                                 .map(|(i, (_, points))| {
                                     let visible = vis.get(i).copied().unwrap_or(true);
-                                    let color = th.palette[i % th.palette.len()].clone();
+                                    let color = series_color(&th, i);
                                     let display = if visible { "inline" } else { "none" };
+                                    let attrs = attributes.get(i).cloned().unwrap_or_default();
                                     let circles = points
                                         .iter()
-                                        .map(|point| {
+                                        .enumerate()
+                                        .map(|(j, point)| {
                                             let cx = xs.map(point.x);
                                             let cy = ys.map(point.y);
+                                            // Bubble radius: 0.5× to 3× the theme radius
+                                            let r = attrs
+                                                .size
+                                                .get(j)
+                                                .map_or(th.point_radius, |s| {
+                                                    th.point_radius * (0.5 + 2.5 * s)
+                                                });
+                                            let opacity = attrs
+                                                .opacity
+                                                .get(j)
+                                                .copied()
+                                                .unwrap_or(th.point_opacity);
+                                            let d = attrs
+                                                .shape
+                                                .get(j)
+                                                .copied()
+                                                .unwrap_or_default()
+                                                .path(cx, cy, r);
+                                            let label = attrs
+                                                .text
+                                                .get(j)
+                                                .map(|t| {
+                                                    view! {
+                                                        <text
+                                                            x=format!("{:.2}", cx + r + 3.0)
+                                                            y=format!("{cy:.2}")
+                                                            dominant-baseline="middle"
+                                                            font-size=th.axis_font_size
+                                                            fill=th.text_color.clone()
+                                                        >
+                                                            {t.clone()}
+                                                        </text>
+                                                    }
+                                                });
+                                            let title = attrs
+                                                .tooltip
+                                                .get(j)
+                                                .map(|fields| {
+                                                    let lines = fields
+                                                        .iter()
+                                                        .map(|(k, v)| format!("{k}: {v}"))
+                                                        .collect::<Vec<_>>()
+                                                        .join("\n");
+                                                    view! { <title>{lines}</title> }
+                                                });
+                                            let fill = attrs
+                                                .color
+                                                .get(j)
+                                                .cloned()
+                                                .unwrap_or_else(|| color.clone());
                                             view! {
-                                                <circle
-                                                    cx=format!("{cx:.2}")
-                                                    cy=format!("{cy:.2}")
-                                                    r=th.point_radius
-                                                    fill=color.clone()
-                                                    opacity=th.point_opacity
-                                                />
+                                                <path d=d fill=fill opacity=opacity>
+                                                    {title}
+                                                </path>
+                                                {label}
                                             }
                                         })
                                         .collect_view();
@@ -430,6 +537,47 @@ pub fn ScatterChart(
                                 })
                                 .collect_view()
                         }}
+                        // Linked highlight: every point of the hovered record
+                        {move || {
+                            let row = dash_hover_row?.get()?;
+                            let series = processed_data.get();
+                            let attributes = series_attributes.get();
+                            let vis = series_visibility.get();
+                            let xs = x_scale.get();
+                            let ys = y_scale.get();
+                            let th = theme.get();
+                            let mut hits = Vec::new();
+                            for (i, ((_, points), attrs)) in series
+                                .iter()
+                                .zip(attributes.iter())
+                                .enumerate()
+                            {
+                                if !vis.get(i).copied().unwrap_or(true) {
+                                    continue;
+                                }
+                                for (j, p) in points.iter().enumerate() {
+                                    if attrs.row_of(j) == Some(row) {
+                                        hits.push((xs.map(p.x), ys.map(p.y)));
+                                    }
+                                }
+                            }
+                            let rings = hits
+                                .into_iter()
+                                .map(|(cx, cy)| {
+                                    view! {
+                                        <circle
+                                            cx=format!("{cx:.2}")
+                                            cy=format!("{cy:.2}")
+                                            r=th.point_radius + 4.0
+                                            fill="none"
+                                            stroke=th.text_color.clone()
+                                            stroke-width=2
+                                        />
+                                    }
+                                })
+                                .collect_view();
+                            Some(view! { <g class="linked-highlight">{rings}</g> })
+                        }}
                         // Keyboard focus indicator
                         {move || {
                             let series = processed_data.get();
@@ -446,7 +594,7 @@ pub fn ScatterChart(
                                     let cx = x_scale.get().map(point.x);
                                     let cy = y_scale.get().map(point.y);
                                     let desc = a11y::describe_point(point, idx, points.len());
-                                    let color = th.palette[si % th.palette.len()].clone();
+                                    let color = series_color(&th, si);
                                     Some(
                                         view! {
                                             <g>
@@ -488,6 +636,7 @@ pub fn ScatterChart(
                                         stroke=theme.get().axis_color
                                         font_size=theme.get().axis_font_size
                                         label=x_label_clone.clone()
+                                        format=x_format.get()
                                     />
                                 }
                             }}
@@ -503,6 +652,7 @@ pub fn ScatterChart(
                                     stroke=theme.get().axis_color
                                     font_size=theme.get().axis_font_size
                                     label=y_label_clone.clone()
+                                    format=y_format.get()
                                 />
                             }
                         }}
@@ -518,6 +668,9 @@ pub fn ScatterChart(
                             cursor_normalized_x=cursor_x
                             cursor_normalized_y=cursor_y
                             mode=TooltipMode::Euclidean
+                            series_attributes=tooltip_attributes
+                            x_format=x_format
+                            y_format=y_format
                         />
 
                         // Zoom/Pan overlay
@@ -527,6 +680,7 @@ pub fn ScatterChart(
                             inner_width=inner_width
                             inner_height=inner_height
                             set_cursor=set_cursor_norm
+                            scale_types=scale_types
                         />
 
                         // SVG Legend overlay (must be last to render on top)
@@ -3,62 +3,105 @@
 /// Takes a `ChartSpec` (which can contain a raw `DataTable`, a `Dataset`, or a
 /// `BarDataset`) and renders the appropriate chart based on the `Mark` type.
 ///
-/// Conversion from `DataTable` to the concrete dataset type happens lazily here,
-/// using the `x`, `y`, and `color` fields from the spec as column selectors.
+/// Conversion from `DataTable` to the concrete dataset type happens lazily here
+/// via `ChartSpec::resolve()`. When the spec does not validate (unknown column,
+/// type mismatch, unsupported encoding, no data) an error panel explaining why
+/// is rendered in place of the chart.
 use crate::components::charts::area_chart::AreaChart;
 use crate::components::charts::bar_chart::{BarChart, BarMode, BarOrientation};
+use crate::components::charts::box_plot::{BoxGroup, BoxPlot};
+use crate::components::charts::candlestick::CandlestickChart;
+use crate::components::charts::chord_chart::ChordChart;
+use crate::components::charts::heatmap::HeatmapChart;
 use crate::components::charts::line_chart::LineChart;
+use crate::components::charts::pie_chart::{PieChart, PieEntry};
+use crate::components::charts::sankey_chart::SankeyChart;
 use crate::components::charts::scatter_chart::ScatterChart;
+use crate::components::charts::strip_chart::StripChart;
 use leptos::prelude::*;
-use lodviz_core::core::data::{BarDataset, Dataset};
-use lodviz_core::core::encoding::Encoding;
+use lodviz_core::core::data::{
+    BarDataset, ChordData, DataType, Dataset, GridData, OhlcBar, SankeyData, StripGroup,
+};
+use lodviz_core::core::datetime::TimeZone;
 use lodviz_core::core::mark::Mark;
+use lodviz_core::core::scale::ScaleType;
 use lodviz_core::core::spec::{ChartData, ChartSpec};
 use lodviz_core::core::theme::ChartConfig;
+use lodviz_core::core::validate::SpecError;
 
-// --- Helpers: resolve ChartData → concrete dataset types ---
+// --- Helpers: resolved ChartData → concrete dataset types ---
 
-/// Resolve the spec's `ChartData` to a `Dataset`.
-///
-/// - `ChartData::TimeSeries(ds)` → used directly
-/// - `ChartData::Table(table)` → converted via `to_dataset()` using spec encoding
-/// - `ChartData::Categorical(_)` → returns empty Dataset
-fn resolve_dataset(spec: &ChartSpec) -> Dataset {
-    match &spec.data {
-        ChartData::TimeSeries(ds) => ds.clone(),
-        ChartData::Table(table) => {
-            let Some(y_field) = &spec.y else {
-                return Dataset::new();
-            };
-            let enc = Encoding::new(spec.x.clone(), y_field.clone())
-                .with_color_opt(spec.color.clone())
-                .with_size_opt(spec.size.clone());
-            table.to_dataset(&enc)
-        }
-        ChartData::Categorical(_) => Dataset::new(),
+/// Outcome of `ChartSpec::resolve()`, computed once per spec change.
+type Resolved = Result<ChartData, SpecError>;
+
+/// The spec's data as a `Dataset` (empty when the spec does not resolve to one).
+fn resolve_dataset(resolved: &Resolved) -> Dataset {
+    match resolved {
+        Ok(ChartData::TimeSeries(ds)) => ds.clone(),
+        _ => Dataset::new(),
     }
 }
 
-/// Resolve the spec's `ChartData` to a `BarDataset`.
-///
-/// - `ChartData::Categorical(bd)` → used directly
-/// - `ChartData::Table(table)` → converted via `to_bar_dataset()` using spec encoding
-/// - `ChartData::TimeSeries(_)` → returns empty BarDataset
-fn resolve_bar_dataset(spec: &ChartSpec) -> BarDataset {
-    match &spec.data {
-        ChartData::Categorical(bd) => bd.clone(),
-        ChartData::Table(table) => {
-            let Some(y_field) = &spec.y else {
-                return BarDataset::new(vec![]);
-            };
-            let enc =
-                Encoding::new(spec.x.clone(), y_field.clone()).with_color_opt(spec.color.clone());
-            table.to_bar_dataset(&enc)
-        }
-        ChartData::TimeSeries(_) => BarDataset::new(vec![]),
+/// The spec's data as a `BarDataset` (empty when the spec does not resolve to one).
+fn resolve_bar_dataset(resolved: &Resolved) -> BarDataset {
+    match resolved {
+        Ok(ChartData::Categorical(bd)) => bd.clone(),
+        _ => BarDataset::new(vec![]),
+    }
+}
+
+/// The spec's data as a `GridData` (empty when the spec does not resolve to one).
+fn resolve_grid(resolved: &Resolved) -> GridData {
+    match resolved {
+        Ok(ChartData::Grid(grid)) => grid.clone(),
+        _ => GridData::default(),
+    }
+}
+
+/// The spec's data as value groups (empty when the spec does not resolve to them).
+fn resolve_groups(resolved: &Resolved) -> Vec<StripGroup> {
+    match resolved {
+        Ok(ChartData::Groups(groups)) => groups.clone(),
+        _ => Vec::new(),
     }
 }
 
+/// The spec's data as OHLC bars (empty when the spec does not resolve to them).
+fn resolve_ohlc(resolved: &Resolved) -> Vec<OhlcBar> {
+    match resolved {
+        Ok(ChartData::Ohlc(bars)) => bars.clone(),
+        _ => Vec::new(),
+    }
+}
+
+/// The spec's data as `SankeyData` (empty when the spec does not resolve to one).
+fn resolve_sankey(resolved: &Resolved) -> SankeyData {
+    match resolved {
+        Ok(ChartData::Flow(sankey)) => sankey.clone(),
+        _ => SankeyData::default(),
+    }
+}
+
+/// The spec's data as `ChordData` (empty when the spec does not resolve to one).
+fn resolve_chord(resolved: &Resolved) -> ChordData {
+    match resolved {
+        Ok(ChartData::Chord(chord)) => chord.clone(),
+        _ => ChordData::default(),
+    }
+}
+
+/// Pie slices: one per category, summed over the bar series.
+fn pie_entries(bars: &BarDataset) -> Vec<PieEntry> {
+    bars.categories
+        .iter()
+        .enumerate()
+        .map(|(i, label)| PieEntry {
+            label: label.clone(),
+            value: bars.series.iter().filter_map(|s| s.values.get(i)).sum(),
+        })
+        .collect()
+}
+
 // --- SmartChart component ---
 
 /// Facade chart component that picks the right renderer from a `ChartSpec`.
@@ -85,6 +128,16 @@ fn resolve_bar_dataset(spec: &ChartSpec) -> BarDataset {
 /// | `Area`          | `AreaChart`    |
 /// | `Bar`           | `BarChart`     |
 /// | `Point`/`Circle`| `ScatterChart` |
+/// | `Rect`          | `HeatmapChart` |
+/// | `Arc`           | `PieChart`     |
+/// | `Boxplot`       | `BoxPlot`      |
+/// | `Tick`          | `StripChart`   |
+/// | `Candlestick`   | `CandlestickChart` |
+/// | `Flow`          | `SankeyChart`, or `ChordChart` for cyclic flows |
+///
+/// `Rule` and `Text` marks are drawn as layers of a `LayerSpec` by `LayeredChart`.
+///
+/// An invalid spec renders a `ChartError` panel with the `SpecError` message.
 #[component]
 pub fn SmartChart(
     /// Reactive chart specification (mark + encoding + data)
@@ -145,35 +198,112 @@ pub fn SmartChart(
             }
         }
 
+        // Temporal x gets calendar ticks and labels (UTC unless configured)
+        if cfg.x_scale.is_none() && s.x.data_type == DataType::Temporal {
+            cfg.x_scale = Some(ScaleType::Time(TimeZone::Utc));
+        }
+
         cfg
     });
 
-    // Signal::derive avoids PartialEq requirement (vs Memo::new)
-    let dataset = Signal::derive(move || resolve_dataset(&spec.get()));
-    let bar_dataset = Signal::derive(move || resolve_bar_dataset(&spec.get()));
+    // Resolve once per spec change; every derived dataset reads this memo
+    let resolved = Memo::new(move |_| spec.get().resolve());
+    let dataset = Signal::derive(move || resolved.with(resolve_dataset));
+    let bar_dataset = Signal::derive(move || resolved.with(resolve_bar_dataset));
+    let pie = Signal::derive(move || pie_entries(&resolved.with(resolve_bar_dataset)));
+    let grid = Signal::derive(move || resolved.with(resolve_grid));
+    let groups = Signal::derive(move || resolved.with(resolve_groups));
+    let boxes = Signal::derive(move || {
+        resolved
+            .with(resolve_groups)
+            .into_iter()
+            .map(|g| BoxGroup {
+                label: g.name,
+                data: g.values,
+            })
+            .collect::<Vec<_>>()
+    });
+    let ohlc = Signal::derive(move || resolved.with(resolve_ohlc));
+    let sankey = Signal::derive(move || resolved.with(resolve_sankey));
+    let chord = Signal::derive(move || resolved.with(resolve_chord));
+    // Cyclic flows resolve to a chord matrix instead of a Sankey graph
+    let circular = Memo::new(move |_| resolved.with(|r| matches!(r, Ok(ChartData::Chord(_)))));
 
     let mark = Memo::new(move |_| spec.get().mark);
+    // Memo on the message so data updates don't rebuild a valid chart
+    let error = Memo::new(move |_| resolved.with(|r| r.as_ref().err().map(|e| e.to_string())));
 
     view! {
         <div style="width: 100%; height: 100%;">
-            {move || match mark.get() {
-                Mark::Line => view! { <LineChart data=dataset config=resolved_config /> }.into_any(),
-                Mark::Area => view! { <AreaChart data=dataset config=resolved_config /> }.into_any(),
-                Mark::Bar => {
-                    view! {
-                        <BarChart
-                            data=bar_dataset
-                            orientation=BarOrientation::Vertical
-                            mode=BarMode::Grouped
-                            config=resolved_config
-                        />
-                    }
-                        .into_any()
+            {move || {
+                if let Some(message) = error.get() {
+                    return view! { <ChartError message=message config=resolved_config /> }
+                        .into_any();
                 }
-                Mark::Point | Mark::Circle => {
-                    view! { <ScatterChart data=dataset config=resolved_config /> }.into_any()
+                match mark.get() {
+                    Mark::Line => view! { <LineChart data=dataset config=resolved_config /> }.into_any(),
+                    Mark::Area => view! { <AreaChart data=dataset config=resolved_config /> }.into_any(),
+                    Mark::Bar => {
+                        view! {
+                            <BarChart
+                                data=bar_dataset
+                                orientation=BarOrientation::Vertical
+                                mode=BarMode::Grouped
+                                config=resolved_config
+                            />
+                        }
+                            .into_any()
+                    }
+                    Mark::Point | Mark::Circle => {
+                        view! { <ScatterChart data=dataset config=resolved_config /> }.into_any()
+                    }
+                    Mark::Rect => view! { <HeatmapChart data=grid config=resolved_config /> }.into_any(),
+                    Mark::Arc => view! { <PieChart data=pie config=resolved_config /> }.into_any(),
+                    Mark::Boxplot => view! { <BoxPlot data=boxes config=resolved_config /> }.into_any(),
+                    Mark::Tick => view! { <StripChart data=groups config=resolved_config /> }.into_any(),
+                    Mark::Candlestick => {
+                        view! { <CandlestickChart data=ohlc config=resolved_config /> }.into_any()
+                    }
+                    Mark::Flow if circular.get() => {
+                        view! { <ChordChart data=chord config=resolved_config /> }.into_any()
+                    }
+                    Mark::Flow => view! { <SankeyChart data=sankey config=resolved_config /> }.into_any(),
+                    // Layer-only marks (`Rule`, `Text`): rejected by `resolve()`, so the
+                    // error view shows instead
+                    _ => ().into_any(),
                 }
             }}
         </div>
     }
 }
+
+/// Placeholder shown instead of a chart whose spec cannot be rendered.
+#[component]
+pub fn ChartError(
+    /// Explanation shown to the user (usually a `SpecError` message)
+    message: String,
+    /// Chart config, for theme colors and dimensions
+    config: Signal<ChartConfig>,
+) -> impl IntoView {
+    let style = move || {
+        let cfg = config.get();
+        let th = cfg.theme.unwrap_or_default();
+        let height = cfg.height.map_or("100%".to_string(), |h| format!("{h}px"));
+        format!(
+            "width: 100%; height: {height}; box-sizing: border-box; display: flex; \
+             flex-direction: column; align-items: center; justify-content: center; \
+             gap: 6px; padding: 16px; text-align: center; \
+             border: 1px dashed {}; background: {}; color: {}; font-family: {}; \
+             font-size: {}px;",
+            th.axis_color, th.background_color, th.text_color, th.font_family, th.axis_font_size
+        )
+    };
+    let title = move || config.get().title.unwrap_or_else(|| "Chart".to_string());
+
+    view! {
+        <div class="lodviz-chart-error" role="alert" style=style>
+            <strong>{move || format!("{} cannot be displayed", title())}</strong>
+            <span>{message}</span>
+        </div>
+    }
+}
@@ -318,6 +318,7 @@ pub fn WaterfallChart(
                                     stroke=theme.get().axis_color
                                     font_size=theme.get().axis_font_size
                                     label=y_label_clone.clone()
+                                    format=config.get().y_number_format()
                                 />
                             }
                         }} // Tooltip overlay (last = captures mouse events above chart content)
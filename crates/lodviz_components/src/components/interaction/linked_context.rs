@@ -15,6 +15,8 @@ pub struct DashboardContext {
     pub hover_x: RwSignal<Option<f64>>,
     /// Current brush/click selection (shared across charts)
     pub selection: RwSignal<Option<Selection>>,
+    /// Source record (`PointAttributes::row`) of the hovered point
+    pub hover_row: RwSignal<Option<usize>>,
 }
 
 impl DashboardContext {
@@ -23,6 +25,7 @@ impl DashboardContext {
         Self {
             hover_x: RwSignal::new(None),
             selection: RwSignal::new(None),
+            hover_row: RwSignal::new(None),
         }
     }
 }
@@ -8,7 +8,7 @@
 /// |-------------|----------|
 /// | **Ctrl + drag** | Box zoom: draws a selection rectangle and zooms to the selected area |
 /// | **Double click** | Reset zoom: restores the original unzoomed domain |
-/// | **Mouse wheel** | Zoom in/out centered at cursor position (if `enable_zoom = true`) |
+/// | **Mouse wheel** | Zoom in/out centered at cursor position (if `wheel_zoom = true`) |
 /// | **Mouse move** | Updates cursor position for tooltips (via `set_cursor` prop) |
 ///
 /// # Example
@@ -30,6 +30,7 @@
 /// }
 /// ```
 use leptos::prelude::*;
+use lodviz_core::core::scale::{AxisScale, Scale, ScaleType};
 use wasm_bindgen::JsCast;
 // use web_sys::MouseEvent; // Unused import
 /// Transform state for zoom and pan
@@ -56,7 +57,8 @@ impl ZoomTransform {
         }
     }
 
-    /// Apply zoom centered at a normalized position (0..1)
+    /// Apply zoom centered at a normalized position (0..1), linearly in
+    /// domain units; see [`ZoomTransform::zoom_scaled`] for non-linear axes
     pub fn zoom(&self, factor: f64, center_x: f64, center_y: f64) -> Self {
         let x_range = self.x_max - self.x_min;
         let y_range = self.y_max - self.y_min;
@@ -139,6 +141,47 @@ impl ZoomTransform {
         }
     }
 
+    /// Box zoom on axes of the given scale kinds: the selection corners
+    /// are inverted through those scales, so e.g. a log axis zooms to the
+    /// decades under the box rather than a linear slice of the domain
+    pub fn zoom_to_box_scaled(
+        &self,
+        (x1, y1, x2, y2): (f64, f64, f64, f64),
+        x_kind: ScaleType,
+        y_kind: ScaleType,
+    ) -> Self {
+        let xs = AxisScale::new(x_kind, (self.x_min, self.x_max), (0.0, 1.0));
+        // Normalized y runs top-down: 0 = y_max, 1 = y_min
+        let ys = AxisScale::new(y_kind, (self.y_min, self.y_max), (1.0, 0.0));
+        Self {
+            x_min: xs.inverse(x1.min(x2)),
+            x_max: xs.inverse(x1.max(x2)),
+            y_min: ys.inverse(y1.max(y2)),
+            y_max: ys.inverse(y1.min(y2)),
+        }
+    }
+
+    /// Wheel zoom on axes of the given scale kinds: the spans are scaled
+    /// in screen space and inverted through those scales, so the value
+    /// under the cursor stays put and a log axis zooms by decades.
+    /// `center_y` runs bottom-up like in [`ZoomTransform::zoom`].
+    pub fn zoom_scaled(
+        &self,
+        factor: f64,
+        (center_x, center_y): (f64, f64),
+        x_kind: ScaleType,
+        y_kind: ScaleType,
+    ) -> Self {
+        let xs = AxisScale::new(x_kind, (self.x_min, self.x_max), (0.0, 1.0));
+        let ys = AxisScale::new(y_kind, (self.y_min, self.y_max), (0.0, 1.0));
+        Self {
+            x_min: xs.inverse(center_x - center_x / factor),
+            x_max: xs.inverse(center_x + (1.0 - center_x) / factor),
+            y_min: ys.inverse(center_y - center_y / factor),
+            y_max: ys.inverse(center_y + (1.0 - center_y) / factor),
+        }
+    }
+
     /// Apply pan by delta in domain units
     pub fn pan(&self, dx: f64, dy: f64) -> Self {
         Self {
@@ -169,8 +212,8 @@ impl ZoomTransform {
 /// - **Double Click**: Immediately resets the zoom to the original domain provided
 ///   via the `original` prop.
 ///
-/// - **Mouse Wheel**: Zooms in/out centered at the cursor position (enabled by default,
-///   controlled by `enable_zoom` prop).
+/// - **Mouse Wheel**: Zooms in/out centered at the cursor position (off by default,
+///   enabled by the `wheel_zoom` prop so the page keeps scrolling over charts).
 ///
 /// # Props
 ///
@@ -178,7 +221,10 @@ impl ZoomTransform {
 /// - `original`: Read-only signal containing the original unzoomed domain (for reset)
 /// - `inner_width` / `inner_height`: Chart area dimensions in pixels
 /// - `enable_zoom`: Enable/disable zoom interactions (default: true)
+/// - `wheel_zoom`: Zoom with the mouse wheel (default: false)
 /// - `set_cursor`: Optional callback to propagate cursor position to tooltips
+/// - `scale_types`: Optional `(x, y)` scale kinds, so box and wheel zoom
+///   follow log / sqrt / symlog axes (linear when omitted)
 #[component]
 pub fn ZoomPan(
     /// Current zoom transform (read/write)
@@ -195,12 +241,18 @@ pub fn ZoomPan(
     /// Enable zoom on scroll
     #[prop(default = true)]
     enable_zoom: bool,
+    /// Zoom with the mouse wheel (off by default)
+    #[prop(optional)]
+    wheel_zoom: bool,
     /// Enable pan on drag
     #[prop(default = true)]
     _enable_pan: bool, // Deprecated/Unused, kept for API compat or future use
     /// Optional cursor position setter (for tooltips)
     #[prop(optional)]
     set_cursor: Option<WriteSignal<Option<(f64, f64)>>>,
+    /// Scale kinds of the x and y axes (linear when omitted)
+    #[prop(optional, into)]
+    scale_types: Option<Signal<(ScaleType, ScaleType)>>,
 ) -> impl IntoView {
     let (selection_start, set_selection_start) = signal(None::<(f64, f64)>);
     let (selection_current, set_selection_current) = signal(None::<(f64, f64)>);
@@ -293,7 +345,10 @@ pub fn ZoomPan(
             // Apply zoom if selection is big enough to be intentional
             if (x1 - x2).abs() > 0.01 && (y1 - y2).abs() > 0.01 {
                 transform.update(|t| {
-                    *t = t.zoom_to_box(x1, y1, x2, y2);
+                    *t = match scale_types.map(|k| k.get()) {
+                        Some((xk, yk)) => t.zoom_to_box_scaled((x1, y1, x2, y2), xk, yk),
+                        None => t.zoom_to_box(x1, y1, x2, y2),
+                    };
                 });
             }
 
@@ -317,6 +372,25 @@ pub fn ZoomPan(
         ev.prevent_default();
     };
 
+    // Wheel zoom around the cursor, through the axis scales
+    let on_wheel = move |ev: web_sys::WheelEvent| {
+        if !enable_zoom || !wheel_zoom || ev.delta_y() == 0.0 {
+            return;
+        }
+        let rect = ev
+            .target()
+            .unwrap()
+            .unchecked_into::<web_sys::Element>()
+            .get_bounding_client_rect();
+        let norm_x = ((ev.client_x() as f64 - rect.left()) / rect.width()).clamp(0.0, 1.0);
+        let norm_y = ((ev.client_y() as f64 - rect.top()) / rect.height()).clamp(0.0, 1.0);
+        let factor = if ev.delta_y() < 0.0 { 1.2 } else { 1.0 / 1.2 };
+        let (xk, yk) = scale_types.map_or((ScaleType::Linear, ScaleType::Linear), |k| k.get());
+        // Screen y runs top-down, zoom centers bottom-up
+        transform.update(|t| *t = t.zoom_scaled(factor, (norm_x, 1.0 - norm_y), xk, yk));
+        ev.prevent_default();
+    };
+
     view! {
         <>
             <rect
@@ -329,6 +403,7 @@ pub fn ZoomPan(
                 on:mouseup=on_mouseup
                 on:mouseleave=on_mouseleave
                 on:dblclick=on_dblclick
+                on:wheel=on_wheel
             />
             {move || {
                 selection_rect
@@ -351,3 +426,52 @@ pub fn ZoomPan(
         </>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: (f64, f64), expected: (f64, f64)) {
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-9 * b.abs().max(1.0);
+        assert!(
+            close(actual.0, expected.0) && close(actual.1, expected.1),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_zoom_scaled_log_keeps_decades() {
+        let t = ZoomTransform::from_domain(1.0, 10_000.0, 1.0, 10_000.0);
+        let zoomed = t.zoom_scaled(2.0, (0.5, 0.5), ScaleType::Log, ScaleType::Linear);
+        assert_close((zoomed.x_min, zoomed.x_max), (10.0, 1000.0));
+        // The linear y axis halves its span around the center value
+        assert_close((zoomed.y_min, zoomed.y_max), (2500.75, 7500.25));
+
+        // The value under the cursor stays put
+        let at_cursor = t.zoom_scaled(4.0, (0.25, 0.0), ScaleType::Log, ScaleType::Log);
+        let xs = AxisScale::new(
+            ScaleType::Log,
+            (at_cursor.x_min, at_cursor.x_max),
+            (0.0, 1.0),
+        );
+        assert_close((xs.inverse(0.25), at_cursor.y_min), (10.0, 1.0));
+    }
+
+    #[test]
+    fn test_zoom_to_box_scaled_log_inverts_corners() {
+        let t = ZoomTransform::from_domain(1.0, 1000.0, 1.0, 1000.0);
+        let third = 1.0 / 3.0;
+        let zoomed = t.zoom_to_box_scaled(
+            (third, third, 2.0 * third, 2.0 * third),
+            ScaleType::Log,
+            ScaleType::Log,
+        );
+        assert_close((zoomed.x_min, zoomed.x_max), (10.0, 100.0));
+        assert_close((zoomed.y_min, zoomed.y_max), (10.0, 100.0));
+
+        // A box in the top-left third: low x decades, high y decades
+        let corner = t.zoom_to_box_scaled((third, 0.0, 0.0, third), ScaleType::Log, ScaleType::Log);
+        assert_close((corner.x_min, corner.x_max), (1.0, 10.0));
+        assert_close((corner.y_min, corner.y_max), (100.0, 1000.0));
+    }
+}
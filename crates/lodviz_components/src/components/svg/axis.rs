@@ -1,6 +1,7 @@
 use leptos::prelude::*;
 use leptos::{component, view, IntoView};
 /// Axis component for X and Y axes with ticks and labels
+use lodviz_core::core::format::NumberFormat;
 use lodviz_core::core::scale::Scale;
 
 /// Axis orientation
@@ -21,6 +22,9 @@ pub enum AxisOrientation {
 /// Axis component for rendering X/Y axes
 ///
 /// Renders an axis line with ticks and labels based on the provided scale.
+/// Tick values and labels come from `Scale::ticks` / `Scale::tick_label`,
+/// so they land on round numbers (roughly `tick_count` of them); a `format`
+/// replaces the scale's labels (e.g. `"$,.0f"`).
 /// Optionally renders an axis label (e.g. "Time", "Amplitude").
 #[component]
 pub fn Axis<S: Scale + Clone + 'static>(
@@ -28,7 +32,7 @@ pub fn Axis<S: Scale + Clone + 'static>(
     orientation: AxisOrientation,
     /// Scale for mapping domain to range
     scale: S,
-    /// Number of ticks to display
+    /// Approximate number of ticks to display
     #[prop(default = 5)]
     tick_count: usize,
     /// Width of the axis (for horizontal) or height (for vertical)
@@ -43,24 +47,25 @@ pub fn Axis<S: Scale + Clone + 'static>(
     /// Optional axis label text
     #[prop(default = None)]
     label: Option<String>,
+    /// Optional number format for tick labels
+    #[prop(default = None)]
+    format: Option<NumberFormat>,
 ) -> impl IntoView {
-    let (domain_min, domain_max) = scale.domain();
     let (range_min, range_max) = scale.range();
 
-    // Generate tick values evenly spaced in the domain
-    let tick_values: Vec<f64> = (0..=tick_count)
-        .map(|i| {
-            let t = i as f64 / tick_count as f64;
-            domain_min + t * (domain_max - domain_min)
+    // Round tick values, their positions and labels
+    let ticks: Vec<(f64, f64, String)> = scale
+        .ticks(tick_count)
+        .into_iter()
+        .map(|value| {
+            let text = match &format {
+                Some(format) => format.format(value),
+                None => scale.tick_label(value, tick_count),
+            };
+            (value, scale.map(value), text)
         })
         .collect();
 
-    // Map tick values to positions
-    let ticks: Vec<(f64, f64)> = tick_values
-        .iter()
-        .map(|&value| (value, scale.map(value)))
-        .collect();
-
     let axis_center = (range_min + range_max) / 2.0;
 
     match orientation {
@@ -81,7 +86,7 @@ pub fn Axis<S: Scale + Clone + 'static>(
                     // Ticks and labels
                     {ticks
                         .iter()
-                        .map(|(value, pos)| {
+                        .map(|(value, pos, text)| {
                             view! {
                                 <g key=*value>
                                     // Tick mark
@@ -101,7 +106,7 @@ pub fn Axis<S: Scale + Clone + 'static>(
                                         font-size=font_size
                                         fill=stroke.clone()
                                     >
-                                        {format!("{:.1}", value)}
+                                        {text.clone()}
                                     </text>
                                 </g>
                             }
@@ -134,7 +139,7 @@ pub fn Axis<S: Scale + Clone + 'static>(
             let label_x_offset = -(45.0 + font_size * 1.2);
             let ticks_view = ticks
                 .iter()
-                .map(|(value, pos)| {
+                .map(|(value, pos, text)| {
                     view! {
                         <g key=*value>
                             <line
@@ -153,7 +158,7 @@ pub fn Axis<S: Scale + Clone + 'static>(
                                 font-size=font_size
                                 fill=stroke.clone()
                             >
-                                {format!("{:.1}", value)}
+                                {text.clone()}
                             </text>
                         </g>
                     }
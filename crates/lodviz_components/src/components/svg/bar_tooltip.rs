@@ -4,7 +4,8 @@
 /// then shows all visible series values for that category.
 /// Supports grouped and stacked modes (with percentages and total).
 use leptos::prelude::*;
-use lodviz_core::core::scale::{BandScale, LinearScale};
+use lodviz_core::core::format::NumberFormat;
+use lodviz_core::core::scale::{AxisScale, BandScale};
 use lodviz_core::core::theme::Margin;
 
 use crate::components::charts::bar_chart::{BarMode, BarOrientation};
@@ -33,7 +34,7 @@ pub fn BarTooltip(
     band_scale: Memo<BandScale>,
     /// Linear scale for the value axis
     #[allow(unused)]
-    value_scale: Memo<LinearScale>,
+    value_scale: Memo<AxisScale>,
     /// Inner width of the chart area
     inner_width: Memo<f64>,
     /// Inner height of the chart area
@@ -44,7 +45,14 @@ pub fn BarTooltip(
     mode: BarMode,
     /// Chart margins (to correct SVG offset coordinates)
     margin: Memo<Margin>,
+    /// Optional number format for values and the stacked total
+    #[prop(optional, into)]
+    value_format: Option<Signal<Option<NumberFormat>>>,
 ) -> impl IntoView {
+    let format_value = move |v: f64| match value_format.and_then(|f| f.get()) {
+        Some(format) => format.format(v),
+        None => format!("{v:.1}"),
+    };
     let (mouse_pos, set_mouse_pos) = signal(None::<(f64, f64)>);
 
     // Determine which category index is hovered
@@ -199,9 +207,9 @@ pub fn BarTooltip(
                                 let ty = box_y + padding + header_h + 12.0 + i as f64 * row_h;
                                 let label = if is_stacked && total > 0.0 {
                                     let pct = val / total * 100.0;
-                                    format!("{name}: {val:.1} ({pct:.0}%)")
+                                    format!("{name}: {} ({pct:.0}%)", format_value(*val))
                                 } else {
-                                    format!("{name}: {val:.1}")
+                                    format!("{name}: {}", format_value(*val))
                                 };
                                 view! {
                                     <g>
@@ -238,7 +246,7 @@ pub fn BarTooltip(
                                         fill="#aaa"
                                         font-family="monospace"
                                     >
-                                        {format!("Total: {total:.1}")}
+                                        {format!("Total: {}", format_value(total))}
                                     </text>
                                 },
                             )
@@ -1,6 +1,7 @@
 /// ColorBar component: a vertical gradient bar with tick labels
 use leptos::prelude::*;
 use lodviz_core::core::color_map::ColorMap;
+use lodviz_core::core::format::NumberFormat;
 
 /// A vertical color legend (gradient bar + tick labels) for continuous color scales.
 #[component]
@@ -23,6 +24,9 @@ pub fn ColorBar(
     text_color: String,
     /// Font size for tick labels
     font_size: f64,
+    /// Optional number format for tick labels
+    #[prop(default = None)]
+    format: Option<NumberFormat>,
 ) -> impl IntoView {
     // Build N evenly-spaced gradient stops
     let n_stops = 20_usize;
@@ -49,7 +53,9 @@ pub fn ColorBar(
             };
             let value = max_value - t * (max_value - min_value);
             let y = t * height;
-            let label = if (value.abs() >= 1000.0) || (value != 0.0 && value.abs() < 0.01) {
+            let label = if let Some(format) = &format {
+                format.format(value)
+            } else if (value.abs() >= 1000.0) || (value != 0.0 && value.abs() < 0.01) {
                 format!("{value:.2e}")
             } else {
                 format!("{value:.2}")
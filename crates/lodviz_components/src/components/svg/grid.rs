@@ -4,7 +4,7 @@ use lodviz_core::core::theme::GridStyle;
 
 /// Grid component for rendering background gridlines
 ///
-/// Renders horizontal and/or vertical gridlines based on the scales and style.
+/// Renders horizontal and/or vertical gridlines at the scales' round ticks.
 #[component]
 pub fn Grid<XS: Scale + Clone + 'static, YS: Scale + Clone + 'static>(
     /// X scale for vertical gridlines
@@ -26,13 +26,7 @@ pub fn Grid<XS: Scale + Clone + 'static, YS: Scale + Clone + 'static>(
 
     // Vertical gridlines (x-axis ticks)
     let vertical_lines = if gs.show_x {
-        let (x_domain_min, x_domain_max) = x_scale.domain();
-        let x_tick_values: Vec<f64> = (0..=tick_count)
-            .map(|i| {
-                let t = i as f64 / tick_count as f64;
-                x_domain_min + t * (x_domain_max - x_domain_min)
-            })
-            .collect();
+        let x_tick_values = x_scale.ticks(tick_count);
 
         let color = gs.color.clone();
         let opacity = gs.opacity;
@@ -66,13 +60,7 @@ pub fn Grid<XS: Scale + Clone + 'static, YS: Scale + Clone + 'static>(
 
     // Horizontal gridlines (y-axis ticks)
     let horizontal_lines = if gs.show_y {
-        let (y_domain_min, y_domain_max) = y_scale.domain();
-        let y_tick_values: Vec<f64> = (0..=tick_count)
-            .map(|i| {
-                let t = i as f64 / tick_count as f64;
-                y_domain_min + t * (y_domain_max - y_domain_min)
-            })
-            .collect();
+        let y_tick_values = y_scale.ticks(tick_count);
 
         let color = gs.color.clone();
         let opacity = gs.opacity;
@@ -1,5 +1,6 @@
 /// Tooltip component for heatmap cells
 use leptos::prelude::*;
+use lodviz_core::core::format::NumberFormat;
 
 /// Floating tooltip that displays the row/column label and value for a hovered heatmap cell.
 #[component]
@@ -20,6 +21,9 @@ pub fn HeatmapTooltip(
     inner_height: Signal<f64>,
     /// Text color
     text_color: String,
+    /// Optional number format for the value
+    #[prop(optional, into)]
+    value_format: Option<Signal<Option<NumberFormat>>>,
 ) -> impl IntoView {
     view! {
         {move || {
@@ -31,7 +35,11 @@ pub fn HeatmapTooltip(
             let w = inner_width.get();
             let h = inner_height.get();
             let tc = text_color.clone();
-            let label = format!("({row}, {col}): {val:.3}");
+            let val = match value_format.and_then(|f| f.get()) {
+                Some(format) => format.format(val),
+                None => format!("{val:.3}"),
+            };
+            let label = format!("({row}, {col}): {val}");
             let box_w = 8.0 + (label.len() as f64) * 6.5;
             let box_h = 28.0;
             let bx = if mx + box_w + 12.0 > w { mx - box_w - 12.0 } else { mx + 12.0 };
@@ -2,10 +2,16 @@
 ///
 /// Shows crosshair + nearest point info per visible series on mouse hover.
 /// Uses binary search via `find_nearest_point` for O(log n) lookup.
+/// When per-point attributes are supplied, their id and tooltip fields are
+/// listed under each series and the hovered record's row is published to
+/// the `DashboardContext` for linked highlighting.
+use crate::components::interaction::linked_context::DashboardContext;
 use leptos::prelude::*;
 use lodviz_core::algorithms::nearest::find_nearest_point;
-use lodviz_core::core::data::DataPoint;
-use lodviz_core::core::scale::{LinearScale, Scale};
+use lodviz_core::core::data::{DataPoint, PointAttributes};
+use lodviz_core::core::datetime::time_label;
+use lodviz_core::core::format::NumberFormat;
+use lodviz_core::core::scale::{AxisScale, Scale};
 
 /// Tooltip selection mode
 #[derive(Clone, Copy, PartialEq, Default, Debug)]
@@ -17,6 +23,36 @@ pub enum TooltipMode {
     Euclidean,
 }
 
+/// Nearest point of one series under the cursor
+#[derive(Clone, PartialEq)]
+struct HoverPoint {
+    name: String,
+    color: String,
+    point: DataPoint,
+    /// Source record of the point (see `PointAttributes::row_of`)
+    row: Option<usize>,
+    /// Extra `(label, value)` lines from the point's attributes
+    lines: Vec<(String, String)>,
+}
+
+/// Format a tooltip value with the optional format, else two decimals
+fn format_value(format: Option<Signal<Option<NumberFormat>>>, value: f64) -> String {
+    match format.and_then(|f| f.get()) {
+        Some(format) => format.format(value),
+        None => format!("{value:.2}"),
+    }
+}
+
+/// Format the header x value: the x format when set, else a calendar label
+/// on time scales (like the axis ticks), else two decimals
+fn format_x(format: Option<Signal<Option<NumberFormat>>>, scale: &AxisScale, value: f64) -> String {
+    match (format.and_then(|f| f.get()), scale) {
+        (Some(format), _) => format.format(value),
+        (None, AxisScale::Time(time)) => time_label(value, time.timezone()),
+        (None, _) => format!("{value:.2}"),
+    }
+}
+
 /// Tooltip overlay for chart interaction (multi-series)
 #[component]
 pub fn Tooltip(
@@ -25,9 +61,9 @@ pub fn Tooltip(
     /// Colors for each visible series (parallel to series_data)
     series_colors: Memo<Vec<String>>,
     /// X scale for pixel-to-data conversion
-    x_scale: Memo<LinearScale>,
+    x_scale: Memo<AxisScale>,
     /// Y scale for pixel-to-data conversion
-    y_scale: Memo<LinearScale>,
+    y_scale: Memo<AxisScale>,
     /// Inner width of the chart area
     inner_width: Memo<f64>,
     /// Inner height of the chart area
@@ -41,6 +77,16 @@ pub fn Tooltip(
     /// Tooltip selection mode
     #[prop(optional, default = TooltipMode::BisectX)]
     mode: TooltipMode,
+    /// Per-point attributes (parallel to series_data, optional)
+    #[prop(optional, into)]
+    series_attributes: Option<Signal<Vec<PointAttributes>>>,
+    /// Optional number format for the x value in the header (time axes
+    /// fall back to calendar labels, others to two decimals)
+    #[prop(optional, into)]
+    x_format: Option<Signal<Option<NumberFormat>>>,
+    /// Optional number format for the series values
+    #[prop(optional, into)]
+    y_format: Option<Signal<Option<NumberFormat>>>,
 ) -> impl IntoView {
     // If external cursor is provided, use it. Otherwise use internal tracking (backward compat or standalone use).
 
@@ -72,18 +118,30 @@ pub fn Tooltip(
         let (mx, my) = effective_mouse_pos.get()?;
         let series = series_data.get();
         let colors = series_colors.get();
+        let attributes = series_attributes.map(|a| a.get()).unwrap_or_default();
         let xs = x_scale.get();
         let ys = y_scale.get();
+        let hover = |si: usize, name: &String, color: &String, idx: usize, pt: DataPoint| {
+            let attrs = attributes.get(si);
+            HoverPoint {
+                name: name.clone(),
+                color: color.clone(),
+                point: pt,
+                row: attrs.map_or(Some(idx), |a| a.row_of(idx)),
+                lines: attrs.map(|a| a.tooltip_lines(idx)).unwrap_or_default(),
+            }
+        };
 
         match mode {
             TooltipMode::BisectX => {
                 let data_x = xs.inverse(mx);
-                let results: Vec<(String, String, DataPoint)> = series
+                let results: Vec<HoverPoint> = series
                     .iter()
                     .zip(colors.iter())
-                    .filter_map(|((name, pts), color)| {
-                        let (_, pt) = find_nearest_point(pts, data_x)?;
-                        Some((name.clone(), color.clone(), *pt))
+                    .enumerate()
+                    .filter_map(|(si, ((name, pts), color))| {
+                        let (idx, pt) = find_nearest_point(pts, data_x)?;
+                        Some(hover(si, name, color, idx, *pt))
                     })
                     .collect();
 
@@ -96,13 +154,13 @@ pub fn Tooltip(
             TooltipMode::Euclidean => {
                 // Find single closest point across ALL series
                 let mut min_dist_sq = f64::MAX;
-                let mut best_match: Option<(String, String, DataPoint)> = None;
+                let mut best_match: Option<HoverPoint> = None;
 
                 // Maximum distance in pixels to trigger tooltip (e.g., 50px radius)
                 let max_dist_sq = 50.0 * 50.0;
 
-                for ((name, pts), color) in series.iter().zip(colors.iter()) {
-                    for pt in pts {
+                for (si, ((name, pts), color)) in series.iter().zip(colors.iter()).enumerate() {
+                    for (idx, pt) in pts.iter().enumerate() {
                         let px = xs.map(pt.x);
                         let py = ys.map(pt.y);
                         let dx = px - mx;
@@ -111,7 +169,7 @@ pub fn Tooltip(
 
                         if dist_sq < min_dist_sq && dist_sq < max_dist_sq {
                             min_dist_sq = dist_sq;
-                            best_match = Some((name.clone(), color.clone(), *pt));
+                            best_match = Some(hover(si, name, color, idx, *pt));
                         }
                     }
                 }
@@ -121,6 +179,15 @@ pub fn Tooltip(
         }
     });
 
+    // Publish the hovered record for linked highlighting (attributed series only)
+    if let (Some(ctx), Some(_)) = (use_context::<DashboardContext>(), series_attributes) {
+        Effect::new(move |_| {
+            let row =
+                nearest_points.with(|r| r.as_ref().and_then(|r| r.first()).and_then(|h| h.row));
+            ctx.hover_row.set(row);
+        });
+    }
+
     view! {
         // Invisible rect to capture mouse events over the entire chart area
         // Only render if external cursor is NOT provided (fallback mode)
@@ -158,12 +225,20 @@ pub fn Tooltip(
             let h = inner_height.get();
             let w = inner_width.get();
             let first = &results[0];
-            let cx = xs.map(first.2.x);
+            let x_text = format_x(x_format, &xs, first.point.x);
+            let cx = xs.map(first.point.x);
             let n_series = results.len();
-            let box_h = 20.0 + n_series as f64 * 18.0;
-            let box_w = 120.0_f64;
+            let n_lines: usize = results.iter().map(|r| r.lines.len()).sum();
+            let box_h = 20.0 + n_series as f64 * 18.0 + n_lines as f64 * 14.0;
+            // ~6px per monospace character at font-size 10
+            let longest = results
+                .iter()
+                .flat_map(|r| r.lines.iter().map(|(k, v)| k.len() + v.len() + 2))
+                .max()
+                .unwrap_or(0);
+            let box_w = (24.0 + longest as f64 * 6.0).max(120.0);
             let box_x = if cx > w * 0.7 { cx - box_w - 10.0 } else { cx + 10.0 };
-            let first_cy = ys.map(first.2.y);
+            let first_cy = ys.map(first.point.y);
             let box_y = if first_cy > h * 0.7 { first_cy - box_h - 10.0 } else { first_cy - 10.0 };
             Some(
 
@@ -188,16 +263,16 @@ pub fn Tooltip(
                         // Highlight circles on each series point
                         {results
                             .iter()
-                            .map(|(_, color, pt)| {
-                                let px = xs.map(pt.x);
-                                let py = ys.map(pt.y);
+                            .map(|r| {
+                                let px = xs.map(r.point.x);
+                                let py = ys.map(r.point.y);
                                 view! {
                                     <circle
                                         cx=format!("{px:.2}")
                                         cy=format!("{py:.2}")
                                         r="5"
                                         fill="white"
-                                        stroke=color.clone()
+                                        stroke=r.color.clone()
                                         stroke-width="2"
                                     />
                                 }
@@ -223,15 +298,38 @@ pub fn Tooltip(
                             font-family="monospace"
                             font-weight="bold"
                         >
-                            {format!("x: {:.2}", first.2.x)}
+                            {format!("x: {x_text}")}
                         </text>
 
-                        // One row per series
+                        // One row per series, followed by its attribute lines
                         {results
                             .iter()
                             .enumerate()
-                            .map(|(i, (name, color, pt))| {
-                                let ty = box_y + 28.0 + i as f64 * 18.0;
+                            .map(|(i, r)| {
+                                let lines_before: usize = results[..i]
+                                    .iter()
+                                    .map(|r| r.lines.len())
+                                    .sum();
+                                let ty = box_y + 28.0 + i as f64 * 18.0
+                                    + lines_before as f64 * 14.0;
+                                let details = r
+                                    .lines
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(k, (label, value))| {
+                                        view! {
+                                            <text
+                                                x=format!("{:.2}", box_x + 20.0)
+                                                y=format!("{:.2}", ty + 14.0 * (k + 1) as f64)
+                                                font-size="10"
+                                                fill="#ddd"
+                                                font-family="monospace"
+                                            >
+                                                {format!("{label}: {value}")}
+                                            </text>
+                                        }
+                                    })
+                                    .collect_view();
                                 view! {
                                     <g>
                                         // Color dot
@@ -239,7 +337,7 @@ pub fn Tooltip(
                                             cx=format!("{:.2}", box_x + 12.0)
                                             cy=format!("{:.2}", ty - 3.0)
                                             r="4"
-                                            fill=color.clone()
+                                            fill=r.color.clone()
                                         />
                                         // Series name + value
                                         <text
@@ -249,8 +347,9 @@ pub fn Tooltip(
                                             fill="white"
                                             font-family="monospace"
                                         >
-                                            {format!("{}: {:.2}", name, pt.y)}
+                                            {format!("{}: {}", r.name, format_value(y_format, r.point.y))}
                                         </text>
+                                        {details}
                                     </g>
                                 }
                             })
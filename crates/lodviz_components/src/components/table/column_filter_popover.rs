@@ -33,6 +33,7 @@ pub fn ColumnFilterPopover(
                 width: None,
                 alignment: Alignment::Left,
                 conditional: None,
+                number_format: None,
             })
     });
     // ── Temporary form states ───────────────────────────────────────────────
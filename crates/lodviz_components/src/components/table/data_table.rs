@@ -6,9 +6,7 @@
 use std::collections::{HashMap, HashSet};
 
 use leptos::prelude::*;
-use lodviz_core::core::table_data::{
-    color_scale_bg, data_bar_pct, format_cell_value, ConditionalRule, TableData,
-};
+use lodviz_core::core::table_data::{color_scale_bg, data_bar_pct, ConditionalRule, TableData};
 
 use crate::hooks::use_container_size::use_container_size;
 
@@ -359,7 +357,7 @@ pub fn DataTable(
                                                         .unwrap_or(
                                                             lodviz_core::core::field_value::FieldValue::Null,
                                                         );
-                                                    let text = format_cell_value(&val);
+                                                    let text = col_def.format_value(&val);
                                                     let bg_color = match &col_def.conditional {
                                                         Some(ConditionalRule::ColorScale { low, mid, high }) => {
                                                             color_scale_bg(
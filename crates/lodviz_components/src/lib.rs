@@ -55,12 +55,14 @@ pub use components::charts::area_chart::AreaChart;
 pub use components::charts::bar_chart::BarChart;
 pub use components::charts::box_plot::{BoxGroup, BoxPlot, ViolinChart};
 pub use components::charts::candlestick::CandlestickChart;
+pub use components::charts::facet_chart::FacetChart;
 pub use components::charts::histogram::Histogram;
+pub use components::charts::layered_chart::LayeredChart;
 pub use components::charts::line_chart::LineChart;
 pub use components::charts::pie_chart::PieChart;
 pub use components::charts::radar::{RadarChart, RadarSeries};
 pub use components::charts::scatter_chart::ScatterChart;
-pub use components::charts::smart_chart::SmartChart;
+pub use components::charts::smart_chart::{ChartError, SmartChart};
 pub use components::charts::waterfall::WaterfallChart;
 pub use components::interaction::brush::Brush;
 pub use components::interaction::linked_context::{DashboardContext, LinkedDashboard};
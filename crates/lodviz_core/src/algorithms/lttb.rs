@@ -41,24 +41,35 @@ use crate::core::data::DataPoint;
 /// assert_eq!(downsampled.len(), 100);
 /// ```
 pub fn lttb_downsample(data: &[DataPoint], threshold: usize) -> Vec<DataPoint> {
+    lttb_indices(data, threshold)
+        .into_iter()
+        .map(|i| data[i])
+        .collect()
+}
+
+/// Indices (ascending) of the points `lttb_downsample` would keep.
+///
+/// Use this to downsample data carried alongside the points (see
+/// `Series::downsample_lttb`).
+pub fn lttb_indices(data: &[DataPoint], threshold: usize) -> Vec<usize> {
     // Edge cases
     if threshold >= data.len() || threshold == 0 {
-        return data.to_vec();
+        return (0..data.len()).collect();
     }
 
     if threshold == 1 {
-        return vec![data[0]];
+        return vec![0];
     }
 
     if threshold == 2 {
-        return vec![data[0], data[data.len() - 1]];
+        return vec![0, data.len() - 1];
     }
 
     // Allocate output vector
     let mut sampled = Vec::with_capacity(threshold);
 
     // Always include first point
-    sampled.push(data[0]);
+    sampled.push(0);
 
     // Bucket size (excluding first and last points)
     let bucket_size = (data.len() - 2) as f64 / (threshold - 2) as f64;
@@ -113,12 +124,12 @@ pub fn lttb_downsample(data: &[DataPoint], threshold: usize) -> Vec<DataPoint> {
         }
 
         // Select the point with the largest triangle area
-        sampled.push(data[max_area_point]);
+        sampled.push(max_area_point);
         point_index = max_area_point;
     }
 
     // Always include last point
-    sampled.push(data[data.len() - 1]);
+    sampled.push(data.len() - 1);
 
     sampled
 }
@@ -233,6 +244,18 @@ mod tests {
         assert!(downsampled.iter().all(|p| (p.y - 5.0).abs() < 1e-10));
     }
 
+    #[test]
+    fn test_lttb_indices_match_points() {
+        let data: Vec<DataPoint> = (0..500)
+            .map(|i| DataPoint::new(i as f64, (i as f64 * 0.05).cos()))
+            .collect();
+
+        let indices = lttb_indices(&data, 40);
+        let points: Vec<DataPoint> = indices.iter().map(|&i| data[i]).collect();
+        assert_eq!(points, lttb_downsample(&data, 40));
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+    }
+
     #[test]
     fn test_lttb_monotonicity() {
         // Test with strictly increasing data
@@ -12,11 +12,22 @@ use crate::core::data::DataPoint;
 ///
 /// Returns `data.to_vec()` unchanged when `data.len() <= 4 * n_pixels`.
 pub fn m4_downsample(data: &[DataPoint], n_pixels: usize) -> Vec<DataPoint> {
+    m4_indices(data, n_pixels)
+        .into_iter()
+        .map(|i| data[i])
+        .collect()
+}
+
+/// Indices of the points `m4_downsample` would keep, in output order.
+///
+/// Use this to downsample data carried alongside the points (see
+/// `Series::downsample_m4`).
+pub fn m4_indices(data: &[DataPoint], n_pixels: usize) -> Vec<usize> {
     if data.is_empty() || n_pixels == 0 {
         return vec![];
     }
     if data.len() <= 4 * n_pixels {
-        return data.to_vec();
+        return (0..data.len()).collect();
     }
 
     let x_min = data[0].x;
@@ -25,58 +36,69 @@ pub fn m4_downsample(data: &[DataPoint], n_pixels: usize) -> Vec<DataPoint> {
 
     if x_range <= 0.0 {
         // All points at the same x — nothing to aggregate further
-        return data.to_vec();
+        return (0..data.len()).collect();
     }
 
     let bucket_width = x_range / n_pixels as f64;
-    let mut result: Vec<DataPoint> = Vec::with_capacity(4 * n_pixels);
+    let mut result: Vec<usize> = Vec::with_capacity(4 * n_pixels);
 
     for bucket_idx in 0..n_pixels {
         let bucket_start = x_min + bucket_idx as f64 * bucket_width;
         let bucket_end = bucket_start + bucket_width;
 
         // Include right-endpoint for the last bucket
-        let in_bucket = |p: &&DataPoint| {
+        let in_bucket = |i: &usize| {
+            let p = &data[*i];
             p.x >= bucket_start && (p.x < bucket_end || bucket_idx == n_pixels - 1)
         };
 
-        let bucket_points: Vec<&DataPoint> = data.iter().filter(in_bucket).collect();
+        let bucket_points: Vec<usize> = (0..data.len()).filter(in_bucket).collect();
 
         if bucket_points.is_empty() {
             continue;
         }
 
-        let first = *bucket_points[0];
-        let last = *bucket_points[bucket_points.len() - 1];
+        let first = bucket_points[0];
+        let last = bucket_points[bucket_points.len() - 1];
 
+        let by_y = |a: &usize, b: &usize| {
+            data[*a]
+                .y
+                .partial_cmp(&data[*b].y)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        };
         let min_pt = *bucket_points
             .iter()
-            .copied()
-            .min_by(|a, b| a.y.partial_cmp(&b.y).unwrap_or(std::cmp::Ordering::Equal))
+            .min_by(|a, b| by_y(a, b))
             .expect("bucket is non-empty");
-
         let max_pt = *bucket_points
             .iter()
-            .copied()
-            .max_by(|a, b| a.y.partial_cmp(&b.y).unwrap_or(std::cmp::Ordering::Equal))
+            .max_by(|a, b| by_y(a, b))
             .expect("bucket is non-empty");
 
         // Collect unique points for this bucket, ordered by x
         let mut pts = [first, last, min_pt, max_pt];
-        pts.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap_or(std::cmp::Ordering::Equal));
+        pts.sort_by(|a, b| {
+            data[*a]
+                .x
+                .partial_cmp(&data[*b].x)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
 
         // Push with de-duplication by (x, y) equality
-        for pt in pts {
+        for i in pts {
+            let pt = data[i];
             if result
                 .last()
-                .map(|prev: &DataPoint| {
+                .map(|&prev| {
+                    let prev = data[prev];
                     (prev.x - pt.x).abs() < f64::EPSILON && (prev.y - pt.y).abs() < f64::EPSILON
                 })
                 .unwrap_or(false)
             {
                 continue;
             }
-            result.push(pt);
+            result.push(i);
         }
     }
 
@@ -128,6 +150,13 @@ mod tests {
         assert!(m4_downsample(&[], 100).is_empty());
     }
 
+    #[test]
+    fn test_m4_indices_match_points() {
+        let data = make_points(2000);
+        let points: Vec<DataPoint> = m4_indices(&data, 50).iter().map(|&i| data[i]).collect();
+        assert_eq!(points, m4_downsample(&data, 50));
+    }
+
     #[test]
     fn test_m4_zero_pixels() {
         let data = make_points(100);
@@ -1,5 +1,6 @@
 /// Statistical utility functions for data analysis
-///
+use serde::{Deserialize, Serialize};
+
 /// Calculate the extent (min, max) of a dataset
 ///
 /// Returns `None` if the slice is empty.
@@ -54,6 +55,18 @@ pub fn median(data: &mut [f64]) -> Option<f64> {
     }
 }
 
+/// Calculate the `p`-quantile (`p` in `[0, 1]`, clamped) with linear interpolation
+///
+/// Returns `None` if the slice is empty.
+/// Note: This function sorts the data, so it modifies the input vector.
+pub fn quantile(data: &mut [f64], p: f64) -> Option<f64> {
+    if data.is_empty() {
+        return None;
+    }
+    data.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    Some(percentile_sorted(data, p.clamp(0.0, 1.0)))
+}
+
 /// Calculate the standard deviation of a dataset
 ///
 /// Returns `None` if the slice has fewer than 2 elements.
@@ -98,7 +111,8 @@ pub struct BoxPlotStats {
 }
 
 /// Rule for determining histogram bin count
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum BinRule {
     /// Sturges: k = ceil(log2(n) + 1)
     Sturges,
@@ -452,6 +466,15 @@ mod tests {
         assert!(median(&mut data).is_none());
     }
 
+    #[test]
+    fn test_quantile() {
+        let mut data = vec![4.0, 1.0, 3.0, 2.0, 5.0];
+        assert!((quantile(&mut data, 0.25).unwrap() - 2.0).abs() < EPSILON);
+        assert!((quantile(&mut data, 0.9).unwrap() - 4.6).abs() < EPSILON);
+        assert!((quantile(&mut data, 2.0).unwrap() - 5.0).abs() < EPSILON);
+        assert!(quantile(&mut [], 0.5).is_none());
+    }
+
     #[test]
     fn test_std_dev_basic() {
         let data = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
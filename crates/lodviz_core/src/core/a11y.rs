@@ -39,6 +39,14 @@ pub fn generate_chart_description(
         Mark::Bar => "Bar",
         Mark::Point => "Scatter",
         Mark::Circle => "Bubble",
+        Mark::Rule => "Rule",
+        Mark::Text => "Text",
+        Mark::Rect => "Heatmap",
+        Mark::Arc => "Pie",
+        Mark::Boxplot => "Box plot",
+        Mark::Tick => "Strip",
+        Mark::Candlestick => "Candlestick",
+        Mark::Flow => "Flow",
     };
 
     let mut desc = format!("{mark_name} chart with {data_len} data points.");
@@ -0,0 +1,397 @@
+/// Arrow IPC and Parquet ingestion (requires the `arrow` feature)
+///
+/// Both readers take the raw file bytes, so they work the same in the
+/// browser (e.g. from a `fetch` response) as on native targets. Arrow types
+/// map onto `DataTable` columns as follows:
+///
+/// | Arrow                                   | Column            |
+/// |-----------------------------------------|-------------------|
+/// | integers, floats, decimals              | `Numeric`         |
+/// | `Boolean`                               | `Bool`            |
+/// | `Utf8`, `LargeUtf8`, `Utf8View`         | `Text`            |
+/// | `Dictionary<_, T>`                      | as `T`            |
+/// | `Timestamp(unit, _)`, `Date32/64`       | `Timestamp` (ms)  |
+/// | `Null`                                  | all nulls         |
+/// | `Struct`                                | one column per field, `parent.child` |
+/// | anything else                           | `Text` (Arrow's display form) |
+///
+/// Arrow nulls become `FieldValue::Null`. Zoned timestamps are stored as UTC
+/// epoch values, so they load as the same instants whatever the zone (pick
+/// a display zone with `ScaleType::Time`); zone-less ones are read as UTC,
+/// like ISO strings without an offset. Parquet files
+/// compressed with Snappy, LZ4 or Gzip are supported; ZSTD and Brotli are
+/// not, as they pull in non-Rust code that does not build for `wasm32`.
+use std::io::Cursor;
+use std::sync::Arc;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::{Date32Type, Date64Type, Float64Type, Int64Type};
+use arrow_array::{Array, RecordBatch};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use arrow_schema::{ArrowError, DataType, SchemaRef, TimeUnit};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+use crate::core::column::{Column, ColumnData};
+use crate::core::datetime::MS_PER_DAY;
+use crate::core::field_value::{DataTable, FieldValue};
+
+/// Leading magic bytes of the Arrow IPC *file* format
+const IPC_FILE_MAGIC: &[u8] = b"ARROW1";
+
+/// Read an Arrow IPC file (`.arrow` / Feather v2) or IPC stream into a table.
+///
+/// The format is detected from the leading magic bytes.
+pub fn read_arrow_ipc(bytes: &[u8]) -> Result<DataTable, String> {
+    let err = |e: ArrowError| format!("Arrow IPC: {e}");
+    if bytes.starts_with(IPC_FILE_MAGIC) {
+        let reader =
+            arrow_ipc::reader::FileReader::try_new(Cursor::new(bytes), None).map_err(err)?;
+        let schema = reader.schema();
+        collect(schema, reader).map_err(err)
+    } else {
+        let reader =
+            arrow_ipc::reader::StreamReader::try_new(Cursor::new(bytes), None).map_err(err)?;
+        let schema = reader.schema();
+        collect(schema, reader).map_err(err)
+    }
+}
+
+/// Read a Parquet file into a table
+pub fn read_parquet(bytes: &[u8]) -> Result<DataTable, String> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(bytes::Bytes::copy_from_slice(bytes))
+        .map_err(|e| format!("Parquet: {e}"))?;
+    let schema = Arc::clone(builder.schema());
+    let reader = builder.build().map_err(|e| format!("Parquet: {e}"))?;
+    collect(schema, reader).map_err(|e| format!("Parquet: {e}"))
+}
+
+/// Convert a single record batch into a table
+pub fn record_batch_to_table(batch: &RecordBatch) -> Result<DataTable, String> {
+    batch_columns(batch)
+        .map(DataTable::from_columns)
+        .map_err(|e| format!("Arrow: {e}"))
+}
+
+/// Concatenate every batch of a reader into one table
+fn collect(
+    schema: SchemaRef,
+    batches: impl Iterator<Item = Result<RecordBatch, ArrowError>>,
+) -> Result<DataTable, ArrowError> {
+    // An empty batch fixes the (flattened) column layout even with no rows
+    let mut columns = batch_columns(&RecordBatch::new_empty(schema))?;
+    for batch in batches {
+        for (col, part) in columns.iter_mut().zip(batch_columns(&batch?)?) {
+            col.append(&part);
+        }
+    }
+    Ok(DataTable::from_columns(columns))
+}
+
+fn batch_columns(batch: &RecordBatch) -> Result<Vec<Column>, ArrowError> {
+    let mut out = Vec::new();
+    for (field, array) in batch.schema().fields().iter().zip(batch.columns()) {
+        flatten(field.name(), array.as_ref(), None, &mut out)?;
+    }
+    Ok(out)
+}
+
+/// Append the column(s) for `array`, expanding structs into dotted children.
+///
+/// `parent_valid` masks rows where an enclosing struct is null.
+fn flatten(
+    name: &str,
+    array: &dyn Array,
+    parent_valid: Option<&[bool]>,
+    out: &mut Vec<Column>,
+) -> Result<(), ArrowError> {
+    let valid: Vec<bool> = (0..array.len())
+        .map(|i| !array.is_null(i) && parent_valid.is_none_or(|p| p[i]))
+        .collect();
+    if let DataType::Struct(fields) = array.data_type() {
+        let s = array.as_struct();
+        for (field, child) in fields.iter().zip(s.columns()) {
+            let child_name = format!("{name}.{}", field.name());
+            flatten(&child_name, child.as_ref(), Some(&valid), out)?;
+        }
+        return Ok(());
+    }
+    out.push(column(name, array, &valid)?);
+    Ok(())
+}
+
+/// Typed column for a non-struct array, null where `valid` is false.
+///
+/// Values are copied array-wide into the column's storage; text goes
+/// straight into its dictionary without per-cell `FieldValue`s.
+fn column(name: &str, array: &dyn Array, valid: &[bool]) -> Result<Column, ArrowError> {
+    let valid_flags = || valid.iter().copied();
+    let col = match array.data_type() {
+        DataType::Null => Column::nulls(name, array.len()),
+        DataType::Boolean => {
+            let values = array.as_boolean().values().iter().collect();
+            Column::from_data(name, ColumnData::Bool(values), valid_flags())
+        }
+        dt if dt.is_numeric() => match arrow_cast::cast(array, &DataType::Float64) {
+            Ok(a) => {
+                let values = a.as_primitive::<Float64Type>().values().to_vec();
+                Column::from_data(name, ColumnData::Numeric(values), valid_flags())
+            }
+            Err(_) => formatted(name, array, valid),
+        },
+        // The zone only affects display: values are UTC epochs either way
+        DataType::Timestamp(unit, _) => {
+            let scale = match unit {
+                TimeUnit::Second => 1_000.0,
+                TimeUnit::Millisecond => 1.0,
+                TimeUnit::Microsecond => 1e-3,
+                TimeUnit::Nanosecond => 1e-6,
+            };
+            match arrow_cast::cast(array, &DataType::Int64) {
+                Ok(a) => {
+                    let a = a.as_primitive::<Int64Type>();
+                    let values = a.values().iter().map(|&v| v as f64 * scale).collect();
+                    Column::from_data(name, ColumnData::Timestamp(values), valid_flags())
+                }
+                Err(_) => formatted(name, array, valid),
+            }
+        }
+        DataType::Date32 => {
+            let a = array.as_primitive::<Date32Type>();
+            let values = a
+                .values()
+                .iter()
+                .map(|&d| f64::from(d) * MS_PER_DAY)
+                .collect();
+            Column::from_data(name, ColumnData::Timestamp(values), valid_flags())
+        }
+        DataType::Date64 => {
+            let a = array.as_primitive::<Date64Type>();
+            let values = a.values().iter().map(|&ms| ms as f64).collect();
+            Column::from_data(name, ColumnData::Timestamp(values), valid_flags())
+        }
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => {
+            match arrow_cast::cast(array, &DataType::Utf8) {
+                Ok(a) => {
+                    let a = a.as_string::<i32>();
+                    let values = (0..a.len()).map(|i| valid[i].then(|| a.value(i)));
+                    Column::from_strs(name, values)
+                }
+                Err(_) => formatted(name, array, valid),
+            }
+        }
+        DataType::Dictionary(_, _) => {
+            let Some(dict) = array.as_any_dictionary_opt() else {
+                return Ok(formatted(name, array, valid));
+            };
+            let values = dict.values();
+            if values.is_empty() {
+                // Every key is null (e.g. an empty batch)
+                return Ok(Column::nulls(name, array.len()));
+            }
+            // Convert the distinct values once, then gather them by key:
+            // text keys become codes into the same shared dictionary
+            let values_valid: Vec<bool> = (0..values.len()).map(|i| !values.is_null(i)).collect();
+            column(name, values.as_ref(), &values_valid)?
+                .take(&dict.normalized_keys())
+                .mask(valid)
+        }
+        _ => formatted(name, array, valid),
+    };
+    Ok(col)
+}
+
+/// Fallback: Arrow's own display form of each cell, as text
+fn formatted(name: &str, array: &dyn Array, valid: &[bool]) -> Column {
+    let cells: Vec<FieldValue> = match ArrayFormatter::try_new(array, &FormatOptions::default()) {
+        Ok(f) => (0..array.len())
+            .map(|i| {
+                if valid[i] {
+                    FieldValue::Text(f.value(i).to_string())
+                } else {
+                    FieldValue::Null
+                }
+            })
+            .collect(),
+        Err(_) => vec![FieldValue::Null; array.len()],
+    };
+    Column::from_values(name, cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::column::ColumnKind;
+    use arrow_array::builder::StringDictionaryBuilder;
+    use arrow_array::types::Int32Type;
+    use arrow_array::{
+        ArrayRef, BooleanArray, Float64Array, Int32Array, StringArray, StructArray,
+        TimestampMicrosecondArray, TimestampMillisecondArray, TimestampSecondArray,
+    };
+    use arrow_schema::{Field, Schema};
+
+    fn sample() -> RecordBatch {
+        let mut dict = StringDictionaryBuilder::<Int32Type>::new();
+        dict.append_value("EU");
+        dict.append_null();
+        dict.append_value("EU");
+        let columns: Vec<(&str, ArrayRef)> = vec![
+            (
+                "id",
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])),
+            ),
+            (
+                "price",
+                Arc::new(Float64Array::from(vec![1.5, 2.5, f64::NAN])),
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec![Some("a"), Some("b"), None])),
+            ),
+            ("region", Arc::new(dict.finish())),
+            (
+                "ok",
+                Arc::new(BooleanArray::from(vec![Some(true), Some(false), None])),
+            ),
+            (
+                "at",
+                Arc::new(TimestampSecondArray::from(vec![0, 60, 86_400]).with_timezone("UTC")),
+            ),
+            (
+                "at_us",
+                Arc::new(TimestampMicrosecondArray::from(vec![1_000, 2_000, 3_000])),
+            ),
+        ];
+        RecordBatch::try_from_iter(columns).unwrap()
+    }
+
+    fn check_sample(t: &DataTable) {
+        assert_eq!(t.len(), 3);
+        assert_eq!(
+            t.column_names(),
+            vec!["id", "price", "name", "region", "ok", "at", "at_us"]
+        );
+        assert_eq!(t.value(0, "id"), FieldValue::Numeric(1.0));
+        assert_eq!(t.value(1, "id"), FieldValue::Null);
+        assert_eq!(t.value(1, "price"), FieldValue::Numeric(2.5));
+        assert_eq!(t.value(2, "name"), FieldValue::Null);
+        assert_eq!(t.column("name").unwrap().kind(), ColumnKind::Text);
+        assert_eq!(t.value(2, "region"), FieldValue::Text("EU".into()));
+        assert_eq!(t.value(1, "region"), FieldValue::Null);
+        assert_eq!(t.value(0, "ok"), FieldValue::Bool(true));
+        assert_eq!(t.value(2, "ok"), FieldValue::Null);
+        assert_eq!(t.value(1, "at"), FieldValue::Timestamp(60_000.0));
+        assert_eq!(t.value(2, "at_us"), FieldValue::Timestamp(3.0));
+        assert_eq!(t.column("at").unwrap().kind(), ColumnKind::Timestamp);
+    }
+
+    fn ipc_file(batches: &[RecordBatch]) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let mut w = arrow_ipc::writer::FileWriter::try_new(&mut buf, &batches[0].schema()).unwrap();
+        for b in batches {
+            w.write(b).unwrap();
+        }
+        w.finish().unwrap();
+        drop(w);
+        buf
+    }
+
+    #[test]
+    fn test_ipc_file_roundtrip() {
+        let batch = sample();
+        let bytes = ipc_file(&[batch.clone(), batch]);
+        let t = read_arrow_ipc(&bytes).unwrap();
+        assert_eq!(t.len(), 6);
+        assert_eq!(t.value(3, "id"), FieldValue::Numeric(1.0));
+        check_sample(&DataTable::from_columns(
+            t.columns().iter().map(|c| c.take(&[0, 1, 2])).collect(),
+        ));
+    }
+
+    #[test]
+    fn test_ipc_stream() {
+        let batch = sample();
+        let mut buf = Vec::new();
+        let mut w = arrow_ipc::writer::StreamWriter::try_new(&mut buf, &batch.schema()).unwrap();
+        w.write(&batch).unwrap();
+        w.finish().unwrap();
+        drop(w);
+        check_sample(&read_arrow_ipc(&buf).unwrap());
+    }
+
+    #[test]
+    fn test_parquet_roundtrip() {
+        let batch = sample();
+        let mut buf = Vec::new();
+        let mut w = parquet::arrow::ArrowWriter::try_new(&mut buf, batch.schema(), None).unwrap();
+        w.write(&batch).unwrap();
+        w.close().unwrap();
+        check_sample(&read_parquet(&buf).unwrap());
+    }
+
+    #[test]
+    fn test_struct_is_flattened() {
+        let inner = StructArray::from(vec![
+            (
+                Arc::new(Field::new("lat", DataType::Float64, true)),
+                Arc::new(Float64Array::from(vec![45.0, 46.0])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("lon", DataType::Float64, true)),
+                Arc::new(Float64Array::from(vec![9.0, 10.0])) as ArrayRef,
+            ),
+        ]);
+        let batch = RecordBatch::try_from_iter(vec![("pos", Arc::new(inner) as ArrayRef)]).unwrap();
+        let t = record_batch_to_table(&batch).unwrap();
+        assert_eq!(t.column_names(), vec!["pos.lat", "pos.lon"]);
+        assert_eq!(t.extract_numeric("pos.lon"), vec![9.0, 10.0]);
+    }
+
+    #[test]
+    fn test_dictionary_maps_onto_column_dictionary() {
+        let mut dict = StringDictionaryBuilder::<Int32Type>::new();
+        for v in ["b", "a", "b", "b"] {
+            dict.append_value(v);
+        }
+        dict.append_null();
+        let batch =
+            RecordBatch::try_from_iter(vec![("k", Arc::new(dict.finish()) as ArrayRef)]).unwrap();
+        let bytes = ipc_file(&[batch.clone(), batch]);
+        let t = read_arrow_ipc(&bytes).unwrap();
+        let col = t.column("k").unwrap();
+        let ColumnData::Text { dict, codes } = col.data() else {
+            panic!("expected a text column");
+        };
+        assert_eq!(dict.values(), ["b", "a"]);
+        assert_eq!(codes[..4], [0, 1, 0, 0]);
+        assert_eq!(col.null_count(), 2);
+        assert_eq!(t.value(6, "k"), FieldValue::Text("a".into()));
+    }
+
+    #[test]
+    fn test_timestamp_zones() {
+        let at = |tz: &str| {
+            let a = TimestampMillisecondArray::from(vec![1_000]).with_timezone(tz);
+            RecordBatch::try_from_iter(vec![("at", Arc::new(a) as ArrayRef)]).unwrap()
+        };
+        let t = record_batch_to_table(&at("+00:00")).unwrap();
+        assert_eq!(t.value(0, "at"), FieldValue::Timestamp(1_000.0));
+        let t = read_arrow_ipc(&ipc_file(&[at("Europe/Rome")])).unwrap();
+        assert_eq!(t.value(0, "at"), FieldValue::Timestamp(1_000.0));
+        assert_eq!(t.column("at").unwrap().kind(), ColumnKind::Timestamp);
+    }
+
+    #[test]
+    fn test_empty_file_keeps_schema() {
+        let schema = Arc::new(Schema::new(vec![Field::new("x", DataType::Int64, true)]));
+        let bytes = ipc_file(&[RecordBatch::new_empty(schema)]);
+        let t = read_arrow_ipc(&bytes).unwrap();
+        assert_eq!(t.len(), 0);
+        assert_eq!(t.column_names(), vec!["x"]);
+    }
+
+    #[test]
+    fn test_garbage_is_error() {
+        assert!(read_arrow_ipc(b"not arrow").is_err());
+        assert!(read_parquet(b"not parquet").is_err());
+    }
+}
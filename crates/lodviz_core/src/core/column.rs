@@ -0,0 +1,855 @@
+/// Columnar storage backing `DataTable`
+///
+/// Each column keeps its values in one typed vector (`f64`, `bool`, or
+/// dictionary-encoded text) plus a validity bitmap, so a column name is
+/// stored once per table and scans over numeric data never hash a key.
+/// Columns holding more than one value type fall back to `FieldValue` cells.
+///
+/// A column serializes as `{"name": .., "type": .., "values": [..]}` where
+/// `type` is a [`ColumnKind`] name and `values` holds plain JSON scalars
+/// (`null` for missing rows; timestamps as epoch milliseconds). `Mixed`
+/// columns store tagged [`FieldValue`]s instead.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::core::field_value::FieldValue;
+
+// --- Validity ---
+
+/// One bit per row: `true` when the row holds a value, `false` when null
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validity {
+    words: Vec<u64>,
+    len: usize,
+    null_count: usize,
+}
+
+impl Validity {
+    /// Create an empty bitmap
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append one row
+    pub fn push(&mut self, valid: bool) {
+        let (word, bit) = (self.len / 64, self.len % 64);
+        if word == self.words.len() {
+            self.words.push(0);
+        }
+        if valid {
+            self.words[word] |= 1 << bit;
+        } else {
+            self.null_count += 1;
+        }
+        self.len += 1;
+    }
+
+    /// True when row `i` holds a value (out-of-range rows count as null)
+    pub fn get(&self, i: usize) -> bool {
+        i < self.len && self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Number of rows tracked
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True when no rows are tracked
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of null rows
+    pub fn null_count(&self) -> usize {
+        self.null_count
+    }
+}
+
+// --- Dictionary ---
+
+/// Distinct strings of a text column, addressed by `u32` codes
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    values: Vec<String>,
+    lookup: HashMap<String, u32>,
+}
+
+impl Dictionary {
+    /// All distinct strings in first-seen order (index = code)
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+
+    /// Number of distinct strings
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// True when the dictionary holds no strings
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// String for a code, if it exists
+    pub fn get(&self, code: u32) -> Option<&str> {
+        self.values.get(code as usize).map(String::as_str)
+    }
+
+    /// Code of a string, if it has been interned
+    pub fn code_of(&self, value: &str) -> Option<u32> {
+        self.lookup.get(value).copied()
+    }
+
+    /// Code of `value`, adding it first if it is new (copies only new strings)
+    #[cfg(feature = "arrow")]
+    pub(crate) fn intern_str(&mut self, value: &str) -> u32 {
+        match self.code_of(value) {
+            Some(code) => code,
+            None => self.intern(value.to_owned()),
+        }
+    }
+
+    fn intern(&mut self, value: String) -> u32 {
+        if let Some(&code) = self.lookup.get(&value) {
+            return code;
+        }
+        let code = self.values.len() as u32;
+        self.lookup.insert(value.clone(), code);
+        self.values.push(value);
+        code
+    }
+}
+
+impl PartialEq for Dictionary {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+// --- ColumnData ---
+
+/// Physical storage of a column.
+///
+/// Null rows keep a placeholder in the typed vector; the column's
+/// [`Validity`] bitmap is the source of truth for nulls.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    /// No non-null value seen yet
+    Null,
+    /// Continuous numerical values
+    Numeric(Vec<f64>),
+    /// Unix timestamps in milliseconds
+    Timestamp(Vec<f64>),
+    /// Boolean flags
+    Bool(Vec<bool>),
+    /// Dictionary-encoded strings (`codes[i]` indexes into `dict`)
+    Text {
+        /// Shared dictionary of distinct strings
+        dict: Arc<Dictionary>,
+        /// Per-row dictionary codes
+        codes: Vec<u32>,
+    },
+    /// Heterogeneous values stored cell by cell
+    Mixed(Vec<FieldValue>),
+}
+
+/// Logical type of a column, derived from its storage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnKind {
+    /// Every row is null
+    Null,
+    /// `FieldValue::Numeric` values
+    Numeric,
+    /// `FieldValue::Timestamp` values
+    Timestamp,
+    /// `FieldValue::Bool` values
+    Bool,
+    /// `FieldValue::Text` values
+    Text,
+    /// More than one value type
+    Mixed,
+}
+
+// --- Column ---
+
+/// A named, typed column of a `DataTable`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    name: String,
+    data: ColumnData,
+    validity: Validity,
+}
+
+impl Column {
+    /// Create an empty column
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            data: ColumnData::Null,
+            validity: Validity::new(),
+        }
+    }
+
+    /// Create a column of `len` null rows
+    pub fn nulls(name: impl Into<String>, len: usize) -> Self {
+        let mut col = Self::new(name);
+        for _ in 0..len {
+            col.validity.push(false);
+        }
+        col
+    }
+
+    /// Build a column from arbitrary values, choosing the tightest storage
+    pub fn from_values(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = FieldValue>,
+    ) -> Self {
+        let mut col = Self::new(name);
+        for v in values {
+            col.push(v);
+        }
+        col
+    }
+
+    /// Build a fully valid numeric column
+    pub fn numeric(name: impl Into<String>, values: Vec<f64>) -> Self {
+        let mut validity = Validity::new();
+        for _ in 0..values.len() {
+            validity.push(true);
+        }
+        Self {
+            name: name.into(),
+            data: ColumnData::Numeric(values),
+            validity,
+        }
+    }
+
+    /// Build a column from typed storage and one validity flag per row
+    /// (`data` must hold a placeholder for every null row)
+    #[cfg(feature = "arrow")]
+    pub(crate) fn from_data(
+        name: impl Into<String>,
+        data: ColumnData,
+        valid: impl IntoIterator<Item = bool>,
+    ) -> Self {
+        let mut validity = Validity::new();
+        for ok in valid {
+            validity.push(ok);
+        }
+        Self {
+            name: name.into(),
+            data,
+            validity,
+        }
+    }
+
+    /// Build a text column from borrowed strings, copying each distinct
+    /// string once into the dictionary
+    #[cfg(feature = "arrow")]
+    pub(crate) fn from_strs<'a>(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = Option<&'a str>>,
+    ) -> Self {
+        let mut dict = Dictionary::default();
+        let mut validity = Validity::new();
+        let codes = values
+            .into_iter()
+            .map(|v| {
+                validity.push(v.is_some());
+                v.map_or(0, |s| dict.intern_str(s))
+            })
+            .collect();
+        Self {
+            name: name.into(),
+            data: ColumnData::Text {
+                dict: Arc::new(dict),
+                codes,
+            },
+            validity,
+        }
+    }
+
+    /// Build a fully valid text column
+    pub fn text<S: Into<String>>(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self::from_values(name, values.into_iter().map(|s| FieldValue::Text(s.into())))
+    }
+
+    /// Column name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Return the same column under a different name
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Physical storage
+    pub fn data(&self) -> &ColumnData {
+        &self.data
+    }
+
+    /// Validity bitmap
+    pub fn validity(&self) -> &Validity {
+        &self.validity
+    }
+
+    /// Logical type of the column
+    pub fn kind(&self) -> ColumnKind {
+        match &self.data {
+            ColumnData::Null => ColumnKind::Null,
+            ColumnData::Numeric(_) => ColumnKind::Numeric,
+            ColumnData::Timestamp(_) => ColumnKind::Timestamp,
+            ColumnData::Bool(_) => ColumnKind::Bool,
+            ColumnData::Text { .. } => ColumnKind::Text,
+            ColumnData::Mixed(_) => ColumnKind::Mixed,
+        }
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    /// True when the column has no rows
+    pub fn is_empty(&self) -> bool {
+        self.validity.is_empty()
+    }
+
+    /// Number of null rows
+    pub fn null_count(&self) -> usize {
+        self.validity.null_count()
+    }
+
+    /// True when row `i` holds a value
+    pub fn is_valid(&self, i: usize) -> bool {
+        self.validity.get(i)
+    }
+
+    /// Value at row `i` (`Null` when missing or out of range)
+    pub fn get(&self, i: usize) -> FieldValue {
+        if !self.validity.get(i) {
+            return FieldValue::Null;
+        }
+        match &self.data {
+            ColumnData::Null => FieldValue::Null,
+            ColumnData::Numeric(v) => FieldValue::Numeric(v[i]),
+            ColumnData::Timestamp(v) => FieldValue::Timestamp(v[i]),
+            ColumnData::Bool(v) => FieldValue::Bool(v[i]),
+            ColumnData::Text { dict, codes } => dict
+                .get(codes[i])
+                .map_or(FieldValue::Null, |s| FieldValue::Text(s.to_owned())),
+            ColumnData::Mixed(v) => v[i].clone(),
+        }
+    }
+
+    /// Value at row `i` cast to f64, following [`FieldValue::as_f64`]
+    pub fn get_f64(&self, i: usize) -> Option<f64> {
+        if !self.validity.get(i) {
+            return None;
+        }
+        match &self.data {
+            ColumnData::Numeric(v) | ColumnData::Timestamp(v) => Some(v[i]),
+            ColumnData::Bool(v) => Some(if v[i] { 1.0 } else { 0.0 }),
+            ColumnData::Mixed(v) => v[i].as_f64(),
+            ColumnData::Null | ColumnData::Text { .. } => None,
+        }
+    }
+
+    /// Value at row `i` as a string slice (text values only)
+    pub fn get_str(&self, i: usize) -> Option<&str> {
+        if !self.validity.get(i) {
+            return None;
+        }
+        match &self.data {
+            ColumnData::Text { dict, codes } => dict.get(codes[i]),
+            ColumnData::Mixed(v) => v[i].as_str(),
+            _ => None,
+        }
+    }
+
+    /// Iterate over all values, materializing each cell
+    pub fn iter(&self) -> impl Iterator<Item = FieldValue> + '_ {
+        (0..self.len()).map(|i| self.get(i))
+    }
+
+    /// Append a value, promoting the storage to `Mixed` on a type change
+    pub fn push(&mut self, value: FieldValue) {
+        let len = self.len();
+        if value.is_null() {
+            self.push_placeholder();
+            self.validity.push(false);
+            return;
+        }
+        if matches!(self.data, ColumnData::Null) {
+            self.data = ColumnData::with_placeholders(&value, len);
+        }
+        let value = match (&mut self.data, value) {
+            (ColumnData::Numeric(v), FieldValue::Numeric(x))
+            | (ColumnData::Timestamp(v), FieldValue::Timestamp(x)) => {
+                v.push(x);
+                None
+            }
+            (ColumnData::Bool(v), FieldValue::Bool(b)) => {
+                v.push(b);
+                None
+            }
+            (ColumnData::Text { dict, codes }, FieldValue::Text(s)) => {
+                // Only a new string needs a private copy of a shared dictionary
+                let code = match dict.code_of(&s) {
+                    Some(code) => code,
+                    None => Arc::make_mut(dict).intern(s),
+                };
+                codes.push(code);
+                None
+            }
+            (ColumnData::Mixed(v), other) => {
+                v.push(other);
+                None
+            }
+            (_, other) => Some(other),
+        };
+        if let Some(value) = value {
+            let mut cells: Vec<FieldValue> = self.iter().collect();
+            cells.push(value);
+            self.data = ColumnData::Mixed(cells);
+        }
+        self.validity.push(true);
+    }
+
+    /// Null out the rows where `valid` is false (rows past `valid` too)
+    #[must_use]
+    #[cfg(feature = "arrow")]
+    pub(crate) fn mask(mut self, valid: &[bool]) -> Self {
+        let mut validity = Validity::new();
+        for i in 0..self.len() {
+            validity.push(self.validity.get(i) && valid.get(i).copied().unwrap_or(false));
+        }
+        self.validity = validity;
+        self
+    }
+
+    /// Append the rows of `other`, keeping typed storage when both columns
+    /// hold the same kind (text codes are remapped once per distinct string)
+    pub(crate) fn append(&mut self, other: &Column) {
+        match (&mut self.data, &other.data) {
+            (ColumnData::Null, _) if self.validity.is_empty() => self.data = other.data.clone(),
+            (ColumnData::Numeric(a), ColumnData::Numeric(b))
+            | (ColumnData::Timestamp(a), ColumnData::Timestamp(b)) => a.extend_from_slice(b),
+            (ColumnData::Bool(a), ColumnData::Bool(b)) => a.extend_from_slice(b),
+            (ColumnData::Mixed(a), ColumnData::Mixed(b)) => a.extend_from_slice(b),
+            (ColumnData::Text { dict, codes }, ColumnData::Text { dict: d, codes: c }) => {
+                if Arc::ptr_eq(dict, d) {
+                    codes.extend_from_slice(c);
+                } else {
+                    let remap: Vec<u32> = d
+                        .values()
+                        .iter()
+                        .map(|s| match dict.code_of(s) {
+                            Some(code) => code,
+                            None => Arc::make_mut(dict).intern(s.clone()),
+                        })
+                        .collect();
+                    codes.extend(
+                        c.iter()
+                            .map(|&k| remap.get(k as usize).copied().unwrap_or(0)),
+                    );
+                }
+            }
+            (_, ColumnData::Null) => {
+                for _ in 0..other.len() {
+                    self.push_placeholder();
+                }
+            }
+            _ => {
+                for value in other.iter() {
+                    self.push(value);
+                }
+                return;
+            }
+        }
+        for i in 0..other.len() {
+            self.validity.push(other.validity.get(i));
+        }
+    }
+
+    /// Gather the rows at `indices` into a new column (out-of-range → null)
+    pub fn take(&self, indices: &[usize]) -> Self {
+        let mut validity = Validity::new();
+        for &i in indices {
+            validity.push(self.validity.get(i));
+        }
+        let n = self.len();
+        let data = match &self.data {
+            ColumnData::Null => ColumnData::Null,
+            ColumnData::Numeric(v) => ColumnData::Numeric(gather(v, indices, n, 0.0)),
+            ColumnData::Timestamp(v) => ColumnData::Timestamp(gather(v, indices, n, 0.0)),
+            ColumnData::Bool(v) => ColumnData::Bool(gather(v, indices, n, false)),
+            ColumnData::Text { dict, codes } => ColumnData::Text {
+                dict: Arc::clone(dict),
+                codes: gather(codes, indices, n, 0),
+            },
+            ColumnData::Mixed(v) => ColumnData::Mixed(gather(v, indices, n, FieldValue::Null)),
+        };
+        Self {
+            name: self.name.clone(),
+            data,
+            validity,
+        }
+    }
+
+    /// Group key of row `i`, as used by `DataTable::group_by`
+    pub(crate) fn group_key(&self, i: usize) -> GroupKey {
+        match self.get(i) {
+            FieldValue::Text(s) => GroupKey::Value(s),
+            FieldValue::Numeric(v) | FieldValue::Timestamp(v) => GroupKey::Value(v.to_string()),
+            FieldValue::Bool(b) => GroupKey::Value(b.to_string()),
+            FieldValue::Null => GroupKey::Null,
+        }
+    }
+
+    /// Row indices grouped by distinct value, in first-occurrence order.
+    ///
+    /// Text columns group on dictionary codes without hashing strings.
+    pub(crate) fn partition(&self) -> Vec<(String, Vec<usize>)> {
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        if let ColumnData::Text { dict, codes } = &self.data {
+            let mut slots: Vec<Option<usize>> = vec![None; dict.len()];
+            let mut null_slot: Option<usize> = None;
+            for (i, &code) in codes.iter().enumerate() {
+                let slot = if self.validity.get(i) {
+                    &mut slots[code as usize]
+                } else {
+                    &mut null_slot
+                };
+                let g = *slot.get_or_insert_with(|| {
+                    groups.push((self.group_key(i).into_label(), Vec::new()));
+                    groups.len() - 1
+                });
+                groups[g].1.push(i);
+            }
+            return groups;
+        }
+
+        let mut lookup: HashMap<GroupKey, usize> = HashMap::new();
+        for i in 0..self.len() {
+            let key = self.group_key(i);
+            let g = *lookup.entry(key.clone()).or_insert_with(|| {
+                groups.push((key.into_label(), Vec::new()));
+                groups.len() - 1
+            });
+            groups[g].1.push(i);
+        }
+        groups
+    }
+
+    fn push_placeholder(&mut self) {
+        match &mut self.data {
+            ColumnData::Null => {}
+            ColumnData::Numeric(v) | ColumnData::Timestamp(v) => v.push(0.0),
+            ColumnData::Bool(v) => v.push(false),
+            ColumnData::Text { codes, .. } => codes.push(0),
+            ColumnData::Mixed(v) => v.push(FieldValue::Null),
+        }
+    }
+}
+
+// --- Serde ---
+
+impl Serialize for Column {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut st = serializer.serialize_struct("Column", 3)?;
+        st.serialize_field("name", &self.name)?;
+        st.serialize_field("type", &self.kind())?;
+        st.serialize_field("values", &Cells(self))?;
+        st.end()
+    }
+}
+
+/// The `values` array of a serialized column
+struct Cells<'a>(&'a Column);
+
+impl Serialize for Cells<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let col = self.0;
+        let rows = 0..col.len();
+        match &col.data {
+            ColumnData::Null => serializer.collect_seq(rows.map(|_| ())),
+            ColumnData::Numeric(v) | ColumnData::Timestamp(v) => {
+                serializer.collect_seq(rows.map(|i| col.is_valid(i).then(|| v[i])))
+            }
+            ColumnData::Bool(v) => {
+                serializer.collect_seq(rows.map(|i| col.is_valid(i).then(|| v[i])))
+            }
+            ColumnData::Text { .. } => serializer.collect_seq(rows.map(|i| col.get_str(i))),
+            ColumnData::Mixed(v) => serializer.collect_seq(v),
+        }
+    }
+}
+
+/// Owned mirror of the serialized column layout
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "values")]
+enum CellsRepr {
+    Null(Vec<()>),
+    Numeric(Vec<Option<f64>>),
+    Timestamp(Vec<Option<f64>>),
+    Bool(Vec<Option<bool>>),
+    Text(Vec<Option<String>>),
+    Mixed(Vec<FieldValue>),
+}
+
+#[derive(Deserialize)]
+struct ColumnRepr {
+    name: String,
+    #[serde(flatten)]
+    cells: CellsRepr,
+}
+
+impl<'de> Deserialize<'de> for Column {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ColumnRepr { name, cells } = ColumnRepr::deserialize(deserializer)?;
+        let col = match cells {
+            CellsRepr::Null(v) => Column::nulls(name, v.len()),
+            CellsRepr::Numeric(v) => Column::from_values(
+                name,
+                v.into_iter()
+                    .map(|x| x.map_or(FieldValue::Null, FieldValue::Numeric)),
+            ),
+            CellsRepr::Timestamp(v) => Column::from_values(
+                name,
+                v.into_iter()
+                    .map(|x| x.map_or(FieldValue::Null, FieldValue::Timestamp)),
+            ),
+            CellsRepr::Bool(v) => Column::from_values(
+                name,
+                v.into_iter()
+                    .map(|x| x.map_or(FieldValue::Null, FieldValue::Bool)),
+            ),
+            CellsRepr::Text(v) => Column::from_values(
+                name,
+                v.into_iter()
+                    .map(|x| x.map_or(FieldValue::Null, FieldValue::Text)),
+            ),
+            CellsRepr::Mixed(v) => Column::from_values(name, v),
+        };
+        Ok(col)
+    }
+}
+
+/// Group label used for null / missing values
+pub(crate) const NULL_GROUP: &str = "__null__";
+
+/// Grouping identity of a cell: nulls form their own group, apart from
+/// any text value (including a literal `"__null__"`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) enum GroupKey {
+    Null,
+    Value(String),
+}
+
+impl GroupKey {
+    /// Display label of the group (`NULL_GROUP` for nulls)
+    pub(crate) fn into_label(self) -> String {
+        match self {
+            Self::Null => String::from(NULL_GROUP),
+            Self::Value(s) => s,
+        }
+    }
+}
+
+impl ColumnData {
+    /// Empty storage matching the type of `value`, pre-filled with `len` placeholders
+    fn with_placeholders(value: &FieldValue, len: usize) -> Self {
+        match value {
+            FieldValue::Numeric(_) => Self::Numeric(vec![0.0; len]),
+            FieldValue::Timestamp(_) => Self::Timestamp(vec![0.0; len]),
+            FieldValue::Bool(_) => Self::Bool(vec![false; len]),
+            FieldValue::Text(_) => Self::Text {
+                dict: Arc::new(Dictionary::default()),
+                codes: vec![0; len],
+            },
+            FieldValue::Null => Self::Null,
+        }
+    }
+}
+
+fn gather<T: Clone>(values: &[T], indices: &[usize], len: usize, fill: T) -> Vec<T> {
+    indices
+        .iter()
+        .map(|&i| {
+            if i < len {
+                values[i].clone()
+            } else {
+                fill.clone()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validity_bits() {
+        let mut v = Validity::new();
+        for i in 0..130 {
+            v.push(i % 3 != 0);
+        }
+        assert_eq!(v.len(), 130);
+        assert!(!v.get(0));
+        assert!(v.get(1));
+        assert!(v.get(128));
+        assert!(!v.get(129));
+        assert!(!v.get(500));
+        assert_eq!(v.null_count(), 44);
+    }
+
+    #[test]
+    fn test_text_column_is_dictionary_encoded() {
+        let col = Column::text("region", ["EU", "US", "EU", "EU"]);
+        assert_eq!(col.kind(), ColumnKind::Text);
+        let ColumnData::Text { dict, codes } = col.data() else {
+            panic!("expected text storage");
+        };
+        assert_eq!(dict.values(), ["EU", "US"]);
+        assert_eq!(codes, &vec![0, 1, 0, 0]);
+        assert_eq!(col.get_str(1), Some("US"));
+    }
+
+    #[test]
+    fn test_leading_nulls_then_numeric() {
+        let col = Column::from_values(
+            "x",
+            [FieldValue::Null, FieldValue::Numeric(2.0), FieldValue::Null],
+        );
+        assert_eq!(col.kind(), ColumnKind::Numeric);
+        assert_eq!(col.len(), 3);
+        assert_eq!(col.null_count(), 2);
+        assert_eq!(col.get(0), FieldValue::Null);
+        assert_eq!(col.get_f64(1), Some(2.0));
+    }
+
+    #[test]
+    fn test_type_change_promotes_to_mixed() {
+        let col = Column::from_values(
+            "v",
+            [
+                FieldValue::Numeric(1.0),
+                FieldValue::Null,
+                FieldValue::Text("n/a".into()),
+            ],
+        );
+        assert_eq!(col.kind(), ColumnKind::Mixed);
+        assert_eq!(col.get(0), FieldValue::Numeric(1.0));
+        assert_eq!(col.get(1), FieldValue::Null);
+        assert_eq!(col.get_str(2), Some("n/a"));
+        assert_eq!(col.get_f64(2), None);
+    }
+
+    #[test]
+    fn test_take_keeps_nulls() {
+        let col = Column::from_values(
+            "x",
+            [
+                FieldValue::Numeric(1.0),
+                FieldValue::Null,
+                FieldValue::Numeric(3.0),
+            ],
+        );
+        let taken = col.take(&[2, 1, 7]);
+        assert_eq!(
+            taken.iter().collect::<Vec<_>>(),
+            vec![FieldValue::Numeric(3.0), FieldValue::Null, FieldValue::Null]
+        );
+    }
+
+    #[test]
+    fn test_null_groups_apart_from_null_text() {
+        let text =
+            Column::from_values("c", [FieldValue::Text(NULL_GROUP.into()), FieldValue::Null]);
+        let mixed = Column::from_values(
+            "c",
+            [
+                FieldValue::Text(NULL_GROUP.into()),
+                FieldValue::Numeric(1.0),
+                FieldValue::Null,
+            ],
+        );
+        assert_eq!(text.partition().len(), 2);
+        assert_eq!(mixed.partition().len(), 3);
+        assert_ne!(text.group_key(0), text.group_key(1));
+    }
+
+    #[test]
+    fn test_push_known_text_keeps_shared_dictionary() {
+        fn dict(c: &Column) -> &Arc<Dictionary> {
+            match c.data() {
+                ColumnData::Text { dict, .. } => dict,
+                _ => panic!("not a text column"),
+            }
+        }
+        let col = Column::text("c", ["a", "b"]);
+        let mut taken = col.take(&[1]);
+        taken.push(FieldValue::Text("a".into()));
+        assert!(Arc::ptr_eq(dict(&col), dict(&taken)));
+        taken.push(FieldValue::Text("z".into()));
+        assert!(!Arc::ptr_eq(dict(&col), dict(&taken)));
+        assert_eq!(dict(&col).len(), 2);
+        assert_eq!(taken.get_str(2), Some("z"));
+    }
+
+    #[test]
+    fn test_partition_first_occurrence_order() {
+        let col = Column::from_values(
+            "c",
+            [
+                FieldValue::Text("B".into()),
+                FieldValue::Null,
+                FieldValue::Text("A".into()),
+                FieldValue::Text("B".into()),
+            ],
+        );
+        let groups = col.partition();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0], ("B".to_owned(), vec![0, 3]));
+        assert_eq!(groups[1], (NULL_GROUP.to_owned(), vec![1]));
+        assert_eq!(groups[2], ("A".to_owned(), vec![2]));
+    }
+
+    #[test]
+    fn test_serde_shape_and_roundtrip() {
+        let col = Column::from_values(
+            "x",
+            vec![
+                FieldValue::Numeric(1.5),
+                FieldValue::Null,
+                FieldValue::Numeric(3.0),
+            ],
+        );
+        let json = serde_json::to_string(&col).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"x","type":"Numeric","values":[1.5,null,3.0]}"#
+        );
+        assert_eq!(serde_json::from_str::<Column>(&json).unwrap(), col);
+
+        let mixed = Column::from_values("m", vec![FieldValue::Bool(true), "a".into()]);
+        let json = serde_json::to_string(&mixed).unwrap();
+        assert_eq!(serde_json::from_str::<Column>(&json).unwrap(), mixed);
+
+        let nulls: Column =
+            serde_json::from_str(r#"{"name":"n","type":"Null","values":[null,null]}"#).unwrap();
+        assert_eq!(nulls.len(), 2);
+        assert_eq!(nulls.kind(), ColumnKind::Null);
+    }
+}
@@ -1,63 +1,668 @@
-/// Minimal CSV parser: text → DataTable
+/// CSV / TSV reader: text → DataTable
 ///
-/// - First non-empty, non-comment line → column headers
-/// - Subsequent lines → DataRow with type-inferred values (Numeric or Text)
+/// RFC 4180 records (quoted fields, `""` escapes, embedded newlines) with
+/// dialect options for the delimiter, header-less input, decimal commas and
+/// per-column type hints.
+///
+/// - A leading UTF-8 BOM is stripped
+/// - First non-empty, non-comment record → column headers (unless header-less)
+/// - Subsequent records → one table row with type-inferred values (Numeric,
+///   Bool, Timestamp or Text)
 /// - Lines starting with `#` and empty lines are skipped
+/// - Malformed rows are loaded as well as possible and reported with their line number
 use std::collections::HashMap;
+use std::fmt;
 
+use crate::core::column::Column;
+use crate::core::datetime::{parse_iso8601, parse_with_format, MS_PER_SECOND};
 use crate::core::field_value::{DataTable, FieldValue};
 
-/// Parse a CSV string into a [`DataTable`].
+// --- Options ---
+
+/// How the cells of a column are converted.
+///
+/// With an explicit hint, cells that fail to convert become Null and are
+/// reported as row errors.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum TypeHint {
+    /// Bool or Timestamp when every non-empty cell of the column parses as
+    /// one; otherwise Numeric per cell when it parses as a number, Text if not
+    #[default]
+    Auto,
+    /// Always numeric
+    Numeric,
+    /// Always text, even when the cell looks like a number
+    Text,
+    /// `true`/`false`/`yes`/`no` (case-insensitive)
+    Bool,
+    /// ISO-8601 / RFC 3339, or one of `CsvOptions::date_formats`
+    Timestamp,
+    /// Timestamp in the given strftime format (see [`parse_with_format`])
+    TimestampFormat(String),
+    /// Numeric Unix time in seconds
+    EpochSeconds,
+    /// Numeric Unix time in milliseconds
+    EpochMillis,
+}
+
+/// CSV dialect and conversion options
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    /// Field separator (`,` for CSV, `\t` for TSV, `;` for many European exports)
+    pub delimiter: char,
+    /// Quote character
+    pub quote: char,
+    /// Whether the first record holds column names; otherwise columns are
+    /// named `column_1`, `column_2`, …
+    pub has_header: bool,
+    /// Parse `1,5`, `1.234,5` and `1.234` as 1.5, 1234.5 and 1234 (`.` only
+    /// groups thousands)
+    pub decimal_comma: bool,
+    /// Lines starting with this character are skipped
+    pub comment: Option<char>,
+    /// Trim whitespace around unquoted cells and header names
+    pub trim: bool,
+    /// Fail on the first row error instead of collecting it
+    pub strict: bool,
+    /// Detect `Auto` columns holding only booleans
+    pub infer_bools: bool,
+    /// Detect `Auto` columns holding only dates / date-times
+    pub infer_dates: bool,
+    /// Extra strftime patterns tried (after ISO-8601) when detecting dates
+    pub date_formats: Vec<String>,
+    /// Per-column conversion, by column name
+    pub type_hints: HashMap<String, TypeHint>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quote: '"',
+            has_header: true,
+            decimal_comma: false,
+            comment: Some('#'),
+            trim: true,
+            strict: false,
+            infer_bools: true,
+            infer_dates: true,
+            date_formats: Vec::new(),
+            type_hints: HashMap::new(),
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Tab-separated values
+    pub fn tsv() -> Self {
+        Self::default().with_delimiter('\t')
+    }
+
+    /// Set the field separator
+    #[must_use]
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the quote character
+    #[must_use]
+    pub fn with_quote(mut self, quote: char) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Treat the first record as data, not as column names
+    #[must_use]
+    pub fn without_header(mut self) -> Self {
+        self.has_header = false;
+        self
+    }
+
+    /// Accept `,` as the decimal separator
+    #[must_use]
+    pub fn with_decimal_comma(mut self, on: bool) -> Self {
+        self.decimal_comma = on;
+        self
+    }
+
+    /// Set (or disable) the comment prefix
+    #[must_use]
+    pub fn with_comment(mut self, comment: Option<char>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// Toggle whitespace trimming
+    #[must_use]
+    pub fn with_trim(mut self, on: bool) -> Self {
+        self.trim = on;
+        self
+    }
+
+    /// Fail on the first row error
+    #[must_use]
+    pub fn with_strict(mut self, on: bool) -> Self {
+        self.strict = on;
+        self
+    }
+
+    /// Toggle boolean column detection
+    #[must_use]
+    pub fn with_infer_bools(mut self, on: bool) -> Self {
+        self.infer_bools = on;
+        self
+    }
+
+    /// Toggle date column detection
+    #[must_use]
+    pub fn with_infer_dates(mut self, on: bool) -> Self {
+        self.infer_dates = on;
+        self
+    }
+
+    /// Add a strftime pattern for date detection, e.g. `"%d/%m/%Y"`
+    #[must_use]
+    pub fn with_date_format(mut self, format: impl Into<String>) -> Self {
+        self.date_formats.push(format.into());
+        self
+    }
+
+    /// Set the conversion for one column
+    #[must_use]
+    pub fn with_type(mut self, column: impl Into<String>, hint: TypeHint) -> Self {
+        self.type_hints.insert(column.into(), hint);
+        self
+    }
+}
+
+// --- Results ---
+
+/// A problem found in one row of the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvRowError {
+    /// 1-based line number where the record starts
+    pub line: usize,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl fmt::Display for CsvRowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// A parsed table together with the row errors encountered
+#[derive(Debug, Clone, Default)]
+pub struct CsvReport {
+    /// The loaded data (malformed rows included as well as possible)
+    pub table: DataTable,
+    /// Row-level problems, in input order
+    pub errors: Vec<CsvRowError>,
+}
+
+// --- Entry points ---
+
+/// Parse a CSV string into a [`DataTable`] with the default options.
 ///
 /// Returns `Err` if the input has no header row, otherwise each data row
-/// is mapped to a `DataRow`. Cells are parsed as `f64` first; on failure
-/// they become `FieldValue::Text`. Missing trailing columns are stored as
-/// `FieldValue::Null`.
+/// appends one cell to every column, in header order. Column types are
+/// inferred from the non-empty cells, in this order: `Bool` when every cell
+/// is `true`/`false`/`yes`/`no`, then `Timestamp` when every cell is a
+/// date / date-time (and not a number). Otherwise each cell is parsed as
+/// `f64` first and becomes `FieldValue::Text` on failure. Empty cells and
+/// missing trailing columns are stored as `FieldValue::Null`.
 pub fn parse_csv(text: &str) -> Result<DataTable, String> {
-    let mut lines = text
-        .lines()
-        .map(str::trim)
-        .filter(|l| !l.is_empty() && !l.starts_with('#'));
+    parse_csv_with(text, &CsvOptions::default())
+}
+
+/// Parse CSV with explicit options, discarding row errors unless `strict`.
+pub fn parse_csv_with(text: &str, options: &CsvOptions) -> Result<DataTable, String> {
+    read_csv(text, options).map(|report| report.table)
+}
+
+/// Parse CSV with explicit options, returning the table and all row errors.
+///
+/// Returns `Err` if there is no header row, or on the first row error when
+/// `options.strict` is set.
+pub fn read_csv(text: &str, options: &CsvOptions) -> Result<CsvReport, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut records = Records::new(text, options);
 
-    // First line is the header
-    let header_line = lines
+    let first = records
         .next()
         .ok_or_else(|| "CSV has no header row".to_string())?;
+    let (names, first_row) = if options.has_header {
+        (header_names(&first.fields), None)
+    } else {
+        let names = (1..=first.fields.len())
+            .map(|i| format!("column_{i}"))
+            .collect();
+        (names, Some(first))
+    };
+    // One pass: each record is converted into the columns as it is read
+    let mut builders: Vec<ColumnBuilder> = names
+        .iter()
+        .map(|name| {
+            let hint = options.type_hints.get(name).cloned().unwrap_or_default();
+            ColumnBuilder::new(name, hint, options)
+        })
+        .collect();
+    let mut errors = Vec::new();
+    let mut pending = first_row;
+    while let Some(record) = pending.take().or_else(|| records.next()) {
+        errors.append(&mut records.errors);
+        if record.fields.len() != names.len() {
+            errors.push(CsvRowError {
+                line: record.line,
+                message: format!(
+                    "expected {} fields, found {}",
+                    names.len(),
+                    record.fields.len()
+                ),
+            });
+        }
+        for (i, builder) in builders.iter_mut().enumerate() {
+            let cell = record.fields.get(i).map(String::as_str);
+            if let Err(message) = builder.push(cell) {
+                errors.push(CsvRowError {
+                    line: record.line,
+                    message: format!("column '{}': {message}", names[i]),
+                });
+            }
+        }
+    }
+    errors.append(&mut records.errors);
+    let columns = builders.into_iter().map(ColumnBuilder::finish).collect();
 
-    let headers: Vec<&str> = header_line.split(',').map(str::trim).collect();
+    errors.sort_by_key(|e| e.line);
+    if options.strict {
+        if let Some(e) = errors.first() {
+            return Err(e.to_string());
+        }
+    }
+    Ok(CsvReport {
+        table: DataTable::from_columns(columns),
+        errors,
+    })
+}
 
-    let mut table = DataTable::default();
+/// Header cells → unique column names (blank → `column_N`, repeats → `name_2`, …)
+fn header_names(fields: &[String]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::with_capacity(fields.len());
+    for (i, field) in fields.iter().enumerate() {
+        let base = if field.is_empty() {
+            format!("column_{}", i + 1)
+        } else {
+            field.clone()
+        };
+        let mut name = base.clone();
+        let mut n = 2;
+        while names.contains(&name) {
+            name = format!("{base}_{n}");
+            n += 1;
+        }
+        names.push(name);
+    }
+    names
+}
 
-    for line in lines {
-        let cells: Vec<&str> = line.split(',').collect();
-        let mut row: HashMap<String, FieldValue> = HashMap::new();
+/// Builds one column from raw cells as they stream in.
+///
+/// `Auto` cells are stored as numbers or text while the builder tracks
+/// whether every non-empty cell so far reads as a bool or a date; `finish`
+/// then relabels the text cells of such a column.
+struct ColumnBuilder<'a> {
+    column: Column,
+    hint: TypeHint,
+    options: &'a CsvOptions,
+    /// Whether a non-empty cell has been pushed
+    seen: bool,
+    /// `Auto`: every non-empty cell so far parses as a bool
+    maybe_bool: bool,
+    /// `Auto`: every non-empty cell so far is a date and not a number
+    maybe_date: bool,
+}
+
+impl<'a> ColumnBuilder<'a> {
+    fn new(name: &str, hint: TypeHint, options: &'a CsvOptions) -> Self {
+        Self {
+            column: Column::new(name),
+            hint,
+            options,
+            seen: false,
+            maybe_bool: options.infer_bools,
+            maybe_date: options.infer_dates,
+        }
+    }
+
+    /// Append one cell; `None` (missing from a short row) and empty cells
+    /// become Null. Under an explicit hint a cell that fails to convert is
+    /// stored as Null and its message returned.
+    fn push(&mut self, cell: Option<&str>) -> Result<(), String> {
+        let cell = match cell {
+            Some(c) if !c.is_empty() => c,
+            _ => {
+                self.column.push(FieldValue::Null);
+                return Ok(());
+            }
+        };
+        self.seen = true;
+        if self.hint == TypeHint::Auto {
+            let number = parse_number(cell, self.options);
+            self.maybe_bool = self.maybe_bool && parse_bool(cell).is_some();
+            self.maybe_date = self.maybe_date
+                && number.is_none()
+                && parse_timestamp(cell, self.options).is_some();
+            let value =
+                number.map_or_else(|| FieldValue::Text(cell.to_owned()), FieldValue::Numeric);
+            self.column.push(value);
+            return Ok(());
+        }
+        let result = convert(cell, &self.hint, self.options);
+        self.column.push(result.clone().unwrap_or(FieldValue::Null));
+        result.map(|_| ())
+    }
+
+    /// The finished column, with `Auto` bool / date detection applied
+    fn finish(self) -> Column {
+        let hint = match self.hint {
+            TypeHint::Auto if self.seen && self.maybe_bool => TypeHint::Bool,
+            TypeHint::Auto if self.seen && self.maybe_date => TypeHint::Timestamp,
+            _ => return self.column,
+        };
+        // Bool and date cells never parse as numbers, so they are all text
+        let values = self.column.iter().map(|v| match v {
+            FieldValue::Text(s) => convert(&s, &hint, self.options).unwrap_or(FieldValue::Null),
+            other => other,
+        });
+        Column::from_values(self.column.name(), values)
+    }
+}
 
-        for (i, &col) in headers.iter().enumerate() {
-            let value = match cells.get(i) {
-                Some(cell) => {
-                    let cell = cell.trim();
-                    if cell.is_empty() {
-                        FieldValue::Null
-                    } else if let Ok(n) = cell.parse::<f64>() {
-                        FieldValue::Numeric(n)
+/// Convert the raw cells of one column according to its type hint.
+///
+/// `None` cells (missing from a short row) and empty cells become Null.
+/// Returns the column and `(row, message)` for every cell that failed to
+/// convert under an explicit hint.
+pub(crate) fn convert_column(
+    name: &str,
+    cells: &[Option<&str>],
+    hint: &TypeHint,
+    options: &CsvOptions,
+) -> (Column, Vec<(usize, String)>) {
+    let mut builder = ColumnBuilder::new(name, hint.clone(), options);
+    let failures = cells
+        .iter()
+        .enumerate()
+        .filter_map(|(row, cell)| builder.push(*cell).err().map(|message| (row, message)))
+        .collect();
+    (builder.finish(), failures)
+}
+
+/// Column-level detection for `Auto` columns: `Bool` or `Timestamp` when
+/// every non-empty cell parses as one, `None` otherwise.
+pub(crate) fn infer_hint<'a>(
+    cells: impl Iterator<Item = &'a str> + Clone,
+    options: &CsvOptions,
+) -> Option<TypeHint> {
+    let mut present = cells.filter(|c| !c.is_empty());
+    present.clone().next()?;
+    if options.infer_bools && present.clone().all(|c| parse_bool(c).is_some()) {
+        Some(TypeHint::Bool)
+    } else if options.infer_dates
+        && present
+            .all(|c| parse_number(c, options).is_none() && parse_timestamp(c, options).is_some())
+    {
+        Some(TypeHint::Timestamp)
+    } else {
+        None
+    }
+}
+
+/// Convert one non-empty cell
+pub(crate) fn convert(
+    cell: &str,
+    hint: &TypeHint,
+    options: &CsvOptions,
+) -> Result<FieldValue, String> {
+    match hint {
+        TypeHint::Auto => Ok(parse_number(cell, options)
+            .map_or_else(|| FieldValue::Text(cell.to_owned()), FieldValue::Numeric)),
+        TypeHint::Text => Ok(FieldValue::Text(cell.to_owned())),
+        TypeHint::Numeric => parse_number(cell, options)
+            .map(FieldValue::Numeric)
+            .ok_or_else(|| format!("'{cell}' is not a number")),
+        TypeHint::Bool => parse_bool(cell)
+            .map(FieldValue::Bool)
+            .ok_or_else(|| format!("'{cell}' is not a boolean")),
+        TypeHint::Timestamp => parse_timestamp(cell, options)
+            .map(FieldValue::Timestamp)
+            .ok_or_else(|| format!("'{cell}' is not a recognized date/time")),
+        TypeHint::TimestampFormat(format) => parse_with_format(cell, format)
+            .map(FieldValue::Timestamp)
+            .ok_or_else(|| format!("'{cell}' does not match '{format}'")),
+        TypeHint::EpochSeconds | TypeHint::EpochMillis => {
+            let scale = if *hint == TypeHint::EpochSeconds {
+                MS_PER_SECOND
+            } else {
+                1.0
+            };
+            parse_number(cell, options)
+                .map(|v| FieldValue::Timestamp(v * scale))
+                .ok_or_else(|| format!("'{cell}' is not an epoch time"))
+        }
+    }
+}
+
+fn parse_bool(cell: &str) -> Option<bool> {
+    match cell.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" => Some(true),
+        "false" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_timestamp(cell: &str, options: &CsvOptions) -> Option<f64> {
+    parse_iso8601(cell).or_else(|| {
+        options
+            .date_formats
+            .iter()
+            .find_map(|f| parse_with_format(cell, f))
+    })
+}
+
+fn parse_number(cell: &str, options: &CsvOptions) -> Option<f64> {
+    let cell = cell.trim();
+    if !options.decimal_comma {
+        return cell.parse().ok();
+    }
+    // `.` groups thousands when `,` marks decimals: `1.234,5` is 1234.5
+    let (int, frac) = match cell.split_once(',') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (cell, None),
+    };
+    let mut groups = int.split('.');
+    let mut normalized = groups.next()?.to_owned();
+    for group in groups {
+        let digits = normalized.ends_with(|c: char| c.is_ascii_digit());
+        if !digits || group.len() != 3 || !group.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        normalized.push_str(group);
+    }
+    if let Some(frac) = frac {
+        normalized.push('.');
+        normalized.push_str(frac);
+    }
+    normalized.parse().ok()
+}
+
+// --- Record reader ---
+
+/// One logical record and the line it starts on
+struct Record {
+    line: usize,
+    fields: Vec<String>,
+    /// An empty line (a lone `""` is a quoted empty cell, not blank)
+    blank: bool,
+}
+
+/// RFC 4180 record iterator over the input text
+struct Records<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    options: &'a CsvOptions,
+    /// Current 1-based line number
+    line: usize,
+    /// Problems found while reading, drained by the caller
+    errors: Vec<CsvRowError>,
+}
+
+impl<'a> Records<'a> {
+    fn new(text: &'a str, options: &'a CsvOptions) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            options,
+            line: 1,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Consume a line terminator (`\n`, `\r\n` or `\r`) if `c` starts one
+    fn eat_newline(&mut self, c: char) -> bool {
+        match c {
+            '\n' => {}
+            '\r' => {
+                if self.chars.peek() == Some(&'\n') {
+                    self.chars.next();
+                }
+            }
+            _ => return false,
+        }
+        self.line += 1;
+        true
+    }
+
+    /// Skip the rest of the current line
+    fn skip_line(&mut self) {
+        while let Some(c) = self.chars.next() {
+            if self.eat_newline(c) {
+                return;
+            }
+        }
+    }
+
+    /// Read one physical record, or `None` at end of input
+    fn read_record(&mut self) -> Option<Record> {
+        self.chars.peek()?;
+        let opts = self.options;
+        let line = self.line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut quoted = false;
+
+        while let Some(c) = self.chars.next() {
+            let at_field_start = if opts.trim {
+                field.trim().is_empty()
+            } else {
+                field.is_empty()
+            };
+            if c == opts.quote && at_field_start {
+                // Opening quote: read until the closing quote
+                field.clear();
+                quoted = true;
+                let mut closed = false;
+                while let Some(q) = self.chars.next() {
+                    if q == opts.quote {
+                        if self.chars.peek() == Some(&opts.quote) {
+                            self.chars.next();
+                            field.push(q);
+                        } else {
+                            closed = true;
+                            break;
+                        }
                     } else {
-                        FieldValue::Text(cell.to_owned())
+                        if q == '\n' || (q == '\r' && self.chars.peek() != Some(&'\n')) {
+                            self.line += 1;
+                        }
+                        field.push(q);
                     }
                 }
-                None => FieldValue::Null,
-            };
-            row.insert(col.to_owned(), value);
+                if !closed {
+                    self.errors.push(CsvRowError {
+                        line,
+                        message: "unterminated quoted field".to_owned(),
+                    });
+                }
+                // Anything between the closing quote and the next separator
+                while let Some(&n) = self.chars.peek() {
+                    if n == opts.delimiter || n == '\n' || n == '\r' {
+                        break;
+                    }
+                    self.chars.next();
+                    if !(opts.trim && n.is_whitespace()) {
+                        self.errors.push(CsvRowError {
+                            line,
+                            message: format!("unexpected '{n}' after closing quote"),
+                        });
+                        field.push(n);
+                    }
+                }
+            } else if c == opts.delimiter {
+                fields.push(finish_field(&mut field, quoted, opts.trim));
+                quoted = false;
+            } else if self.eat_newline(c) {
+                break;
+            } else {
+                field.push(c);
+            }
         }
+        fields.push(finish_field(&mut field, quoted, opts.trim));
+        let blank = !quoted && fields.len() == 1 && fields[0].is_empty();
+        Some(Record {
+            line,
+            fields,
+            blank,
+        })
+    }
+}
 
-        table.push(row);
+impl Iterator for Records<'_> {
+    type Item = Record;
+
+    /// Next non-empty, non-comment record
+    fn next(&mut self) -> Option<Record> {
+        loop {
+            if let Some(comment) = self.options.comment {
+                let mut ahead = self.chars.clone();
+                let first = ahead.find(|c| !(*c == ' ' || *c == '\t'));
+                if first == Some(comment) {
+                    self.skip_line();
+                    continue;
+                }
+            }
+            let record = self.read_record()?;
+            if !record.blank {
+                return Some(record);
+            }
+        }
     }
+}
 
-    Ok(table)
+fn finish_field(field: &mut String, quoted: bool, trim: bool) -> String {
+    let value = std::mem::take(field);
+    if trim && !quoted {
+        value.trim().to_owned()
+    } else {
+        value
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::column::ColumnKind;
 
     #[test]
     fn test_parse_csv_basic() {
@@ -87,7 +692,7 @@ mod tests {
     fn test_parse_csv_missing_cells_become_null() {
         let csv = "a,b,c\n1.0,2.0";
         let table = parse_csv(csv).unwrap();
-        let row = &table.rows()[0];
+        let row = table.row(0).unwrap();
         assert_eq!(row.get("a"), Some(&FieldValue::Numeric(1.0)));
         assert_eq!(row.get("b"), Some(&FieldValue::Numeric(2.0)));
         assert_eq!(row.get("c"), Some(&FieldValue::Null));
@@ -98,4 +703,142 @@ mod tests {
         assert!(parse_csv("").is_err());
         assert!(parse_csv("# only comments\n").is_err());
     }
+
+    #[test]
+    fn test_quoted_fields_and_embedded_newlines() {
+        let csv =
+            "id,desc\r\n1,\"Hello, \"\"world\"\"\"\r\n2,\"line one\nline two\"\r\n3,plain\r\n";
+        let report = read_csv(csv, &CsvOptions::default()).unwrap();
+        assert!(report.errors.is_empty());
+        assert_eq!(
+            report.table.extract_text("desc"),
+            vec!["Hello, \"world\"", "line one\nline two", "plain"]
+        );
+        assert_eq!(report.table.extract_numeric("id"), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_semicolon_decimal_comma_and_bom() {
+        let csv = "\u{feff}name;amount\n\"Müller; GmbH\";1.234,5\nB;7,25\n";
+        let opts = CsvOptions::default()
+            .with_delimiter(';')
+            .with_decimal_comma(true);
+        let table = parse_csv_with(csv, &opts).unwrap();
+        assert_eq!(table.column_names(), vec!["name", "amount"]);
+        assert_eq!(table.extract_text("name"), vec!["Müller; GmbH", "B"]);
+        assert_eq!(table.extract_numeric("amount"), vec![1234.5, 7.25]);
+    }
+
+    #[test]
+    fn test_decimal_comma_thousands_without_decimals() {
+        let csv = "amount\n1.234\n-1.234.567\n12\n";
+        let opts = CsvOptions::default().with_decimal_comma(true);
+        let table = parse_csv_with(csv, &opts).unwrap();
+        assert_eq!(
+            table.extract_numeric("amount"),
+            vec![1234.0, -1_234_567.0, 12.0]
+        );
+        // A `.` that does not group three digits is not a number here
+        let opts = opts.with_type("amount", TypeHint::Numeric);
+        let report = read_csv("amount\n1.5\n", &opts).unwrap();
+        assert_eq!(report.table.value(0, "amount"), FieldValue::Null);
+        assert_eq!(report.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_quoted_empty_single_column_row_is_kept() {
+        let table = parse_csv("note\n\"\"\nhi\n\n").unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.value(0, "note"), FieldValue::Null);
+        assert_eq!(table.value(1, "note"), FieldValue::Text("hi".into()));
+    }
+
+    #[test]
+    fn test_tsv_without_header() {
+        let table = parse_csv_with("a\t1\nb\t2\n", &CsvOptions::tsv().without_header()).unwrap();
+        assert_eq!(table.column_names(), vec!["column_1", "column_2"]);
+        assert_eq!(table.extract_numeric("column_2"), vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_type_hints() {
+        let csv = "zip,qty\n01234,5\n99999,n/a\n";
+        let opts = CsvOptions::default()
+            .with_type("zip", TypeHint::Text)
+            .with_type("qty", TypeHint::Numeric);
+        let report = read_csv(csv, &opts).unwrap();
+        assert_eq!(report.table.extract_text("zip"), vec!["01234", "99999"]);
+        assert_eq!(report.table.value(1, "qty"), FieldValue::Null);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].line, 3);
+        assert!(report.errors[0].message.contains("'n/a' is not a number"));
+    }
+
+    #[test]
+    fn test_row_errors_report_line_numbers() {
+        let csv = "a,b\n1,2\n\"multi\nline\",2\n3,4,5\n6,\"open\n";
+        let report = read_csv(csv, &CsvOptions::default()).unwrap();
+        let lines: Vec<usize> = report.errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![5, 6]);
+        assert!(report.errors[0]
+            .message
+            .contains("expected 2 fields, found 3"));
+        assert!(report.errors[1].message.contains("unterminated"));
+        assert_eq!(report.table.len(), 4);
+
+        let strict = CsvOptions::default().with_strict(true);
+        let err = read_csv(csv, &strict).unwrap_err();
+        assert!(err.starts_with("line 5:"));
+    }
+
+    #[test]
+    fn test_infers_timestamps_and_bools() {
+        let csv = "when,active,note\n2024-03-01T12:00:00Z,yes,yes\n2024-03-02,No,maybe\n,TRUE,\n";
+        let table = parse_csv(csv).unwrap();
+        let col = table.column("when").unwrap();
+        assert_eq!(col.kind(), ColumnKind::Timestamp);
+        assert_eq!(
+            table.value(0, "when"),
+            FieldValue::Timestamp(parse_iso8601("2024-03-01T12:00:00Z").unwrap())
+        );
+        assert_eq!(table.value(2, "when"), FieldValue::Null);
+        assert_eq!(table.column("active").unwrap().kind(), ColumnKind::Bool);
+        assert_eq!(table.value(1, "active"), FieldValue::Bool(false));
+        // Mixed words stay text rather than becoming a half-bool column
+        assert_eq!(table.extract_text("note"), vec!["yes", "maybe"]);
+    }
+
+    #[test]
+    fn test_date_formats_and_epoch_hints() {
+        let csv = "day,secs,ms\n01/03/2024,1709251200,1709251200000\n";
+        let opts = CsvOptions::default()
+            .with_date_format("%d/%m/%Y")
+            .with_type("secs", TypeHint::EpochSeconds)
+            .with_type("ms", TypeHint::EpochMillis);
+        let table = parse_csv_with(csv, &opts).unwrap();
+        let expected = FieldValue::Timestamp(1_709_251_200_000.0);
+        assert_eq!(table.value(0, "day"), expected);
+        assert_eq!(table.value(0, "secs"), expected);
+        assert_eq!(table.value(0, "ms"), expected);
+    }
+
+    #[test]
+    fn test_timestamp_format_hint_reports_mismatch() {
+        let csv = "t\n2024.03.01\nsoon\n";
+        let opts =
+            CsvOptions::default().with_type("t", TypeHint::TimestampFormat("%Y.%m.%d".into()));
+        let report = read_csv(csv, &opts).unwrap();
+        assert_eq!(
+            report.table.value(0, "t"),
+            FieldValue::Timestamp(1_709_251_200_000.0)
+        );
+        assert_eq!(report.table.value(1, "t"), FieldValue::Null);
+        assert_eq!(report.errors[0].line, 3);
+    }
+
+    #[test]
+    fn test_duplicate_and_blank_headers() {
+        let table = parse_csv("x,x,\n1,2,3").unwrap();
+        assert_eq!(table.column_names(), vec!["x", "x_2", "column_3"]);
+    }
 }
@@ -1,11 +1,29 @@
 /// Data structures for representing visualization data
 ///
+/// Every type here (de)serializes with serde as a plain JSON object using
+/// the Rust field names, e.g. `{"x": 1.0, "y": 2.0}` for a `DataPoint` and
+/// `{"name": "s", "data": [..], "visible": true}` for a `Series`.
+/// `WaterfallKind` uses its variant name (`"Delta"`), `DataType` the
+/// lowercase Vega-Lite name (`"quantitative"`); `Option` fields may be omitted.
+/// JSON has no NaN: a NaN coordinate is written as `null` and read back as NaN.
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::algorithms::lttb::lttb_indices;
+use crate::algorithms::m4::m4_indices;
+
+/// Deserialize an `f64` written by serde_json, where `null` stands for NaN
+pub(crate) fn f64_or_nan<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(f64::NAN))
+}
+
 /// A single data point with x and y coordinates
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct DataPoint {
     /// The x-coordinate value
+    #[serde(deserialize_with = "f64_or_nan")]
     pub x: f64,
     /// The y-coordinate value
+    #[serde(deserialize_with = "f64_or_nan")]
     pub y: f64,
 }
 
@@ -16,15 +34,214 @@ impl DataPoint {
     }
 }
 
-/// A series of data points with a name
-#[derive(Debug, Clone)]
+/// Marker symbol for a point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PointShape {
+    /// Filled circle
+    #[default]
+    Circle,
+    /// Axis-aligned square
+    Square,
+    /// Upward triangle
+    Triangle,
+    /// Square rotated 45°
+    Diamond,
+    /// Plus-shaped cross
+    Cross,
+}
+
+impl PointShape {
+    /// Shapes in the order they are assigned to categories
+    pub const ALL: [PointShape; 5] = [
+        Self::Circle,
+        Self::Square,
+        Self::Triangle,
+        Self::Diamond,
+        Self::Cross,
+    ];
+
+    /// Shape for the `i`-th category (cycles after five)
+    pub fn nth(i: usize) -> Self {
+        Self::ALL[i % Self::ALL.len()]
+    }
+
+    /// SVG path of the shape centered at `(cx, cy)` with radius `r`
+    pub fn path(self, cx: f64, cy: f64, r: f64) -> String {
+        match self {
+            Self::Circle => format!(
+                "M {:.2} {cy:.2} a {r:.2} {r:.2} 0 1 0 {:.2} 0 a {r:.2} {r:.2} 0 1 0 {:.2} 0 Z",
+                cx - r,
+                2.0 * r,
+                -2.0 * r
+            ),
+            Self::Square => {
+                let h = r * 0.886; // same area as the circle
+                format!(
+                    "M {:.2} {:.2} h {:.2} v {:.2} h {:.2} Z",
+                    cx - h,
+                    cy - h,
+                    2.0 * h,
+                    2.0 * h,
+                    -2.0 * h
+                )
+            }
+            Self::Triangle => {
+                let h = r * 1.2;
+                format!(
+                    "M {cx:.2} {:.2} L {:.2} {:.2} L {:.2} {:.2} Z",
+                    cy - h,
+                    cx + h * 0.866,
+                    cy + h * 0.5,
+                    cx - h * 0.866,
+                    cy + h * 0.5
+                )
+            }
+            Self::Diamond => {
+                let h = r * 1.25;
+                format!(
+                    "M {cx:.2} {:.2} L {:.2} {cy:.2} L {cx:.2} {:.2} L {:.2} {cy:.2} Z",
+                    cy - h,
+                    cx + h,
+                    cy + h,
+                    cx - h
+                )
+            }
+            Self::Cross => {
+                let (a, b) = (r, r / 3.0);
+                format!(
+                    "M {:.2} {:.2} h {:.2} v {:.2} h {:.2} v {:.2} h {:.2} v {:.2} h {:.2} v {:.2} h {:.2} v {:.2} h {:.2} Z",
+                    cx - b,
+                    cy - a,
+                    2.0 * b,
+                    a - b,
+                    a - b,
+                    2.0 * b,
+                    b - a,
+                    a - b,
+                    -2.0 * b,
+                    b - a,
+                    b - a,
+                    -2.0 * b,
+                    a - b
+                )
+            }
+        }
+    }
+}
+
+/// Encoded per-point channel values and record metadata, parallel to
+/// `Series::data`.
+///
+/// Each vector is either empty (channel not encoded) or has one entry per
+/// point. Values are already mapped to visuals: `size` is a relative
+/// magnitude in `[0, 1]`, `opacity` an alpha in `[0, 1]`. `row` links a
+/// point back to its source record and survives downsampling (see
+/// [`Series::downsample_lttb`]); aggregated points have no single record.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PointAttributes {
+    /// Relative size in `[0, 1]` (area-proportional)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub size: Vec<f64>,
+    /// Alpha in `[0, 1]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub opacity: Vec<f64>,
+    /// Marker symbol
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shape: Vec<PointShape>,
+    /// Label drawn next to the point
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub text: Vec<String>,
+    /// Extra `(field, formatted value)` pairs for the tooltip
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tooltip: Vec<Vec<(String, String)>>,
+    /// CSS color overriding the series color
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub color: Vec<String>,
+    /// Stable record identifier
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub id: Vec<String>,
+    /// Index of the source record (table row, or position in the
+    /// original `data` before downsampling); `None` when the point
+    /// summarizes several records
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub row: Vec<Option<usize>>,
+}
+
+impl PointAttributes {
+    /// True when no channel is encoded
+    pub fn is_empty(&self) -> bool {
+        self.size.is_empty()
+            && self.opacity.is_empty()
+            && self.shape.is_empty()
+            && self.text.is_empty()
+            && self.tooltip.is_empty()
+            && self.color.is_empty()
+            && self.id.is_empty()
+            && self.row.is_empty()
+    }
+
+    /// Source record of point `i` (`i` itself when `row` is not set)
+    pub fn row_of(&self, i: usize) -> Option<usize> {
+        self.row.get(i).copied().unwrap_or(Some(i))
+    }
+
+    /// `(label, value)` lines describing point `i` in a tooltip: the id,
+    /// then the tooltip fields
+    pub fn tooltip_lines(&self, i: usize) -> Vec<(String, String)> {
+        let mut lines: Vec<(String, String)> = self
+            .id
+            .get(i)
+            .map(|id| ("id".to_owned(), id.clone()))
+            .into_iter()
+            .collect();
+        lines.extend(self.tooltip.get(i).into_iter().flatten().cloned());
+        lines
+    }
+
+    /// Keep only the entries at `indices` (in that order)
+    pub fn select(&self, indices: &[usize]) -> Self {
+        fn pick<T: Clone>(v: &[T], indices: &[usize]) -> Vec<T> {
+            if v.is_empty() {
+                return Vec::new();
+            }
+            indices.iter().filter_map(|&i| v.get(i).cloned()).collect()
+        }
+        Self {
+            size: pick(&self.size, indices),
+            opacity: pick(&self.opacity, indices),
+            shape: pick(&self.shape, indices),
+            text: pick(&self.text, indices),
+            tooltip: pick(&self.tooltip, indices),
+            color: pick(&self.color, indices),
+            id: pick(&self.id, indices),
+            row: pick(&self.row, indices),
+        }
+    }
+}
+
+/// A series of data points with a name.
+///
+/// Non-exhaustive: build it with [`Series::new`] and the `with_*` setters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct Series<T> {
     /// The name of the series, often used in legends
     pub name: String,
     /// The collection of underlying data points
     pub data: Vec<T>,
-    /// Whether the series is currently visible on the chart
+    /// Whether the series is currently visible on the chart (default `true`)
+    #[serde(default = "default_visible")]
     pub visible: bool,
+    /// Per-point encoded channels and record metadata
+    #[serde(default, skip_serializing_if = "PointAttributes::is_empty")]
+    pub attributes: PointAttributes,
+    /// SVG `stroke-dasharray` for the series' line (`None` = solid)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stroke_dash: Option<String>,
+}
+
+fn default_visible() -> bool {
+    true
 }
 
 impl<T> Series<T> {
@@ -34,12 +251,59 @@ impl<T> Series<T> {
             name: name.into(),
             data,
             visible: true,
+            attributes: PointAttributes::default(),
+            stroke_dash: None,
         }
     }
+
+    /// Attach per-point channel values (parallel to `data`)
+    pub fn with_attributes(mut self, attributes: PointAttributes) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    /// Draw the series' line with an SVG dash pattern
+    pub fn with_stroke_dash(mut self, dash: impl Into<String>) -> Self {
+        self.stroke_dash = Some(dash.into());
+        self
+    }
+}
+
+impl Series<DataPoint> {
+    /// Keep only the points at `indices` together with their attributes.
+    ///
+    /// Without explicit `row`s the original positions are recorded, so a
+    /// kept point can always be traced back to its record.
+    pub fn select(&self, indices: &[usize]) -> Self {
+        let mut attributes = self.attributes.select(indices);
+        if self.attributes.row.is_empty() {
+            attributes.row = indices.iter().map(|&i| Some(i)).collect();
+        }
+        Self {
+            name: self.name.clone(),
+            data: indices
+                .iter()
+                .filter_map(|&i| self.data.get(i).copied())
+                .collect(),
+            visible: self.visible,
+            attributes,
+            stroke_dash: self.stroke_dash.clone(),
+        }
+    }
+
+    /// LTTB-downsample to at most `threshold` points, keeping attributes aligned
+    pub fn downsample_lttb(&self, threshold: usize) -> Self {
+        self.select(&lttb_indices(&self.data, threshold))
+    }
+
+    /// M4-downsample to at most `4 * n_pixels` points, keeping attributes aligned
+    pub fn downsample_m4(&self, n_pixels: usize) -> Self {
+        self.select(&m4_indices(&self.data, n_pixels))
+    }
 }
 
 /// A dataset containing multiple series
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Dataset {
     /// The collection of series forming this dataset
     pub series: Vec<Series<DataPoint>>,
@@ -62,6 +326,22 @@ impl Dataset {
             series: vec![series],
         }
     }
+
+    /// Palette slot per series: series sharing a name (the `detail` paths
+    /// of one color group) share a slot, in first-seen order
+    pub fn color_indices(&self) -> Vec<usize> {
+        let mut names: Vec<&str> = Vec::new();
+        self.series
+            .iter()
+            .map(|s| match names.iter().position(|n| *n == s.name) {
+                Some(i) => i,
+                None => {
+                    names.push(&s.name);
+                    names.len() - 1
+                }
+            })
+            .collect()
+    }
 }
 
 impl Default for Dataset {
@@ -71,17 +351,22 @@ impl Default for Dataset {
 }
 
 /// OHLC (Open, High, Low, Close) price bar for candlestick charts
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct OhlcBar {
     /// X position, typically a Unix timestamp or sequential index
+    #[serde(deserialize_with = "f64_or_nan")]
     pub timestamp: f64,
     /// The opening price of the period
+    #[serde(deserialize_with = "f64_or_nan")]
     pub open: f64,
     /// The highest price reached during the period
+    #[serde(deserialize_with = "f64_or_nan")]
     pub high: f64,
     /// The lowest price reached during the period
+    #[serde(deserialize_with = "f64_or_nan")]
     pub low: f64,
     /// The closing price of the period
+    #[serde(deserialize_with = "f64_or_nan")]
     pub close: f64,
 }
 
@@ -104,7 +389,7 @@ impl OhlcBar {
 }
 
 /// Category of a waterfall bar
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WaterfallKind {
     /// Opening value (baseline starts at zero)
     Start,
@@ -115,7 +400,7 @@ pub enum WaterfallKind {
 }
 
 /// A single bar in a waterfall chart
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WaterfallBar {
     /// The textual label of the category/step
     pub label: String,
@@ -157,7 +442,7 @@ impl WaterfallBar {
 /// A category-based dataset for bar charts
 ///
 /// Each series provides one value per category.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct BarDataset {
     /// The list of category labels on the primary axis
     pub categories: Vec<String>,
@@ -184,7 +469,7 @@ impl BarDataset {
 }
 
 /// A single named series for a bar chart
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BarSeries {
     /// The identifier name for this data series
     pub name: String,
@@ -193,7 +478,8 @@ pub struct BarSeries {
 }
 
 /// Type of data for encoding channels
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DataType {
     /// Continuous numerical data
     Quantitative,
@@ -210,7 +496,7 @@ pub enum DataType {
 // ---------------------------------------------------------------------------
 
 /// A 2-D grid of values (rows × columns), used by HeatmapChart and ContourChart
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct GridData {
     /// Row-major matrix of values: `values[row][col]`
     pub values: Vec<Vec<f64>>,
@@ -242,7 +528,7 @@ impl GridData {
 }
 
 /// A named group of values for a StripChart
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StripGroup {
     /// Display name for this group (shown on the categorical axis)
     pub name: String,
@@ -251,7 +537,7 @@ pub struct StripGroup {
 }
 
 /// A node in a Sankey diagram
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SankeyNode {
     /// Display label for this node
     pub label: String,
@@ -260,7 +546,7 @@ pub struct SankeyNode {
 }
 
 /// A directional flow link between two Sankey nodes
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SankeyLink {
     /// Index into `SankeyData::nodes` for the source
     pub source: usize,
@@ -273,7 +559,7 @@ pub struct SankeyLink {
 }
 
 /// Complete data for a Sankey flow diagram
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct SankeyData {
     /// All nodes in the diagram
     pub nodes: Vec<SankeyNode>,
@@ -281,8 +567,58 @@ pub struct SankeyData {
     pub links: Vec<SankeyLink>,
 }
 
+impl SankeyData {
+    /// True when the links loop back on themselves (e.g. `A → B → A`).
+    ///
+    /// Sankey layouts need an acyclic flow; cyclic flows are better drawn
+    /// as a chord diagram (see [`Self::to_chord`]).
+    pub fn has_cycle(&self) -> bool {
+        // Kahn's algorithm: a cycle leaves nodes that never reach in-degree 0
+        let n = self.nodes.len();
+        let mut in_degree = vec![0usize; n];
+        for l in &self.links {
+            if l.source < n && l.target < n {
+                in_degree[l.target] += 1;
+            }
+        }
+        let mut ready: Vec<usize> = (0..n).filter(|&i| in_degree[i] == 0).collect();
+        let mut visited = 0;
+        while let Some(node) = ready.pop() {
+            visited += 1;
+            for l in self
+                .links
+                .iter()
+                .filter(|l| l.source == node && l.target < n)
+            {
+                in_degree[l.target] -= 1;
+                if in_degree[l.target] == 0 {
+                    ready.push(l.target);
+                }
+            }
+        }
+        visited < n
+    }
+
+    /// The same flows as a chord matrix (`matrix[source][target]` sums the
+    /// link values)
+    pub fn to_chord(&self) -> ChordData {
+        let n = self.nodes.len();
+        let mut matrix = vec![vec![0.0; n]; n];
+        for l in &self.links {
+            if l.source < n && l.target < n {
+                matrix[l.source][l.target] += l.value;
+            }
+        }
+        ChordData {
+            matrix,
+            labels: self.nodes.iter().map(|n| n.label.clone()).collect(),
+            colors: None,
+        }
+    }
+}
+
 /// Complete data for a Chord diagram
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ChordData {
     /// Square matrix where `matrix[i][j]` = flow from group i to group j
     pub matrix: Vec<Vec<f64>>,
@@ -296,6 +632,19 @@ pub struct ChordData {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_nan_roundtrips_through_null() {
+        let json = serde_json::to_string(&DataPoint::new(1.0, f64::NAN)).unwrap();
+        assert_eq!(json, r#"{"x":1.0,"y":null}"#);
+        let back: DataPoint = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.x, 1.0);
+        assert!(back.y.is_nan());
+
+        let bar = OhlcBar::new(0.0, 1.0, f64::NAN, 0.5, 1.0);
+        let back: OhlcBar = serde_json::from_str(&serde_json::to_string(&bar).unwrap()).unwrap();
+        assert!(back.high.is_nan());
+    }
+
     #[test]
     fn test_data_point_creation() {
         let point = DataPoint::new(1.0, 2.0);
@@ -328,4 +677,99 @@ mod tests {
         assert_eq!(dataset.series.len(), 1);
         assert_eq!(dataset.series[0].name, "s1");
     }
+
+    #[test]
+    fn test_downsample_keeps_attributes_aligned() {
+        let data: Vec<DataPoint> = (0..300)
+            .map(|i| DataPoint::new(i as f64, (i as f64 * 0.1).sin()))
+            .collect();
+        let attributes = PointAttributes {
+            id: (0..300).map(|i| format!("r{i}")).collect(),
+            size: (0..300).map(|i| i as f64 / 299.0).collect(),
+            ..PointAttributes::default()
+        };
+        let series = Series::new("s", data).with_attributes(attributes);
+
+        for reduced in [series.downsample_lttb(30), series.downsample_m4(10)] {
+            let attrs = &reduced.attributes;
+            assert!(reduced.data.len() < 300);
+            assert_eq!(attrs.id.len(), reduced.data.len());
+            assert_eq!(attrs.row.len(), reduced.data.len());
+            for (j, p) in reduced.data.iter().enumerate() {
+                let row = attrs.row_of(j).unwrap();
+                assert_eq!(p.x, row as f64);
+                assert_eq!(attrs.id[j], format!("r{row}"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_tooltip_lines() {
+        let attrs = PointAttributes {
+            id: vec!["a".into()],
+            tooltip: vec![vec![("city".into(), "Rome".into())]],
+            ..PointAttributes::default()
+        };
+        assert_eq!(
+            attrs.tooltip_lines(0),
+            vec![
+                ("id".to_owned(), "a".to_owned()),
+                ("city".to_owned(), "Rome".to_owned())
+            ]
+        );
+        assert!(attrs.tooltip_lines(1).is_empty());
+        assert_eq!(attrs.row_of(4), Some(4));
+    }
+
+    #[test]
+    fn test_serde_shapes() {
+        let ds = Dataset::from_series(Series::new("s", vec![DataPoint::new(1.0, 2.0)]));
+        assert_eq!(
+            serde_json::to_string(&ds).unwrap(),
+            r#"{"series":[{"name":"s","data":[{"x":1.0,"y":2.0}],"visible":true}]}"#
+        );
+
+        let s: Series<DataPoint> = serde_json::from_str(r#"{"name":"a","data":[]}"#).unwrap();
+        assert!(s.visible);
+
+        let bar = WaterfallBar::delta("Q1", -3.0);
+        assert_eq!(
+            serde_json::to_string(&bar).unwrap(),
+            r#"{"label":"Q1","value":-3.0,"kind":"Delta"}"#
+        );
+
+        let sankey: SankeyData = serde_json::from_str(
+            r#"{"nodes":[{"label":"A"},{"label":"B","color":"red"}],
+                "links":[{"source":0,"target":1,"value":2.0}]}"#,
+        )
+        .unwrap();
+        assert_eq!(sankey.nodes[1].color.as_deref(), Some("red"));
+        assert_eq!(sankey.links[0].color, None);
+    }
+
+    #[test]
+    fn test_sankey_cycle_to_chord() {
+        let node = |label: &str| SankeyNode {
+            label: label.to_owned(),
+            color: None,
+        };
+        let link = |source, target, value| SankeyLink {
+            source,
+            target,
+            value,
+            color: None,
+        };
+        let mut flows = SankeyData {
+            nodes: vec![node("A"), node("B"), node("C")],
+            links: vec![link(0, 1, 2.0), link(1, 2, 1.0), link(0, 1, 3.0)],
+        };
+        assert!(!flows.has_cycle());
+        flows.links.push(link(2, 0, 4.0));
+        assert!(flows.has_cycle());
+
+        let chord = flows.to_chord();
+        assert_eq!(chord.labels, vec!["A", "B", "C"]);
+        assert_eq!(chord.matrix[0][1], 5.0);
+        assert_eq!(chord.matrix[2][0], 4.0);
+    }
 }
@@ -0,0 +1,868 @@
+/// Calendar arithmetic on Unix millisecond timestamps (proleptic Gregorian, UTC)
+///
+/// `FieldValue::Timestamp` stores milliseconds since 1970-01-01T00:00:00Z.
+/// These helpers split a timestamp into calendar parts and back, and parse
+/// ISO-8601 / strftime-formatted text, without any platform clock or
+/// timezone database. Calendar intervals (`TimeInterval`) and axis labels
+/// honour a `TimeZone`: UTC or a fixed offset.
+use serde::{Deserialize, Serialize};
+
+/// Milliseconds in one second
+pub const MS_PER_SECOND: f64 = 1_000.0;
+/// Milliseconds in one minute
+pub const MS_PER_MINUTE: f64 = 60.0 * MS_PER_SECOND;
+/// Milliseconds in one hour
+pub const MS_PER_HOUR: f64 = 60.0 * MS_PER_MINUTE;
+/// Milliseconds in one day
+pub const MS_PER_DAY: f64 = 24.0 * MS_PER_HOUR;
+
+/// Broken-down calendar representation of a timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTimeParts {
+    /// Calendar year (may be negative)
+    pub year: i32,
+    /// Month 1–12
+    pub month: u32,
+    /// Day of month 1–31
+    pub day: u32,
+    /// Hour 0–23
+    pub hour: u32,
+    /// Minute 0–59
+    pub minute: u32,
+    /// Second 0–59
+    pub second: u32,
+    /// Millisecond 0–999
+    pub millisecond: u32,
+}
+
+impl DateTimeParts {
+    /// Midnight on the given date
+    pub fn date(year: i32, month: u32, day: u32) -> Self {
+        Self {
+            year,
+            month,
+            day,
+            hour: 0,
+            minute: 0,
+            second: 0,
+            millisecond: 0,
+        }
+    }
+
+    /// Split a Unix millisecond timestamp into UTC calendar parts
+    pub fn from_timestamp_ms(ms: f64) -> Self {
+        let ms = ms.floor() as i64;
+        let days = ms.div_euclid(MS_PER_DAY as i64);
+        let rem = ms.rem_euclid(MS_PER_DAY as i64);
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year,
+            month,
+            day,
+            hour: (rem / 3_600_000) as u32,
+            minute: (rem / 60_000 % 60) as u32,
+            second: (rem / 1_000 % 60) as u32,
+            millisecond: (rem % 1_000) as u32,
+        }
+    }
+
+    /// Unix millisecond timestamp of these UTC calendar parts
+    pub fn to_timestamp_ms(&self) -> f64 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        days as f64 * MS_PER_DAY
+            + f64::from(self.hour) * MS_PER_HOUR
+            + f64::from(self.minute) * MS_PER_MINUTE
+            + f64::from(self.second) * MS_PER_SECOND
+            + f64::from(self.millisecond)
+    }
+
+    /// ISO weekday: 1 = Monday … 7 = Sunday
+    pub fn weekday(&self) -> u32 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        // 1970-01-01 was a Thursday (ISO 4)
+        ((days + 3).rem_euclid(7) + 1) as u32
+    }
+
+    /// Quarter 1–4
+    pub fn quarter(&self) -> u32 {
+        (self.month - 1) / 3 + 1
+    }
+
+    /// Whether every field is within its calendar range
+    pub fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month)
+            && (1..=days_in_month(self.year, self.month)).contains(&self.day)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 61
+            && self.millisecond < 1_000
+    }
+}
+
+/// Days since 1970-01-01 for a civil date (Howard Hinnant's algorithm)
+pub fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let y = i64::from(year) - i64::from(month <= 2);
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = i64::from(month);
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Civil date `(year, month, day)` for a count of days since 1970-01-01
+pub fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year as i32, month, day)
+}
+
+/// Returns `true` for Gregorian leap years
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Number of days in `month` (1–12) of `year`
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// --- Time units ---
+
+/// Calendar unit a timestamp can be floored to (e.g. for "monthly" totals)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeUnit {
+    /// January 1st
+    Year,
+    /// First day of the quarter
+    Quarter,
+    /// First day of the month
+    Month,
+    /// Monday of the ISO week
+    Week,
+    /// Midnight
+    Day,
+    /// Start of the hour
+    Hour,
+    /// Start of the minute
+    Minute,
+    /// Start of the second
+    Second,
+}
+
+impl TimeUnit {
+    /// Lowercase name, used for derived column names
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Year => "year",
+            Self::Quarter => "quarter",
+            Self::Month => "month",
+            Self::Week => "week",
+            Self::Day => "day",
+            Self::Hour => "hour",
+            Self::Minute => "minute",
+            Self::Second => "second",
+        }
+    }
+
+    /// Floor a Unix ms timestamp to the start of its unit (UTC)
+    pub fn truncate(self, ms: f64) -> f64 {
+        let p = DateTimeParts::from_timestamp_ms(ms);
+        let floored = match self {
+            Self::Year => DateTimeParts::date(p.year, 1, 1),
+            Self::Quarter => DateTimeParts::date(p.year, (p.quarter() - 1) * 3 + 1, 1),
+            Self::Month => DateTimeParts::date(p.year, p.month, 1),
+            Self::Week => {
+                let monday = days_from_civil(p.year, p.month, p.day) - i64::from(p.weekday() - 1);
+                let (y, m, d) = civil_from_days(monday);
+                DateTimeParts::date(y, m, d)
+            }
+            Self::Day => DateTimeParts::date(p.year, p.month, p.day),
+            Self::Hour => DateTimeParts {
+                minute: 0,
+                second: 0,
+                millisecond: 0,
+                ..p
+            },
+            Self::Minute => DateTimeParts {
+                second: 0,
+                millisecond: 0,
+                ..p
+            },
+            Self::Second => DateTimeParts {
+                millisecond: 0,
+                ..p
+            },
+        };
+        floored.to_timestamp_ms()
+    }
+
+    /// Short label of the unit containing `ms`: `2024`, `2024 Q1`,
+    /// `2024-03`, `2024-03-04`, `2024-03-04 13:00`, ...
+    pub fn label(self, ms: f64) -> String {
+        let p = DateTimeParts::from_timestamp_ms(self.truncate(ms));
+        let date = format!("{:04}-{:02}-{:02}", p.year, p.month, p.day);
+        match self {
+            Self::Year => format!("{:04}", p.year),
+            Self::Quarter => format!("{:04} Q{}", p.year, p.quarter()),
+            Self::Month => format!("{:04}-{:02}", p.year, p.month),
+            Self::Week | Self::Day => date,
+            Self::Hour | Self::Minute => format!("{date} {:02}:{:02}", p.hour, p.minute),
+            Self::Second => format!("{date} {:02}:{:02}:{:02}", p.hour, p.minute, p.second),
+        }
+    }
+}
+
+// --- Time zones ---
+
+/// Zone calendar fields are read in: UTC or a fixed offset (no DST rules)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeZone {
+    /// Coordinated Universal Time
+    #[default]
+    Utc,
+    /// Fixed offset east of UTC, in minutes (e.g. `60` for UTC+01:00)
+    FixedOffset(i32),
+}
+
+impl TimeZone {
+    /// Offset from UTC in milliseconds
+    pub fn offset_ms(self) -> f64 {
+        match self {
+            Self::Utc => 0.0,
+            Self::FixedOffset(minutes) => f64::from(minutes) * MS_PER_MINUTE,
+        }
+    }
+
+    /// Shift a UTC timestamp to wall-clock time, as if the zone were UTC
+    pub fn to_local(self, ms: f64) -> f64 {
+        ms + self.offset_ms()
+    }
+
+    /// Inverse of `to_local`
+    pub fn to_utc(self, local_ms: f64) -> f64 {
+        local_ms - self.offset_ms()
+    }
+
+    /// Calendar parts of `ms` on this zone's wall clock
+    pub fn parts(self, ms: f64) -> DateTimeParts {
+        DateTimeParts::from_timestamp_ms(self.to_local(ms))
+    }
+}
+
+// --- Calendar intervals ---
+
+/// A calendar step such as "5 minutes" or "3 months", used to place axis
+/// ticks on wall-clock boundaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TimeInterval {
+    /// Base unit
+    pub unit: TimeUnit,
+    /// Number of units per step (at least 1; ignored for weeks)
+    pub step: u32,
+}
+
+impl TimeInterval {
+    /// `step` × `unit`
+    pub const fn new(unit: TimeUnit, step: u32) -> Self {
+        Self { unit, step }
+    }
+
+    /// Approximate length in milliseconds (months: 30 days, years: 365)
+    pub fn duration_ms(self) -> f64 {
+        let unit = match self.unit {
+            TimeUnit::Second => MS_PER_SECOND,
+            TimeUnit::Minute => MS_PER_MINUTE,
+            TimeUnit::Hour => MS_PER_HOUR,
+            TimeUnit::Day => MS_PER_DAY,
+            TimeUnit::Week => return 7.0 * MS_PER_DAY,
+            TimeUnit::Month => 30.0 * MS_PER_DAY,
+            TimeUnit::Quarter => 90.0 * MS_PER_DAY,
+            TimeUnit::Year => 365.0 * MS_PER_DAY,
+        };
+        unit * f64::from(self.step.max(1))
+    }
+
+    /// Latest interval boundary at or before `ms`, in `tz` wall-clock time.
+    ///
+    /// Multi-unit steps align to the enclosing unit: 15 minutes lands on
+    /// :00/:15/:30/:45, 3 months on Jan/Apr/Jul/Oct, 2 days on the 1st,
+    /// 3rd, ... of the month and 10 years on multiples of ten.
+    pub fn floor(self, ms: f64, tz: TimeZone) -> f64 {
+        let local = tz.to_local(ms);
+        let p = DateTimeParts::from_timestamp_ms(self.unit.truncate(local));
+        let step = self.step.max(1);
+        let align = |v: u32, base: u32| base + (v - base) / step * step;
+        let floored = match self.unit {
+            TimeUnit::Year => {
+                DateTimeParts::date(p.year.div_euclid(step as i32) * step as i32, 1, 1)
+            }
+            TimeUnit::Quarter => {
+                DateTimeParts::date(p.year, (p.quarter() - 1) / step * step * 3 + 1, 1)
+            }
+            TimeUnit::Month => DateTimeParts::date(p.year, align(p.month, 1), 1),
+            TimeUnit::Week => p,
+            TimeUnit::Day => DateTimeParts::date(p.year, p.month, align(p.day, 1)),
+            TimeUnit::Hour => DateTimeParts {
+                hour: align(p.hour, 0),
+                ..p
+            },
+            TimeUnit::Minute => DateTimeParts {
+                minute: align(p.minute, 0),
+                ..p
+            },
+            TimeUnit::Second => DateTimeParts {
+                second: align(p.second, 0),
+                ..p
+            },
+        };
+        tz.to_utc(floored.to_timestamp_ms())
+    }
+
+    /// Earliest interval boundary at or after `ms`
+    pub fn ceil(self, ms: f64, tz: TimeZone) -> f64 {
+        let floored = self.floor(ms, tz);
+        if floored < ms {
+            self.floor(self.offset(floored, 1, tz), tz)
+        } else {
+            floored
+        }
+    }
+
+    /// `ms` moved by `n` steps; months and years keep the day of month,
+    /// clamped to the target month's length
+    pub fn offset(self, ms: f64, n: i64, tz: TimeZone) -> f64 {
+        let units = n * i64::from(self.step.max(1));
+        let months = |m: i64| {
+            let p = tz.parts(ms);
+            let total = i64::from(p.year) * 12 + i64::from(p.month) - 1 + m;
+            let (year, month) = (total.div_euclid(12) as i32, total.rem_euclid(12) as u32 + 1);
+            let shifted = DateTimeParts {
+                year,
+                month,
+                day: p.day.min(days_in_month(year, month)),
+                ..p
+            };
+            tz.to_utc(shifted.to_timestamp_ms())
+        };
+        match self.unit {
+            TimeUnit::Year => months(units * 12),
+            TimeUnit::Quarter => months(units * 3),
+            TimeUnit::Month => months(units),
+            TimeUnit::Week => ms + n as f64 * 7.0 * MS_PER_DAY,
+            // Fixed offsets have no DST, so days are always 24 hours
+            TimeUnit::Day => ms + units as f64 * MS_PER_DAY,
+            TimeUnit::Hour => ms + units as f64 * MS_PER_HOUR,
+            TimeUnit::Minute => ms + units as f64 * MS_PER_MINUTE,
+            TimeUnit::Second => ms + units as f64 * MS_PER_SECOND,
+        }
+    }
+
+    /// Every interval boundary inside `[start, stop]`, ascending
+    pub fn range(self, start: f64, stop: f64, tz: TimeZone) -> Vec<f64> {
+        let mut out = Vec::new();
+        if !start.is_finite() || !stop.is_finite() {
+            return out;
+        }
+        let mut t = self.ceil(start, tz);
+        while t <= stop {
+            out.push(t);
+            // Re-floor so day steps restart on the 1st of each month
+            let next = self.floor(self.offset(t, 1, tz), tz);
+            t = if next > t {
+                next
+            } else {
+                self.offset(t, 1, tz)
+            };
+        }
+        out
+    }
+}
+
+/// Multi-scale axis label: the coarsest calendar field at which `ms` is
+/// not a boundary decides the format — `.250`, `:30`, `13:45`, `Mar 4`,
+/// `Mar` or `2024`
+pub fn time_label(ms: f64, tz: TimeZone) -> String {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let p = tz.parts(ms);
+    let month = MONTHS[p.month as usize - 1];
+    if p.millisecond != 0 {
+        format!(".{:03}", p.millisecond)
+    } else if p.second != 0 {
+        format!(":{:02}", p.second)
+    } else if p.hour != 0 || p.minute != 0 {
+        format!("{:02}:{:02}", p.hour, p.minute)
+    } else if p.day != 1 {
+        format!("{month} {}", p.day)
+    } else if p.month != 1 {
+        month.to_owned()
+    } else {
+        format!("{}", p.year)
+    }
+}
+
+// --- Parsing ---
+
+/// Parse an ISO-8601 / RFC 3339 date or date-time into a Unix ms timestamp.
+///
+/// Accepts `YYYY-MM-DD`, optionally followed by `T` (or a space) and
+/// `HH:MM[:SS[.fff]]`, optionally followed by `Z` or an offset `±HH[:MM]`.
+/// Times without an offset are taken as UTC.
+pub fn parse_iso8601(s: &str) -> Option<f64> {
+    let s = s.trim();
+    let mut cur = Cursor::new(s);
+    let year = cur.number(4, 4)? as i32;
+    cur.expect('-')?;
+    let month = cur.number(2, 2)?;
+    cur.expect('-')?;
+    let day = cur.number(2, 2)?;
+    let mut parts = DateTimeParts::date(year, month, day);
+    let mut offset_ms = 0.0;
+
+    if let Some(sep) = cur.peek() {
+        if sep != 'T' && sep != 't' && sep != ' ' {
+            return None;
+        }
+        cur.bump();
+        parts.hour = cur.number(2, 2)?;
+        cur.expect(':')?;
+        parts.minute = cur.number(2, 2)?;
+        if cur.eat(':') {
+            parts.second = cur.number(2, 2)?;
+            if cur.eat('.') || cur.eat(',') {
+                parts.millisecond = cur.fraction_ms()?;
+            }
+        }
+        match cur.peek() {
+            None => {}
+            Some('Z' | 'z') => {
+                cur.bump();
+            }
+            Some(_) => offset_ms = cur.offset()?,
+        }
+    }
+    if !cur.is_done() || !parts.is_valid() {
+        return None;
+    }
+    Some(parts.to_timestamp_ms() - offset_ms)
+}
+
+/// Parse `s` with a strftime-style `format` into a Unix ms timestamp.
+///
+/// Supported directives: `%Y` `%y` `%m` `%d` `%e` `%H` `%I` `%p` `%M` `%S`
+/// `%f` (fraction) `%b`/`%B` (English month names) `%j` (day of year)
+/// `%z` (offset) `%s` (epoch seconds) and `%%`. Any other character must
+/// match literally; a space matches any run of whitespace.
+pub fn parse_with_format(s: &str, format: &str) -> Option<f64> {
+    let mut cur = Cursor::new(s.trim());
+    let mut parts = DateTimeParts::date(1970, 1, 1);
+    let mut day_of_year: Option<u32> = None;
+    let mut pm: Option<bool> = None;
+    let mut offset_ms = 0.0;
+
+    let mut fmt = format.chars();
+    while let Some(f) = fmt.next() {
+        if f != '%' {
+            if f.is_whitespace() {
+                cur.skip_whitespace();
+            } else {
+                cur.expect(f)?;
+            }
+            continue;
+        }
+        match fmt.next()? {
+            'Y' => {
+                let neg = cur.eat('-');
+                let y = cur.number(1, 4)? as i32;
+                parts.year = if neg { -y } else { y };
+            }
+            'y' => {
+                let y = cur.number(2, 2)? as i32;
+                // POSIX pivot: 69–99 → 1900s, 00–68 → 2000s
+                parts.year = if y >= 69 { 1900 + y } else { 2000 + y };
+            }
+            'm' => parts.month = cur.number(1, 2)?,
+            'd' => parts.day = cur.number(1, 2)?,
+            'e' => {
+                cur.skip_whitespace();
+                parts.day = cur.number(1, 2)?;
+            }
+            'j' => day_of_year = Some(cur.number(1, 3)?),
+            'H' => parts.hour = cur.number(1, 2)?,
+            'I' => parts.hour = cur.number(1, 2)?,
+            'M' => parts.minute = cur.number(1, 2)?,
+            'S' => parts.second = cur.number(1, 2)?,
+            'f' => parts.millisecond = cur.fraction_ms()?,
+            'p' => {
+                let word = cur.alpha();
+                pm = match word.to_ascii_lowercase().as_str() {
+                    "am" => Some(false),
+                    "pm" => Some(true),
+                    _ => return None,
+                };
+            }
+            'b' | 'B' => parts.month = month_from_name(&cur.alpha())?,
+            'z' => {
+                if !cur.eat('Z') {
+                    offset_ms = cur.offset()?;
+                }
+            }
+            's' => {
+                let neg = cur.eat('-');
+                let secs = f64::from(cur.number(1, 10)?);
+                let secs = if neg { -secs } else { secs };
+                parts = DateTimeParts::from_timestamp_ms(secs * MS_PER_SECOND);
+            }
+            '%' => cur.expect('%')?,
+            _ => return None,
+        }
+    }
+    if !cur.is_done() {
+        return None;
+    }
+    if let Some(pm) = pm {
+        if !(1..=12).contains(&parts.hour) {
+            return None;
+        }
+        parts.hour = parts.hour % 12 + if pm { 12 } else { 0 };
+    }
+    if let Some(doy) = day_of_year {
+        let days = days_from_civil(parts.year, 1, 1) + i64::from(doy) - 1;
+        let (y, m, d) = civil_from_days(days);
+        if y != parts.year || doy == 0 {
+            return None;
+        }
+        (parts.month, parts.day) = (m, d);
+    }
+    if !parts.is_valid() {
+        return None;
+    }
+    Some(parts.to_timestamp_ms() - offset_ms)
+}
+
+fn month_from_name(name: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    let lower = name.to_ascii_lowercase();
+    if lower.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|m| lower.starts_with(m))
+        .map(|i| i as u32 + 1)
+}
+
+/// Minimal character cursor for the date parsers
+struct Cursor<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            chars: s.chars().peekable(),
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) {
+        self.chars.next();
+    }
+
+    fn is_done(&mut self) -> bool {
+        self.chars.peek().is_none()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Option<()> {
+        self.eat(c).then_some(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    /// Unsigned decimal of `min..=max` digits
+    fn number(&mut self, min: usize, max: usize) -> Option<u32> {
+        let mut value: u32 = 0;
+        let mut n = 0;
+        while n < max {
+            let Some(d) = self.peek().and_then(|c| c.to_digit(10)) else {
+                break;
+            };
+            value = value.checked_mul(10)?.checked_add(d)?;
+            self.bump();
+            n += 1;
+        }
+        (n >= min).then_some(value)
+    }
+
+    /// Digits after a decimal point, as milliseconds (extra precision dropped)
+    fn fraction_ms(&mut self) -> Option<u32> {
+        let mut ms = 0;
+        let mut n = 0;
+        while let Some(d) = self.peek().and_then(|c| c.to_digit(10)) {
+            if n < 3 {
+                ms = ms * 10 + d;
+            }
+            n += 1;
+            self.bump();
+        }
+        if n == 0 {
+            return None;
+        }
+        for _ in n..3 {
+            ms *= 10;
+        }
+        Some(ms)
+    }
+
+    fn alpha(&mut self) -> String {
+        let mut s = String::new();
+        while let Some(c) = self.peek().filter(char::is_ascii_alphabetic) {
+            s.push(c);
+            self.bump();
+        }
+        s
+    }
+
+    /// `±HH`, `±HHMM` or `±HH:MM` → offset in milliseconds
+    fn offset(&mut self) -> Option<f64> {
+        let sign = match self.peek()? {
+            '+' => 1.0,
+            '-' => -1.0,
+            _ => return None,
+        };
+        self.bump();
+        let hours = self.number(2, 2)?;
+        // Minutes are optional, but always two digits when present
+        let minutes = if self.eat(':') || self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.number(2, 2)?
+        } else {
+            0
+        };
+        if hours > 23 || minutes > 59 {
+            return None;
+        }
+        Some(sign * (f64::from(hours) * MS_PER_HOUR + f64::from(minutes) * MS_PER_MINUTE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        for days in [-800_000, -1, 0, 59, 11_016, 19_782, 2_932_896] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn test_parts_from_timestamp() {
+        // 2024-02-29T13:45:30.250Z
+        let ms = 1_709_214_330_250.0;
+        let p = DateTimeParts::from_timestamp_ms(ms);
+        assert_eq!((p.year, p.month, p.day), (2024, 2, 29));
+        assert_eq!(
+            (p.hour, p.minute, p.second, p.millisecond),
+            (13, 45, 30, 250)
+        );
+        assert_eq!(p.weekday(), 4); // Thursday
+        assert_eq!(p.quarter(), 1);
+        assert_eq!(p.to_timestamp_ms(), ms);
+    }
+
+    #[test]
+    fn test_before_epoch() {
+        let p = DateTimeParts::from_timestamp_ms(-1.0);
+        assert_eq!((p.year, p.month, p.day), (1969, 12, 31));
+        assert_eq!(
+            (p.hour, p.minute, p.second, p.millisecond),
+            (23, 59, 59, 999)
+        );
+    }
+
+    #[test]
+    fn test_parse_iso8601() {
+        let noon = DateTimeParts {
+            hour: 12,
+            ..DateTimeParts::date(2024, 3, 1)
+        }
+        .to_timestamp_ms();
+        assert_eq!(parse_iso8601("2024-03-01T12:00:00Z"), Some(noon));
+        assert_eq!(parse_iso8601("2024-03-01 12:00"), Some(noon));
+        assert_eq!(parse_iso8601("2024-03-01T14:00:00+02:00"), Some(noon));
+        assert_eq!(parse_iso8601("2024-03-01T07:00:00.000-0500"), Some(noon));
+        assert_eq!(
+            parse_iso8601("2024-03-01T12:00:00.1234Z"),
+            Some(noon + 123.0)
+        );
+        assert_eq!(
+            parse_iso8601("2024-03-01"),
+            Some(DateTimeParts::date(2024, 3, 1).to_timestamp_ms())
+        );
+        assert_eq!(parse_iso8601("2024-02-30"), None);
+        assert_eq!(parse_iso8601("2024-03-01T25:00"), None);
+        assert_eq!(parse_iso8601("20240301"), None);
+        assert_eq!(parse_iso8601("2024-03-01 hello"), None);
+        assert_eq!(parse_iso8601("2024-03-01T14:00:00+02"), Some(noon));
+        assert_eq!(parse_iso8601("2024-03-01T14:00:00+02:3"), None);
+        assert_eq!(parse_iso8601("2024-03-01T14:00:00+023"), None);
+        assert_eq!(parse_iso8601("2024-03-01T14:00:00+02:"), None);
+    }
+
+    #[test]
+    fn test_parse_with_format() {
+        let expected = DateTimeParts {
+            hour: 15,
+            minute: 30,
+            ..DateTimeParts::date(2024, 3, 1)
+        }
+        .to_timestamp_ms();
+        assert_eq!(
+            parse_with_format("01/03/2024 15:30", "%d/%m/%Y %H:%M"),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_with_format("Mar 1, 24 3:30 PM", "%b %d, %y %I:%M %p"),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_with_format("2024-061", "%Y-%j"),
+            Some(DateTimeParts::date(2024, 3, 1).to_timestamp_ms())
+        );
+        assert_eq!(parse_with_format("1709307000", "%s"), Some(expected));
+        assert_eq!(parse_with_format("31/02/2024", "%d/%m/%Y"), None);
+        assert_eq!(parse_with_format("01-03-2024", "%d/%m/%Y"), None);
+    }
+
+    #[test]
+    fn test_days_in_month() {
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(1900, 2), 28);
+        assert_eq!(days_in_month(2000, 2), 29);
+        assert_eq!(days_in_month(2023, 4), 30);
+    }
+
+    #[test]
+    fn test_time_unit_truncate() {
+        // Wednesday 2024-05-15 13:45:30.250
+        let t = DateTimeParts {
+            millisecond: 250,
+            ..DateTimeParts::date(2024, 5, 15)
+        }
+        .to_timestamp_ms()
+            + 13.0 * MS_PER_HOUR
+            + 45.0 * MS_PER_MINUTE
+            + 30.0 * MS_PER_SECOND;
+        let at = |y, m, d| DateTimeParts::date(y, m, d).to_timestamp_ms();
+        assert_eq!(TimeUnit::Year.truncate(t), at(2024, 1, 1));
+        assert_eq!(TimeUnit::Quarter.truncate(t), at(2024, 4, 1));
+        assert_eq!(TimeUnit::Month.truncate(t), at(2024, 5, 1));
+        assert_eq!(TimeUnit::Week.truncate(t), at(2024, 5, 13));
+        assert_eq!(TimeUnit::Day.truncate(t), at(2024, 5, 15));
+        assert_eq!(
+            TimeUnit::Hour.truncate(t),
+            at(2024, 5, 15) + 13.0 * MS_PER_HOUR
+        );
+        assert_eq!(TimeUnit::Second.truncate(t) % 1_000.0, 0.0);
+        assert_eq!(TimeUnit::Quarter.label(t), "2024 Q2");
+        assert_eq!(TimeUnit::Month.label(t), "2024-05");
+        assert_eq!(TimeUnit::Minute.label(t), "2024-05-15 13:45");
+    }
+
+    #[test]
+    fn test_interval_floor_and_offset() {
+        let at = |y, m, d| DateTimeParts::date(y, m, d).to_timestamp_ms();
+        let t = at(2024, 5, 15) + 13.0 * MS_PER_HOUR + 47.0 * MS_PER_MINUTE;
+        let utc = TimeZone::Utc;
+        let every = TimeInterval::new;
+        assert_eq!(
+            every(TimeUnit::Minute, 15).floor(t, utc),
+            at(2024, 5, 15) + 13.0 * MS_PER_HOUR + 45.0 * MS_PER_MINUTE
+        );
+        assert_eq!(every(TimeUnit::Month, 3).floor(t, utc), at(2024, 4, 1));
+        assert_eq!(every(TimeUnit::Year, 10).floor(t, utc), at(2020, 1, 1));
+        assert_eq!(every(TimeUnit::Day, 1).ceil(t, utc), at(2024, 5, 16));
+        assert_eq!(
+            every(TimeUnit::Month, 1).offset(at(2024, 1, 31), 1, utc),
+            at(2024, 2, 29)
+        );
+        assert_eq!(
+            every(TimeUnit::Day, 2).range(at(2024, 1, 28), at(2024, 2, 4), utc),
+            vec![
+                at(2024, 1, 29),
+                at(2024, 1, 31),
+                at(2024, 2, 1),
+                at(2024, 2, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interval_in_fixed_offset() {
+        // Local midnight at UTC-05:00 is 05:00 UTC
+        let est = TimeZone::FixedOffset(-300);
+        let t = DateTimeParts::date(2024, 3, 4).to_timestamp_ms() + 2.0 * MS_PER_HOUR;
+        assert_eq!(
+            TimeInterval::new(TimeUnit::Day, 1).floor(t, est),
+            DateTimeParts::date(2024, 3, 3).to_timestamp_ms() + 5.0 * MS_PER_HOUR
+        );
+        assert_eq!(est.parts(t).hour, 21);
+    }
+
+    #[test]
+    fn test_time_label_multi_scale() {
+        let utc = TimeZone::Utc;
+        let day = DateTimeParts::date(2024, 3, 4).to_timestamp_ms();
+        assert_eq!(
+            time_label(DateTimeParts::date(2024, 1, 1).to_timestamp_ms(), utc),
+            "2024"
+        );
+        assert_eq!(
+            time_label(DateTimeParts::date(2024, 3, 1).to_timestamp_ms(), utc),
+            "Mar"
+        );
+        assert_eq!(time_label(day, utc), "Mar 4");
+        assert_eq!(time_label(day + 12.0 * MS_PER_HOUR, utc), "12:00");
+        assert_eq!(time_label(day + 30.0 * MS_PER_SECOND, utc), ":30");
+        assert_eq!(time_label(day + 250.0, utc), ".250");
+        assert_eq!(time_label(day, TimeZone::FixedOffset(60)), "01:00");
+    }
+}
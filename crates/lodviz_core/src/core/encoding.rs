@@ -1,15 +1,41 @@
 /// Encoding channels for Grammar of Graphics
 ///
 /// Encodings map data fields to visual channels (x, y, color, size, etc.)
+use serde::{Deserialize, Serialize};
+
 use super::data::DataType;
+use crate::algorithms::statistics::BinRule;
+use crate::core::column::{Column, ColumnKind};
+use crate::core::datetime::TimeUnit;
+use crate::core::field_value::{DataTable, FieldValue};
+use crate::core::format::NumberFormat;
+use crate::core::table_data::format_cell_value;
+use crate::core::transform::{AggregateField, AggregateOp, SortField, Transform};
 
-/// A field with its data type for encoding
-#[derive(Debug, Clone)]
+/// A field with its data type for encoding.
+///
+/// Serializes Vega-Lite style: `{"field": "sales", "type": "quantitative"}`,
+/// plus optional `"aggregate"`, `"bin"` and `"time_unit"` keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Field {
     /// Name of the field in the data
+    #[serde(rename = "field")]
     pub name: String,
     /// Type of data (quantitative, temporal, nominal, ordinal)
+    #[serde(rename = "type")]
     pub data_type: DataType,
+    /// Summarize the field per group of the other (non-aggregated) channels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregate: Option<AggregateOp>,
+    /// Discretize a numeric field into bins
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bin: Option<BinRule>,
+    /// Floor a temporal field to a calendar unit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_unit: Option<TimeUnit>,
+    /// d3-format specifier for the field's numbers in labels and tooltips
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
 }
 
 impl Field {
@@ -18,9 +44,47 @@ impl Field {
         Self {
             name: name.into(),
             data_type,
+            aggregate: None,
+            bin: None,
+            time_unit: None,
+            format: None,
         }
     }
 
+    /// Summarize with `op` (e.g. `Field::quantitative("revenue").with_aggregate(AggregateOp::Sum)`)
+    pub fn with_aggregate(mut self, op: AggregateOp) -> Self {
+        self.aggregate = Some(op);
+        self
+    }
+
+    /// Bin the field's values using `rule`
+    pub fn with_bin(mut self, rule: BinRule) -> Self {
+        self.bin = Some(rule);
+        self
+    }
+
+    /// Floor the field's timestamps to `unit`
+    pub fn with_time_unit(mut self, unit: TimeUnit) -> Self {
+        self.time_unit = Some(unit);
+        self
+    }
+
+    /// Show the field's numbers with a d3-format specifier (e.g. `"$.2s"`)
+    pub fn with_format(mut self, specifier: impl Into<String>) -> Self {
+        self.format = Some(specifier.into());
+        self
+    }
+
+    /// Parsed `format`; None when unset or not a valid specifier
+    pub fn number_format(&self) -> Option<NumberFormat> {
+        NumberFormat::new(self.format.as_deref()?).ok()
+    }
+
+    /// True when the plotted value is derived from the column
+    pub fn is_derived(&self) -> bool {
+        self.aggregate.is_some() || self.bin.is_some() || self.time_unit.is_some()
+    }
+
     /// Create a quantitative field (continuous numerical)
     pub fn quantitative(name: impl Into<String>) -> Self {
         Self::new(name, DataType::Quantitative)
@@ -42,8 +106,12 @@ impl Field {
     }
 }
 
-/// Encoding specification for mapping data to visual channels
+/// Encoding specification for mapping data to visual channels.
+///
+/// Non-exhaustive so channels can be added: build it with [`Encoding::new`]
+/// and the `with_*` setters.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Encoding {
     /// X-axis encoding (required)
     pub x: Field,
@@ -53,6 +121,20 @@ pub struct Encoding {
     pub color: Option<Field>,
     /// Size encoding (optional, for points/circles)
     pub size: Option<Field>,
+    /// Per-point opacity (optional)
+    pub opacity: Option<Field>,
+    /// Per-point marker shape (optional)
+    pub shape: Option<Field>,
+    /// Line dash pattern; splits series like color (optional)
+    pub stroke_dash: Option<Field>,
+    /// Per-point text label (optional)
+    pub text: Option<Field>,
+    /// Extra fields listed in the tooltip
+    pub tooltip: Vec<Field>,
+    /// Splits series without changing their color (optional)
+    pub detail: Option<Field>,
+    /// Sort order of the points along each series' path (optional)
+    pub order: Option<Field>,
 }
 
 impl Encoding {
@@ -63,6 +145,13 @@ impl Encoding {
             y,
             color: None,
             size: None,
+            opacity: None,
+            shape: None,
+            stroke_dash: None,
+            text: None,
+            tooltip: Vec::new(),
+            detail: None,
+            order: None,
         }
     }
 
@@ -78,6 +167,48 @@ impl Encoding {
         self
     }
 
+    /// Set the opacity encoding channel
+    pub fn with_opacity(mut self, opacity: Field) -> Self {
+        self.opacity = Some(opacity);
+        self
+    }
+
+    /// Set the shape encoding channel
+    pub fn with_shape(mut self, shape: Field) -> Self {
+        self.shape = Some(shape);
+        self
+    }
+
+    /// Set the stroke dash encoding channel
+    pub fn with_stroke_dash(mut self, stroke_dash: Field) -> Self {
+        self.stroke_dash = Some(stroke_dash);
+        self
+    }
+
+    /// Set the text encoding channel
+    pub fn with_text(mut self, text: Field) -> Self {
+        self.text = Some(text);
+        self
+    }
+
+    /// Add a field to the tooltip
+    pub fn with_tooltip(mut self, field: Field) -> Self {
+        self.tooltip.push(field);
+        self
+    }
+
+    /// Set the detail (grouping) channel
+    pub fn with_detail(mut self, detail: Field) -> Self {
+        self.detail = Some(detail);
+        self
+    }
+
+    /// Set the order channel
+    pub fn with_order(mut self, order: Field) -> Self {
+        self.order = Some(order);
+        self
+    }
+
     /// Set an optional color encoding channel (no-op when `None`)
     pub fn with_color_opt(self, color: Option<Field>) -> Self {
         match color {
@@ -93,11 +224,149 @@ impl Encoding {
             None => self,
         }
     }
+
+    /// Apply the fields' time units, bins and aggregates to `table`.
+    ///
+    /// Returns the derived table and a plain encoding over its columns, or
+    /// `None` when no field is derived. Derived columns are named
+    /// `<unit>_<field>`, `bin_<field>` and `<op>_<field>` (`count` for
+    /// counts); aggregates group by every non-aggregated channel. Rows come
+    /// out sorted by a binned / time-unit x. With `categorical_x` such an x
+    /// becomes text labels (`2024-03`, `10–20`) for bar categories.
+    pub fn derive(&self, table: &DataTable, categorical_x: bool) -> Option<(DataTable, Encoding)> {
+        let mut enc = self.clone();
+        if !enc.fields_mut().iter().any(|f| f.is_derived()) {
+            return None;
+        }
+
+        let x_time_unit = enc.x.time_unit;
+        let x_binned = enc.x.bin.is_some();
+        let mut steps = Vec::new();
+        for field in enc.fields_mut() {
+            if let Some(unit) = field.time_unit.take() {
+                let src = field.name.clone();
+                field.name = format!("{}_{src}", unit.name());
+                field.data_type = DataType::Temporal;
+                steps.push(Transform::calculate(field.name.clone(), move |t, i| {
+                    t.column(&src)
+                        .and_then(|c| c.get_f64(i))
+                        .map_or(FieldValue::Null, |ms| {
+                            FieldValue::Timestamp(unit.truncate(ms))
+                        })
+                }));
+            }
+            if let Some(rule) = field.bin.take() {
+                steps.push(Transform::bin(field.name.clone(), rule));
+                field.name = format!("bin_{}", field.name);
+                field.data_type = DataType::Quantitative;
+            }
+        }
+        let mut table = table.transform(&steps);
+
+        if categorical_x && (x_time_unit.is_some() || x_binned) {
+            // Order categories by time / bin start before the values become text
+            let x = enc.x.name.clone();
+            table = Transform::sort(vec![SortField::asc(x.as_str())]).apply(&table);
+            let labels: Vec<FieldValue> = (0..table.len())
+                .map(|i| {
+                    let Some(v) = table.column(&x).and_then(|c| c.get_f64(i)) else {
+                        return FieldValue::Null;
+                    };
+                    FieldValue::Text(match x_time_unit {
+                        Some(unit) => unit.label(v),
+                        None => format!(
+                            "{}–{}",
+                            format_cell_value(&FieldValue::Numeric(v)),
+                            format_cell_value(&table.value(i, &format!("{x}_end")))
+                        ),
+                    })
+                })
+                .collect();
+            table.insert_column(Column::from_values(x.as_str(), labels));
+            enc.x.data_type = DataType::Ordinal;
+        }
+
+        let fields = enc.fields_mut();
+        if fields.iter().any(|f| f.aggregate.is_some()) {
+            let mut groupby: Vec<String> = Vec::new();
+            let mut outputs: Vec<AggregateField> = Vec::new();
+            for field in fields {
+                let Some(op) = field.aggregate.take() else {
+                    if !groupby.contains(&field.name) {
+                        groupby.push(field.name.clone());
+                    }
+                    continue;
+                };
+                let out = match op {
+                    AggregateOp::Count => "count".to_owned(),
+                    _ => format!("{}_{}", op.name(), field.name),
+                };
+                if !outputs.iter().any(|o| o.as_name == out) {
+                    outputs.push(AggregateField::new(op, field.name.clone(), out.clone()));
+                }
+                field.name = out;
+                field.data_type = DataType::Quantitative;
+            }
+            let groupby: Vec<&str> = groupby.iter().map(String::as_str).collect();
+            table = Transform::aggregate(&groupby, outputs).apply(&table);
+        }
+        // Line / area / point x runs left to right; text categories keep their order
+        let x_continuous = table
+            .column(&enc.x.name)
+            .is_some_and(|c| matches!(c.kind(), ColumnKind::Numeric | ColumnKind::Timestamp));
+        if x_continuous {
+            table = Transform::sort(vec![SortField::asc(enc.x.name.as_str())]).apply(&table);
+        }
+        Some((table, enc))
+    }
+
+    /// Every set channel, x first
+    pub(crate) fn fields(&self) -> Vec<&Field> {
+        let mut out = vec![&self.x, &self.y];
+        out.extend(self.color.as_ref());
+        out.extend(self.size.as_ref());
+        out.extend(self.opacity.as_ref());
+        out.extend(self.shape.as_ref());
+        out.extend(self.stroke_dash.as_ref());
+        out.extend(self.text.as_ref());
+        out.extend(self.tooltip.iter());
+        out.extend(self.detail.as_ref());
+        out.extend(self.order.as_ref());
+        out
+    }
+
+    /// Every set channel, x first (mutable)
+    fn fields_mut(&mut self) -> Vec<&mut Field> {
+        let mut out = vec![&mut self.x, &mut self.y];
+        out.extend(self.color.as_mut());
+        out.extend(self.size.as_mut());
+        out.extend(self.opacity.as_mut());
+        out.extend(self.shape.as_mut());
+        out.extend(self.stroke_dash.as_mut());
+        out.extend(self.text.as_mut());
+        out.extend(self.tooltip.iter_mut());
+        out.extend(self.detail.as_mut());
+        out.extend(self.order.as_mut());
+        out
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::datetime::DateTimeParts;
+    use crate::data_row;
+
+    fn sales() -> DataTable {
+        let day = |m, d| FieldValue::Timestamp(DateTimeParts::date(2024, m, d).to_timestamp_ms());
+        DataTable::from_rows(vec![
+            data_row! { "date" => day(2, 3), "region" => "EU", "revenue" => 5.0_f64 },
+            data_row! { "date" => day(1, 5), "region" => "EU", "revenue" => 10.0_f64 },
+            data_row! { "date" => day(1, 20), "region" => "US", "revenue" => 7.0_f64 },
+            data_row! { "date" => day(1, 28), "region" => "EU", "revenue" => 2.0_f64 },
+            data_row! { "date" => day(2, 9), "region" => "US", "revenue" => 1.0_f64 },
+        ])
+    }
 
     #[test]
     fn test_field_creation() {
@@ -158,4 +427,84 @@ mod tests {
         assert!(encoding.color.is_some());
         assert!(encoding.size.is_some());
     }
+
+    #[test]
+    fn test_monthly_revenue_by_region() {
+        let enc = Encoding::new(
+            Field::temporal("date").with_time_unit(TimeUnit::Month),
+            Field::quantitative("revenue").with_aggregate(AggregateOp::Sum),
+        )
+        .with_color(Field::nominal("region"));
+
+        let ds = sales().to_dataset(&enc);
+        let eu = &ds.series[0];
+        assert_eq!(eu.name, "EU");
+        let jan = DateTimeParts::date(2024, 1, 1).to_timestamp_ms();
+        let feb = DateTimeParts::date(2024, 2, 1).to_timestamp_ms();
+        let points: Vec<(f64, f64)> = eu.data.iter().map(|p| (p.x, p.y)).collect();
+        assert_eq!(points, vec![(jan, 12.0), (feb, 5.0)]);
+
+        let bars = sales().to_bar_dataset(&enc);
+        assert_eq!(bars.categories, vec!["2024-01", "2024-02"]);
+        assert_eq!(bars.series[0].name, "EU");
+        assert_eq!(bars.series[0].values, vec![12.0, 5.0]);
+        assert_eq!(bars.series[1].values, vec![7.0, 1.0]);
+    }
+
+    #[test]
+    fn test_bin_and_count() {
+        let enc = Encoding::new(
+            Field::quantitative("revenue").with_bin(BinRule::Fixed(2)),
+            Field::quantitative("revenue").with_aggregate(AggregateOp::Count),
+        );
+        let (table, derived) = enc.derive(&sales(), true).unwrap();
+        assert_eq!(derived.x.name, "bin_revenue");
+        assert_eq!(derived.y.name, "count");
+        assert_eq!(
+            table.value(0, "bin_revenue"),
+            FieldValue::Text("1–5.5".into())
+        );
+        assert_eq!(table.extract_numeric("count"), vec![3.0, 2.0]);
+    }
+
+    #[test]
+    fn test_quantile_per_group() {
+        let enc = Encoding::new(
+            Field::nominal("region"),
+            Field::quantitative("revenue").with_aggregate(AggregateOp::Quantile(0.5)),
+        );
+        let bars = sales().to_bar_dataset(&enc);
+        assert_eq!(bars.categories, vec!["EU", "US"]);
+        assert_eq!(bars.series[0].values, vec![5.0, 4.0]);
+
+        assert_eq!(AggregateOp::Quantile(0.9).name(), "q90");
+        assert_eq!(AggregateOp::Quantile(0.07).name(), "q7");
+        let mut close = Encoding::new(
+            Field::quantitative("revenue").with_aggregate(AggregateOp::Quantile(0.499)),
+            Field::quantitative("revenue").with_aggregate(AggregateOp::Quantile(0.501)),
+        );
+        close.color = Some(Field::nominal("region"));
+        let (_, derived) = close.derive(&sales(), false).unwrap();
+        assert_eq!(derived.x.name, "q49.9_revenue");
+        assert_eq!(derived.y.name, "q50.1_revenue");
+
+        let plain = Encoding::new(Field::nominal("region"), Field::quantitative("revenue"));
+        assert!(plain.derive(&sales(), false).is_none());
+    }
+
+    #[test]
+    fn test_field_options_serde() {
+        let f = Field::temporal("date").with_time_unit(TimeUnit::Week);
+        let json = serde_json::to_value(&f).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"field": "date", "type": "temporal", "time_unit": "week"})
+        );
+        let q: Field = serde_json::from_value(serde_json::json!(
+            {"field": "v", "type": "quantitative", "aggregate": {"quantile": 0.9}, "bin": {"fixed": 4}}
+        ))
+        .unwrap();
+        assert_eq!(q.aggregate, Some(AggregateOp::Quantile(0.9)));
+        assert_eq!(q.bin, Some(BinRule::Fixed(4)));
+    }
 }
@@ -0,0 +1,1152 @@
+/// Expression language for calculated fields and filter predicates
+///
+/// Expressions are parsed once from text (e.g. a config file) and evaluated
+/// per row of a `DataTable`, producing `FieldValue`s:
+///
+/// ```text
+/// price * qty
+/// if(region == "EU", amount * 1.1, amount)
+/// log10(x) >= 2 && !isnull(`unit price`)
+/// year(date)
+/// ```
+///
+/// Evaluation never panics: missing columns and nulls propagate as `Null`,
+/// non-finite arithmetic results become `Null`, and parse depth is bounded.
+use std::fmt;
+
+use crate::core::column::Column;
+use crate::core::datetime::DateTimeParts;
+use crate::core::field_value::{DataTable, FieldValue};
+
+/// Maximum nesting depth accepted by the parser
+const MAX_DEPTH: usize = 64;
+
+// --- AST ---
+
+/// Unary operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    /// Arithmetic negation `-x`
+    Neg,
+    /// Logical negation `!x` / `not x`
+    Not,
+}
+
+/// Binary operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    /// `+` (also concatenates text)
+    Add,
+    /// `-`
+    Sub,
+    /// `*`
+    Mul,
+    /// `/`
+    Div,
+    /// `%`
+    Rem,
+    /// `^` (power, right-associative)
+    Pow,
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `&&` / `and` (short-circuit)
+    And,
+    /// `||` / `or` (short-circuit)
+    Or,
+}
+
+impl BinaryOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+            Self::Pow => "^",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::And => "&&",
+            Self::Or => "||",
+        }
+    }
+}
+
+/// Built-in function
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Function {
+    /// `if(cond, then, else)` — only the taken branch is evaluated
+    If,
+    /// `abs(x)`
+    Abs,
+    /// `sqrt(x)`
+    Sqrt,
+    /// `exp(x)`
+    Exp,
+    /// `ln(x)` / `log(x)` — natural logarithm
+    Ln,
+    /// `log10(x)`
+    Log10,
+    /// `log2(x)`
+    Log2,
+    /// `pow(x, y)`
+    Pow,
+    /// `round(x)` or `round(x, digits)`
+    Round,
+    /// `floor(x)`
+    Floor,
+    /// `ceil(x)`
+    Ceil,
+    /// `min(a, b, ...)` — ignores nulls
+    Min,
+    /// `max(a, b, ...)` — ignores nulls
+    Max,
+    /// `coalesce(a, b, ...)` — first non-null argument
+    Coalesce,
+    /// `isnull(x)`
+    IsNull,
+    /// `number(x)` — parse text or cast bool/timestamp to a number
+    Number,
+    /// `string(x)` — display form of any value
+    String,
+    /// `lower(s)`
+    Lower,
+    /// `upper(s)`
+    Upper,
+    /// `length(s)` — character count
+    Length,
+    /// `contains(s, needle)`
+    Contains,
+    /// `year(t)`
+    Year,
+    /// `quarter(t)` — 1–4
+    Quarter,
+    /// `month(t)` — 1–12
+    Month,
+    /// `day(t)` — day of month 1–31
+    Day,
+    /// `weekday(t)` — ISO, 1 = Monday … 7 = Sunday
+    Weekday,
+    /// `hour(t)`
+    Hour,
+    /// `minute(t)`
+    Minute,
+    /// `second(t)`
+    Second,
+    /// `date(year, month, day)` — UTC midnight timestamp
+    Date,
+}
+
+impl Function {
+    /// Look up a function by its (case-insensitive) name
+    pub fn from_name(name: &str) -> Option<Self> {
+        let f = match name.to_ascii_lowercase().as_str() {
+            "if" => Self::If,
+            "abs" => Self::Abs,
+            "sqrt" => Self::Sqrt,
+            "exp" => Self::Exp,
+            "ln" | "log" => Self::Ln,
+            "log10" => Self::Log10,
+            "log2" => Self::Log2,
+            "pow" => Self::Pow,
+            "round" => Self::Round,
+            "floor" => Self::Floor,
+            "ceil" => Self::Ceil,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            "coalesce" => Self::Coalesce,
+            "isnull" => Self::IsNull,
+            "number" => Self::Number,
+            "string" => Self::String,
+            "lower" => Self::Lower,
+            "upper" => Self::Upper,
+            "length" => Self::Length,
+            "contains" => Self::Contains,
+            "year" => Self::Year,
+            "quarter" => Self::Quarter,
+            "month" => Self::Month,
+            "day" => Self::Day,
+            "weekday" => Self::Weekday,
+            "hour" => Self::Hour,
+            "minute" => Self::Minute,
+            "second" => Self::Second,
+            "date" => Self::Date,
+            _ => return None,
+        };
+        Some(f)
+    }
+
+    /// Canonical name
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::If => "if",
+            Self::Abs => "abs",
+            Self::Sqrt => "sqrt",
+            Self::Exp => "exp",
+            Self::Ln => "ln",
+            Self::Log10 => "log10",
+            Self::Log2 => "log2",
+            Self::Pow => "pow",
+            Self::Round => "round",
+            Self::Floor => "floor",
+            Self::Ceil => "ceil",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Coalesce => "coalesce",
+            Self::IsNull => "isnull",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Lower => "lower",
+            Self::Upper => "upper",
+            Self::Length => "length",
+            Self::Contains => "contains",
+            Self::Year => "year",
+            Self::Quarter => "quarter",
+            Self::Month => "month",
+            Self::Day => "day",
+            Self::Weekday => "weekday",
+            Self::Hour => "hour",
+            Self::Minute => "minute",
+            Self::Second => "second",
+            Self::Date => "date",
+        }
+    }
+
+    /// Accepted argument count `(min, max)`; `None` = variadic
+    fn arity(self) -> (usize, Option<usize>) {
+        match self {
+            Self::If | Self::Date => (3, Some(3)),
+            Self::Pow | Self::Contains => (2, Some(2)),
+            Self::Round => (1, Some(2)),
+            Self::Min | Self::Max | Self::Coalesce => (1, None),
+            _ => (1, Some(1)),
+        }
+    }
+}
+
+/// Parsed expression tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Constant value
+    Literal(FieldValue),
+    /// Column reference
+    Field(String),
+    /// Unary operation
+    Unary(UnaryOp, Box<Expr>),
+    /// Binary operation
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    /// Function call
+    Call(Function, Vec<Expr>),
+}
+
+impl Expr {
+    /// Parse an expression from source text
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            depth: 0,
+        };
+        let expr = parser.expr()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(t) => Err(format!("unexpected {} at position {}", t, parser.offset())),
+        }
+    }
+
+    /// Evaluate against row `row` of `table`
+    pub fn eval(&self, table: &DataTable, row: usize) -> FieldValue {
+        match self {
+            Self::Literal(v) => v.clone(),
+            Self::Field(name) => table.value(row, name),
+            Self::Unary(op, e) => {
+                let v = e.eval(table, row);
+                match op {
+                    UnaryOp::Neg => v.as_f64().map_or(FieldValue::Null, |x| number(-x)),
+                    UnaryOp::Not => FieldValue::Bool(!truthy(&v)),
+                }
+            }
+            Self::Binary(BinaryOp::And, l, r) => {
+                FieldValue::Bool(truthy(&l.eval(table, row)) && truthy(&r.eval(table, row)))
+            }
+            Self::Binary(BinaryOp::Or, l, r) => {
+                FieldValue::Bool(truthy(&l.eval(table, row)) || truthy(&r.eval(table, row)))
+            }
+            Self::Binary(op, l, r) => binary(*op, l.eval(table, row), r.eval(table, row)),
+            Self::Call(f, args) => call(*f, args, table, row),
+        }
+    }
+
+    /// Evaluate as a filter predicate (see [`truthy`])
+    pub fn test(&self, table: &DataTable, row: usize) -> bool {
+        truthy(&self.eval(table, row))
+    }
+
+    /// Evaluate for every row, producing a column named `name`
+    pub fn eval_column(&self, table: &DataTable, name: impl Into<String>) -> Column {
+        Column::from_values(name, (0..table.len()).map(|i| self.eval(table, i)))
+    }
+
+    /// Column names referenced by the expression, in order of appearance
+    pub fn fields(&self) -> Vec<&str> {
+        let mut out = Vec::new();
+        self.collect_fields(&mut out);
+        out
+    }
+
+    fn collect_fields<'a>(&'a self, out: &mut Vec<&'a str>) {
+        match self {
+            Self::Literal(_) => {}
+            Self::Field(name) => {
+                if !out.contains(&name.as_str()) {
+                    out.push(name);
+                }
+            }
+            Self::Unary(_, e) => e.collect_fields(out),
+            Self::Binary(_, l, r) => {
+                l.collect_fields(out);
+                r.collect_fields(out);
+            }
+            Self::Call(_, args) => args.iter().for_each(|a| a.collect_fields(out)),
+        }
+    }
+}
+
+impl std::str::FromStr for Expr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Prints a fully parenthesized form. Every tree produced by [`Expr::parse`]
+/// prints to text that parses back to the same tree (timestamp and NaN
+/// literals have no source syntax).
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(FieldValue::Numeric(v)) | Self::Literal(FieldValue::Timestamp(v)) => {
+                if v.is_sign_negative() {
+                    write!(f, "({v})")
+                } else {
+                    write!(f, "{v}")
+                }
+            }
+            Self::Literal(FieldValue::Text(s)) => {
+                f.write_str("\"")?;
+                for c in s.chars() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\t' => f.write_str("\\t")?,
+                        c => write!(f, "{c}")?,
+                    }
+                }
+                f.write_str("\"")
+            }
+            Self::Literal(FieldValue::Bool(b)) => write!(f, "{b}"),
+            Self::Literal(FieldValue::Null) => f.write_str("null"),
+            Self::Field(name) => {
+                let plain = name.chars().enumerate().all(|(i, c)| {
+                    c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit())
+                });
+                if plain && !is_keyword(name) && !name.is_empty() {
+                    f.write_str(name)
+                } else {
+                    write!(f, "`{}`", name.replace('`', "``"))
+                }
+            }
+            Self::Unary(UnaryOp::Neg, e) => write!(f, "(-{e})"),
+            Self::Unary(UnaryOp::Not, e) => write!(f, "(!{e})"),
+            Self::Binary(op, l, r) => write!(f, "({l} {} {r})", op.symbol()),
+            Self::Call(func, args) => {
+                write!(f, "{}(", func.name())?;
+                for (i, a) in args.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{a}")?;
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+/// Truthiness used by predicates: `false`, `0`, `NaN`, `""` and `null` are false
+pub fn truthy(v: &FieldValue) -> bool {
+    match v {
+        FieldValue::Bool(b) => *b,
+        FieldValue::Numeric(x) => *x != 0.0 && !x.is_nan(),
+        FieldValue::Text(s) => !s.is_empty(),
+        FieldValue::Timestamp(_) => true,
+        FieldValue::Null => false,
+    }
+}
+
+// --- Evaluation helpers ---
+
+/// Numeric result, mapping NaN/±inf to `Null`
+fn number(x: f64) -> FieldValue {
+    if x.is_finite() {
+        FieldValue::Numeric(x)
+    } else {
+        FieldValue::Null
+    }
+}
+
+fn timestamp(x: f64) -> FieldValue {
+    if x.is_finite() {
+        FieldValue::Timestamp(x)
+    } else {
+        FieldValue::Null
+    }
+}
+
+fn display(v: &FieldValue) -> String {
+    match v {
+        FieldValue::Numeric(x) | FieldValue::Timestamp(x) => x.to_string(),
+        FieldValue::Text(s) => s.clone(),
+        FieldValue::Bool(b) => b.to_string(),
+        FieldValue::Null => String::new(),
+    }
+}
+
+fn binary(op: BinaryOp, l: FieldValue, r: FieldValue) -> FieldValue {
+    use FieldValue::{Null, Text, Timestamp};
+    match op {
+        BinaryOp::Eq => return FieldValue::Bool(values_equal(&l, &r)),
+        BinaryOp::Ne => return FieldValue::Bool(!values_equal(&l, &r)),
+        BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+            let Some(ord) = compare(&l, &r) else {
+                return Null;
+            };
+            return FieldValue::Bool(match op {
+                BinaryOp::Lt => ord.is_lt(),
+                BinaryOp::Le => ord.is_le(),
+                BinaryOp::Gt => ord.is_gt(),
+                _ => ord.is_ge(),
+            });
+        }
+        _ => {}
+    }
+    if l.is_null() || r.is_null() {
+        return Null;
+    }
+    if op == BinaryOp::Add && (matches!(l, Text(_)) || matches!(r, Text(_))) {
+        return Text(display(&l) + &display(&r));
+    }
+    let (Some(a), Some(b)) = (l.as_f64(), r.as_f64()) else {
+        return Null;
+    };
+    match (op, &l, &r) {
+        // Timestamp ± duration stays a timestamp; the difference of two is a duration
+        (BinaryOp::Add, Timestamp(_), Timestamp(_)) => Null,
+        (BinaryOp::Add, Timestamp(_), _) | (BinaryOp::Add, _, Timestamp(_)) => timestamp(a + b),
+        (BinaryOp::Sub, Timestamp(_), Timestamp(_)) => number(a - b),
+        (BinaryOp::Sub, Timestamp(_), _) => timestamp(a - b),
+        _ => number(match op {
+            BinaryOp::Add => a + b,
+            BinaryOp::Sub => a - b,
+            BinaryOp::Mul => a * b,
+            BinaryOp::Div => a / b,
+            BinaryOp::Rem => a % b,
+            _ => a.powf(b),
+        }),
+    }
+}
+
+fn values_equal(l: &FieldValue, r: &FieldValue) -> bool {
+    match (l, r) {
+        (FieldValue::Null, FieldValue::Null) => true,
+        (FieldValue::Null, _) | (_, FieldValue::Null) => false,
+        (FieldValue::Text(a), FieldValue::Text(b)) => a == b,
+        (FieldValue::Text(_), _) | (_, FieldValue::Text(_)) => false,
+        _ => l.as_f64() == r.as_f64(),
+    }
+}
+
+fn compare(l: &FieldValue, r: &FieldValue) -> Option<std::cmp::Ordering> {
+    match (l, r) {
+        (FieldValue::Text(a), FieldValue::Text(b)) => Some(a.cmp(b)),
+        _ => l.as_f64()?.partial_cmp(&r.as_f64()?),
+    }
+}
+
+fn call(f: Function, args: &[Expr], table: &DataTable, row: usize) -> FieldValue {
+    let arg = |i: usize| args.get(i).map_or(FieldValue::Null, |a| a.eval(table, row));
+    let num = |i: usize| arg(i).as_f64();
+    let unary = |g: fn(f64) -> f64| num(0).map_or(FieldValue::Null, |x| number(g(x)));
+    let date_part = |g: fn(&DateTimeParts) -> u32| {
+        arg(0).as_timestamp().map_or(FieldValue::Null, |t| {
+            FieldValue::Numeric(f64::from(g(&DateTimeParts::from_timestamp_ms(t))))
+        })
+    };
+    let text = |i: usize| match arg(i) {
+        FieldValue::Null => None,
+        v => Some(display(&v)),
+    };
+
+    match f {
+        Function::If => {
+            if truthy(&arg(0)) {
+                arg(1)
+            } else {
+                arg(2)
+            }
+        }
+        Function::Abs => unary(f64::abs),
+        Function::Sqrt => unary(f64::sqrt),
+        Function::Exp => unary(f64::exp),
+        Function::Ln => unary(f64::ln),
+        Function::Log10 => unary(f64::log10),
+        Function::Log2 => unary(f64::log2),
+        Function::Floor => unary(f64::floor),
+        Function::Ceil => unary(f64::ceil),
+        Function::Pow => match (num(0), num(1)) {
+            (Some(x), Some(y)) => number(x.powf(y)),
+            _ => FieldValue::Null,
+        },
+        Function::Round => {
+            let Some(x) = num(0) else {
+                return FieldValue::Null;
+            };
+            match num(1) {
+                Some(d) => {
+                    let factor = 10f64.powi(d.round() as i32);
+                    number((x * factor).round() / factor)
+                }
+                None => number(x.round()),
+            }
+        }
+        Function::Min | Function::Max => {
+            let values = (0..args.len()).filter_map(num);
+            let folded = if f == Function::Min {
+                values.reduce(f64::min)
+            } else {
+                values.reduce(f64::max)
+            };
+            folded.map_or(FieldValue::Null, number)
+        }
+        Function::Coalesce => (0..args.len())
+            .map(arg)
+            .find(|v| !v.is_null())
+            .unwrap_or(FieldValue::Null),
+        Function::IsNull => FieldValue::Bool(arg(0).is_null()),
+        Function::Number => match arg(0) {
+            FieldValue::Text(s) => s.trim().parse().map_or(FieldValue::Null, number),
+            v => v.as_f64().map_or(FieldValue::Null, number),
+        },
+        Function::String => text(0).map_or(FieldValue::Null, FieldValue::Text),
+        Function::Lower => text(0).map_or(FieldValue::Null, |s| FieldValue::Text(s.to_lowercase())),
+        Function::Upper => text(0).map_or(FieldValue::Null, |s| FieldValue::Text(s.to_uppercase())),
+        Function::Length => text(0).map_or(FieldValue::Null, |s| {
+            FieldValue::Numeric(s.chars().count() as f64)
+        }),
+        Function::Contains => match (text(0), text(1)) {
+            (Some(s), Some(needle)) => FieldValue::Bool(s.contains(&needle)),
+            _ => FieldValue::Null,
+        },
+        Function::Year => arg(0).as_timestamp().map_or(FieldValue::Null, |t| {
+            FieldValue::Numeric(f64::from(DateTimeParts::from_timestamp_ms(t).year))
+        }),
+        Function::Quarter => date_part(DateTimeParts::quarter),
+        Function::Month => date_part(|p| p.month),
+        Function::Day => date_part(|p| p.day),
+        Function::Weekday => date_part(DateTimeParts::weekday),
+        Function::Hour => date_part(|p| p.hour),
+        Function::Minute => date_part(|p| p.minute),
+        Function::Second => date_part(|p| p.second),
+        Function::Date => match (num(0), num(1), num(2)) {
+            (Some(y), Some(m), Some(d))
+                if (1.0..=12.0).contains(&m) && (1.0..=31.0).contains(&d) =>
+            {
+                timestamp(DateTimeParts::date(y as i32, m as u32, d as u32).to_timestamp_ms())
+            }
+            _ => FieldValue::Null,
+        },
+    }
+}
+
+// --- Tokenizer ---
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    /// Backtick-quoted column name
+    Quoted(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(v) => write!(f, "number {v}"),
+            Self::Str(s) => write!(f, "string {s:?}"),
+            Self::Ident(s) => write!(f, "'{s}'"),
+            Self::Quoted(s) => write!(f, "`{s}`"),
+            Self::Op(op) => write!(f, "'{op}'"),
+            Self::LParen => f.write_str("'('"),
+            Self::RParen => f.write_str("')'"),
+            Self::Comma => f.write_str("','"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    /// Character offset in the source, for error messages
+    offset: usize,
+}
+
+const OPERATORS: [&str; 17] = [
+    "==", "!=", "<=", ">=", "&&", "||", "<", ">", "+", "-", "*", "/", "%", "^", "!", "=", "|",
+];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let kind = if c.is_ascii_digit()
+            || (c == '.' && chars.get(i + 1).is_some_and(char::is_ascii_digit))
+        {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            if i < chars.len() && matches!(chars[i], 'e' | 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && matches!(chars[j], '+' | '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let v = text
+                .parse()
+                .ok()
+                .filter(|v: &f64| v.is_finite())
+                .ok_or_else(|| format!("invalid number '{text}' at position {start}"))?;
+            TokenKind::Number(v)
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            TokenKind::Ident(chars[start..i].iter().collect())
+        } else if c == '"' || c == '\'' || c == '`' {
+            i += 1;
+            let mut s = String::new();
+            loop {
+                match chars.get(i) {
+                    None => return Err(format!("unterminated quote starting at position {start}")),
+                    // A doubled backtick inside a quoted field name is a literal one
+                    Some('`') if c == '`' && chars.get(i + 1) == Some(&'`') => {
+                        s.push('`');
+                        i += 2;
+                    }
+                    Some(&q) if q == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some('\\') if c != '`' => {
+                        let escaped = chars.get(i + 1).ok_or_else(|| {
+                            format!("unterminated quote starting at position {start}")
+                        })?;
+                        s.push(match escaped {
+                            'n' => '\n',
+                            't' => '\t',
+                            other => *other,
+                        });
+                        i += 2;
+                    }
+                    Some(&ch) => {
+                        s.push(ch);
+                        i += 1;
+                    }
+                }
+            }
+            if c == '`' {
+                TokenKind::Quoted(s)
+            } else {
+                TokenKind::Str(s)
+            }
+        } else if c == '(' {
+            i += 1;
+            TokenKind::LParen
+        } else if c == ')' {
+            i += 1;
+            TokenKind::RParen
+        } else if c == ',' {
+            i += 1;
+            TokenKind::Comma
+        } else {
+            let op = OPERATORS
+                .iter()
+                .find(|op| {
+                    op.chars()
+                        .enumerate()
+                        .all(|(k, oc)| chars.get(i + k) == Some(&oc))
+                })
+                .ok_or_else(|| format!("unexpected character '{c}' at position {start}"))?;
+            if *op == "=" || *op == "|" {
+                return Err(format!(
+                    "unexpected '{op}' at position {start} (did you mean '{op}{op}'?)"
+                ));
+            }
+            i += op.len();
+            TokenKind::Op(op)
+        };
+        tokens.push(Token {
+            kind,
+            offset: start,
+        });
+    }
+    Ok(tokens)
+}
+
+fn is_keyword(name: &str) -> bool {
+    matches!(
+        name.to_ascii_lowercase().as_str(),
+        "and" | "or" | "not" | "true" | "false" | "null"
+    )
+}
+
+// --- Parser (precedence climbing) ---
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&TokenKind> {
+        self.tokens.get(self.pos).map(|t| &t.kind)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(0, |t| t.offset)
+    }
+
+    fn next(&mut self) -> Option<TokenKind> {
+        let t = self.tokens.get(self.pos).map(|t| t.kind.clone());
+        self.pos += 1;
+        t
+    }
+
+    /// Consume a binary operator from `ops` (symbols or keywords)
+    fn eat_op(&mut self, ops: &[(&str, BinaryOp)]) -> Option<BinaryOp> {
+        let found = match self.peek()? {
+            TokenKind::Op(sym) => ops.iter().find(|(s, _)| s == sym),
+            TokenKind::Ident(word) => ops.iter().find(|(s, _)| s.eq_ignore_ascii_case(word)),
+            _ => None,
+        };
+        let op = found?.1;
+        self.pos += 1;
+        Some(op)
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(format!(
+                "expression nested too deeply at position {}",
+                self.offset()
+            ));
+        }
+        let e = self.binary_level(0);
+        self.depth -= 1;
+        e
+    }
+
+    fn binary_level(&mut self, level: usize) -> Result<Expr, String> {
+        const LEVELS: [&[(&str, BinaryOp)]; 6] = [
+            &[("||", BinaryOp::Or), ("or", BinaryOp::Or)],
+            &[("&&", BinaryOp::And), ("and", BinaryOp::And)],
+            &[("==", BinaryOp::Eq), ("!=", BinaryOp::Ne)],
+            &[
+                ("<=", BinaryOp::Le),
+                (">=", BinaryOp::Ge),
+                ("<", BinaryOp::Lt),
+                (">", BinaryOp::Gt),
+            ],
+            &[("+", BinaryOp::Add), ("-", BinaryOp::Sub)],
+            &[
+                ("*", BinaryOp::Mul),
+                ("/", BinaryOp::Div),
+                ("%", BinaryOp::Rem),
+            ],
+        ];
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+        let mut lhs = self.binary_level(level + 1)?;
+        while let Some(op) = self.eat_op(LEVELS[level]) {
+            let rhs = self.binary_level(level + 1)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        let op = match self.peek() {
+            Some(TokenKind::Op("-")) => Some(UnaryOp::Neg),
+            Some(TokenKind::Op("!")) => Some(UnaryOp::Not),
+            Some(TokenKind::Ident(w)) if w.eq_ignore_ascii_case("not") => Some(UnaryOp::Not),
+            _ => None,
+        };
+        if let Some(op) = op {
+            self.pos += 1;
+            let operand = self.nested(Self::unary)?;
+            // `-1` is a negative literal, so printed trees parse back unchanged
+            return Ok(match (op, operand) {
+                (UnaryOp::Neg, Expr::Literal(FieldValue::Numeric(v))) => {
+                    Expr::Literal(FieldValue::Numeric(-v))
+                }
+                (op, operand) => Expr::Unary(op, Box::new(operand)),
+            });
+        }
+        let base = self.primary()?;
+        if self.peek() == Some(&TokenKind::Op("^")) {
+            self.pos += 1;
+            // Right-associative and binds tighter than unary minus on its left
+            let exponent = self.nested(Self::unary)?;
+            return Ok(Expr::Binary(
+                BinaryOp::Pow,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn nested(&mut self, f: fn(&mut Self) -> Result<Expr, String>) -> Result<Expr, String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(format!(
+                "expression nested too deeply at position {}",
+                self.offset()
+            ));
+        }
+        let e = f(self);
+        self.depth -= 1;
+        e
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let offset = self.offset();
+        let Some(token) = self.next() else {
+            return Err("unexpected end of expression".to_owned());
+        };
+        match token {
+            TokenKind::Number(v) => Ok(Expr::Literal(FieldValue::Numeric(v))),
+            TokenKind::Str(s) => Ok(Expr::Literal(FieldValue::Text(s))),
+            TokenKind::Quoted(name) => Ok(Expr::Field(name)),
+            TokenKind::LParen => {
+                let e = self.expr()?;
+                self.expect_rparen()?;
+                Ok(e)
+            }
+            TokenKind::Ident(name) => {
+                if self.peek() == Some(&TokenKind::LParen) {
+                    self.pos += 1;
+                    return self.call(&name, offset);
+                }
+                match name.to_ascii_lowercase().as_str() {
+                    "true" => Ok(Expr::Literal(FieldValue::Bool(true))),
+                    "false" => Ok(Expr::Literal(FieldValue::Bool(false))),
+                    "null" => Ok(Expr::Literal(FieldValue::Null)),
+                    "and" | "or" | "not" => {
+                        Err(format!("unexpected '{name}' at position {offset}"))
+                    }
+                    _ => Ok(Expr::Field(name)),
+                }
+            }
+            other => Err(format!("unexpected {other} at position {offset}")),
+        }
+    }
+
+    fn call(&mut self, name: &str, offset: usize) -> Result<Expr, String> {
+        let func = Function::from_name(name)
+            .ok_or_else(|| format!("unknown function '{name}' at position {offset}"))?;
+        let mut args = Vec::new();
+        if self.peek() == Some(&TokenKind::RParen) {
+            self.pos += 1;
+        } else {
+            loop {
+                args.push(self.expr()?);
+                match self.next() {
+                    Some(TokenKind::Comma) => continue,
+                    Some(TokenKind::RParen) => break,
+                    _ => {
+                        return Err(format!(
+                            "expected ',' or ')' in call to '{name}' at position {}",
+                            self.offset()
+                        ))
+                    }
+                }
+            }
+        }
+        let (min, max) = func.arity();
+        if args.len() < min || max.is_some_and(|m| args.len() > m) {
+            let expected = match max {
+                Some(m) if m == min => format!("{min}"),
+                Some(m) => format!("{min}–{m}"),
+                None => format!("at least {min}"),
+            };
+            return Err(format!(
+                "'{}' expects {expected} argument(s), got {} at position {offset}",
+                func.name(),
+                args.len()
+            ));
+        }
+        Ok(Expr::Call(func, args))
+    }
+
+    fn expect_rparen(&mut self) -> Result<(), String> {
+        let offset = self.offset();
+        match self.next() {
+            Some(TokenKind::RParen) => Ok(()),
+            Some(other) => Err(format!(
+                "expected ')' but found {other} at position {offset}"
+            )),
+            None => Err("expected ')' before end of expression".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_row;
+
+    fn table() -> DataTable {
+        DataTable::from_rows(vec![
+            data_row! { "price" => 2.5_f64, "qty" => 4.0_f64, "region" => "EU", "amount" => 100.0_f64 },
+            data_row! { "price" => 1.0_f64, "qty" => 0.0_f64, "region" => "US", "amount" => 50.0_f64 },
+        ])
+    }
+
+    fn eval(src: &str, row: usize) -> FieldValue {
+        Expr::parse(src).unwrap().eval(&table(), row)
+    }
+
+    #[test]
+    fn test_arithmetic_and_precedence() {
+        assert_eq!(eval("price * qty", 0), FieldValue::Numeric(10.0));
+        assert_eq!(eval("1 + 2 * 3 - 4 / 2", 0), FieldValue::Numeric(5.0));
+        assert_eq!(eval("(1 + 2) * 3", 0), FieldValue::Numeric(9.0));
+        assert_eq!(eval("-2 ^ 2", 0), FieldValue::Numeric(-4.0));
+        assert_eq!(eval("2 ^ 3 ^ 2", 0), FieldValue::Numeric(512.0));
+        assert_eq!(eval("7 % 4", 0), FieldValue::Numeric(3.0));
+    }
+
+    #[test]
+    fn test_if_and_comparison() {
+        let src = r#"if(region == "EU", amount * 1.1, amount)"#;
+        match eval(src, 0) {
+            FieldValue::Numeric(v) => assert!((v - 110.0).abs() < 1e-9),
+            other => panic!("expected numeric, got {other:?}"),
+        }
+        assert_eq!(eval(src, 1), FieldValue::Numeric(50.0));
+        assert_eq!(
+            eval("price > 2 and not (qty == 0)", 0),
+            FieldValue::Bool(true)
+        );
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(eval("log10(amount)", 0), FieldValue::Numeric(2.0));
+        assert_eq!(
+            eval("round(price * 1.234, 2)", 0),
+            FieldValue::Numeric(3.09)
+        );
+        assert_eq!(eval("max(price, qty, null)", 0), FieldValue::Numeric(4.0));
+        assert_eq!(
+            eval("upper(region) + '-' + qty", 0),
+            FieldValue::Text("EU-4".into())
+        );
+        assert_eq!(eval("coalesce(missing, 7)", 0), FieldValue::Numeric(7.0));
+        assert_eq!(eval("number(' 12.5 ')", 0), FieldValue::Numeric(12.5));
+    }
+
+    #[test]
+    fn test_dates() {
+        let t = DataTable::from_rows(vec![data_row! {
+            "date" => FieldValue::Timestamp(1_709_214_330_250.0)
+        }]);
+        let e = |s: &str| Expr::parse(s).unwrap().eval(&t, 0);
+        assert_eq!(e("year(date)"), FieldValue::Numeric(2024.0));
+        assert_eq!(e("month(date)"), FieldValue::Numeric(2.0));
+        assert_eq!(e("weekday(date)"), FieldValue::Numeric(4.0));
+        assert_eq!(
+            e("date(2024, 3, 1) - date(2024, 2, 1)"),
+            FieldValue::Numeric(29.0 * 86_400_000.0)
+        );
+    }
+
+    #[test]
+    fn test_null_and_division_by_zero() {
+        assert_eq!(eval("amount / qty", 1), FieldValue::Null);
+        assert_eq!(eval("missing + 1", 0), FieldValue::Null);
+        assert_eq!(eval("missing == null", 0), FieldValue::Bool(true));
+        assert!(!Expr::parse("missing > 1").unwrap().test(&table(), 0));
+    }
+
+    #[test]
+    fn test_quoted_field_names() {
+        let t = DataTable::from_rows(vec![data_row! { "unit price" => 3.0_f64 }]);
+        let e = Expr::parse("`unit price` * 2").unwrap();
+        assert_eq!(e.eval(&t, 0), FieldValue::Numeric(6.0));
+        assert_eq!(e.fields(), vec!["unit price"]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Expr::parse("price *")
+            .unwrap_err()
+            .contains("end of expression"));
+        assert!(Expr::parse("foo(1)")
+            .unwrap_err()
+            .contains("unknown function 'foo'"));
+        assert!(Expr::parse("if(1, 2)").unwrap_err().contains("expects 3"));
+        assert!(Expr::parse("a = 1").unwrap_err().contains("'=='"));
+        assert!(Expr::parse("(1 + 2").is_err());
+        assert!(Expr::parse("1 2").unwrap_err().contains("position 2"));
+        let deep = format!("{}1{}", "(".repeat(200), ")".repeat(200));
+        assert!(Expr::parse(&deep).unwrap_err().contains("too deeply"));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for src in [
+            r#"if(region == "EU", amount * 1.1, -amount)"#,
+            "!(a < 2) || `b c` >= 3 % 2",
+            "2 ^ -x",
+        ] {
+            let e = Expr::parse(src).unwrap();
+            assert_eq!(Expr::parse(&e.to_string()).unwrap(), e);
+        }
+    }
+
+    /// Random tree in the image of `Expr::parse` (xorshift-driven)
+    fn random_expr(state: &mut u64, depth: usize) -> Expr {
+        let mut next = |n: u64| {
+            *state ^= *state << 13;
+            *state ^= *state >> 7;
+            *state ^= *state << 17;
+            *state % n
+        };
+        const TEXT: [char; 10] = ['a', ' ', '"', '\'', '\\', '\r', '\n', '\t', '\u{1b}', 'é'];
+        const FIELDS: [&str; 6] = ["x", "unit price", "a`b", "and", "_1", "year"];
+        const OPS: [BinaryOp; 14] = [
+            BinaryOp::Add,
+            BinaryOp::Sub,
+            BinaryOp::Mul,
+            BinaryOp::Div,
+            BinaryOp::Rem,
+            BinaryOp::Pow,
+            BinaryOp::Eq,
+            BinaryOp::Ne,
+            BinaryOp::Lt,
+            BinaryOp::Le,
+            BinaryOp::Gt,
+            BinaryOp::Ge,
+            BinaryOp::And,
+            BinaryOp::Or,
+        ];
+        let leaf = depth == 0 || next(3) == 0;
+        match if leaf { next(5) } else { 5 + next(4) } {
+            0 => {
+                let v = (next(2_000_001) as f64 - 1_000_000.0) / 8.0;
+                Expr::Literal(FieldValue::Numeric(v * 10f64.powi(next(41) as i32 - 20)))
+            }
+            1 => {
+                let len = next(6) as usize;
+                let text = (0..len).map(|_| TEXT[next(10) as usize]).collect();
+                Expr::Literal(FieldValue::Text(text))
+            }
+            2 => Expr::Literal(FieldValue::Bool(next(2) == 0)),
+            3 => Expr::Literal(FieldValue::Null),
+            4 => Expr::Field(FIELDS[next(6) as usize].to_owned()),
+            5 => match random_expr(state, depth - 1) {
+                // `-<number>` parses as a negative literal
+                Expr::Literal(FieldValue::Numeric(_)) => {
+                    Expr::Unary(UnaryOp::Not, Box::new(Expr::Field("x".into())))
+                }
+                e => Expr::Unary(UnaryOp::Neg, Box::new(e)),
+            },
+            6 => Expr::Unary(UnaryOp::Not, Box::new(random_expr(state, depth - 1))),
+            7 => {
+                let op = OPS[next(14) as usize];
+                let l = random_expr(state, depth - 1);
+                let r = random_expr(state, depth - 1);
+                Expr::Binary(op, Box::new(l), Box::new(r))
+            }
+            _ => {
+                let args = (0..3).map(|_| random_expr(state, depth - 1)).collect();
+                Expr::Call(Function::If, args)
+            }
+        }
+    }
+
+    #[test]
+    fn test_display_round_trip_random_trees() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..2_000 {
+            let e = random_expr(&mut state, 5);
+            let printed = e.to_string();
+            assert_eq!(Expr::parse(&printed), Ok(e), "printed as {printed}");
+        }
+        // Escapes and negative literals
+        let e = Expr::parse(r#"-1 + "it\'s \"q\" \\ end""#).unwrap();
+        assert_eq!(
+            e,
+            Expr::Binary(
+                BinaryOp::Add,
+                Box::new(Expr::Literal(FieldValue::Numeric(-1.0))),
+                Box::new(Expr::Literal(FieldValue::Text(r#"it's "q" \ end"#.into())))
+            )
+        );
+        assert_eq!(Expr::parse(&e.to_string()).unwrap(), e);
+        assert!(Expr::parse("1e999").is_err());
+    }
+}
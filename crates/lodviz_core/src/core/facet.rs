@@ -0,0 +1,486 @@
+/// Faceting: small multiples of one chart spec split by nominal fields
+///
+/// A [`FacetSpec`] splits a table spec's rows by the values of a `row`
+/// and/or `column` field and resolves one ready-to-render [`ChartSpec`] per
+/// panel. Positional scales are shared across panels by default (so panels
+/// are directly comparable) or independent per panel; series are aligned so
+/// the same name gets the same color everywhere and one legend serves the
+/// whole grid.
+use serde::{Deserialize, Serialize};
+
+use super::data::{BarDataset, Dataset, Series};
+use super::encoding::Field;
+use super::field_value::DataTable;
+use super::spec::{ChartData, ChartSpec};
+use super::validate::{check_field, SpecError};
+
+/// Whether a positional scale is shared by all panels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScaleResolve {
+    /// One domain spanning every panel
+    #[default]
+    Shared,
+    /// Each panel fits its own data
+    Independent,
+}
+
+/// A chart repeated per value of `row` / `column`
+#[derive(Debug, Clone)]
+pub struct FacetSpec {
+    /// The per-panel chart; its data must be a table
+    pub spec: ChartSpec,
+    /// Split into grid rows by this field
+    pub row: Option<Field>,
+    /// Split into grid columns by this field
+    pub column: Option<Field>,
+    /// With only `column`: wrap to a new row after this many panels
+    pub wrap: Option<usize>,
+    /// X scale resolution
+    pub x_scale: ScaleResolve,
+    /// Y scale resolution
+    pub y_scale: ScaleResolve,
+}
+
+impl FacetSpec {
+    /// Facet `spec` (add `row` and/or `column` before resolving)
+    pub fn new(spec: ChartSpec) -> Self {
+        Self {
+            spec,
+            row: None,
+            column: None,
+            wrap: None,
+            x_scale: ScaleResolve::Shared,
+            y_scale: ScaleResolve::Shared,
+        }
+    }
+
+    /// Split into grid rows by `field`
+    pub fn with_row(mut self, field: Field) -> Self {
+        self.row = Some(field);
+        self
+    }
+
+    /// Split into grid columns by `field`
+    pub fn with_column(mut self, field: Field) -> Self {
+        self.column = Some(field);
+        self
+    }
+
+    /// Wrap column panels after `n` per row
+    pub fn with_wrap(mut self, n: usize) -> Self {
+        self.wrap = Some(n.max(1));
+        self
+    }
+
+    /// Set how the x scale is resolved across panels
+    pub fn with_x_scale(mut self, resolve: ScaleResolve) -> Self {
+        self.x_scale = resolve;
+        self
+    }
+
+    /// Set how the y scale is resolved across panels
+    pub fn with_y_scale(mut self, resolve: ScaleResolve) -> Self {
+        self.y_scale = resolve;
+        self
+    }
+
+    /// Validate and split into panels.
+    ///
+    /// Each panel's spec carries pre-built data, no legend, and — for shared
+    /// scales — the common `x_domain` / `y_domain` in its config.
+    pub fn resolve(&self) -> Result<FacetGrid, SpecError> {
+        let mark = self.spec.mark;
+        let ChartData::Table(_) = &self.spec.data else {
+            return Err(SpecError::IncompatibleData {
+                mark,
+                data: "Dataset (facets split a table)",
+            });
+        };
+        if self.row.is_none() && self.column.is_none() {
+            return Err(SpecError::MissingChannel {
+                mark,
+                channel: "column",
+            });
+        }
+        // Transform once; panels convert slices of the checked table
+        let table = self.spec.checked_table()?.unwrap_or_default();
+        for (channel, field) in [("row", &self.row), ("column", &self.column)] {
+            if let Some(field) = field {
+                check_field(&table, channel, field)?;
+            }
+        }
+
+        let split = |field: &Option<Field>| match field {
+            Some(f) => table.partition(&f.name),
+            None => vec![(String::new(), (0..table.len()).collect())],
+        };
+        let (row_groups, col_groups) = (split(&self.row), split(&self.column));
+        let wrap = self.wrap.filter(|_| self.row.is_none());
+
+        let mut panels = Vec::new();
+        for (ri, (row_key, row_rows)) in row_groups.iter().enumerate() {
+            for (ci, (col_key, col_rows)) in col_groups.iter().enumerate() {
+                let rows: Vec<usize> = row_rows
+                    .iter()
+                    .copied()
+                    .filter(|i| col_rows.binary_search(i).is_ok())
+                    .collect();
+                if rows.is_empty() {
+                    continue;
+                }
+                let (row, column) = match wrap {
+                    Some(n) => (ci / n, ci % n),
+                    None => (ri, ci),
+                };
+                panels.push(FacetPanel {
+                    row,
+                    column,
+                    row_label: self.row.as_ref().map(|_| row_key.clone()),
+                    column_label: self.column.as_ref().map(|_| col_key.clone()),
+                    spec: self.panel_spec(table.select_rows(&rows)),
+                });
+            }
+        }
+
+        let series = align_series(&mut panels);
+        let (x_domain, y_domain) = shared_domains(&mut panels, self.x_scale, self.y_scale);
+        let (rows, columns) = match wrap {
+            Some(n) => (col_groups.len().div_ceil(n), n.min(col_groups.len())),
+            None => (row_groups.len(), col_groups.len()),
+        };
+        Ok(FacetGrid {
+            panels,
+            rows,
+            columns,
+            series,
+            x_domain,
+            y_domain,
+        })
+    }
+
+    fn panel_spec(&self, table: DataTable) -> ChartSpec {
+        let mut data = self.spec.convert(&table);
+        // A panel may lose all its rows to nulls; keep its slot empty
+        if data.is_empty() {
+            data = match self.spec.mark {
+                super::mark::Mark::Bar => ChartData::Categorical(BarDataset::new(vec![])),
+                _ => ChartData::TimeSeries(Dataset::new()),
+            };
+        }
+        // Cheap: the source table's columns are shared
+        let mut spec = self.spec.clone();
+        spec.data = data;
+        spec.transforms.clear();
+        spec.config.title = None;
+        spec.config.show_legend = Some(false);
+        spec
+    }
+}
+
+/// Give every panel the same series, in the same order, so palette indices
+/// (colors) agree. Returns the series names.
+fn align_series(panels: &mut [FacetPanel]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut add = |n: &str| {
+        if !names.iter().any(|m| m == n) {
+            names.push(n.to_owned());
+        }
+    };
+    for p in panels.iter() {
+        match &p.spec.data {
+            ChartData::TimeSeries(ds) => ds.series.iter().for_each(|s| add(&s.name)),
+            ChartData::Categorical(bd) => bd.series.iter().for_each(|s| add(&s.name)),
+            _ => {}
+        }
+    }
+    for p in panels.iter_mut() {
+        match &mut p.spec.data {
+            ChartData::TimeSeries(ds) => {
+                let mut old = std::mem::take(&mut ds.series);
+                for name in &names {
+                    let series = match old.iter().position(|s| &s.name == name) {
+                        Some(i) => old.swap_remove(i),
+                        None => Series::new(name.clone(), Vec::new()),
+                    };
+                    ds.series.push(series);
+                }
+            }
+            ChartData::Categorical(bd) => {
+                let n = bd.categories.len();
+                let mut old = std::mem::take(&mut bd.series);
+                for name in &names {
+                    match old.iter().position(|s| &s.name == name) {
+                        Some(i) => bd.series.push(old.swap_remove(i)),
+                        None => bd.add_series(name.clone(), vec![0.0; n]),
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// An optional `(min, max)` domain
+type Domain = Option<(f64, f64)>;
+
+/// Compute shared domains and write them into the panels' configs.
+///
+/// Bars share categories (x) and a zero-based value axis (y).
+fn shared_domains(panels: &mut [FacetPanel], x: ScaleResolve, y: ScaleResolve) -> (Domain, Domain) {
+    let bars = panels
+        .iter()
+        .any(|p| matches!(p.spec.data, ChartData::Categorical(_)));
+    if bars {
+        if x == ScaleResolve::Shared {
+            let mut categories: Vec<String> = Vec::new();
+            for p in panels.iter() {
+                if let ChartData::Categorical(bd) = &p.spec.data {
+                    for c in &bd.categories {
+                        if !categories.contains(c) {
+                            categories.push(c.clone());
+                        }
+                    }
+                }
+            }
+            for p in panels.iter_mut() {
+                if let ChartData::Categorical(bd) = &mut p.spec.data {
+                    *bd = with_categories(bd, &categories);
+                }
+            }
+        }
+        let max = panels
+            .iter()
+            .filter_map(|p| p.spec.data.as_bar_dataset())
+            .flat_map(|bd| bd.series.iter().flat_map(|s| s.values.iter().copied()))
+            .fold(0.0_f64, f64::max);
+        let y_domain =
+            (y == ScaleResolve::Shared).then_some((0.0, if max <= 0.0 { 1.0 } else { max * 1.1 }));
+        for p in panels.iter_mut() {
+            p.spec.config.y_domain = y_domain;
+        }
+        return (None, y_domain);
+    }
+
+    let extent = |get: fn(&super::data::DataPoint) -> f64| {
+        panels
+            .iter()
+            .filter_map(|p| p.spec.data.as_dataset())
+            .flat_map(|ds| ds.series.iter().flat_map(|s| s.data.iter().map(get)))
+            .filter(|v| v.is_finite())
+            .fold(None, |acc: Option<(f64, f64)>, v| {
+                Some(acc.map_or((v, v), |(lo, hi)| (lo.min(v), hi.max(v))))
+            })
+            .map(|(lo, hi)| {
+                if lo < hi {
+                    (lo, hi)
+                } else {
+                    (lo - 0.5, hi + 0.5)
+                }
+            })
+    };
+    let x_domain = extent(|p| p.x).filter(|_| x == ScaleResolve::Shared);
+    let y_domain = extent(|p| p.y)
+        .filter(|_| y == ScaleResolve::Shared)
+        .map(|(lo, hi)| {
+            let pad = (hi - lo) * 0.05;
+            (lo - pad, hi + pad)
+        });
+    for p in panels.iter_mut() {
+        p.spec.config.x_domain = x_domain;
+        p.spec.config.y_domain = y_domain;
+    }
+    (x_domain, y_domain)
+}
+
+/// `bd` re-indexed onto `categories` (missing ones become 0)
+fn with_categories(bd: &BarDataset, categories: &[String]) -> BarDataset {
+    let mut out = BarDataset::new(categories.to_vec());
+    for s in &bd.series {
+        let values = categories
+            .iter()
+            .map(|c| {
+                bd.categories
+                    .iter()
+                    .position(|own| own == c)
+                    .and_then(|i| s.values.get(i).copied())
+                    .unwrap_or(0.0)
+            })
+            .collect();
+        out.add_series(s.name.clone(), values);
+    }
+    out
+}
+
+// --- Resolved grid ---
+
+/// One sub-chart of a facet grid
+#[derive(Debug, Clone)]
+pub struct FacetPanel {
+    /// Grid row (0-based)
+    pub row: usize,
+    /// Grid column (0-based)
+    pub column: usize,
+    /// Value of the `row` field, when faceting by row
+    pub row_label: Option<String>,
+    /// Value of the `column` field, when faceting by column
+    pub column_label: Option<String>,
+    /// Ready-to-render spec with pre-built data
+    pub spec: ChartSpec,
+}
+
+impl FacetPanel {
+    /// Header text: the facet values, joined with ` · `
+    pub fn header(&self) -> String {
+        [&self.row_label, &self.column_label]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+/// Panels laid out on a grid, with the legend and shared domains
+#[derive(Debug, Clone)]
+pub struct FacetGrid {
+    /// Non-empty panels (empty row/column combinations are skipped)
+    pub panels: Vec<FacetPanel>,
+    /// Number of grid rows
+    pub rows: usize,
+    /// Number of grid columns
+    pub columns: usize,
+    /// Series names in palette order, for a shared legend
+    pub series: Vec<String>,
+    /// Shared x domain (continuous x, shared scale)
+    pub x_domain: Option<(f64, f64)>,
+    /// Shared y / value domain (shared scale)
+    pub y_domain: Option<(f64, f64)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mark::Mark;
+    use crate::core::transform::{Predicate, Transform};
+    use crate::data_row;
+
+    fn sales() -> DataTable {
+        DataTable::from_rows(vec![
+            data_row! { "region" => "EU", "product" => "A", "month" => 1.0_f64, "v" => 10.0_f64 },
+            data_row! { "region" => "EU", "product" => "A", "month" => 2.0_f64, "v" => 20.0_f64 },
+            data_row! { "region" => "US", "product" => "B", "month" => 1.0_f64, "v" => 50.0_f64 },
+            data_row! { "region" => "US", "product" => "A", "month" => 3.0_f64, "v" => 40.0_f64 },
+            data_row! { "region" => "JP", "product" => "B", "month" => 2.0_f64, "v" => 5.0_f64 },
+        ])
+    }
+
+    fn line_spec() -> ChartSpec {
+        ChartSpec::builder()
+            .from_table(sales())
+            .mark(Mark::Line)
+            .x(Field::quantitative("month"))
+            .y(Field::quantitative("v"))
+            .color(Field::nominal("product"))
+            .build()
+    }
+
+    #[test]
+    fn test_column_facets_share_domains() {
+        let grid = FacetSpec::new(line_spec())
+            .with_column(Field::nominal("region"))
+            .resolve()
+            .unwrap();
+        assert_eq!((grid.rows, grid.columns), (1, 3));
+        let headers: Vec<String> = grid.panels.iter().map(FacetPanel::header).collect();
+        assert_eq!(headers, vec!["EU", "US", "JP"]);
+        assert_eq!(grid.series, vec!["A", "B"]);
+        assert_eq!(grid.x_domain, Some((1.0, 3.0)));
+        let (lo, hi) = grid.y_domain.unwrap();
+        assert!(lo < 5.0 && hi > 50.0);
+
+        // EU has no "B" series but keeps the slot so colors line up
+        let eu = grid.panels[0].spec.data.as_dataset().unwrap();
+        assert_eq!(eu.series.len(), 2);
+        assert!(eu.series[1].data.is_empty());
+        assert_eq!(grid.panels[0].spec.config.x_domain, Some((1.0, 3.0)));
+        assert_eq!(grid.panels[0].spec.config.show_legend, Some(false));
+    }
+
+    #[test]
+    fn test_independent_and_wrapped() {
+        let grid = FacetSpec::new(line_spec())
+            .with_column(Field::nominal("region"))
+            .with_wrap(2)
+            .with_y_scale(ScaleResolve::Independent)
+            .resolve()
+            .unwrap();
+        assert_eq!((grid.rows, grid.columns), (2, 2));
+        assert_eq!((grid.panels[2].row, grid.panels[2].column), (1, 0));
+        assert!(grid.y_domain.is_none());
+        assert!(grid.panels[0].spec.config.y_domain.is_none());
+    }
+
+    #[test]
+    fn test_row_by_column_bars() {
+        let spec = ChartSpec::builder()
+            .from_table(sales())
+            .mark(Mark::Bar)
+            .x(Field::nominal("product"))
+            .y(Field::quantitative("v"))
+            .build();
+        let grid = FacetSpec::new(spec)
+            .with_row(Field::nominal("region"))
+            .resolve()
+            .unwrap();
+        assert_eq!((grid.rows, grid.columns), (3, 1));
+        let eu = grid.panels[0].spec.data.as_bar_dataset().unwrap();
+        assert_eq!(eu.categories, vec!["A", "B"]);
+        assert_eq!(eu.series[0].values, vec![10.0, 0.0]);
+        let (lo, hi) = grid.y_domain.unwrap();
+        assert_eq!(lo, 0.0);
+        assert!((hi - 55.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_transforms_apply_once_before_split() {
+        let spec = ChartSpec::builder()
+            .from_table(sales())
+            .mark(Mark::Line)
+            .x(Field::quantitative("month"))
+            .y(Field::quantitative("v"))
+            .transform(Transform::filter(Predicate::expr("v > 5").unwrap()))
+            .build();
+        let grid = FacetSpec::new(spec)
+            .with_column(Field::nominal("region"))
+            .resolve()
+            .unwrap();
+        // JP's only row is filtered out before splitting
+        let headers: Vec<String> = grid.panels.iter().map(FacetPanel::header).collect();
+        assert_eq!(headers, vec!["EU", "US"]);
+        assert!(grid.panels.iter().all(|p| p.spec.transforms.is_empty()));
+        let us = grid.panels[1].spec.data.as_dataset().unwrap();
+        assert_eq!(us.series[0].data.len(), 2);
+    }
+
+    #[test]
+    fn test_facet_errors() {
+        let err = FacetSpec::new(line_spec()).resolve().unwrap_err();
+        assert!(matches!(
+            err,
+            SpecError::MissingChannel {
+                channel: "column",
+                ..
+            }
+        ));
+        let err = FacetSpec::new(line_spec())
+            .with_column(Field::nominal("regoin"))
+            .resolve()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "encoding.column: unknown field 'regoin' (did you mean 'region'?)"
+        );
+    }
+}
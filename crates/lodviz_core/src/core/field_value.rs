@@ -3,25 +3,55 @@
 /// `DataTable` is the entry point for the "raw data → chart" pipeline.
 /// Users pass business data as rows of named columns; the table converts
 /// to `Dataset` or `BarDataset` using an `Encoding` specification.
+///
+/// JSON shape (serde):
+///
+/// - `FieldValue` → `{"type": "Numeric", "value": 1.5}`, `{"type": "Null"}`
+/// - `DataTable` → `{"columns": [{"name": "x", "type": "Numeric", "values": [1.5, null]}]}`
+///   (see [`Column`] for the per-column layout)
+///
+/// JSON has no NaN/infinity: such numbers are written as `null`. A `null`
+/// `FieldValue` number reads back as NaN; in a typed numeric column it reads
+/// back as a missing value.
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::core::column::{Column, ColumnKind, GroupKey, NULL_GROUP};
 use crate::core::data::{
-    BarDataset, ChordData, DataPoint, Dataset, GridData, SankeyData, SankeyLink, SankeyNode,
-    Series, StripGroup,
+    f64_or_nan, BarDataset, ChordData, DataPoint, Dataset, GridData, OhlcBar, PointAttributes,
+    PointShape, SankeyData, SankeyLink, SankeyNode, Series, StripGroup,
 };
-use crate::core::encoding::Encoding;
+use crate::core::datetime::{time_label, TimeZone};
+use crate::core::encoding::{Encoding, Field};
+use crate::core::table_data::{format_cell_value, format_cell_value_with};
+
+/// Hidden column carrying source row indices through `Encoding::derive`
+const SOURCE_ROW: &str = "\u{0}row";
+
+/// Dash patterns assigned to `stroke_dash` categories in first-seen order
+const DASH_PATTERNS: [Option<&str>; 5] = [
+    None,
+    Some("6 3"),
+    Some("2 3"),
+    Some("8 3 2 3"),
+    Some("12 4"),
+];
 
 // --- FieldValue ---
 
 /// A single typed value in a data column
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
 pub enum FieldValue {
-    /// Continuous numerical value (f64)
-    Numeric(f64),
+    /// Continuous numerical value (f64); NaN is written as `null`
+    Numeric(#[serde(deserialize_with = "f64_or_nan")] f64),
     /// String / categorical value
     Text(String),
     /// Unix timestamp in milliseconds
-    Timestamp(f64),
+    Timestamp(#[serde(deserialize_with = "f64_or_nan")] f64),
     /// Boolean flag
     Bool(bool),
     /// Missing / null value
@@ -101,25 +131,113 @@ impl From<bool> for FieldValue {
 
 /// A single row of named column → typed value mappings.
 ///
-/// Uses `HashMap` for O(1) column lookup with no extra dependencies.
+/// Used to build a `DataTable` row by row and to materialize rows back out
+/// of it; the table itself stores data by column.
 pub type DataRow = HashMap<String, FieldValue>;
 
+// --- RowRef ---
+
+/// Borrowed view of one `DataTable` row, as yielded by [`DataTable::rows`]
+#[derive(Debug, Clone, Copy)]
+pub struct RowRef<'a> {
+    table: &'a DataTable,
+    index: usize,
+}
+
+impl<'a> RowRef<'a> {
+    /// Row position in the table
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Value of `col` (`None` when the table has no such column)
+    pub fn get(&self, col: &str) -> Option<FieldValue> {
+        self.table.column(col).map(|c| c.get(self.index))
+    }
+
+    /// Numeric value of `col`, if set
+    pub fn get_f64(&self, col: &str) -> Option<f64> {
+        self.table.column(col)?.get_f64(self.index)
+    }
+
+    /// Text value of `col`, borrowed from the column
+    pub fn get_str(&self, col: &str) -> Option<&'a str> {
+        self.table.column(col)?.get_str(self.index)
+    }
+
+    /// Materialize the row as a `DataRow`
+    pub fn to_row(&self) -> DataRow {
+        self.table
+            .columns
+            .iter()
+            .map(|c| (c.name().to_owned(), c.get(self.index)))
+            .collect()
+    }
+}
+
 // --- DataTable ---
 
-/// A tidy table of heterogeneous rows, convertible to chart datasets.
+/// A tidy table of heterogeneous columns, convertible to chart datasets.
 ///
-/// Each row is a `DataRow` (`HashMap<String, FieldValue>`).
+/// Data is stored column by column (see [`Column`]): numbers live in typed
+/// `Vec<f64>`s, text is dictionary-encoded and nulls are tracked in a
+/// validity bitmap. Rows can still be pushed and read back as `DataRow`s.
 /// Conversion methods (`to_dataset`, `to_bar_dataset`) use an `Encoding`
 /// to select which columns map to x, y, and color channels.
-#[derive(Debug, Clone, Default)]
+///
+/// Clones share their column storage until one of them is modified, so a
+/// table can be handed to several specs without copying its data.
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct DataTable {
-    rows: Vec<DataRow>,
+    columns: Arc<Vec<Column>>,
+    index: HashMap<String, usize>,
+    len: usize,
+}
+
+impl Serialize for DataTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut st = serializer.serialize_struct("DataTable", 1)?;
+        st.serialize_field("columns", &*self.columns)?;
+        st.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for DataTable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct TableRepr {
+            columns: Vec<Column>,
+        }
+        let TableRepr { columns } = TableRepr::deserialize(deserializer)?;
+        let mut seen = std::collections::HashSet::new();
+        for col in &columns {
+            if col.len() != columns[0].len() {
+                return Err(serde::de::Error::custom(format!(
+                    "column '{}' has {} rows, expected {}",
+                    col.name(),
+                    col.len(),
+                    columns[0].len()
+                )));
+            }
+            if !seen.insert(col.name()) {
+                return Err(serde::de::Error::custom(format!(
+                    "duplicate column '{}'",
+                    col.name()
+                )));
+            }
+        }
+        Ok(Self::from_columns(columns))
+    }
 }
 
 impl DataTable {
     /// Create a new table from a pre-built list of rows
     pub fn new(rows: Vec<DataRow>) -> Self {
-        Self { rows }
+        let mut table = Self::default();
+        for row in rows {
+            table.push(row);
+        }
+        table
     }
 
     /// Alias for `new` — matches the target developer-experience API
@@ -127,42 +245,151 @@ impl DataTable {
         Self::new(rows)
     }
 
-    /// Append a row to the table
-    pub fn push(&mut self, row: DataRow) {
-        self.rows.push(row);
+    /// Create a table directly from columns.
+    ///
+    /// Shorter columns are padded with nulls to the longest column's length;
+    /// a later column replaces an earlier one with the same name.
+    pub fn from_columns(columns: Vec<Column>) -> Self {
+        let mut table = Self::default();
+        for col in columns {
+            table.insert_column(col);
+        }
+        table
+    }
+
+    /// Append a row to the table.
+    ///
+    /// Columns absent from `row` receive a null; keys not yet in the table
+    /// become new columns (back-filled with nulls, added in name order).
+    pub fn push(&mut self, mut row: DataRow) {
+        let columns = Arc::make_mut(&mut self.columns);
+        for col in columns.iter_mut() {
+            col.push(row.remove(col.name()).unwrap_or(FieldValue::Null));
+        }
+        let mut new_cols: Vec<(String, FieldValue)> = row.into_iter().collect();
+        new_cols.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, value) in new_cols {
+            let mut col = Column::nulls(name, self.len);
+            col.push(value);
+            self.index.insert(col.name().to_owned(), columns.len());
+            columns.push(col);
+        }
+        self.len += 1;
+    }
+
+    /// Add a column, replacing any existing column with the same name.
+    ///
+    /// Lengths are reconciled by padding the shorter side with nulls.
+    pub fn insert_column(&mut self, mut column: Column) {
+        let columns = Arc::make_mut(&mut self.columns);
+        if column.len() > self.len {
+            let target = column.len();
+            for col in columns.iter_mut() {
+                while col.len() < target {
+                    col.push(FieldValue::Null);
+                }
+            }
+            self.len = target;
+        }
+        while column.len() < self.len {
+            column.push(FieldValue::Null);
+        }
+        match self.index.get(column.name()) {
+            Some(&i) => columns[i] = column,
+            None => {
+                self.index.insert(column.name().to_owned(), columns.len());
+                columns.push(column);
+            }
+        }
+    }
+
+    /// Remove a column by name, returning it if present
+    pub fn remove_column(&mut self, name: &str) -> Option<Column> {
+        let i = self.index.remove(name)?;
+        let col = Arc::make_mut(&mut self.columns).remove(i);
+        for idx in self.index.values_mut() {
+            if *idx > i {
+                *idx -= 1;
+            }
+        }
+        Some(col)
     }
 
     /// Number of rows
     pub fn len(&self) -> usize {
-        self.rows.len()
+        self.len
     }
 
     /// True when the table has no rows
     pub fn is_empty(&self) -> bool {
-        self.rows.is_empty()
+        self.len == 0
+    }
+
+    /// All columns in insertion order
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Look up a column by name
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.index.get(name).map(|&i| &self.columns[i])
+    }
+
+    /// Column names in insertion order
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(Column::name).collect()
+    }
+
+    /// Value of `col` at row `row` (`Null` when either is missing)
+    pub fn value(&self, row: usize, col: &str) -> FieldValue {
+        self.column(col).map_or(FieldValue::Null, |c| c.get(row))
+    }
+
+    /// Materialize row `i` as a `DataRow`
+    pub fn row(&self, i: usize) -> Option<DataRow> {
+        (i < self.len).then(|| {
+            self.columns
+                .iter()
+                .map(|c| (c.name().to_owned(), c.get(i)))
+                .collect()
+        })
     }
 
-    /// Read-only slice of all rows
-    pub fn rows(&self) -> &[DataRow] {
-        &self.rows
+    /// Borrowed views of all rows, read straight from the columns.
+    ///
+    /// Nothing is copied until a cell is read; use [`RowRef::to_row`] to
+    /// materialize a `DataRow`.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = RowRef<'_>> + '_ {
+        (0..self.len).map(move |index| RowRef { table: self, index })
+    }
+
+    /// New table holding only the rows at `indices`, in that order
+    pub fn select_rows(&self, indices: &[usize]) -> Self {
+        Self {
+            columns: Arc::new(self.columns.iter().map(|c| c.take(indices)).collect()),
+            index: self.index.clone(),
+            len: indices.len(),
+        }
     }
 
     // --- Column extraction helpers ---
 
     /// Extract all non-null numeric values from a column (skips Null rows)
     pub fn extract_numeric(&self, col: &str) -> Vec<f64> {
-        self.rows
-            .iter()
-            .filter_map(|row| row.get(col)?.as_f64())
-            .collect()
+        self.column(col)
+            .map(|c| (0..self.len).filter_map(|i| c.get_f64(i)).collect())
+            .unwrap_or_default()
     }
 
     /// Extract all text values from a column (skips non-Text rows)
     pub fn extract_text(&self, col: &str) -> Vec<String> {
-        self.rows
-            .iter()
-            .filter_map(|row| row.get(col)?.as_str().map(ToOwned::to_owned))
-            .collect()
+        self.column(col)
+            .map(|c| {
+                (0..self.len)
+                    .filter_map(|i| c.get_str(i).map(ToOwned::to_owned))
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
     // --- Grouping ---
@@ -172,30 +399,87 @@ impl DataTable {
     /// Returns groups in first-occurrence order, as `(group_key, DataTable)`.
     /// Used for `color` encoding → multi-series generation.
     pub fn group_by(&self, col: &str) -> Vec<(String, Self)> {
-        let mut order: Vec<String> = Vec::new();
-        let mut groups: HashMap<String, Vec<DataRow>> = HashMap::new();
-
-        for row in &self.rows {
-            let key = match row.get(col) {
-                Some(FieldValue::Text(s)) => s.clone(),
-                Some(FieldValue::Numeric(v)) => v.to_string(),
-                Some(FieldValue::Timestamp(v)) => v.to_string(),
-                Some(FieldValue::Bool(b)) => b.to_string(),
-                Some(FieldValue::Null) | None => String::from("__null__"),
-            };
-            if !groups.contains_key(&key) {
-                order.push(key.clone());
+        self.partition(col)
+            .into_iter()
+            .map(|(key, indices)| (key, self.select_rows(&indices)))
+            .collect()
+    }
+
+    /// Row indices per distinct value of `col`, in first-occurrence order.
+    ///
+    /// A missing column puts every row in the null group.
+    pub(crate) fn partition(&self, col: &str) -> Vec<(String, Vec<usize>)> {
+        match self.column(col) {
+            Some(c) => c.partition(),
+            None if self.len == 0 => Vec::new(),
+            None => vec![(NULL_GROUP.to_owned(), (0..self.len).collect())],
+        }
+    }
+
+    /// Row indices grouped by the combined values of `cols`, in
+    /// first-occurrence order (keys are the per-column group keys)
+    pub(crate) fn partition_by(&self, cols: &[&str]) -> Vec<(Vec<String>, Vec<usize>)> {
+        let mut groups: Vec<(Vec<String>, Vec<usize>)> =
+            vec![(Vec::new(), (0..self.len).collect())];
+        for col in cols {
+            let column = self.column(col);
+            let mut next: Vec<(Vec<String>, Vec<usize>)> = Vec::new();
+            for (keys, rows) in groups {
+                let mut slots: HashMap<GroupKey, usize> = HashMap::new();
+                for i in rows {
+                    let key = column.map_or(GroupKey::Null, |c| c.group_key(i));
+                    let slot = *slots.entry(key.clone()).or_insert_with(|| {
+                        let mut k = keys.clone();
+                        k.push(key.into_label());
+                        next.push((k, Vec::new()));
+                        next.len() - 1
+                    });
+                    next[slot].1.push(i);
+                }
             }
-            groups.entry(key).or_default().push(row.clone());
+            groups = next;
         }
+        groups
+    }
 
-        order
-            .into_iter()
-            .map(|key| {
-                let rows = groups.remove(&key).unwrap_or_default();
-                (key, Self::new(rows))
-            })
-            .collect()
+    /// Per-row position in `[0, 1]`: numbers scaled over the column's
+    /// extent, other values by first-seen category rank (missing → 0)
+    fn unit_values(&self, col: &str) -> Vec<f64> {
+        let Some(c) = self.column(col) else {
+            return vec![0.0; self.len];
+        };
+        if matches!(c.kind(), ColumnKind::Numeric | ColumnKind::Timestamp) {
+            let values: Vec<Option<f64>> = (0..self.len).map(|i| c.get_f64(i)).collect();
+            let (lo, hi) = values
+                .iter()
+                .flatten()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                    (lo.min(v), hi.max(v))
+                });
+            return values
+                .into_iter()
+                .map(|v| match v {
+                    Some(v) if hi > lo => (v - lo) / (hi - lo),
+                    Some(_) => 1.0,
+                    None => 0.0,
+                })
+                .collect();
+        }
+        let ranks = self.category_ranks(col);
+        let top = ranks.iter().copied().max().unwrap_or(0).max(1) as f64;
+        ranks.into_iter().map(|r| r as f64 / top).collect()
+    }
+
+    /// Per-row index of the row's value among the column's distinct values
+    /// (first-seen order)
+    fn category_ranks(&self, col: &str) -> Vec<usize> {
+        let mut ranks = vec![0; self.len];
+        for (rank, (_, rows)) in self.partition(col).into_iter().enumerate() {
+            for i in rows {
+                ranks[i] = rank;
+            }
+        }
+        ranks
     }
 
     // --- Dataset conversion ---
@@ -204,24 +488,161 @@ impl DataTable {
     ///
     /// - `encoding.x` → x column (numeric / timestamp)
     /// - `encoding.y` → y column (numeric)
-    /// - `encoding.color` → if set, groups rows into multiple `Series`
+    /// - `encoding.color` / `stroke_dash` → if set, group rows into named `Series`
+    /// - `encoding.detail` → splits series further; the parts keep their
+    ///   group's name (and so its color)
+    /// - `encoding.order` → sorts each series' points (row order otherwise)
+    /// - `size`, `opacity`, `shape`, `text`, `tooltip` → per-point
+    ///   [`PointAttributes`]; `stroke_dash` also sets each series' dash pattern
+    /// - every point records its table row in `PointAttributes::row`
+    ///   (`None` for points aggregating several rows)
     ///
     /// Rows where x or y are missing / non-numeric are silently skipped.
+    /// Fields with `aggregate`, `bin` or `time_unit` are derived first
+    /// (see [`Encoding::derive`]).
     pub fn to_dataset(&self, encoding: &Encoding) -> Dataset {
-        let color_col = encoding.color.as_ref().map(|f| f.name.as_str());
-
-        if let Some(col) = color_col {
-            let groups = self.group_by(col);
-            let mut dataset = Dataset::new();
-            for (name, sub) in groups {
-                let points = sub.extract_xy(&encoding.x.name, &encoding.y.name);
-                dataset.add_series(Series::new(name, points));
+        let fields = encoding.fields();
+        if fields.iter().any(|f| f.aggregate.is_some()) {
+            if let Some((table, encoding)) = encoding.derive(self, false) {
+                let mut dataset = table.to_dataset(&encoding);
+                // Aggregated points summarize groups, not source rows
+                for s in &mut dataset.series {
+                    s.attributes.row = vec![None; s.data.len()];
+                }
+                return dataset;
+            }
+        } else if fields.iter().any(|f| f.is_derived()) {
+            // Bins and time units keep one row per record, possibly reordered:
+            // carry the source index through the derivation
+            let mut indexed = self.clone();
+            let index = (0..self.len).map(|i| i as f64).collect();
+            indexed.insert_column(Column::numeric(SOURCE_ROW, index));
+            if let Some((table, encoding)) = encoding.derive(&indexed, false) {
+                let source = table.column(SOURCE_ROW);
+                let mut dataset = table.to_dataset(&encoding);
+                for s in &mut dataset.series {
+                    for row in &mut s.attributes.row {
+                        *row = row.and_then(|r| source?.get_f64(r)).map(|v| v as usize);
+                    }
+                }
+                return dataset;
             }
-            dataset
+        }
+        fn name_of(f: &Option<Field>) -> Option<&str> {
+            f.as_ref().map(|f| f.name.as_str())
+        }
+        let (x_col, y_col) = (&encoding.x.name, &encoding.y.name);
+
+        // Series split: named by color and dash; detail only splits
+        let named: Vec<&str> = [name_of(&encoding.color), name_of(&encoding.stroke_dash)]
+            .into_iter()
+            .flatten()
+            .collect();
+        let mut split = named.clone();
+        split.extend(name_of(&encoding.detail));
+        let groups = if split.is_empty() {
+            vec![(Vec::new(), (0..self.len).collect())]
         } else {
-            let points = self.extract_xy(&encoding.x.name, &encoding.y.name);
-            Dataset::from_series(Series::new("default", points))
+            self.partition_by(&split)
+        };
+        let dash_slots = name_of(&encoding.stroke_dash).map(|col| self.partition(col));
+
+        // Per-row channel values over the whole table, so scales agree across series
+        let size = name_of(&encoding.size).map(|c| self.unit_values(c));
+        let opacity = name_of(&encoding.opacity).map(|c| self.unit_values(c));
+        let shape = name_of(&encoding.shape).map(|c| self.category_ranks(c));
+        let order = encoding.order.as_ref().and_then(|f| self.column(&f.name));
+        let (Some(xc), Some(yc)) = (self.column(x_col), self.column(y_col)) else {
+            return Dataset::new();
+        };
+
+        let mut dataset = Dataset::new();
+        for (keys, rows) in groups {
+            let mut rows: Vec<usize> = rows
+                .into_iter()
+                .filter(|&i| xc.get_f64(i).is_some() && yc.get_f64(i).is_some())
+                .collect();
+            if let Some(oc) = order {
+                rows.sort_by(|&a, &b| match (oc.get_f64(a), oc.get_f64(b)) {
+                    (Some(va), Some(vb)) => va.total_cmp(&vb),
+                    _ => oc.group_key(a).cmp(&oc.group_key(b)),
+                });
+            }
+            let points = rows
+                .iter()
+                .map(|&i| {
+                    DataPoint::new(xc.get_f64(i).unwrap_or(0.0), yc.get_f64(i).unwrap_or(0.0))
+                })
+                .collect();
+            let per_row = |values: &Option<Vec<f64>>, map: fn(f64) -> f64| {
+                values
+                    .as_ref()
+                    .map(|v| rows.iter().map(|&i| map(v[i])).collect())
+                    .unwrap_or_default()
+            };
+            let attributes = PointAttributes {
+                // Area-proportional: radius grows with the square root
+                size: per_row(&size, f64::sqrt),
+                opacity: per_row(&opacity, |u| 0.15 + 0.85 * u),
+                shape: shape
+                    .as_ref()
+                    .map(|ranks| rows.iter().map(|&i| PointShape::nth(ranks[i])).collect())
+                    .unwrap_or_default(),
+                text: encoding
+                    .text
+                    .as_ref()
+                    .map(|f| {
+                        let format = f.number_format();
+                        rows.iter()
+                            .map(|&i| {
+                                format_cell_value_with(&self.value(i, &f.name), format.as_ref())
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                tooltip: if encoding.tooltip.is_empty() {
+                    Vec::new()
+                } else {
+                    let formats: Vec<_> =
+                        encoding.tooltip.iter().map(Field::number_format).collect();
+                    rows.iter()
+                        .map(|&i| {
+                            encoding
+                                .tooltip
+                                .iter()
+                                .zip(&formats)
+                                .map(|(f, format)| {
+                                    let value = self.value(i, &f.name);
+                                    (
+                                        f.name.clone(),
+                                        format_cell_value_with(&value, format.as_ref()),
+                                    )
+                                })
+                                .collect()
+                        })
+                        .collect()
+                },
+                color: Vec::new(),
+                id: Vec::new(),
+                row: rows.iter().map(|&i| Some(i)).collect(),
+            };
+
+            let name = if named.is_empty() {
+                "default".to_owned()
+            } else {
+                keys[..named.len()].join(" · ")
+            };
+            let mut series = Series::new(name, points).with_attributes(attributes);
+            if let Some(slots) = &dash_slots {
+                let key = &keys[named.len() - 1];
+                let slot = slots.iter().position(|(k, _)| k == key).unwrap_or(0);
+                if let Some(dash) = DASH_PATTERNS[slot % DASH_PATTERNS.len()] {
+                    series = series.with_stroke_dash(dash);
+                }
+            }
+            dataset.add_series(series);
         }
+        dataset
     }
 
     /// Convert to `BarDataset` for bar charts.
@@ -232,65 +653,54 @@ impl DataTable {
     ///
     /// When `color` is absent, produces a single series named `"default"`.
     /// Rows where the value is missing / non-numeric are recorded as `0.0`.
+    /// Derived fields are applied first; a binned or time-unit x becomes
+    /// labelled categories (`"2024-03"`, `"10–20"`).
     pub fn to_bar_dataset(&self, encoding: &Encoding) -> BarDataset {
-        let cat_col = &encoding.x.name;
-        let val_col = &encoding.y.name;
+        if let Some((table, encoding)) = encoding.derive(self, true) {
+            return table.to_bar_dataset(&encoding);
+        }
+        let cat_col = self.column(&encoding.x.name);
+        let val_col = self.column(&encoding.y.name);
         let series_col = encoding.color.as_ref().map(|f| f.name.as_str());
 
-        // Collect ordered unique categories
-        let categories: Vec<String> = {
-            let mut seen = std::collections::HashSet::new();
-            let mut ordered = Vec::new();
-            for row in &self.rows {
-                if let Some(FieldValue::Text(s)) = row.get(cat_col.as_str()) {
-                    if seen.insert(s.clone()) {
-                        ordered.push(s.clone());
-                    }
+        // Category of every row (None for non-text) + ordered unique categories
+        let mut cat_index: HashMap<&str, usize> = HashMap::new();
+        let mut categories: Vec<String> = Vec::new();
+        let row_cat: Vec<Option<usize>> = (0..self.len)
+            .map(|i| {
+                let s = cat_col?.get_str(i)?;
+                Some(*cat_index.entry(s).or_insert_with(|| {
+                    categories.push(s.to_owned());
+                    categories.len() - 1
+                }))
+            })
+            .collect();
+
+        // First row of each category decides its value
+        let values_for = |indices: &mut dyn Iterator<Item = usize>| -> Vec<f64> {
+            let mut first: Vec<Option<usize>> = vec![None; categories.len()];
+            for i in indices {
+                if let Some(c) = row_cat[i] {
+                    first[c].get_or_insert(i);
                 }
             }
-            ordered
+            first
+                .into_iter()
+                .map(|row| row.and_then(|i| val_col?.get_f64(i)).unwrap_or(0.0))
+                .collect()
         };
 
         let mut bar_dataset = BarDataset::new(categories.clone());
 
         if let Some(scol) = series_col {
             // Multi-series: one BarSeries per distinct color-field value
-            let groups = self.group_by(scol);
-            for (name, sub) in groups {
-                let values: Vec<f64> = categories
-                    .iter()
-                    .map(|cat| {
-                        sub.rows
-                            .iter()
-                            .find(|row| {
-                                row.get(cat_col.as_str())
-                                    .and_then(FieldValue::as_str)
-                                    .map(|s| s == cat.as_str())
-                                    .unwrap_or(false)
-                            })
-                            .and_then(|row| row.get(val_col.as_str())?.as_f64())
-                            .unwrap_or(0.0)
-                    })
-                    .collect();
+            for (name, indices) in self.partition(scol) {
+                let values = values_for(&mut indices.into_iter());
                 bar_dataset.add_series(name, values);
             }
         } else {
             // Single series
-            let values: Vec<f64> = categories
-                .iter()
-                .map(|cat| {
-                    self.rows
-                        .iter()
-                        .find(|row| {
-                            row.get(cat_col.as_str())
-                                .and_then(FieldValue::as_str)
-                                .map(|s| s == cat.as_str())
-                                .unwrap_or(false)
-                        })
-                        .and_then(|row| row.get(val_col.as_str())?.as_f64())
-                        .unwrap_or(0.0)
-                })
-                .collect();
+            let values = values_for(&mut (0..self.len));
             bar_dataset.add_series("default", values);
         }
 
@@ -313,11 +723,15 @@ impl DataTable {
     /// Group A| 4.5
     /// ```
     pub fn to_strip_groups(&self, group_col: &str, value_col: &str) -> Vec<StripGroup> {
-        self.group_by(group_col)
+        let values = self.column(value_col);
+        self.partition(group_col)
             .into_iter()
-            .map(|(name, sub)| StripGroup {
+            .map(|(name, indices)| StripGroup {
                 name,
-                values: sub.extract_numeric(value_col),
+                values: indices
+                    .into_iter()
+                    .filter_map(|i| values?.get_f64(i))
+                    .collect(),
             })
             .collect()
     }
@@ -342,57 +756,48 @@ impl DataTable {
         value_col: &str,
         color_col: Option<&str>,
     ) -> SankeyData {
-        let mut label_index: std::collections::HashMap<String, usize> =
-            std::collections::HashMap::new();
+        let mut label_index: HashMap<String, usize> = HashMap::new();
         let mut nodes: Vec<SankeyNode> = Vec::new();
         let mut links: Vec<SankeyLink> = Vec::new();
 
-        for row in &self.rows {
-            let Some(src_label) = row
-                .get(source_col)
-                .and_then(FieldValue::as_str)
-                .map(str::to_owned)
-            else {
+        let (Some(src), Some(dst), Some(val)) = (
+            self.column(source_col),
+            self.column(target_col),
+            self.column(value_col),
+        ) else {
+            return SankeyData { nodes, links };
+        };
+        let color = color_col.and_then(|col| self.column(col));
+
+        let mut node_for = |label: &str, nodes: &mut Vec<SankeyNode>| -> usize {
+            *label_index.entry(label.to_owned()).or_insert_with(|| {
+                nodes.push(SankeyNode {
+                    label: label.to_owned(),
+                    color: None,
+                });
+                nodes.len() - 1
+            })
+        };
+
+        for i in 0..self.len {
+            let Some(src_label) = src.get_str(i) else {
                 continue;
             };
-            let Some(dst_label) = row
-                .get(target_col)
-                .and_then(FieldValue::as_str)
-                .map(str::to_owned)
-            else {
+            let Some(dst_label) = dst.get_str(i) else {
                 continue;
             };
-            let Some(value) = row.get(value_col).and_then(FieldValue::as_f64) else {
+            let Some(value) = val.get_f64(i) else {
                 continue;
             };
 
-            let color = color_col
-                .and_then(|col| row.get(col))
-                .and_then(FieldValue::as_str)
-                .map(str::to_owned);
-
-            let source = *label_index.entry(src_label.clone()).or_insert_with(|| {
-                let idx = nodes.len();
-                nodes.push(SankeyNode {
-                    label: src_label,
-                    color: None,
-                });
-                idx
-            });
-            let target = *label_index.entry(dst_label.clone()).or_insert_with(|| {
-                let idx = nodes.len();
-                nodes.push(SankeyNode {
-                    label: dst_label,
-                    color: None,
-                });
-                idx
-            });
+            let source = node_for(src_label, &mut nodes);
+            let target = node_for(dst_label, &mut nodes);
 
             links.push(SankeyLink {
                 source,
                 target,
                 value,
-                color,
+                color: color.and_then(|c| c.get_str(i)).map(str::to_owned),
             });
         }
 
@@ -412,26 +817,9 @@ impl DataTable {
     /// Asia     |      6 |       11 |    0
     /// ```
     pub fn to_chord_matrix(&self, label_col: &str, value_cols: &[&str]) -> ChordData {
-        let labels: Vec<String> = self
-            .rows
-            .iter()
-            .filter_map(|row| row.get(label_col)?.as_str().map(str::to_owned))
-            .collect();
-
-        let matrix: Vec<Vec<f64>> = self
-            .rows
-            .iter()
-            .map(|row| {
-                value_cols
-                    .iter()
-                    .map(|col| row.get(*col).and_then(FieldValue::as_f64).unwrap_or(0.0))
-                    .collect()
-            })
-            .collect();
-
         ChordData {
-            matrix,
-            labels,
+            matrix: self.wide_matrix(value_cols),
+            labels: self.extract_text(label_col),
             colors: None,
         }
     }
@@ -449,36 +837,20 @@ impl DataTable {
     /// Product B |  6.3 |  9.0 |  7.4
     /// ```
     pub fn to_grid_wide(&self, label_col: Option<&str>, value_cols: &[&str]) -> GridData {
-        let row_labels = label_col.map(|col| {
-            self.rows
-                .iter()
-                .filter_map(|row| row.get(col)?.as_str().map(str::to_owned))
-                .collect()
-        });
-
-        let values: Vec<Vec<f64>> = self
-            .rows
-            .iter()
-            .map(|row| {
-                value_cols
-                    .iter()
-                    .map(|col| row.get(*col).and_then(FieldValue::as_f64).unwrap_or(0.0))
-                    .collect()
-            })
-            .collect();
-
-        let col_labels = Some(value_cols.iter().map(|s| (*s).to_owned()).collect());
-
         GridData {
-            values,
-            row_labels,
-            col_labels,
+            values: self.wide_matrix(value_cols),
+            row_labels: label_col.map(|col| self.extract_text(col)),
+            col_labels: Some(value_cols.iter().map(|s| (*s).to_owned()).collect()),
         }
     }
 
     /// Long/tidy-format table → `GridData` for `HeatmapChart` / `ContourChart`.
     ///
-    /// Each row is one cell: integer row/col indices and a numeric value.
+    /// Each row is one cell: row/col indices and a numeric value. Numeric
+    /// index columns holding small non-negative integers (below
+    /// `MAX_GRID_INDEX`) are used as-is; any other column — text,
+    /// timestamps, fractional or large numbers — is treated as categories
+    /// in first-seen order that become the row/column labels.
     /// Missing cells are filled with `fill_value` (use `0.0` or `f64::NAN`).
     ///
     /// ```text
@@ -498,22 +870,27 @@ impl DataTable {
     ) -> GridData {
         let mut n_rows = 0usize;
         let mut n_cols = 0usize;
+        let mut rows = GridAxis::default();
+        let mut cols = GridAxis::default();
         let mut cells: Vec<(usize, usize, f64)> = Vec::new();
 
-        for row in &self.rows {
-            let Some(r) = row.get(row_col).and_then(FieldValue::as_f64) else {
-                continue;
-            };
-            let Some(c) = row.get(col_col).and_then(FieldValue::as_f64) else {
-                continue;
-            };
-            let Some(v) = row.get(value_col).and_then(FieldValue::as_f64) else {
-                continue;
-            };
-            let (ri, ci) = (r as usize, c as usize);
-            n_rows = n_rows.max(ri + 1);
-            n_cols = n_cols.max(ci + 1);
-            cells.push((ri, ci, v));
+        if let (Some(rc), Some(cc), Some(vc)) = (
+            self.column(row_col),
+            self.column(col_col),
+            self.column(value_col),
+        ) {
+            rows.as_index = is_index_column(rc);
+            cols.as_index = is_index_column(cc);
+            for i in 0..self.len {
+                let (Some(ri), Some(ci), Some(v)) =
+                    (rows.index(rc, i), cols.index(cc, i), vc.get_f64(i))
+                else {
+                    continue;
+                };
+                n_rows = n_rows.max(ri + 1);
+                n_cols = n_cols.max(ci + 1);
+                cells.push((ri, ci, v));
+            }
         }
 
         let mut values = vec![vec![fill_value; n_cols]; n_rows];
@@ -523,25 +900,103 @@ impl DataTable {
 
         GridData {
             values,
-            row_labels: None,
-            col_labels: None,
+            row_labels: (!rows.labels.is_empty()).then_some(rows.labels),
+            col_labels: (!cols.labels.is_empty()).then_some(cols.labels),
+        }
+    }
+
+    /// Price table → `Vec<OhlcBar>` for `CandlestickChart`.
+    ///
+    /// Rows sharing a `time_col` value form one bar: the first price is the
+    /// open, the last the close, and the extremes the high and low. A table
+    /// of ticks bucketed to a period (e.g. with a day time unit) becomes
+    /// daily candles. Bars are sorted by time; rows with missing values are
+    /// skipped.
+    pub fn to_ohlc(&self, time_col: &str, price_col: &str) -> Vec<OhlcBar> {
+        let (Some(tc), Some(pc)) = (self.column(time_col), self.column(price_col)) else {
+            return Vec::new();
+        };
+        let mut bars: Vec<OhlcBar> = Vec::new();
+        let mut bar_index: HashMap<u64, usize> = HashMap::new();
+        for i in 0..self.len {
+            let (Some(t), Some(price)) = (tc.get_f64(i), pc.get_f64(i)) else {
+                continue;
+            };
+            match bar_index.get(&t.to_bits()) {
+                Some(&b) => {
+                    let bar = &mut bars[b];
+                    bar.high = bar.high.max(price);
+                    bar.low = bar.low.min(price);
+                    bar.close = price;
+                }
+                None => {
+                    bar_index.insert(t.to_bits(), bars.len());
+                    bars.push(OhlcBar::new(t, price, price, price, price));
+                }
+            }
         }
+        bars.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+        bars
     }
 
-    // --- Internal helper ---
+    // --- Internal helpers ---
 
-    fn extract_xy(&self, x_col: &str, y_col: &str) -> Vec<DataPoint> {
-        self.rows
-            .iter()
-            .filter_map(|row| {
-                let x = row.get(x_col)?.as_f64()?;
-                let y = row.get(y_col)?.as_f64()?;
-                Some(DataPoint::new(x, y))
+    /// One row of numeric values per table row, one cell per column (missing → 0.0)
+    fn wide_matrix(&self, value_cols: &[&str]) -> Vec<Vec<f64>> {
+        let cols: Vec<Option<&Column>> = value_cols.iter().map(|c| self.column(c)).collect();
+        (0..self.len)
+            .map(|i| {
+                cols.iter()
+                    .map(|c| c.and_then(|c| c.get_f64(i)).unwrap_or(0.0))
+                    .collect()
             })
             .collect()
     }
 }
 
+/// Largest numeric value used directly as a grid row/column index
+const MAX_GRID_INDEX: usize = 4096;
+
+/// True when every value of `col` is a non-negative integer index below
+/// `MAX_GRID_INDEX` (timestamps never are)
+fn is_index_column(col: &Column) -> bool {
+    matches!(col.kind(), ColumnKind::Numeric | ColumnKind::Bool)
+        && (0..col.len()).all(|i| {
+            col.get_f64(i)
+                .is_none_or(|v| v >= 0.0 && v.fract() == 0.0 && v < MAX_GRID_INDEX as f64)
+        })
+}
+
+/// Positions along one grid axis: numeric indices as-is, or categories
+/// in first-seen order
+#[derive(Default)]
+struct GridAxis {
+    as_index: bool,
+    labels: Vec<String>,
+    positions: HashMap<String, usize>,
+}
+
+impl GridAxis {
+    /// Grid position of row `i`, recording new categories in `labels`
+    fn index(&mut self, col: &Column, i: usize) -> Option<usize> {
+        if self.as_index {
+            return col.get_f64(i).map(|v| v as usize);
+        }
+        let label = match col.get(i) {
+            FieldValue::Null => return None,
+            FieldValue::Text(s) => s,
+            FieldValue::Timestamp(t) => time_label(t, TimeZone::Utc),
+            other => format_cell_value(&other),
+        };
+        let next = self.labels.len();
+        let pos = *self.positions.entry(label.clone()).or_insert(next);
+        if pos == next {
+            self.labels.push(label);
+        }
+        Some(pos)
+    }
+}
+
 // --- Convenience macros / helpers for building DataRow ---
 
 /// Build a `DataRow` from key-value pairs.
@@ -647,6 +1102,91 @@ mod tests {
         assert_eq!(dataset.series[1].data.len(), 2);
     }
 
+    #[test]
+    fn test_to_dataset_point_attributes() {
+        let table = make_table();
+        let enc = Encoding::new(Field::quantitative("x"), Field::quantitative("y"))
+            .with_size(Field::quantitative("y"))
+            .with_opacity(Field::quantitative("x"))
+            .with_shape(Field::nominal("cat"))
+            .with_text(Field::nominal("cat"))
+            .with_tooltip(Field::quantitative("y"));
+        let attrs = &table.to_dataset(&enc).series[0].attributes;
+        assert_eq!(attrs.size.len(), 4);
+        assert_eq!(attrs.size[0], 0.0);
+        assert_eq!(attrs.size[3], 1.0);
+        assert!((attrs.opacity[0] - 0.15).abs() < 1e-9);
+        assert_eq!(attrs.opacity[3], 1.0);
+        assert_eq!(attrs.shape[..2], [PointShape::Circle, PointShape::Square]);
+        assert_eq!(attrs.text, vec!["A", "B", "A", "B"]);
+        assert_eq!(attrs.tooltip[1], vec![("y".to_owned(), "20".to_owned())]);
+        assert_eq!(attrs.row, vec![Some(0), Some(1), Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn test_to_dataset_rows_after_derive() {
+        use crate::algorithms::statistics::BinRule;
+        use crate::core::transform::AggregateOp;
+
+        let table = DataTable::from_rows(
+            [(4.0, 40.0), (1.0, 10.0), (3.0, 30.0), (1.0, 5.0)]
+                .into_iter()
+                .map(|(x, y): (f64, f64)| crate::data_row! { "x" => x, "y" => y })
+                .collect(),
+        );
+        // Binned x reorders the points; rows still name the source records
+        let binned = Encoding::new(
+            Field::quantitative("x").with_bin(BinRule::Fixed(3)),
+            Field::quantitative("y"),
+        );
+        let ds = table.to_dataset(&binned);
+        let series = &ds.series[0];
+        assert_eq!(series.data.len(), 4);
+        for (j, p) in series.data.iter().enumerate() {
+            let row = series.attributes.row_of(j).unwrap();
+            assert_eq!(table.value(row, "y"), FieldValue::Numeric(p.y));
+        }
+
+        // Aggregated points summarize several records
+        let summed = Encoding::new(
+            Field::quantitative("x"),
+            Field::quantitative("y").with_aggregate(AggregateOp::Sum),
+        );
+        let ds = table.to_dataset(&summed);
+        let attrs = &ds.series[0].attributes;
+        assert_eq!(ds.series[0].data.len(), 3);
+        assert_eq!(attrs.row, vec![None; 3]);
+        assert_eq!(attrs.row_of(0), None);
+    }
+
+    #[test]
+    fn test_to_dataset_stroke_dash_detail_order() {
+        let table = DataTable::from_rows(
+            [
+                (3.0, 30.0, "a", "r1"),
+                (1.0, 10.0, "a", "r1"),
+                (2.0, 20.0, "a", "r2"),
+                (1.0, 5.0, "b", "r1"),
+                (2.0, 6.0, "b", "r1"),
+            ]
+            .into_iter()
+            .map(|(x, y, kind, run)| data_row! { "x" => x, "y" => y, "kind" => kind, "run" => run })
+            .collect(),
+        );
+        let enc = Encoding::new(Field::quantitative("x"), Field::quantitative("y"))
+            .with_stroke_dash(Field::nominal("kind"))
+            .with_detail(Field::nominal("run"))
+            .with_order(Field::quantitative("x"));
+        let dataset = table.to_dataset(&enc);
+        let names: Vec<&str> = dataset.series.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "a", "b"]);
+        assert_eq!(dataset.color_indices(), vec![0, 0, 1]);
+        assert_eq!(dataset.series[0].stroke_dash, None);
+        assert_eq!(dataset.series[2].stroke_dash.as_deref(), Some("6 3"));
+        let xs: Vec<f64> = dataset.series[0].data.iter().map(|p| p.x).collect();
+        assert_eq!(xs, vec![1.0, 3.0]);
+    }
+
     #[test]
     fn test_to_bar_dataset() {
         let mut t = DataTable::default();
@@ -699,6 +1239,46 @@ mod tests {
         assert_eq!(row.get("flag"), Some(&FieldValue::Bool(true)));
     }
 
+    #[test]
+    fn test_push_backfills_new_and_missing_columns() {
+        let mut t = DataTable::default();
+        t.push(crate::data_row! { "a" => 1.0_f64 });
+        t.push(crate::data_row! { "b" => "x" });
+        assert_eq!(t.len(), 2);
+        assert_eq!(t.column_names(), vec!["a", "b"]);
+        assert_eq!(t.value(0, "b"), FieldValue::Null);
+        assert_eq!(t.value(1, "a"), FieldValue::Null);
+        assert_eq!(t.value(1, "b"), FieldValue::Text("x".into()));
+        let row = t.row(1).unwrap();
+        assert_eq!(row.get("a"), Some(&FieldValue::Null));
+        assert!(t.row(2).is_none());
+    }
+
+    #[test]
+    fn test_rows_borrow_cells() {
+        let mut t = DataTable::default();
+        t.push(crate::data_row! { "a" => 1.0_f64, "b" => "x" });
+        t.push(crate::data_row! { "a" => 2.0_f64 });
+        let rows: Vec<RowRef> = t.rows().collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get_str("b"), Some("x"));
+        assert_eq!(rows[1].get_f64("a"), Some(2.0));
+        assert_eq!(rows[1].get("b"), Some(FieldValue::Null));
+        assert_eq!(rows[1].get("missing"), None);
+        assert_eq!(rows[1].to_row(), t.row(1).unwrap());
+    }
+
+    #[test]
+    fn test_from_columns_pads_short_columns() {
+        let t = DataTable::from_columns(vec![
+            Column::numeric("x", vec![1.0, 2.0, 3.0]),
+            Column::text("label", ["a"]),
+        ]);
+        assert_eq!(t.len(), 3);
+        assert_eq!(t.extract_text("label"), vec!["a"]);
+        assert_eq!(t.value(2, "label"), FieldValue::Null);
+    }
+
     #[test]
     fn test_empty_table() {
         let t = DataTable::default();
@@ -822,4 +1402,92 @@ mod tests {
         assert_eq!(grid.values[0], vec![1.0, 0.0]); // (0,0)=1, (0,1)=fill
         assert_eq!(grid.values[1], vec![0.0, 2.0]); // (1,0)=fill, (1,1)=2
     }
+
+    #[test]
+    fn test_to_grid_long_categories() {
+        let t = DataTable::from_rows(vec![
+            crate::data_row! { "day" => "Mon", "hour" => 0.0_f64, "n" => 3.0_f64 },
+            crate::data_row! { "day" => "Tue", "hour" => 1.0_f64, "n" => 5.0_f64 },
+            crate::data_row! { "day" => "Mon", "hour" => 1.0_f64, "n" => 4.0_f64 },
+        ]);
+        let grid = t.to_grid_long("day", "hour", "n", 0.0);
+        assert_eq!(
+            grid.row_labels,
+            Some(vec!["Mon".to_owned(), "Tue".to_owned()])
+        );
+        assert_eq!(grid.col_labels, None);
+        assert_eq!(grid.values, vec![vec![3.0, 4.0], vec![0.0, 5.0]]);
+    }
+
+    #[test]
+    fn test_to_grid_long_large_numbers_become_categories() {
+        let t = DataTable::from_rows(vec![
+            crate::data_row! { "id" => 1e12_f64, "k" => 0.0_f64, "n" => 1.0_f64 },
+            crate::data_row! { "id" => 2.5_f64, "k" => 1.0_f64, "n" => 2.0_f64 },
+        ]);
+        let grid = t.to_grid_long("id", "k", "n", 0.0);
+        assert_eq!(
+            grid.row_labels,
+            Some(vec!["1000000000000".to_owned(), "2.5".to_owned()])
+        );
+        assert_eq!(grid.values, vec![vec![1.0, 0.0], vec![0.0, 2.0]]);
+    }
+
+    #[test]
+    fn test_to_ohlc_buckets() {
+        let t = DataTable::from_rows(vec![
+            crate::data_row! { "t" => 2.0_f64, "price" => 7.0_f64 },
+            crate::data_row! { "t" => 1.0_f64, "price" => 10.0_f64 },
+            crate::data_row! { "t" => 1.0_f64, "price" => 12.0_f64 },
+            crate::data_row! { "t" => 1.0_f64, "price" => 9.0_f64 },
+            crate::data_row! { "t" => 1.0_f64, "price" => 11.0_f64 },
+        ]);
+        let bars = t.to_ohlc("t", "price");
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0], OhlcBar::new(1.0, 10.0, 12.0, 9.0, 11.0));
+        assert_eq!(bars[1], OhlcBar::new(2.0, 7.0, 7.0, 7.0, 7.0));
+    }
+
+    #[test]
+    fn test_serde_field_value_shape() {
+        let json = serde_json::to_string(&FieldValue::Numeric(1.5)).unwrap();
+        assert_eq!(json, r#"{"type":"Numeric","value":1.5}"#);
+        assert_eq!(
+            serde_json::to_string(&FieldValue::Null).unwrap(),
+            r#"{"type":"Null"}"#
+        );
+        let ts: FieldValue = serde_json::from_str(r#"{"type":"Timestamp","value":0}"#).unwrap();
+        assert_eq!(ts, FieldValue::Timestamp(0.0));
+    }
+
+    #[test]
+    fn test_serde_table_roundtrip() {
+        let t = make_table();
+        let json = serde_json::to_string(&t).unwrap();
+        assert!(json.starts_with(r#"{"columns":[{"name":"#));
+        let back: DataTable = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, t);
+
+        let ragged = r#"{"columns":[
+            {"name":"a","type":"Numeric","values":[1]},
+            {"name":"b","type":"Numeric","values":[1,2]}]}"#;
+        assert!(serde_json::from_str::<DataTable>(ragged).is_err());
+    }
+
+    #[test]
+    fn test_serde_nan_roundtrip() {
+        let json = serde_json::to_string(&FieldValue::Numeric(f64::NAN)).unwrap();
+        assert_eq!(json, r#"{"type":"Numeric","value":null}"#);
+        let back: FieldValue = serde_json::from_str(&json).unwrap();
+        assert!(back.as_f64().is_some_and(f64::is_nan));
+
+        let mixed = DataTable::from_columns(vec![Column::from_values(
+            "m",
+            vec![FieldValue::Numeric(f64::NAN), "a".into()],
+        )]);
+        let back: DataTable =
+            serde_json::from_str(&serde_json::to_string(&mixed).unwrap()).unwrap();
+        assert!(back.value(0, "m").as_f64().is_some_and(f64::is_nan));
+        assert_eq!(back.value(1, "m"), FieldValue::Text("a".into()));
+    }
 }
@@ -0,0 +1,792 @@
+/// d3-format style number formatting with locale support
+///
+/// A specifier string such as `",.2f"`, `".1%"`, `"$,.0f"`, `"~s"` or
+/// `"+.3e"` is parsed once into a [`FormatSpec`] and applied to values by a
+/// [`NumberFormat`], which also carries the [`Locale`] (decimal and
+/// thousands separators, digit grouping and currency symbol). The grammar
+/// and output follow d3-format:
+///
+/// ```text
+/// [[fill]align][sign][symbol][0][width][,][.precision][~][type]
+/// ```
+///
+/// ```
+/// use lodviz_core::core::format::{Locale, NumberFormat};
+///
+/// let euro = NumberFormat::new("$.2s").unwrap().with_locale(Locale::en_us().with_currency("€", ""));
+/// assert_eq!(euro.format(1_234_567.0), "€1.2M");
+/// ```
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// SI prefixes from yocto (1e-24) to yotta (1e24), indexed by `exponent / 3 + 8`
+const SI_PREFIXES: [&str; 17] = [
+    "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+];
+
+/// Why a format specifier cannot be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatError {
+    /// The specifier does not match the format grammar
+    InvalidSpecifier(String),
+    /// The trailing type character is not a known format type
+    UnknownType(char),
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSpecifier(spec) => write!(f, "invalid format specifier '{spec}'"),
+            Self::UnknownType(ty) => write!(f, "unknown format type '{ty}'"),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// Separators, digit grouping and currency symbol used when formatting
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Locale {
+    /// Decimal point
+    pub decimal: String,
+    /// Thousands separator (inserted by the `,` flag)
+    pub thousands: String,
+    /// Group sizes from the right, repeated cyclically (`[3]` = 1,234,567).
+    /// A size of 0 (or an empty list) keeps the remaining digits together.
+    pub grouping: Vec<usize>,
+    /// Currency symbol placed before the number by the `$` flag
+    pub currency_prefix: String,
+    /// Currency symbol placed after the number by the `$` flag
+    pub currency_suffix: String,
+    /// Minus sign for negative values
+    pub minus: String,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::en_us()
+    }
+}
+
+impl Locale {
+    /// English (US): `1,234.5`, `$1,234.50`
+    pub fn en_us() -> Self {
+        Self {
+            decimal: ".".to_owned(),
+            thousands: ",".to_owned(),
+            grouping: vec![3],
+            currency_prefix: "$".to_owned(),
+            currency_suffix: String::new(),
+            minus: "-".to_owned(),
+        }
+    }
+
+    /// German: `1.234,5`, `1.234,50 €`
+    pub fn de_de() -> Self {
+        Self {
+            decimal: ",".to_owned(),
+            thousands: ".".to_owned(),
+            currency_prefix: String::new(),
+            currency_suffix: "\u{a0}€".to_owned(),
+            ..Self::en_us()
+        }
+    }
+
+    /// French: `1 234,5`, `1 234,50 €` (non-breaking spaces)
+    pub fn fr_fr() -> Self {
+        Self {
+            thousands: "\u{a0}".to_owned(),
+            ..Self::de_de()
+        }
+    }
+
+    /// Replace the currency symbol (`prefix` before, `suffix` after the number)
+    pub fn with_currency(mut self, prefix: impl Into<String>, suffix: impl Into<String>) -> Self {
+        self.currency_prefix = prefix.into();
+        self.currency_suffix = suffix.into();
+        self
+    }
+
+    /// Set the decimal point and thousands separator
+    pub fn with_separators(
+        mut self,
+        decimal: impl Into<String>,
+        thousands: impl Into<String>,
+    ) -> Self {
+        self.decimal = decimal.into();
+        self.thousands = thousands.into();
+        self
+    }
+
+    /// Insert thousands separators into a string of digits, d3 `formatGroup`
+    /// style: stop once `width` characters are reached.
+    fn group(&self, digits: &str, width: usize) -> String {
+        let chars: Vec<char> = digits.chars().collect();
+        let mut end = chars.len();
+        let mut parts: Vec<String> = Vec::new();
+        let mut length = 0;
+        let mut j = 0;
+        let mut size = self.grouping.first().copied().unwrap_or(0);
+        while end > 0 {
+            if size == 0 {
+                size = end;
+            }
+            if length + size + 1 > width {
+                size = width.saturating_sub(length).max(1);
+            }
+            let start = end.saturating_sub(size);
+            parts.push(chars[start..end].iter().collect());
+            end = start;
+            length += size + 1;
+            if length > width {
+                break;
+            }
+            j = (j + 1) % self.grouping.len().max(1);
+            size = self.grouping.get(j).copied().unwrap_or(0);
+        }
+        parts.reverse();
+        parts.join(&self.thousands)
+    }
+}
+
+/// Padding alignment within the field width
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    /// `<` — pad on the right
+    Left,
+    /// `>` — pad on the left (default)
+    #[default]
+    Right,
+    /// `^` — pad both sides
+    Center,
+    /// `=` — pad between the sign/symbol and the digits
+    AfterSign,
+}
+
+/// Sign display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Sign {
+    /// `-` — minus for negative values only (default)
+    #[default]
+    Minus,
+    /// `+` — plus for zero or positive, minus for negative
+    Plus,
+    /// `(` — parentheses around negative values
+    Parens,
+    /// ` ` — space for zero or positive, minus for negative
+    Space,
+}
+
+/// Prefix symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    /// `$` — the locale's currency symbol
+    Currency,
+    /// `#` — `0b`, `0o` or `0x` for binary, octal and hexadecimal types
+    Radix,
+}
+
+/// A parsed format specifier
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatSpec {
+    /// Padding character (default space)
+    pub fill: char,
+    /// Padding alignment
+    pub align: Align,
+    /// Sign display
+    pub sign: Sign,
+    /// Currency or radix prefix
+    pub symbol: Option<Symbol>,
+    /// Zero padding (`0` flag): fill with `0` after the sign
+    pub zero: bool,
+    /// Minimum field width
+    pub width: Option<usize>,
+    /// Group thousands (`,` flag)
+    pub comma: bool,
+    /// Digits after the point, or significant digits for `g`, `p`, `r`, `s`
+    pub precision: Option<usize>,
+    /// Trim insignificant trailing zeros (`~` flag)
+    pub trim: bool,
+    /// Format type: one of `e f g r s % p d b o x X n`, or None for
+    /// shortest representation
+    pub kind: Option<char>,
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        Self {
+            fill: ' ',
+            align: Align::Right,
+            sign: Sign::Minus,
+            symbol: None,
+            zero: false,
+            width: None,
+            comma: false,
+            precision: None,
+            trim: false,
+            kind: None,
+        }
+    }
+}
+
+fn align_of(c: char) -> Option<Align> {
+    match c {
+        '<' => Some(Align::Left),
+        '>' => Some(Align::Right),
+        '^' => Some(Align::Center),
+        '=' => Some(Align::AfterSign),
+        _ => None,
+    }
+}
+
+impl FromStr for FormatSpec {
+    type Err = FormatError;
+
+    fn from_str(specifier: &str) -> Result<Self, Self::Err> {
+        let invalid = || FormatError::InvalidSpecifier(specifier.to_owned());
+        let chars: Vec<char> = specifier.chars().collect();
+        let mut spec = Self::default();
+        let mut i = 0;
+
+        if let Some(align) = chars.get(1).copied().and_then(align_of) {
+            spec.fill = chars[0];
+            spec.align = align;
+            i = 2;
+        } else if let Some(align) = chars.first().copied().and_then(align_of) {
+            spec.align = align;
+            i = 1;
+        }
+        if let Some(sign) = chars.get(i).and_then(|c| match c {
+            '-' => Some(Sign::Minus),
+            '+' => Some(Sign::Plus),
+            '(' => Some(Sign::Parens),
+            ' ' => Some(Sign::Space),
+            _ => None,
+        }) {
+            spec.sign = sign;
+            i += 1;
+        }
+        match chars.get(i) {
+            Some('$') => {
+                spec.symbol = Some(Symbol::Currency);
+                i += 1;
+            }
+            Some('#') => {
+                spec.symbol = Some(Symbol::Radix);
+                i += 1;
+            }
+            _ => {}
+        }
+        if chars.get(i) == Some(&'0') {
+            spec.zero = true;
+            i += 1;
+        }
+        let digits = |i: &mut usize| -> Option<usize> {
+            let start = *i;
+            while chars.get(*i).is_some_and(char::is_ascii_digit) {
+                *i += 1;
+            }
+            chars[start..*i].iter().collect::<String>().parse().ok()
+        };
+        spec.width = digits(&mut i);
+        if chars.get(i) == Some(&',') {
+            spec.comma = true;
+            i += 1;
+        }
+        if chars.get(i) == Some(&'.') {
+            i += 1;
+            spec.precision = Some(digits(&mut i).ok_or_else(invalid)?);
+        }
+        if chars.get(i) == Some(&'~') {
+            spec.trim = true;
+            i += 1;
+        }
+        if let Some(&ty) = chars.get(i) {
+            if !(ty.is_ascii_alphabetic() || ty == '%') {
+                return Err(invalid());
+            }
+            if !"efgrs%pdboxXn".contains(ty) {
+                return Err(FormatError::UnknownType(ty));
+            }
+            spec.kind = Some(ty);
+            i += 1;
+        }
+        if i < chars.len() {
+            return Err(invalid());
+        }
+        Ok(spec)
+    }
+}
+
+impl fmt::Display for FormatSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let align = match self.align {
+            Align::Left => '<',
+            Align::Right => '>',
+            Align::Center => '^',
+            Align::AfterSign => '=',
+        };
+        if self.fill != ' ' || self.align != Align::Right {
+            write!(f, "{}{align}", self.fill)?;
+        }
+        match self.sign {
+            Sign::Minus => {}
+            Sign::Plus => f.write_str("+")?,
+            Sign::Parens => f.write_str("(")?,
+            Sign::Space => f.write_str(" ")?,
+        }
+        match self.symbol {
+            Some(Symbol::Currency) => f.write_str("$")?,
+            Some(Symbol::Radix) => f.write_str("#")?,
+            None => {}
+        }
+        if self.zero {
+            f.write_str("0")?;
+        }
+        if let Some(width) = self.width {
+            write!(f, "{width}")?;
+        }
+        if self.comma {
+            f.write_str(",")?;
+        }
+        if let Some(precision) = self.precision {
+            write!(f, ".{precision}")?;
+        }
+        if self.trim {
+            f.write_str("~")?;
+        }
+        if let Some(kind) = self.kind {
+            write!(f, "{kind}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A format specifier bound to a locale, ready to format values
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFormat {
+    spec: FormatSpec,
+    locale: Locale,
+}
+
+impl NumberFormat {
+    /// Parse `specifier` with the default (en-US) locale
+    pub fn new(specifier: &str) -> Result<Self, FormatError> {
+        Ok(Self::from_spec(specifier.parse()?))
+    }
+
+    /// Wrap an already parsed specifier
+    pub fn from_spec(spec: FormatSpec) -> Self {
+        Self {
+            spec,
+            locale: Locale::default(),
+        }
+    }
+
+    /// Format with `locale` instead of en-US
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// The parsed specifier
+    pub fn spec(&self) -> &FormatSpec {
+        &self.spec
+    }
+
+    /// The locale
+    pub fn locale(&self) -> &Locale {
+        &self.locale
+    }
+
+    /// Format `value`
+    pub fn format(&self, value: f64) -> String {
+        let spec = &self.spec;
+        let locale = &self.locale;
+
+        // `n` is `,g`; no type is `g` with trimming and 12 significant digits
+        let mut comma = spec.comma;
+        let mut trim = spec.trim;
+        let mut default_precision = 6;
+        let kind = match spec.kind {
+            Some('n') => {
+                comma = true;
+                'g'
+            }
+            Some(kind) => kind,
+            None => {
+                trim = true;
+                default_precision = 12;
+                'g'
+            }
+        };
+        let precision = match spec.precision {
+            None => default_precision,
+            Some(p) if "gprs".contains(kind) => p.clamp(1, 21),
+            Some(p) => p.min(20),
+        };
+        let (mut fill, mut align) = (spec.fill, spec.align);
+        let zero = spec.zero || (fill == '0' && align == Align::AfterSign);
+        if zero {
+            fill = '0';
+            align = Align::AfterSign;
+        }
+
+        let mut prefix = match spec.symbol {
+            Some(Symbol::Currency) => locale.currency_prefix.clone(),
+            Some(Symbol::Radix) if "boxX".contains(kind) => {
+                format!("0{}", kind.to_ascii_lowercase())
+            }
+            _ => String::new(),
+        };
+        let mut suffix = match spec.symbol {
+            Some(Symbol::Currency) => locale.currency_suffix.clone(),
+            _ if "%p".contains(kind) => "%".to_owned(),
+            _ => String::new(),
+        };
+
+        let mut negative = value < 0.0 || (value == 0.0 && value.is_sign_negative());
+        let (mut body, si) = if value.is_nan() {
+            ("NaN".to_owned(), "")
+        } else if value.is_infinite() {
+            ("Infinity".to_owned(), "")
+        } else {
+            format_type(kind, value.abs(), precision)
+        };
+        if trim {
+            body = trim_zeros(&body);
+        }
+        if negative && spec.sign != Sign::Plus && body.parse::<f64>().is_ok_and(|v| v == 0.0) {
+            negative = false;
+        }
+        let sign = match (negative, spec.sign) {
+            (true, Sign::Parens) => "(",
+            (true, _) => locale.minus.as_str(),
+            (false, Sign::Plus) => "+",
+            (false, Sign::Space) => " ",
+            (false, _) => "",
+        };
+        prefix.insert_str(0, sign);
+        suffix.insert_str(0, si);
+        if negative && spec.sign == Sign::Parens {
+            suffix.push(')');
+        }
+
+        // Split off everything from the first non-digit (point, exponent, ...)
+        if "defgprs%".contains(kind) {
+            if let Some(pos) = body.find(|c: char| !c.is_ascii_digit()) {
+                let tail = if body[pos..].starts_with('.') {
+                    format!("{}{}", locale.decimal, &body[pos + 1..])
+                } else {
+                    body[pos..].to_owned()
+                };
+                suffix.insert_str(0, &tail);
+                body.truncate(pos);
+            }
+        }
+
+        if comma && !zero {
+            body = locale.group(&body, usize::MAX);
+        }
+        let width = spec.width.unwrap_or(0);
+        let length = prefix.chars().count() + body.chars().count() + suffix.chars().count();
+        let mut padding: String = if length < width {
+            std::iter::repeat_n(fill, width - length).collect()
+        } else {
+            String::new()
+        };
+        if comma && zero {
+            let limit = if padding.is_empty() {
+                usize::MAX
+            } else {
+                width.saturating_sub(suffix.chars().count())
+            };
+            body = locale.group(&format!("{padding}{body}"), limit);
+            padding.clear();
+        }
+
+        match align {
+            Align::Left => format!("{prefix}{body}{suffix}{padding}"),
+            Align::AfterSign => format!("{prefix}{padding}{body}{suffix}"),
+            Align::Center => {
+                let half = padding.chars().count() / 2;
+                let left: String = padding.chars().take(half).collect();
+                let right: String = padding.chars().skip(half).collect();
+                format!("{left}{prefix}{body}{suffix}{right}")
+            }
+            Align::Right => format!("{padding}{prefix}{body}{suffix}"),
+        }
+    }
+}
+
+impl FromStr for NumberFormat {
+    type Err = FormatError;
+
+    fn from_str(specifier: &str) -> Result<Self, Self::Err> {
+        Self::new(specifier)
+    }
+}
+
+/// Format `value` with `specifier` in the en-US locale
+pub fn format_number(specifier: &str, value: f64) -> Result<String, FormatError> {
+    Ok(NumberFormat::new(specifier)?.format(value))
+}
+
+/// Format a non-negative finite `x` by type; returns the digits and the SI
+/// prefix (only non-empty for `s`).
+fn format_type(kind: char, x: f64, p: usize) -> (String, &'static str) {
+    let s = match kind {
+        'e' => exponential(x, p),
+        'f' => format!("{x:.p$}"),
+        'g' => to_precision(x, p),
+        'r' => format_rounded(x, p),
+        's' => return format_si(x, p),
+        '%' => format!("{:.p$}", x * 100.0),
+        'p' => format_rounded(x * 100.0, p),
+        'd' => format!("{:.0}", x.round()),
+        'b' => format!("{:b}", x.round() as u64),
+        'o' => format!("{:o}", x.round() as u64),
+        'x' => format!("{:x}", x.round() as u64),
+        'X' => format!("{:X}", x.round() as u64),
+        _ => to_precision(x, p),
+    };
+    (s, "")
+}
+
+/// `x.toExponential(p)`: `1.234e+3`
+fn exponential(x: f64, p: usize) -> String {
+    let s = format!("{x:.p$e}");
+    match s.split_once('e') {
+        Some((mantissa, exp)) if !exp.starts_with('-') => format!("{mantissa}e+{exp}"),
+        _ => s,
+    }
+}
+
+/// `x.toPrecision(p)`: `p` significant digits, exponential when the
+/// exponent is below -6 or at least `p`
+fn to_precision(x: f64, p: usize) -> String {
+    let (_, exp) = decimal_parts(x, p);
+    if exp < -6 || exp >= p as i32 {
+        exponential(x, p - 1)
+    } else {
+        let decimals = (p as i32 - 1 - exp).max(0) as usize;
+        format!("{x:.decimals$}")
+    }
+}
+
+/// Significant digits (without point) and decimal exponent of `x` rounded
+/// to `p` significant digits; `p == 0` gives the shortest representation.
+fn decimal_parts(x: f64, p: usize) -> (String, i32) {
+    let s = if p == 0 {
+        format!("{x:e}")
+    } else {
+        format!("{x:.prec$e}", prec = p - 1)
+    };
+    let (mantissa, exp) = s.split_once('e').unwrap_or((&s, "0"));
+    (mantissa.replace('.', ""), exp.parse().unwrap_or(0))
+}
+
+/// Fixed notation rounded to `p` significant digits
+fn format_rounded(x: f64, p: usize) -> String {
+    let (digits, exp) = decimal_parts(x, p);
+    if exp < 0 {
+        format!("0.{}{digits}", "0".repeat((-exp - 1) as usize))
+    } else if digits.len() > exp as usize + 1 {
+        let (int, frac) = digits.split_at(exp as usize + 1);
+        format!("{int}.{frac}")
+    } else {
+        format!("{digits}{}", "0".repeat(exp as usize + 1 - digits.len()))
+    }
+}
+
+/// `p` significant digits scaled by the nearest SI prefix
+fn format_si(x: f64, p: usize) -> (String, &'static str) {
+    let (digits, exp) = decimal_parts(x, p);
+    let prefix_exp = exp.div_euclid(3).clamp(-8, 8);
+    let prefix = SI_PREFIXES[(prefix_exp + 8) as usize];
+    let i = exp - prefix_exp * 3 + 1;
+    let n = digits.len() as i32;
+    let s = if i == n {
+        digits
+    } else if i > n {
+        format!("{digits}{}", "0".repeat((i - n) as usize))
+    } else if i > 0 {
+        let (int, frac) = digits.split_at(i as usize);
+        format!("{int}.{frac}")
+    } else {
+        let (small, _) = decimal_parts(x, (p as i32 + i - 1).max(0) as usize);
+        format!("0.{}{small}", "0".repeat((-i) as usize))
+    };
+    (s, prefix)
+}
+
+/// Drop insignificant trailing zeros (and a bare point) from the fractional part
+fn trim_zeros(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut i0: Option<usize> = None;
+    let mut i1 = 0;
+    for (i, &b) in bytes.iter().enumerate().skip(1) {
+        match b {
+            b'.' => {
+                i0 = Some(i);
+                i1 = i;
+            }
+            b'0' => {
+                if i0 == Some(0) {
+                    i0 = Some(i);
+                }
+                i1 = i;
+            }
+            b'1'..=b'9' => {
+                if i0.is_some() {
+                    i0 = Some(0);
+                }
+            }
+            _ => break,
+        }
+    }
+    match i0 {
+        Some(i0) if i0 > 0 => format!("{}{}", &s[..i0], &s[i1 + 1..]),
+        _ => s.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn f(spec: &str, value: f64) -> String {
+        format_number(spec, value).unwrap()
+    }
+
+    #[test]
+    fn fixed_with_grouping() {
+        assert_eq!(f(",.2f", 1234567.891), "1,234,567.89");
+        assert_eq!(f(",.2f", -1234.5), "-1,234.50");
+        assert_eq!(f(".0f", 2.5001), "3");
+        assert_eq!(f(",d", 1234567.0), "1,234,567");
+        assert_eq!(f(",", 1234.5678), "1,234.5678");
+    }
+
+    #[test]
+    fn percent_and_currency() {
+        assert_eq!(f(".1%", 0.1234), "12.3%");
+        assert_eq!(f(".0%", 0.5), "50%");
+        assert_eq!(f("$,.0f", 1234.4), "$1,234");
+        assert_eq!(f("($,.2f", -3.5), "($3.50)");
+        assert_eq!(f(".2p", 0.01234), "1.2%");
+    }
+
+    #[test]
+    fn si_prefixes() {
+        assert_eq!(f("~s", 1500.0), "1.5k");
+        assert_eq!(f(".2s", 1_234_567.0), "1.2M");
+        assert_eq!(f("s", 0.000042), "42.0000µ");
+        assert_eq!(f("~s", 0.000042), "42µ");
+        assert_eq!(f(".3s", 999.0), "999");
+        assert_eq!(f("~s", 0.0), "0");
+    }
+
+    #[test]
+    fn exponent_and_precision() {
+        assert_eq!(f("+.3e", 1234.5), "+1.234e+3");
+        assert_eq!(f(".2e", 0.00012), "1.20e-4");
+        assert_eq!(f(".3g", 1234.0), "1.23e+3");
+        assert_eq!(f(".3g", 0.0123), "0.0123");
+        assert_eq!(f(".3r", 1234.0), "1230");
+        assert_eq!(f(".3r", 0.012345), "0.0123");
+        assert_eq!(f("", 0.1 + 0.2), "0.3");
+    }
+
+    #[test]
+    fn signs_and_zero() {
+        assert_eq!(f("+d", 3.0), "+3");
+        assert_eq!(f(" d", 3.0), " 3");
+        assert_eq!(f(".1f", -0.01), "0.0");
+        assert_eq!(f("+.1f", -0.01), "-0.0");
+        assert_eq!(f("d", f64::NAN), "NaN");
+        assert_eq!(f("d", f64::NEG_INFINITY), "-Infinity");
+    }
+
+    #[test]
+    fn width_fill_and_align() {
+        assert_eq!(f("8d", 42.0), "      42");
+        assert_eq!(f("<8d", 42.0), "42      ");
+        assert_eq!(f("*^7d", 42.0), "**42***");
+        assert_eq!(f("08.2f", -1.5), "-0001.50");
+        assert_eq!(f("08,d", 1234.0), "0,001,234");
+        assert_eq!(f("=+8d", 42.0), "+     42");
+    }
+
+    #[test]
+    fn radix_types() {
+        assert_eq!(f("#x", 255.0), "0xff");
+        assert_eq!(f("X", 255.0), "FF");
+        assert_eq!(f("#b", 5.0), "0b101");
+        assert_eq!(f("o", 8.0), "10");
+    }
+
+    #[test]
+    fn trims_trailing_zeros() {
+        assert_eq!(f(".4~f", 3.5), "3.5");
+        assert_eq!(f(".4~f", 3.0), "3");
+        assert_eq!(f(".2~e", 1500.0), "1.5e+3");
+    }
+
+    #[test]
+    fn locales() {
+        let de = NumberFormat::new("$,.2f")
+            .unwrap()
+            .with_locale(Locale::de_de());
+        assert_eq!(de.format(1234.5), "1.234,50\u{a0}€");
+        let fr = NumberFormat::new(",.1f")
+            .unwrap()
+            .with_locale(Locale::fr_fr());
+        assert_eq!(fr.format(-1234567.26), "-1\u{a0}234\u{a0}567,3");
+        let euro = NumberFormat::new("$.2s")
+            .unwrap()
+            .with_locale(Locale::en_us().with_currency("€", ""));
+        assert_eq!(euro.format(1_234_567.0), "€1.2M");
+        let indian = Locale {
+            grouping: vec![3, 2, 2],
+            ..Locale::en_us()
+        };
+        let inr = NumberFormat::new(",d").unwrap().with_locale(indian);
+        assert_eq!(inr.format(12_345_678.0), "1,23,45,678");
+        for (grouping, expected) in [(vec![], "1234567"), (vec![3, 0], "1234,567")] {
+            let locale = Locale {
+                grouping,
+                ..Locale::en_us()
+            };
+            let format = NumberFormat::new(",d").unwrap().with_locale(locale);
+            assert_eq!(format.format(1_234_567.0), expected);
+        }
+        let zero_fill = Locale {
+            grouping: vec![],
+            ..Locale::en_us()
+        };
+        let padded = NumberFormat::new("08,d").unwrap().with_locale(zero_fill);
+        assert_eq!(padded.format(1234.0), "00001234");
+    }
+
+    #[test]
+    fn parse_errors_and_round_trip() {
+        assert_eq!(
+            "q".parse::<FormatSpec>(),
+            Err(FormatError::UnknownType('q'))
+        );
+        assert!(matches!(
+            ".f".parse::<FormatSpec>(),
+            Err(FormatError::InvalidSpecifier(_))
+        ));
+        assert!(matches!(
+            ",.2f!".parse::<FormatSpec>(),
+            Err(FormatError::InvalidSpecifier(_))
+        ));
+        for spec in ["$,.2f", "+.3e", "*^10,.1~s", "#x", "(08d"] {
+            assert_eq!(spec.parse::<FormatSpec>().unwrap().to_string(), spec);
+        }
+    }
+}
@@ -0,0 +1,342 @@
+/// Combining `DataTable`s: key joins and vertical concatenation
+///
+/// Joins match rows on one or more key columns by typed value: the number
+/// `1` never matches the text `"1"`, and nulls never match (as in SQL).
+/// Concatenation stacks tables with a unioned schema, filling columns
+/// a table lacks with `FieldValue::Null`.
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::core::column::Column;
+use crate::core::field_value::{DataTable, FieldValue};
+
+/// Suffix appended to right-hand columns whose names clash with the left side
+pub const RIGHT_SUFFIX: &str = "_right";
+
+/// Which unmatched rows a join keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JoinKind {
+    /// Only rows with a match on both sides
+    #[default]
+    Inner,
+    /// Every left row; right columns are null when unmatched
+    Left,
+    /// Every row from both sides
+    Outer,
+}
+
+/// Why two tables cannot be joined
+#[derive(Debug, Clone, PartialEq)]
+pub enum JoinError {
+    /// `left_on` and `right_on` name a different number of key columns
+    KeyCountMismatch {
+        /// Number of left key columns
+        left: usize,
+        /// Number of right key columns
+        right: usize,
+    },
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeyCountMismatch { left, right } => write!(
+                f,
+                "join needs as many right key columns as left ones ({left} vs {right})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+impl DataTable {
+    /// Join with `other` on the key columns `on`, present in both tables.
+    ///
+    /// Output columns: the keys, then the left non-key columns, then the
+    /// right non-key columns (suffixed with [`RIGHT_SUFFIX`] on name clash).
+    /// Rows keep left order; unmatched right rows of an outer join follow.
+    pub fn join(&self, other: &DataTable, on: &[&str], kind: JoinKind) -> DataTable {
+        self.join_keys(other, on, on, kind)
+    }
+
+    /// Join where the key columns have different names on each side.
+    ///
+    /// `left_on[i]` is matched against `right_on[i]`; output key columns use
+    /// the left names. Fails when `left_on` and `right_on` differ in length.
+    pub fn join_on(
+        &self,
+        other: &DataTable,
+        left_on: &[&str],
+        right_on: &[&str],
+        kind: JoinKind,
+    ) -> Result<DataTable, JoinError> {
+        if left_on.len() != right_on.len() {
+            return Err(JoinError::KeyCountMismatch {
+                left: left_on.len(),
+                right: right_on.len(),
+            });
+        }
+        Ok(self.join_keys(other, left_on, right_on, kind))
+    }
+
+    /// Join on key lists of equal length
+    fn join_keys(
+        &self,
+        other: &DataTable,
+        left_on: &[&str],
+        right_on: &[&str],
+        kind: JoinKind,
+    ) -> DataTable {
+        let mut right_index: HashMap<Vec<KeyCell>, Vec<usize>> = HashMap::new();
+        for r in 0..other.len() {
+            if let Some(key) = join_key(other, right_on, r) {
+                right_index.entry(key).or_default().push(r);
+            }
+        }
+
+        // Matched (left, right) row pairs; usize::MAX marks the missing side
+        let mut left_rows = Vec::new();
+        let mut right_rows = Vec::new();
+        let mut right_matched = vec![false; other.len()];
+        for l in 0..self.len() {
+            let matches = join_key(self, left_on, l).and_then(|k| right_index.get(&k));
+            match matches {
+                Some(rs) => {
+                    for &r in rs {
+                        left_rows.push(l);
+                        right_rows.push(r);
+                        right_matched[r] = true;
+                    }
+                }
+                None if kind != JoinKind::Inner => {
+                    left_rows.push(l);
+                    right_rows.push(usize::MAX);
+                }
+                None => {}
+            }
+        }
+        if kind == JoinKind::Outer {
+            for (r, _) in right_matched.iter().enumerate().filter(|(_, m)| !**m) {
+                left_rows.push(usize::MAX);
+                right_rows.push(r);
+            }
+        }
+
+        let mut columns = Vec::new();
+        for (lk, rk) in left_on.iter().zip(right_on) {
+            let values = left_rows.iter().zip(&right_rows).map(|(&l, &r)| {
+                if l == usize::MAX {
+                    other.value(r, rk)
+                } else {
+                    self.value(l, lk)
+                }
+            });
+            columns.push(Column::from_values(*lk, values));
+        }
+        let mut names: HashSet<String> = left_on.iter().map(|s| (*s).to_owned()).collect();
+        for col in self.columns() {
+            if !left_on.contains(&col.name()) {
+                names.insert(col.name().to_owned());
+                columns.push(col.take(&left_rows));
+            }
+        }
+        for col in other.columns() {
+            if right_on.contains(&col.name()) {
+                continue;
+            }
+            let mut name = col.name().to_owned();
+            while names.contains(&name) {
+                name.push_str(RIGHT_SUFFIX);
+            }
+            names.insert(name.clone());
+            columns.push(col.take(&right_rows).with_name(name));
+        }
+
+        DataTable::from_columns(columns)
+    }
+
+    /// Stack `tables` vertically.
+    ///
+    /// Columns are unioned in first-appearance order; a table lacking a
+    /// column contributes nulls for it.
+    pub fn concat(tables: &[DataTable]) -> DataTable {
+        let mut names: Vec<&str> = Vec::new();
+        for t in tables {
+            for name in t.column_names() {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        let columns = names
+            .iter()
+            .map(|name| {
+                let mut col = Column::new(*name);
+                for t in tables {
+                    match t.column(name) {
+                        Some(c) => col.append(c),
+                        None => col.append(&Column::nulls(*name, t.len())),
+                    }
+                }
+                col
+            })
+            .collect();
+        DataTable::from_columns(columns)
+    }
+
+    /// Append the rows of `other` (see [`DataTable::concat`])
+    pub fn append(&mut self, other: &DataTable) {
+        *self = DataTable::concat(&[std::mem::take(self), other.clone()]);
+    }
+}
+
+/// One typed key cell; numbers, timestamps, flags and text never match
+/// each other
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum KeyCell {
+    /// `f64` bits, with `-0.0` folded into `0.0`
+    Number(u64),
+    /// Timestamp bits, folded like numbers
+    Timestamp(u64),
+    Bool(bool),
+    Text(String),
+}
+
+/// Composite join key for row `row`, or `None` if any key cell is null (or NaN)
+fn join_key(table: &DataTable, keys: &[&str], row: usize) -> Option<Vec<KeyCell>> {
+    let bits = |x: f64| (!x.is_nan()).then(|| (x + 0.0).to_bits());
+    keys.iter()
+        .map(|k| match table.column(k)?.get(row) {
+            FieldValue::Numeric(x) => bits(x).map(KeyCell::Number),
+            FieldValue::Timestamp(t) => bits(t).map(KeyCell::Timestamp),
+            FieldValue::Bool(b) => Some(KeyCell::Bool(b)),
+            FieldValue::Text(s) => Some(KeyCell::Text(s)),
+            FieldValue::Null => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_row;
+
+    fn facts() -> DataTable {
+        DataTable::from_rows(vec![
+            data_row! { "product_id" => 1.0_f64, "region" => "EU", "sales" => 10.0_f64 },
+            data_row! { "product_id" => 2.0_f64, "region" => "US", "sales" => 20.0_f64 },
+            data_row! { "product_id" => 1.0_f64, "region" => "US", "sales" => 5.0_f64 },
+            data_row! { "product_id" => 9.0_f64, "region" => "EU", "sales" => 1.0_f64 },
+        ])
+    }
+
+    fn products() -> DataTable {
+        DataTable::from_rows(vec![
+            data_row! { "product_id" => 1.0_f64, "name" => "Widget" },
+            data_row! { "product_id" => 2.0_f64, "name" => "Gadget" },
+            data_row! { "product_id" => 3.0_f64, "name" => "Gizmo" },
+        ])
+    }
+
+    #[test]
+    fn test_inner_join() {
+        let t = facts().join(&products(), &["product_id"], JoinKind::Inner);
+        assert_eq!(t.len(), 3);
+        assert_eq!(t.extract_text("name"), vec!["Widget", "Gadget", "Widget"]);
+        assert_eq!(t.extract_numeric("sales"), vec![10.0, 20.0, 5.0]);
+        assert_eq!(t.column_names()[0], "product_id");
+    }
+
+    #[test]
+    fn test_left_join_keeps_unmatched() {
+        let t = facts().join(&products(), &["product_id"], JoinKind::Left);
+        assert_eq!(t.len(), 4);
+        assert_eq!(t.value(3, "name"), FieldValue::Null);
+        assert_eq!(t.value(3, "product_id"), FieldValue::Numeric(9.0));
+    }
+
+    #[test]
+    fn test_outer_join_appends_right_only_rows() {
+        let t = facts().join(&products(), &["product_id"], JoinKind::Outer);
+        assert_eq!(t.len(), 5);
+        assert_eq!(t.value(4, "product_id"), FieldValue::Numeric(3.0));
+        assert_eq!(t.value(4, "name"), FieldValue::Text("Gizmo".into()));
+        assert_eq!(t.value(4, "sales"), FieldValue::Null);
+    }
+
+    #[test]
+    fn test_multi_key_join_and_name_clash() {
+        let targets = DataTable::from_rows(vec![
+            data_row! { "id" => 1.0_f64, "reg" => "US", "sales" => 7.0_f64 },
+        ]);
+        let t = facts()
+            .join_on(
+                &targets,
+                &["product_id", "region"],
+                &["id", "reg"],
+                JoinKind::Inner,
+            )
+            .unwrap();
+        assert_eq!(t.len(), 1);
+        assert_eq!(t.value(0, "sales"), FieldValue::Numeric(5.0));
+        assert_eq!(t.value(0, "sales_right"), FieldValue::Numeric(7.0));
+    }
+
+    #[test]
+    fn test_mismatched_key_lists_are_an_error() {
+        let err = facts()
+            .join_on(
+                &products(),
+                &["product_id", "region"],
+                &["product_id"],
+                JoinKind::Inner,
+            )
+            .unwrap_err();
+        assert_eq!(err, JoinError::KeyCountMismatch { left: 2, right: 1 });
+    }
+
+    #[test]
+    fn test_keys_match_by_type() {
+        let left = DataTable::from_rows(vec![
+            data_row! { "k" => 1.0_f64, "a" => 1.0_f64 },
+            data_row! { "k" => -0.0_f64, "a" => 2.0_f64 },
+        ]);
+        let right = DataTable::from_rows(vec![
+            data_row! { "k" => "1", "b" => 10.0_f64 },
+            data_row! { "k" => 0.0_f64, "b" => 20.0_f64 },
+            data_row! { "k" => true, "b" => 30.0_f64 },
+        ]);
+        // Only -0.0 == 0.0 matches: "1" is text, true is a flag
+        let t = left.join(&right, &["k"], JoinKind::Inner);
+        assert_eq!(t.len(), 1);
+        assert_eq!(t.value(0, "a"), FieldValue::Numeric(2.0));
+        assert_eq!(t.value(0, "b"), FieldValue::Numeric(20.0));
+    }
+
+    #[test]
+    fn test_null_keys_never_match() {
+        let left =
+            DataTable::from_rows(vec![data_row! { "k" => FieldValue::Null, "a" => 1.0_f64 }]);
+        let right =
+            DataTable::from_rows(vec![data_row! { "k" => FieldValue::Null, "b" => 2.0_f64 }]);
+        assert_eq!(left.join(&right, &["k"], JoinKind::Inner).len(), 0);
+        assert_eq!(left.join(&right, &["k"], JoinKind::Outer).len(), 2);
+    }
+
+    #[test]
+    fn test_concat_reconciles_schema() {
+        let a = DataTable::from_rows(vec![data_row! { "x" => 1.0_f64, "y" => "a" }]);
+        let b = DataTable::from_rows(vec![data_row! { "x" => 2.0_f64, "z" => true }]);
+        let t = DataTable::concat(&[a.clone(), b]);
+        assert_eq!(t.len(), 2);
+        assert_eq!(t.column_names(), vec!["x", "y", "z"]);
+        assert_eq!(t.extract_numeric("x"), vec![1.0, 2.0]);
+        assert_eq!(t.value(1, "y"), FieldValue::Null);
+        assert_eq!(t.value(0, "z"), FieldValue::Null);
+
+        let mut c = a;
+        c.append(&t);
+        assert_eq!(c.len(), 3);
+    }
+}
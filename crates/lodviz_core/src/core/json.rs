@@ -0,0 +1,381 @@
+/// JSON loaders: text → DataTable
+///
+/// Three layouts are supported:
+/// - array of objects (`[{"x": 1, "y": 2}, ...]`) — one object per row
+/// - column-oriented object (`{"x": [1, 2], "y": [3, 4]}`)
+/// - newline-delimited JSON (one object per line)
+///
+/// Nested objects are flattened into dotted column names (`{"geo": {"lat": 1}}`
+/// → `geo.lat`); when that name is also a key of its own, the later one
+/// wins. JSON numbers, booleans and nulls keep their type; string
+/// columns go through the same date / boolean detection as CSV, and arrays
+/// are kept as their JSON text.
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+use crate::core::column::Column;
+use crate::core::csv::{self, CsvOptions, TypeHint};
+use crate::core::field_value::{DataTable, FieldValue};
+
+/// JSON loading and type inference options
+#[derive(Debug, Clone)]
+pub struct JsonOptions {
+    /// Joins nested object keys into column names
+    pub separator: String,
+    /// Detect string columns holding only booleans
+    pub infer_bools: bool,
+    /// Detect string columns holding only dates / date-times
+    pub infer_dates: bool,
+    /// Extra strftime patterns tried (after ISO-8601) when detecting dates
+    pub date_formats: Vec<String>,
+    /// Per-column conversion, by (flattened) column name
+    pub type_hints: HashMap<String, TypeHint>,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        Self {
+            separator: ".".to_owned(),
+            infer_bools: true,
+            infer_dates: true,
+            date_formats: Vec::new(),
+            type_hints: HashMap::new(),
+        }
+    }
+}
+
+impl JsonOptions {
+    /// Set the separator used to flatten nested keys
+    #[must_use]
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    /// Toggle boolean column detection
+    #[must_use]
+    pub fn with_infer_bools(mut self, on: bool) -> Self {
+        self.infer_bools = on;
+        self
+    }
+
+    /// Toggle date column detection
+    #[must_use]
+    pub fn with_infer_dates(mut self, on: bool) -> Self {
+        self.infer_dates = on;
+        self
+    }
+
+    /// Add a strftime pattern for date detection, e.g. `"%d/%m/%Y"`
+    #[must_use]
+    pub fn with_date_format(mut self, format: impl Into<String>) -> Self {
+        self.date_formats.push(format.into());
+        self
+    }
+
+    /// Set the conversion for one column
+    #[must_use]
+    pub fn with_type(mut self, column: impl Into<String>, hint: TypeHint) -> Self {
+        self.type_hints.insert(column.into(), hint);
+        self
+    }
+
+    /// The CSV options sharing this inference configuration
+    fn inference(&self) -> CsvOptions {
+        CsvOptions {
+            infer_bools: self.infer_bools,
+            infer_dates: self.infer_dates,
+            date_formats: self.date_formats.clone(),
+            ..CsvOptions::default()
+        }
+    }
+}
+
+// --- Entry points ---
+
+/// Parse a JSON array of objects or a column-oriented object into a [`DataTable`]
+pub fn parse_json(text: &str) -> Result<DataTable, String> {
+    parse_json_with(text, &JsonOptions::default())
+}
+
+/// Parse a JSON array of objects or a column-oriented object with options.
+///
+/// Returns `Err` on invalid JSON, on any other top-level layout, or when a
+/// cell cannot be converted to its column's explicit type hint.
+pub fn parse_json_with(text: &str, options: &JsonOptions) -> Result<DataTable, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let value: Value = serde_json::from_str(text).map_err(|e| format!("invalid JSON: {e}"))?;
+    from_json_value(value, options)
+}
+
+/// Build a [`DataTable`] from an already-parsed JSON value (same layouts as
+/// [`parse_json_with`])
+pub fn from_json_value(value: Value, options: &JsonOptions) -> Result<DataTable, String> {
+    let mut builder = Builder::default();
+    match value {
+        Value::Array(items) => {
+            for (i, item) in items.into_iter().enumerate() {
+                let Value::Object(obj) = item else {
+                    return Err(format!("element {i} is not an object"));
+                };
+                builder.push_row(obj, &options.separator);
+            }
+        }
+        Value::Object(obj) if obj.values().all(Value::is_array) => {
+            for (key, values) in obj {
+                let Value::Array(values) = values else {
+                    unreachable!("checked above");
+                };
+                builder.push_column(&key, values, &options.separator);
+            }
+        }
+        _ => {
+            return Err(
+                "expected an array of objects or an object whose values are arrays".to_owned(),
+            )
+        }
+    }
+    builder.finish(options)
+}
+
+/// Parse newline-delimited JSON (one object per line) into a [`DataTable`]
+pub fn parse_ndjson(text: &str) -> Result<DataTable, String> {
+    parse_ndjson_with(text, &JsonOptions::default())
+}
+
+/// Parse newline-delimited JSON with options.
+///
+/// Blank lines are skipped; errors carry the 1-based line number.
+pub fn parse_ndjson_with(text: &str, options: &JsonOptions) -> Result<DataTable, String> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    let mut builder = Builder::default();
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(Value::Object(obj)) => builder.push_row(obj, &options.separator),
+            Ok(_) => return Err(format!("line {}: expected a JSON object", i + 1)),
+            Err(e) => return Err(format!("line {}: invalid JSON: {e}", i + 1)),
+        }
+    }
+    builder.finish(options)
+}
+
+// --- Column assembly ---
+
+/// Accumulates flattened JSON values per column, in first-appearance order
+#[derive(Default)]
+struct Builder {
+    names: Vec<String>,
+    index: HashMap<String, usize>,
+    cells: Vec<Vec<Value>>,
+    rows: usize,
+}
+
+impl Builder {
+    fn column_mut(&mut self, name: String) -> &mut Vec<Value> {
+        let idx = *self.index.entry(name.clone()).or_insert_with(|| {
+            self.names.push(name);
+            self.cells.push(Vec::new());
+            self.cells.len() - 1
+        });
+        &mut self.cells[idx]
+    }
+
+    /// Set `name` at `row`; a flattened name seen twice in one row (`a.b`
+    /// next to `{"a": {"b": ..}}`) keeps the last value
+    fn set(&mut self, name: String, row: usize, value: Value) {
+        let col = self.column_mut(name);
+        if col.len() > row {
+            col[row] = value;
+        } else {
+            col.resize(row, Value::Null);
+            col.push(value);
+        }
+    }
+
+    fn push_row(&mut self, obj: Map<String, Value>, sep: &str) {
+        let mut flat = Vec::new();
+        flatten(String::new(), Value::Object(obj), sep, &mut flat);
+        for (name, value) in flat {
+            self.set(name, self.rows, value);
+        }
+        self.rows += 1;
+    }
+
+    /// Add one column of a column-oriented object; all columns start at row 0
+    fn push_column(&mut self, key: &str, values: Vec<Value>, sep: &str) {
+        let len = values.len();
+        for (row, value) in values.into_iter().enumerate() {
+            let mut flat = Vec::new();
+            flatten(key.to_owned(), value, sep, &mut flat);
+            for (name, v) in flat {
+                self.set(name, row, v);
+            }
+        }
+        self.rows = self.rows.max(len);
+    }
+
+    fn finish(self, options: &JsonOptions) -> Result<DataTable, String> {
+        let inference = options.inference();
+        let mut columns = Vec::with_capacity(self.names.len());
+        for (name, values) in self.names.into_iter().zip(self.cells) {
+            let hint = options.type_hints.get(&name).cloned().unwrap_or_default();
+            columns.push(convert_values(&name, &values, &hint, &inference)?);
+        }
+        Ok(DataTable::from_columns(columns))
+    }
+}
+
+/// Flatten nested objects into `(dotted name, leaf value)` pairs
+fn flatten(prefix: String, value: Value, sep: &str, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(obj) => {
+            for (key, v) in obj {
+                let name = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{prefix}{sep}{key}")
+                };
+                flatten(name, v, sep, out);
+            }
+        }
+        leaf => out.push((prefix, leaf)),
+    }
+}
+
+/// Text form of a JSON leaf, as a CSV cell would hold it
+fn cell_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+fn convert_values(
+    name: &str,
+    values: &[Value],
+    hint: &TypeHint,
+    inference: &CsvOptions,
+) -> Result<Column, String> {
+    if *hint != TypeHint::Auto {
+        let texts: Vec<Option<String>> = values.iter().map(cell_text).collect();
+        let cells: Vec<Option<&str>> = texts.iter().map(Option::as_deref).collect();
+        let (column, failures) = csv::convert_column(name, &cells, hint, inference);
+        return match failures.first() {
+            Some((row, message)) => Err(format!("row {row}: column '{name}': {message}")),
+            None => Ok(column),
+        };
+    }
+
+    let strings = values.iter().filter_map(Value::as_str);
+    let string_hint = csv::infer_hint(strings, inference);
+    let converted = values.iter().map(|v| match v {
+        Value::Null => FieldValue::Null,
+        Value::Bool(b) => FieldValue::Bool(*b),
+        Value::Number(n) => n.as_f64().map_or(FieldValue::Null, FieldValue::Numeric),
+        Value::String(s) if s.is_empty() => FieldValue::Null,
+        Value::String(s) => match &string_hint {
+            Some(h) => csv::convert(s, h, inference).unwrap_or(FieldValue::Null),
+            None => FieldValue::Text(s.clone()),
+        },
+        other => FieldValue::Text(other.to_string()),
+    });
+    Ok(Column::from_values(name, converted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::column::ColumnKind;
+
+    #[test]
+    fn test_array_of_objects_with_nested_fields() {
+        let json = r#"[
+            {"id": 1, "name": "a", "geo": {"lat": 45.5, "lon": 9.2}, "ok": true},
+            {"id": 2, "name": "b", "geo": {"lat": 41.9}, "tags": ["x", "y"]}
+        ]"#;
+        let t = parse_json(json).unwrap();
+        assert_eq!(
+            t.column_names(),
+            vec!["id", "name", "geo.lat", "geo.lon", "ok", "tags"]
+        );
+        assert_eq!(t.extract_numeric("geo.lat"), vec![45.5, 41.9]);
+        assert_eq!(t.value(1, "geo.lon"), FieldValue::Null);
+        assert_eq!(t.value(0, "ok"), FieldValue::Bool(true));
+        assert_eq!(t.value(1, "ok"), FieldValue::Null);
+        assert_eq!(t.value(1, "tags"), FieldValue::Text(r#"["x","y"]"#.into()));
+    }
+
+    #[test]
+    fn test_colliding_flattened_keys_keep_last() {
+        let t = parse_json(r#"[{"a.b": 1, "a": {"b": 2}}, {"a": {"b": 3}, "a.b": 4}]"#).unwrap();
+        assert_eq!(t.column_names(), vec!["a.b"]);
+        assert_eq!(t.extract_numeric("a.b"), vec![2.0, 4.0]);
+
+        let t = parse_json(r#"{"a.b": [1, 2], "a": [{"b": 3}]}"#).unwrap();
+        assert_eq!(t.extract_numeric("a.b"), vec![3.0, 2.0]);
+    }
+
+    #[test]
+    fn test_column_oriented_object() {
+        let json = r#"{"x": [1, 2, 3], "label": ["a", "b"]}"#;
+        let t = parse_json(json).unwrap();
+        assert_eq!(t.len(), 3);
+        assert_eq!(t.extract_numeric("x"), vec![1.0, 2.0, 3.0]);
+        assert_eq!(t.value(2, "label"), FieldValue::Null);
+    }
+
+    #[test]
+    fn test_ndjson_with_line_errors() {
+        let ndjson = "{\"a\": 1}\n\n{\"a\": 2, \"b\": \"x\"}\n";
+        let t = parse_ndjson(ndjson).unwrap();
+        assert_eq!(t.extract_numeric("a"), vec![1.0, 2.0]);
+        assert_eq!(t.value(0, "b"), FieldValue::Null);
+
+        let err = parse_ndjson("{\"a\": 1}\n{oops}\n").unwrap_err();
+        assert!(err.starts_with("line 2:"));
+    }
+
+    #[test]
+    fn test_string_inference_matches_csv() {
+        let json = r#"[
+            {"when": "2024-03-01T12:00:00Z", "flag": "yes", "zip": "01234"},
+            {"when": "2024-03-02", "flag": "no", "zip": "99999"}
+        ]"#;
+        let t = parse_json(json).unwrap();
+        assert_eq!(t.column("when").unwrap().kind(), ColumnKind::Timestamp);
+        assert_eq!(t.column("flag").unwrap().kind(), ColumnKind::Bool);
+        // JSON strings are not re-parsed as numbers
+        assert_eq!(t.extract_text("zip"), vec!["01234", "99999"]);
+    }
+
+    #[test]
+    fn test_type_hints_and_separator() {
+        let json = r#"[{"meta": {"ts": 1709251200}}]"#;
+        let opts = JsonOptions::default()
+            .with_separator("_")
+            .with_type("meta_ts", TypeHint::EpochSeconds);
+        let t = parse_json_with(json, &opts).unwrap();
+        assert_eq!(
+            t.value(0, "meta_ts"),
+            FieldValue::Timestamp(1_709_251_200_000.0)
+        );
+
+        let bad = JsonOptions::default().with_type("v", TypeHint::Numeric);
+        let err = parse_json_with(r#"[{"v": 1}, {"v": "x"}]"#, &bad).unwrap_err();
+        assert!(err.contains("row 1"));
+    }
+
+    #[test]
+    fn test_rejects_other_layouts() {
+        assert!(parse_json("42").is_err());
+        assert!(parse_json(r#"{"a": 1}"#).is_err());
+        assert!(parse_json("[1, 2]").unwrap_err().contains("element 0"));
+        assert!(parse_json("[").unwrap_err().starts_with("invalid JSON"));
+    }
+}
@@ -0,0 +1,624 @@
+/// Layered chart specs: several marks over shared x/y scales
+///
+/// A [`LayerSpec`] stacks [`Layer`]s — e.g. a line, its points, a rule at a
+/// target value and a text label — that are drawn against one pair of
+/// scales and axes. Layers without their own data use the spec's shared
+/// data (after the shared transforms).
+///
+/// `LayerSpec::resolve()` validates every layer and returns the geometry of
+/// each one together with the combined x/y domains.
+use super::data::{DataType, Dataset};
+use super::encoding::{Encoding, Field};
+use super::field_value::DataTable;
+use super::mark::Mark;
+use super::spec::{ChartData, ChartSpec};
+use super::table_data::format_cell_value_with;
+use super::theme::ChartConfig;
+use super::transform::Transform;
+use super::validate::{check_field, SpecError};
+
+// --- Spec ---
+
+/// One mark of a layered chart
+#[derive(Debug, Clone)]
+pub struct Layer {
+    /// The visual mark
+    pub mark: Mark,
+    /// Own data source (`None` = the spec's shared data)
+    pub data: Option<ChartData>,
+    /// X channel
+    pub x: Option<Field>,
+    /// Y channel
+    pub y: Option<Field>,
+    /// Color channel (splits series marks into series)
+    pub color: Option<Field>,
+    /// Label text (`Text` marks)
+    pub text: Option<Field>,
+    /// Transforms applied after the shared ones
+    pub transforms: Vec<Transform>,
+}
+
+impl Layer {
+    /// A layer drawing `mark` from the shared data
+    pub fn new(mark: Mark) -> Self {
+        Self {
+            mark,
+            data: None,
+            x: None,
+            y: None,
+            color: None,
+            text: None,
+            transforms: Vec::new(),
+        }
+    }
+
+    /// Use this layer's own data instead of the shared data
+    pub fn with_data(mut self, data: ChartData) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Use this layer's own table instead of the shared data
+    pub fn with_table(self, table: DataTable) -> Self {
+        self.with_data(ChartData::Table(table))
+    }
+
+    /// Set the x channel
+    pub fn with_x(mut self, x: Field) -> Self {
+        self.x = Some(x);
+        self
+    }
+
+    /// Set the y channel
+    pub fn with_y(mut self, y: Field) -> Self {
+        self.y = Some(y);
+        self
+    }
+
+    /// Set the color channel
+    pub fn with_color(mut self, color: Field) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the label text channel
+    pub fn with_text(mut self, text: Field) -> Self {
+        self.text = Some(text);
+        self
+    }
+
+    /// Append a layer-specific transform
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transforms.push(transform);
+        self
+    }
+}
+
+/// Several layers sharing x/y scales, axes and config
+#[derive(Debug, Clone, Default)]
+pub struct LayerSpec {
+    /// Data shared by layers without their own
+    pub data: Option<ChartData>,
+    /// Transforms applied to shared table data
+    pub transforms: Vec<Transform>,
+    /// Layers, drawn in order (first at the bottom)
+    pub layers: Vec<Layer>,
+    /// Layout and styling configuration
+    pub config: ChartConfig,
+}
+
+impl LayerSpec {
+    /// An empty spec
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the shared data
+    pub fn with_data(mut self, data: ChartData) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    /// Set the shared data to a table
+    pub fn with_table(self, table: DataTable) -> Self {
+        self.with_data(ChartData::Table(table))
+    }
+
+    /// Append a shared transform
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transforms.push(transform);
+        self
+    }
+
+    /// Add a layer on top of the existing ones
+    pub fn with_layer(mut self, layer: Layer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Set the config
+    pub fn with_config(mut self, config: ChartConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Validate every layer and compute its geometry and the shared domains.
+    ///
+    /// Layer errors are reported as [`SpecError::Layer`].
+    pub fn resolve(&self) -> Result<ResolvedLayers, SpecError> {
+        let layers = self
+            .layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                self.resolve_layer(layer).map_err(|e| SpecError::Layer {
+                    index,
+                    error: Box::new(e),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut xs = Extent::default();
+        let mut ys = Extent::default();
+        for layer in &layers {
+            match &layer.geometry {
+                LayerGeometry::Series(ds) => {
+                    for p in ds.series.iter().flat_map(|s| &s.data) {
+                        xs.add(p.x);
+                        ys.add(p.y);
+                    }
+                    if layer.mark == Mark::Area {
+                        ys.add(0.0);
+                    }
+                }
+                LayerGeometry::Rules { axis, values } => {
+                    let ext = match axis {
+                        RuleAxis::X => &mut xs,
+                        RuleAxis::Y => &mut ys,
+                    };
+                    values.iter().for_each(|&v| ext.add(v));
+                }
+                LayerGeometry::Labels(labels) => {
+                    for l in labels {
+                        xs.add(l.x);
+                        ys.add(l.y);
+                    }
+                }
+            }
+        }
+        if layers.iter().all(|l| l.geometry.is_empty()) {
+            return Err(SpecError::EmptyData {
+                after_transforms: false,
+            });
+        }
+        Ok(ResolvedLayers {
+            layers,
+            x_domain: xs.domain(),
+            y_domain: ys.domain(),
+        })
+    }
+
+    fn resolve_layer(&self, layer: &Layer) -> Result<ResolvedLayer, SpecError> {
+        let (data, mut transforms) = match &layer.data {
+            Some(data) => (data, Vec::new()),
+            None => (
+                self.data.as_ref().ok_or(SpecError::EmptyData {
+                    after_transforms: false,
+                })?,
+                self.transforms.clone(),
+            ),
+        };
+        transforms.extend(layer.transforms.iter().cloned());
+        let missing = |channel| SpecError::MissingChannel {
+            mark: layer.mark,
+            channel,
+        };
+
+        let geometry = match layer.mark {
+            Mark::Line | Mark::Area | Mark::Point | Mark::Circle => {
+                let spec = ChartSpec {
+                    data: data.clone(),
+                    mark: layer.mark,
+                    x: layer.x.clone().ok_or_else(|| missing("x"))?,
+                    y: Some(layer.y.clone().ok_or_else(|| missing("y"))?),
+                    color: layer.color.clone(),
+                    size: None,
+                    opacity: None,
+                    shape: None,
+                    stroke_dash: None,
+                    text: None,
+                    tooltip: Vec::new(),
+                    detail: None,
+                    order: None,
+                    transforms,
+                    config: ChartConfig::default(),
+                };
+                match spec.resolve() {
+                    Ok(ChartData::TimeSeries(ds)) => LayerGeometry::Series(ds),
+                    Ok(_) | Err(SpecError::EmptyData { .. }) => {
+                        LayerGeometry::Series(Dataset::new())
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            Mark::Rule | Mark::Text => {
+                let ChartData::Table(source) = data else {
+                    return Err(SpecError::IncompatibleData {
+                        mark: layer.mark,
+                        data: "Dataset (rules and labels read a table)",
+                    });
+                };
+                let table = source.transform(&transforms);
+                let channels = [
+                    ("x", layer.x.as_ref()),
+                    ("y", layer.y.as_ref()),
+                    ("text", layer.text.as_ref()),
+                ];
+                for (channel, field) in channels {
+                    if let Some(field) = field {
+                        check_field(&table, channel, field)?;
+                    }
+                }
+                let (table, layer) = derive(layer, table);
+                if layer.mark == Mark::Rule {
+                    rules(&layer, &table)?
+                } else {
+                    labels(&layer, &table)?
+                }
+            }
+            Mark::Bar => {
+                return Err(SpecError::UnsupportedMark {
+                    mark: layer.mark,
+                    reason: "cannot be layered (their x axis is categorical)".to_owned(),
+                })
+            }
+            Mark::Rect
+            | Mark::Arc
+            | Mark::Boxplot
+            | Mark::Tick
+            | Mark::Candlestick
+            | Mark::Flow => {
+                return Err(SpecError::UnsupportedMark {
+                    mark: layer.mark,
+                    reason: "cannot be layered (draw them on their own with a ChartSpec)"
+                        .to_owned(),
+                })
+            }
+        };
+        Ok(ResolvedLayer {
+            mark: layer.mark,
+            geometry,
+        })
+    }
+}
+
+/// Apply the rule / label fields' time units, bins and aggregates the way
+/// `ChartSpec::resolve` does, returning the derived table and fields
+fn derive(layer: &Layer, table: DataTable) -> (DataTable, Layer) {
+    let encoding = match (layer.mark, &layer.x, &layer.y, &layer.text) {
+        // A rule has one channel: encode it on both x and y so it is derived
+        // once (identical aggregates share an output column)
+        (Mark::Rule, Some(f), None, _) | (Mark::Rule, None, Some(f), _) => {
+            Encoding::new(f.clone(), f.clone())
+        }
+        (Mark::Text, Some(x), Some(y), Some(text)) => {
+            Encoding::new(x.clone(), y.clone()).with_text(text.clone())
+        }
+        _ => return (table, layer.clone()),
+    };
+    let Some((table, enc)) = encoding.derive(&table, false) else {
+        return (table, layer.clone());
+    };
+    let mut layer = layer.clone();
+    if layer.mark == Mark::Rule {
+        let field = if layer.x.is_some() {
+            &mut layer.x
+        } else {
+            &mut layer.y
+        };
+        *field = Some(enc.x);
+    } else {
+        layer.x = Some(enc.x);
+        layer.y = Some(enc.y);
+        layer.text = enc.text;
+    }
+    (table, layer)
+}
+
+fn rules(layer: &Layer, table: &DataTable) -> Result<LayerGeometry, SpecError> {
+    let (axis, field) = match (&layer.x, &layer.y) {
+        (Some(x), None) => (RuleAxis::X, x),
+        (None, Some(y)) => (RuleAxis::Y, y),
+        (None, None) => {
+            return Err(SpecError::MissingChannel {
+                mark: Mark::Rule,
+                channel: "y",
+            })
+        }
+        (Some(_), Some(_)) => {
+            return Err(SpecError::IncompatibleEncoding {
+                mark: Mark::Rule,
+                channel: "x",
+                reason: "take either x (vertical rules) or y (horizontal rules), not both"
+                    .to_owned(),
+            })
+        }
+    };
+    let values = table
+        .column(&field.name)
+        .map(|c| (0..c.len()).filter_map(|i| c.get_f64(i)).collect())
+        .unwrap_or_default();
+    Ok(LayerGeometry::Rules { axis, values })
+}
+
+fn labels(layer: &Layer, table: &DataTable) -> Result<LayerGeometry, SpecError> {
+    let channel = |field: &Option<Field>, name| {
+        field.clone().ok_or(SpecError::MissingChannel {
+            mark: Mark::Text,
+            channel: name,
+        })
+    };
+    let (x, y, text) = (
+        channel(&layer.x, "x")?,
+        channel(&layer.y, "y")?,
+        channel(&layer.text, "text")?,
+    );
+    for (name, field) in [("x", &x), ("y", &y)] {
+        if !matches!(field.data_type, DataType::Quantitative | DataType::Temporal) {
+            return Err(SpecError::IncompatibleEncoding {
+                mark: Mark::Text,
+                channel: name,
+                reason: format!("need a quantitative or temporal field on {name}"),
+            });
+        }
+    }
+    let (Some(xc), Some(yc)) = (table.column(&x.name), table.column(&y.name)) else {
+        return Ok(LayerGeometry::Labels(Vec::new()));
+    };
+    let format = text.number_format();
+    let labels = (0..table.len())
+        .filter_map(|i| {
+            let value = table.value(i, &text.name);
+            if value.is_null() {
+                return None;
+            }
+            Some(TextLabel {
+                x: xc.get_f64(i)?,
+                y: yc.get_f64(i)?,
+                text: format_cell_value_with(&value, format.as_ref()),
+            })
+        })
+        .collect();
+    Ok(LayerGeometry::Labels(labels))
+}
+
+// --- Resolved geometry ---
+
+/// Direction of a rule layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleAxis {
+    /// Vertical rules at x values
+    X,
+    /// Horizontal rules at y values
+    Y,
+}
+
+/// A positioned text label
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLabel {
+    /// X position (data units)
+    pub x: f64,
+    /// Y position (data units)
+    pub y: f64,
+    /// Label text
+    pub text: String,
+}
+
+/// What a resolved layer draws, in data units
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayerGeometry {
+    /// Line / area / point series
+    Series(Dataset),
+    /// Reference lines spanning the plot
+    Rules {
+        /// Axis the values lie on
+        axis: RuleAxis,
+        /// Rule positions
+        values: Vec<f64>,
+    },
+    /// Text labels
+    Labels(Vec<TextLabel>),
+}
+
+impl LayerGeometry {
+    /// True when the layer draws nothing
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Series(ds) => ds.series.iter().all(|s| s.data.is_empty()),
+            Self::Rules { values, .. } => values.is_empty(),
+            Self::Labels(labels) => labels.is_empty(),
+        }
+    }
+}
+
+/// A layer ready to draw
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedLayer {
+    /// The layer's mark
+    pub mark: Mark,
+    /// Its geometry
+    pub geometry: LayerGeometry,
+}
+
+/// All layers of a spec with the domains their shared scales span
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedLayers {
+    /// Layers in drawing order
+    pub layers: Vec<ResolvedLayer>,
+    /// Combined x extent (`(0, 1)` when nothing sets it)
+    pub x_domain: (f64, f64),
+    /// Combined y extent (`(0, 1)` when nothing sets it)
+    pub y_domain: (f64, f64),
+}
+
+/// Running min/max of finite values
+#[derive(Default)]
+struct Extent(Option<(f64, f64)>);
+
+impl Extent {
+    fn add(&mut self, v: f64) {
+        if v.is_finite() {
+            self.0 = Some(self.0.map_or((v, v), |(lo, hi)| (lo.min(v), hi.max(v))));
+        }
+    }
+
+    /// The extent, widened around a single value
+    fn domain(&self) -> (f64, f64) {
+        match self.0 {
+            None => (0.0, 1.0),
+            Some((lo, hi)) if lo == hi => (lo - 0.5, hi + 0.5),
+            Some(d) => d,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::field_value::FieldValue;
+    use crate::core::transform::AggregateOp;
+    use crate::data_row;
+
+    fn sales() -> DataTable {
+        DataTable::from_rows(vec![
+            data_row! { "day" => 1.0_f64, "amount" => 80.0_f64 },
+            data_row! { "day" => 2.0_f64, "amount" => 95.0_f64 },
+            data_row! { "day" => 3.0_f64, "amount" => 120.0_f64 },
+        ])
+    }
+
+    fn target() -> DataTable {
+        DataTable::from_rows(vec![
+            data_row! { "day" => 3.0_f64, "goal" => 100.0_f64, "label" => "Target" },
+        ])
+    }
+
+    fn chart() -> LayerSpec {
+        let x = Field::quantitative("day");
+        let y = Field::quantitative("amount");
+        LayerSpec::new()
+            .with_table(sales())
+            .with_layer(Layer::new(Mark::Line).with_x(x.clone()).with_y(y.clone()))
+            .with_layer(Layer::new(Mark::Point).with_x(x).with_y(y))
+            .with_layer(
+                Layer::new(Mark::Rule)
+                    .with_table(target())
+                    .with_y(Field::quantitative("goal")),
+            )
+            .with_layer(
+                Layer::new(Mark::Text)
+                    .with_table(target())
+                    .with_x(Field::quantitative("day"))
+                    .with_y(Field::quantitative("goal"))
+                    .with_text(Field::nominal("label")),
+            )
+    }
+
+    #[test]
+    fn test_layers_share_domains() {
+        let resolved = chart().resolve().unwrap();
+        assert_eq!(resolved.layers.len(), 4);
+        assert_eq!(resolved.x_domain, (1.0, 3.0));
+        assert_eq!(resolved.y_domain, (80.0, 120.0));
+
+        let LayerGeometry::Rules { axis, values } = &resolved.layers[2].geometry else {
+            panic!("expected rules");
+        };
+        assert_eq!((*axis, values.as_slice()), (RuleAxis::Y, &[100.0][..]));
+        let LayerGeometry::Labels(labels) = &resolved.layers[3].geometry else {
+            panic!("expected labels");
+        };
+        assert_eq!(labels[0].text, "Target");
+    }
+
+    #[test]
+    fn test_rule_extends_domain() {
+        let spec = chart().with_layer(
+            Layer::new(Mark::Rule)
+                .with_table(DataTable::from_rows(vec![data_row! { "max" => 150.0_f64 }]))
+                .with_y(Field::quantitative("max")),
+        );
+        assert_eq!(spec.resolve().unwrap().y_domain, (80.0, 150.0));
+    }
+
+    #[test]
+    fn test_layer_errors_are_indexed() {
+        let spec = chart().with_layer(Layer::new(Mark::Line).with_x(Field::quantitative("day")));
+        let err = spec.resolve().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "layer[4]: Line charts need an encoding for 'y'"
+        );
+
+        let both = LayerSpec::new().with_table(target()).with_layer(
+            Layer::new(Mark::Rule)
+                .with_x(Field::quantitative("day"))
+                .with_y(Field::quantitative("goal")),
+        );
+        assert!(matches!(
+            both.resolve(),
+            Err(SpecError::Layer { index: 0, .. })
+        ));
+
+        let typo = LayerSpec::new()
+            .with_table(target())
+            .with_layer(Layer::new(Mark::Rule).with_y(Field::quantitative("gaol")));
+        let Err(SpecError::Layer { error, .. }) = typo.resolve() else {
+            panic!("expected a layer error");
+        };
+        assert!(matches!(*error, SpecError::UnknownField { .. }));
+    }
+
+    #[test]
+    fn test_rules_and_labels_apply_aggregates() {
+        let data = DataTable::from_rows(vec![
+            data_row! { "day" => 1.0_f64, "y" => 2.0_f64 },
+            data_row! { "day" => 1.0_f64, "y" => 4.0_f64 },
+            data_row! { "day" => 2.0_f64, "y" => 9.0_f64 },
+        ]);
+        let mean = Field::quantitative("y").with_aggregate(AggregateOp::Mean);
+        let spec = LayerSpec::new()
+            .with_table(data)
+            .with_layer(Layer::new(Mark::Rule).with_y(mean.clone()))
+            .with_layer(
+                Layer::new(Mark::Text)
+                    .with_x(Field::quantitative("day"))
+                    .with_y(mean.clone())
+                    .with_text(mean),
+            );
+        let resolved = spec.resolve().unwrap();
+        let LayerGeometry::Rules { values, .. } = &resolved.layers[0].geometry else {
+            panic!("expected rules");
+        };
+        assert_eq!(values, &[5.0]);
+        let LayerGeometry::Labels(labels) = &resolved.layers[1].geometry else {
+            panic!("expected labels");
+        };
+        let points: Vec<(f64, f64)> = labels.iter().map(|l| (l.x, l.y)).collect();
+        assert_eq!(points, [(1.0, 3.0), (2.0, 9.0)]);
+        assert_eq!(labels[0].text, "3");
+    }
+
+    #[test]
+    fn test_empty_layers() {
+        assert!(matches!(
+            LayerSpec::new().resolve(),
+            Err(SpecError::EmptyData { .. })
+        ));
+        let nulls = DataTable::from_rows(vec![data_row! { "v" => FieldValue::Null }]);
+        let spec = LayerSpec::new()
+            .with_table(nulls)
+            .with_layer(Layer::new(Mark::Rule).with_y(Field::quantitative("v")));
+        assert!(matches!(spec.resolve(), Err(SpecError::EmptyData { .. })));
+    }
+}
@@ -1,8 +1,15 @@
 /// Mark types for Grammar of Graphics
 ///
 /// Marks define how data is visually represented (points, lines, areas, etc.)
-/// Visual mark types for data representation
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+/// Visual mark types for data representation.
+///
+/// Non-exhaustive so marks can be added: matches outside `lodviz_core`
+/// need a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum Mark {
     /// Line connecting data points
     Line,
@@ -14,6 +21,22 @@ pub enum Mark {
     Point,
     /// Circles with variable size
     Circle,
+    /// Reference lines across the plot at x or y values
+    Rule,
+    /// Text labels at x/y positions
+    Text,
+    /// Grid cells colored by value (heatmap)
+    Rect,
+    /// Angular slices of a whole (pie / donut)
+    Arc,
+    /// Quartile box and whiskers per category
+    Boxplot,
+    /// One short tick per value along a category (strip plot)
+    Tick,
+    /// Open-high-low-close candles per period
+    Candlestick,
+    /// Weighted links between nodes (Sankey, or chord for cyclic flows)
+    Flow,
 }
 
 #[cfg(test)]
@@ -4,25 +4,54 @@
 /// that form the foundation of the lodviz-rs library.
 /// Accessibility primitives and structures
 pub mod a11y;
+/// Arrow IPC / Parquet ingestion
+#[cfg(feature = "arrow")]
+pub mod arrow;
 /// Color mapping utilities for continuous data (Oklab interpolation, sequential/diverging maps)
 pub mod color_map;
+/// Columnar storage backing `DataTable` (typed vectors + validity bitmap)
+pub mod column;
 /// CSV parsing utilities
 pub mod csv;
 /// Fundamental data abstractions
 pub mod data;
+/// Calendar arithmetic on Unix millisecond timestamps
+pub mod datetime;
 /// Visual encoding specifications
 pub mod encoding;
+/// Expression language for calculated fields and filters
+pub mod expr;
+/// Faceting: small multiples split by nominal fields
+pub mod facet;
 /// Typeless field value storage
 pub mod field_value;
+/// d3-format style number formatting with locales
+pub mod format;
+/// Joins and concatenation of `DataTable`s
+pub mod join;
+/// JSON / NDJSON loaders
+pub mod json;
+/// Layered chart specs: several marks over shared scales
+pub mod layer;
 /// Rendering primitives representations
 pub mod mark;
+/// "Show me" chart recommendations from column types and cardinalities
+pub mod recommend;
 /// Data to screen mapping scales
 pub mod scale;
 /// Interactive selection definitions
 pub mod selection;
 /// Vega-lite inspired Chart specifications
 pub mod spec;
+/// Serializable (JSON) chart spec documents and loader
+pub mod spec_doc;
 /// Data model and pure logic for the visual DataTable component
 pub mod table_data;
 /// Chart theming and styling configuration
 pub mod theme;
+/// Declarative data transforms (filter, aggregate, fold, pivot, window, bin)
+pub mod transform;
+/// ChartSpec validation and resolution with typed errors
+pub mod validate;
+/// Vega-Lite subset importer
+pub mod vega_lite;
@@ -0,0 +1,474 @@
+/// "Show me": ranked chart recommendations for a `DataTable`
+///
+/// [`recommend`] profiles the chosen columns (measurement type and number
+/// of distinct values) and proposes ready-to-resolve [`ChartSpec`]s, best
+/// first:
+///
+/// | Columns | Charts |
+/// |---------|--------|
+/// | temporal + quantitative | line, area (colored by a nominal field if any) |
+/// | nominal + quantitative | bar (sum), box plot, pie |
+/// | quantitative + quantitative | scatter (colored / sized by further fields) |
+/// | nominal + nominal (+ quantitative) | heatmap of the mean (or count) |
+/// | one quantitative | histogram |
+/// | one nominal | bar of counts, pie |
+/// | one temporal | bar of counts per time unit |
+use std::collections::HashSet;
+
+use super::column::{ColumnKind, GroupKey};
+use super::data::DataType;
+use super::datetime::TimeUnit;
+use super::encoding::Field;
+use super::field_value::DataTable;
+use super::mark::Mark;
+use super::spec::ChartSpec;
+use super::transform::AggregateOp;
+use crate::algorithms::statistics::BinRule;
+
+/// Most categories a nominal field may have to be used as a color
+const MAX_COLOR_CATEGORIES: usize = 10;
+/// Most categories a pie chart may have
+const MAX_PIE_SLICES: usize = 6;
+/// Categories beyond which a bar chart becomes hard to read
+const MAX_BAR_CATEGORIES: usize = 50;
+
+/// Measurement type and cardinality of one column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnProfile {
+    /// Column name
+    pub name: String,
+    /// Inferred measurement type
+    pub data_type: DataType,
+    /// Number of distinct non-null values
+    pub cardinality: usize,
+    /// Smallest and largest value (numeric and temporal columns)
+    pub extent: Option<(f64, f64)>,
+}
+
+impl ColumnProfile {
+    /// Profile column `name`; `None` when it is missing or entirely null
+    pub fn of(table: &DataTable, name: &str) -> Option<Self> {
+        let column = table.column(name)?;
+        let data_type = match column.kind() {
+            ColumnKind::Null => return None,
+            ColumnKind::Numeric => DataType::Quantitative,
+            ColumnKind::Timestamp => DataType::Temporal,
+            ColumnKind::Text | ColumnKind::Bool | ColumnKind::Mixed => DataType::Nominal,
+        };
+        let distinct: HashSet<GroupKey> = (0..column.len())
+            .filter(|&i| column.is_valid(i))
+            .map(|i| column.group_key(i))
+            .collect();
+        let extent = matches!(data_type, DataType::Quantitative | DataType::Temporal)
+            .then(|| {
+                (0..column.len()).filter_map(|i| column.get_f64(i)).fold(
+                    None,
+                    |acc: Option<(f64, f64)>, v| {
+                        Some(acc.map_or((v, v), |(lo, hi)| (lo.min(v), hi.max(v))))
+                    },
+                )
+            })
+            .flatten();
+        Some(Self {
+            name: name.to_owned(),
+            data_type,
+            cardinality: distinct.len(),
+            extent,
+        })
+    }
+
+    fn field(&self) -> Field {
+        Field::new(self.name.clone(), self.data_type)
+    }
+}
+
+/// One suggested chart
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    /// Spec over the whole table, ready to resolve or render
+    pub spec: ChartSpec,
+    /// Fitness in `(0, 1]`; higher is better
+    pub score: f64,
+    /// Short human-readable rationale
+    pub reason: String,
+}
+
+/// Ranked chart candidates for `columns` of `table` (all columns when empty).
+///
+/// Columns that are missing or entirely null are ignored. Among several
+/// candidates for a role the first listed column wins, except that nominal
+/// fields used as categories or colors prefer the fewest distinct values.
+pub fn recommend(table: &DataTable, columns: &[&str]) -> Vec<Recommendation> {
+    let names = if columns.is_empty() {
+        table.column_names()
+    } else {
+        columns.to_vec()
+    };
+    let profiles: Vec<ColumnProfile> = names
+        .iter()
+        .filter_map(|name| ColumnProfile::of(table, name))
+        .collect();
+    let of_type = |t: DataType| -> Vec<&ColumnProfile> {
+        profiles.iter().filter(|p| p.data_type == t).collect()
+    };
+    let (temporal, quantitative) = (of_type(DataType::Temporal), of_type(DataType::Quantitative));
+    let nominal = of_type(DataType::Nominal);
+
+    let mut out = Recommender {
+        table,
+        recommendations: Vec::new(),
+    };
+    // Fewest categories first: the best color / grouping candidate
+    let mut by_cardinality = nominal.clone();
+    by_cardinality.sort_by_key(|p| p.cardinality);
+    let color = by_cardinality
+        .first()
+        .copied()
+        .filter(|p| p.cardinality <= MAX_COLOR_CATEGORIES);
+
+    match (temporal.first(), quantitative.as_slice(), nominal.first()) {
+        (Some(t), [q, ..], _) => out.trend(t, q, color),
+        (None, [q1, q2, rest @ ..], _) => out.scatter(q1, q2, rest.first().copied(), color),
+        (None, [q], Some(_)) => {
+            let category = by_cardinality[0];
+            out.by_category(category, q);
+            if let Some(&other) = by_cardinality.get(1) {
+                out.heatmap(category, other, Some(q));
+            }
+            out.histogram(q, 0.5);
+        }
+        (None, [q], None) => out.histogram(q, 1.0),
+        (Some(t), [], _) => out.count_over_time(t),
+        (None, [], Some(_)) => {
+            if let [a, b, ..] = by_cardinality.as_slice() {
+                out.heatmap(a, b, None);
+            }
+            out.counts(by_cardinality[0]);
+        }
+        (None, [], None) => {}
+    }
+
+    let mut recommendations = out.recommendations;
+    recommendations.sort_by(|a, b| b.score.total_cmp(&a.score));
+    recommendations
+}
+
+/// Accumulates candidates for one table
+struct Recommender<'a> {
+    table: &'a DataTable,
+    recommendations: Vec<Recommendation>,
+}
+
+impl Recommender<'_> {
+    fn push(&mut self, spec: ChartSpec, score: f64, reason: impl Into<String>) {
+        self.recommendations.push(Recommendation {
+            spec,
+            score,
+            reason: reason.into(),
+        });
+    }
+
+    fn spec(
+        &self,
+        mark: Mark,
+        x: Field,
+        y: Field,
+        color: Option<Field>,
+        title: String,
+    ) -> ChartSpec {
+        let mut builder = ChartSpec::builder()
+            .from_table(self.table.clone())
+            .mark(mark)
+            .x(x)
+            .y(y)
+            .title(title);
+        if let Some(color) = color {
+            builder = builder.color(color);
+        }
+        builder.build()
+    }
+
+    /// Temporal x, quantitative y: line first, area second
+    fn trend(&mut self, t: &ColumnProfile, q: &ColumnProfile, color: Option<&ColumnProfile>) {
+        let title = format!("{} over {}", q.name, t.name);
+        let color = color.map(ColumnProfile::field);
+        let line = self.spec(
+            Mark::Line,
+            t.field(),
+            q.field(),
+            color.clone(),
+            title.clone(),
+        );
+        self.push(line, 1.0, "trend of a measure over time");
+        let area = self.spec(Mark::Area, t.field(), q.field(), color, title);
+        self.push(area, 0.7, "trend with emphasized volume");
+    }
+
+    /// Two quantitative fields: scatter, sized by a third when present
+    fn scatter(
+        &mut self,
+        x: &ColumnProfile,
+        y: &ColumnProfile,
+        size: Option<&ColumnProfile>,
+        color: Option<&ColumnProfile>,
+    ) {
+        let title = format!("{} vs {}", y.name, x.name);
+        let mut spec = self.spec(
+            Mark::Point,
+            x.field(),
+            y.field(),
+            color.map(ColumnProfile::field),
+            title,
+        );
+        let reason = match size {
+            Some(size) => {
+                spec.size = Some(size.field());
+                "relationship between measures, sized by a third"
+            }
+            None => "relationship between two measures",
+        };
+        self.push(spec, 1.0, reason);
+    }
+
+    /// Nominal x, quantitative y: bar of sums, box plot, pie
+    fn by_category(&mut self, category: &ColumnProfile, q: &ColumnProfile) {
+        let title = format!("{} by {}", q.name, category.name);
+        let readable = if category.cardinality > MAX_BAR_CATEGORIES {
+            0.5
+        } else {
+            1.0
+        };
+        let sum = q.field().with_aggregate(AggregateOp::Sum);
+        let bar = self.spec(
+            Mark::Bar,
+            category.field(),
+            sum.clone(),
+            None,
+            title.clone(),
+        );
+        self.push(bar, 0.95 * readable, "compare a measure across categories");
+
+        // Distributions need several values per category
+        if self.table.len() >= 5 * category.cardinality {
+            let boxplot = self.spec(
+                Mark::Boxplot,
+                category.field(),
+                q.field(),
+                None,
+                title.clone(),
+            );
+            self.push(boxplot, 0.75 * readable, "distribution per category");
+        }
+        if category.cardinality <= MAX_PIE_SLICES {
+            let pie = self.spec(Mark::Arc, category.field(), sum, None, title);
+            self.push(pie, 0.5, "share of the total per category");
+        }
+    }
+
+    /// Two nominal fields: heatmap of the mean of `value`, or of row counts
+    fn heatmap(&mut self, x: &ColumnProfile, y: &ColumnProfile, value: Option<&ColumnProfile>) {
+        let (color, title) = match value {
+            Some(v) => (
+                v.field().with_aggregate(AggregateOp::Mean),
+                format!("mean {} by {} and {}", v.name, y.name, x.name),
+            ),
+            None => (
+                x.field().with_aggregate(AggregateOp::Count),
+                format!("count by {} and {}", y.name, x.name),
+            ),
+        };
+        let spec = self.spec(Mark::Rect, x.field(), y.field(), Some(color), title);
+        self.push(spec, 0.6, "matrix of two categorical fields");
+    }
+
+    /// One quantitative field: binned counts
+    fn histogram(&mut self, q: &ColumnProfile, score: f64) {
+        let spec = self.spec(
+            Mark::Bar,
+            q.field().with_bin(BinRule::default()),
+            q.field().with_aggregate(AggregateOp::Count),
+            None,
+            format!("distribution of {}", q.name),
+        );
+        self.push(spec, score, "distribution of a single measure");
+    }
+
+    /// One nominal field: row counts per category
+    fn counts(&mut self, category: &ColumnProfile) {
+        let title = format!("count by {}", category.name);
+        let count = category.field().with_aggregate(AggregateOp::Count);
+        let bar = self.spec(
+            Mark::Bar,
+            category.field(),
+            count.clone(),
+            None,
+            title.clone(),
+        );
+        self.push(bar, 0.9, "frequency of each category");
+        if category.cardinality <= MAX_PIE_SLICES {
+            let pie = self.spec(Mark::Arc, category.field(), count, None, title);
+            self.push(pie, 0.5, "share of each category");
+        }
+    }
+
+    /// One temporal field: row counts per calendar unit fitting its span
+    fn count_over_time(&mut self, t: &ColumnProfile) {
+        const DAY: f64 = 86_400_000.0;
+        let span = t.extent.map_or(0.0, |(lo, hi)| hi - lo);
+        let unit = if span > 3.0 * 365.0 * DAY {
+            TimeUnit::Year
+        } else if span > 90.0 * DAY {
+            TimeUnit::Month
+        } else if span > 3.0 * DAY {
+            TimeUnit::Day
+        } else {
+            TimeUnit::Hour
+        };
+        let spec = self.spec(
+            Mark::Bar,
+            t.field().with_time_unit(unit),
+            t.field().with_aggregate(AggregateOp::Count),
+            None,
+            format!("count per {}", unit.name()),
+        );
+        self.push(spec, 0.9, "number of records over time");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::column::Column;
+    use crate::core::field_value::FieldValue;
+    use crate::core::spec::ChartData;
+    use crate::data_row;
+
+    fn sales() -> DataTable {
+        let mut rows = Vec::new();
+        for i in 0..30 {
+            let mut row = data_row! {
+                "region" => ["EU", "US", "JP"][i % 3],
+                "product" => ["A", "B"][i % 2],
+                "units" => (i * 7 % 11) as f64,
+                "price" => 10.0 + i as f64,
+            };
+            row.insert(
+                "date".into(),
+                FieldValue::Timestamp(1_700_000_000_000.0 + i as f64 * 86_400_000.0),
+            );
+            rows.push(row);
+        }
+        DataTable::from_rows(rows)
+    }
+
+    fn marks(recs: &[Recommendation]) -> Vec<Mark> {
+        recs.iter().map(|r| r.spec.mark).collect()
+    }
+
+    #[test]
+    fn test_profile() {
+        let table = sales();
+        let region = ColumnProfile::of(&table, "region").unwrap();
+        assert_eq!(region.data_type, DataType::Nominal);
+        assert_eq!(region.cardinality, 3);
+        let price = ColumnProfile::of(&table, "price").unwrap();
+        assert_eq!(price.extent, Some((10.0, 39.0)));
+        assert!(ColumnProfile::of(&table, "missing").is_none());
+    }
+
+    #[test]
+    fn test_rules() {
+        let table = sales();
+        let trend = recommend(&table, &["date", "units", "region"]);
+        assert_eq!(marks(&trend), vec![Mark::Line, Mark::Area]);
+        assert_eq!(trend[0].spec.color.as_ref().unwrap().name, "region");
+
+        let bars = recommend(&table, &["region", "units"]);
+        // Histogram of the measure comes last
+        assert_eq!(
+            marks(&bars),
+            vec![Mark::Bar, Mark::Boxplot, Mark::Arc, Mark::Bar]
+        );
+        assert_eq!(
+            bars[0].spec.y.as_ref().unwrap().aggregate,
+            Some(AggregateOp::Sum)
+        );
+
+        let scatter = recommend(&table, &["price", "units", "product"]);
+        assert_eq!(marks(&scatter), vec![Mark::Point]);
+        assert_eq!(scatter[0].spec.color.as_ref().unwrap().name, "product");
+
+        let histogram = recommend(&table, &["price"]);
+        assert_eq!(marks(&histogram), vec![Mark::Bar]);
+        assert!(histogram[0].spec.x.bin.is_some());
+
+        let matrix = recommend(&table, &["region", "product", "units"]);
+        assert!(marks(&matrix).contains(&Mark::Rect));
+
+        let counts = recommend(&table, &["date"]);
+        assert_eq!(counts[0].spec.x.time_unit, Some(TimeUnit::Day));
+    }
+
+    #[test]
+    fn test_ranked_and_resolvable() {
+        let table = sales();
+        let all = recommend(&table, &[]);
+        assert_eq!(all[0].spec.mark, Mark::Line);
+        for cols in [
+            &["date", "units", "region"][..],
+            &["region", "units"],
+            &["price", "units", "product"],
+            &["price"],
+            &["region", "product", "units"],
+            &["region", "product"],
+            &["date"],
+        ] {
+            let recs = recommend(&table, cols);
+            assert!(!recs.is_empty(), "{cols:?}");
+            assert!(recs.windows(2).all(|w| w[0].score >= w[1].score));
+            for rec in recs {
+                let data = rec.spec.resolve();
+                assert!(data.is_ok(), "{cols:?} {:?}: {data:?}", rec.spec.mark);
+                if rec.spec.mark == Mark::Rect {
+                    assert!(matches!(data, Ok(ChartData::Grid(_))));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_selection() {
+        let table = sales();
+        assert!(recommend(&table, &["missing"]).is_empty());
+        assert!(recommend(&DataTable::default(), &[]).is_empty());
+    }
+
+    #[test]
+    fn test_every_recommendation_validates() {
+        let mut with_flag = sales();
+        let flags: Vec<FieldValue> = (0..with_flag.len())
+            .map(|i| FieldValue::Bool(i % 4 == 0))
+            .collect();
+        with_flag.insert_column(Column::from_values("promo", flags));
+        let cases: [&[&str]; 8] = [
+            &[],
+            &["date", "units"],
+            &["region", "units"],
+            &["price", "units", "region"],
+            &["region", "product"],
+            &["promo", "units"],
+            &["promo"],
+            &["promo", "region", "price"],
+        ];
+        for columns in cases {
+            let recs = recommend(&with_flag, columns);
+            assert!(!recs.is_empty(), "{columns:?}");
+            for rec in recs {
+                if let Err(e) = rec.spec.validate() {
+                    panic!("{columns:?} {:?}: {e}", rec.spec.mark);
+                }
+                assert!(!rec.spec.resolve().unwrap().is_empty());
+            }
+        }
+    }
+}
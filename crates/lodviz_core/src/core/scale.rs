@@ -2,6 +2,23 @@
 ///
 /// Scales are pure functions that convert data values (domain) to
 /// visual coordinates (range), essential for all chart types.
+///
+/// Continuous scales also pick axis ticks: `ticks(count)` returns round
+/// values (1-2-5 steps, like d3-scale) and `nice(count)` widens the domain
+/// so it starts and ends on such values. `TimeScale` does the same on
+/// calendar boundaries (seconds through years) in a chosen `TimeZone`.
+/// `PowScale`, `SqrtScale` and `SymlogScale` map through a transform
+/// first; the latter handles zero and negative values, unlike `LogScale`.
+///
+/// Charts pick a continuous scale per axis with `ScaleType`; `AxisScale`
+/// is the matching runtime scale.
+///
+/// `BandScale` and `PointScale` place categories along an axis, while
+/// `OrdinalScale`, `QuantizeScale`, `QuantileScale` and `ThresholdScale`
+/// map categories or binned numbers onto discrete outputs such as colors.
+use crate::algorithms::statistics::quantile;
+use crate::core::datetime::{time_label, TimeInterval, TimeUnit, TimeZone};
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 /// Trait for scale transformations
@@ -17,6 +34,188 @@ pub trait Scale: Debug {
 
     /// Get the range (output range)
     fn range(&self) -> (f64, f64);
+
+    /// Roughly `count` round tick values inside the domain, in domain order
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        let (d0, d1) = self.domain();
+        linear_ticks(d0, d1, count)
+    }
+
+    /// Label for a tick produced by `ticks(count)`
+    fn tick_label(&self, value: f64, count: usize) -> String {
+        let (d0, d1) = self.domain();
+        let step = tick_step(d0, d1, count).abs();
+        // Enough decimals to tell neighbouring ticks apart
+        let decimals = if step > 0.0 && step < 1.0 {
+            (-step.log10().floor()) as usize
+        } else {
+            0
+        };
+        let label = format!("{value:.decimals$}");
+        // Avoid "-0"
+        if label
+            .trim_start_matches('-')
+            .chars()
+            .all(|c| c == '0' || c == '.')
+        {
+            label.trim_start_matches('-').to_owned()
+        } else {
+            label
+        }
+    }
+
+    /// The same scale with its domain extended to round values.
+    ///
+    /// Defaults to an unchanged copy, so custom scales only need to
+    /// override it when they have a notion of round values.
+    fn nice(&self, _count: usize) -> Self
+    where
+        Self: Sized + Clone,
+    {
+        self.clone()
+    }
+}
+
+/// Tick count charts use to nice their data domains: fine enough that the
+/// domain grows by at most one small step on each side
+pub const NICE_TICKS: usize = 10;
+
+/// Distance between round ticks for roughly `count` ticks over
+/// `[start, stop]`: 1, 2 or 5 times a power of ten, negative when
+/// `stop < start`, 0 when the interval or `count` is empty
+pub fn tick_step(start: f64, stop: f64, count: usize) -> f64 {
+    if count == 0 || !start.is_finite() || !stop.is_finite() || start == stop {
+        return 0.0;
+    }
+    let raw = (stop - start).abs() / count as f64;
+    let power = raw.log10().floor();
+    let error = raw / 10f64.powf(power);
+    let factor = if error >= 50f64.sqrt() {
+        10.0
+    } else if error >= 10f64.sqrt() {
+        5.0
+    } else if error >= 2f64.sqrt() {
+        2.0
+    } else {
+        1.0
+    };
+    let step = factor * 10f64.powf(power);
+    if stop < start {
+        -step
+    } else {
+        step
+    }
+}
+
+/// Round tick values inside `[start, stop]` (either order), ordered from
+/// `start` to `stop`; a degenerate interval yields its single value
+pub fn linear_ticks(start: f64, stop: f64, count: usize) -> Vec<f64> {
+    if start == stop && start.is_finite() && count > 0 {
+        return vec![start];
+    }
+    let step = tick_step(start, stop, count).abs();
+    if step == 0.0 {
+        return Vec::new();
+    }
+    let (lo, hi) = (start.min(stop), start.max(stop));
+    // Multiply integers by the step, or divide by its inverse for steps
+    // below one, so that 3 × 0.1 gives 0.3 and not 0.30000000000000004
+    let mut ticks: Vec<f64> = if step >= 1.0 {
+        let (i0, i1) = ((lo / step).ceil() as i64, (hi / step).floor() as i64);
+        (i0..=i1).map(|i| i as f64 * step).collect()
+    } else {
+        let inv = (1.0 / step).round();
+        let (i0, i1) = ((lo * inv).ceil() as i64, (hi * inv).floor() as i64);
+        (i0..=i1).map(|i| i as f64 / inv).collect()
+    };
+    if stop < start {
+        ticks.reverse();
+    }
+    ticks
+}
+
+/// `domain` widened outward to multiples of its tick step (order kept)
+pub fn nice_domain(domain: (f64, f64), count: usize) -> (f64, f64) {
+    let (d0, d1) = domain;
+    let (mut lo, mut hi) = (d0.min(d1), d0.max(d1));
+    let mut prev = 0.0;
+    // Widening can change the step; repeat until it settles
+    for _ in 0..10 {
+        let step = tick_step(lo, hi, count);
+        if step == 0.0 || step == prev {
+            break;
+        }
+        lo = (lo / step).floor() * step;
+        hi = (hi / step).ceil() * step;
+        prev = step;
+    }
+    if d1 < d0 {
+        (hi, lo)
+    } else {
+        (lo, hi)
+    }
+}
+
+/// Calendar steps for time ticks, finest first (as in d3-scale)
+const TIME_INTERVALS: [TimeInterval; 18] = [
+    TimeInterval::new(TimeUnit::Second, 1),
+    TimeInterval::new(TimeUnit::Second, 5),
+    TimeInterval::new(TimeUnit::Second, 15),
+    TimeInterval::new(TimeUnit::Second, 30),
+    TimeInterval::new(TimeUnit::Minute, 1),
+    TimeInterval::new(TimeUnit::Minute, 5),
+    TimeInterval::new(TimeUnit::Minute, 15),
+    TimeInterval::new(TimeUnit::Minute, 30),
+    TimeInterval::new(TimeUnit::Hour, 1),
+    TimeInterval::new(TimeUnit::Hour, 3),
+    TimeInterval::new(TimeUnit::Hour, 6),
+    TimeInterval::new(TimeUnit::Hour, 12),
+    TimeInterval::new(TimeUnit::Day, 1),
+    TimeInterval::new(TimeUnit::Day, 2),
+    TimeInterval::new(TimeUnit::Week, 1),
+    TimeInterval::new(TimeUnit::Month, 1),
+    TimeInterval::new(TimeUnit::Quarter, 1),
+    TimeInterval::new(TimeUnit::Year, 1),
+];
+
+/// Calendar interval giving roughly `count` ticks over `[start, stop]`
+/// (Unix ms, either order): the listed step closest to `span / count`,
+/// or whole years (1-2-5 multiples) for long spans. `None` when ticks
+/// would be under a second apart; millisecond ticks are linear.
+pub fn time_interval(start: f64, stop: f64, count: usize) -> Option<TimeInterval> {
+    if count == 0 || !start.is_finite() || !stop.is_finite() || start == stop {
+        return None;
+    }
+    let target = (stop - start).abs() / count as f64;
+    let i = TIME_INTERVALS.partition_point(|iv| iv.duration_ms() <= target);
+    if i == TIME_INTERVALS.len() {
+        let year = TimeInterval::new(TimeUnit::Year, 1).duration_ms();
+        let step = tick_step(start / year, stop / year, count).abs().max(1.0);
+        return Some(TimeInterval::new(TimeUnit::Year, step as u32));
+    }
+    if i == 0 {
+        return None;
+    }
+    let (below, above) = (TIME_INTERVALS[i - 1], TIME_INTERVALS[i]);
+    // Compare ratios, not differences: 5 min vs 15 min for a 9-minute target
+    if target / below.duration_ms() < above.duration_ms() / target {
+        Some(below)
+    } else {
+        Some(above)
+    }
+}
+
+/// Tick timestamps on calendar boundaries inside `[start, stop]`, ordered
+/// from `start` to `stop`; sub-second spans fall back to `linear_ticks`
+pub fn time_ticks(start: f64, stop: f64, count: usize, tz: TimeZone) -> Vec<f64> {
+    let Some(interval) = time_interval(start, stop, count) else {
+        return linear_ticks(start, stop, count);
+    };
+    let mut ticks = interval.range(start.min(stop), start.max(stop), tz);
+    if stop < start {
+        ticks.reverse();
+    }
+    ticks
 }
 
 /// Linear scale for continuous numerical data
@@ -74,6 +273,10 @@ impl Scale for LinearScale {
     fn range(&self) -> (f64, f64) {
         self.range
     }
+
+    fn nice(&self, count: usize) -> Self {
+        Self::new(nice_domain(self.domain, count), self.range)
+    }
 }
 
 /// Logarithmic scale for data with large dynamic range
@@ -142,20 +345,394 @@ impl Scale for LogScale {
     fn range(&self) -> (f64, f64) {
         self.range
     }
+
+    /// Powers of the base; with few decades also their integer multiples
+    /// (`1, 2, … 9, 10, 20, …`), otherwise every n-th power
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        let (d0, d1) = self.domain;
+        let (lo, hi) = (d0.min(d1), d0.max(d1));
+        let (e0, e1) = (lo.log(self.base).floor(), hi.log(self.base).ceil());
+        let mut ticks = if e1 - e0 < count as f64 {
+            let mut out = Vec::new();
+            let multiples = self.base.floor().max(2.0) as u32;
+            for e in (e0 as i32)..=(e1 as i32) {
+                for k in 1..multiples {
+                    // Divide for negative exponents: 3 / 10, not 3 × 0.1
+                    let t = if e >= 0 {
+                        f64::from(k) * self.base.powi(e)
+                    } else {
+                        f64::from(k) / self.base.powi(-e)
+                    };
+                    if t >= lo * (1.0 - 1e-12) && t <= hi * (1.0 + 1e-12) {
+                        out.push(t);
+                    }
+                }
+            }
+            out
+        } else {
+            linear_ticks(e0, e1, count)
+                .into_iter()
+                .filter(|e| e.fract() == 0.0)
+                .map(|e| self.base.powf(e))
+                .filter(|&t| t >= lo * (1.0 - 1e-12) && t <= hi * (1.0 + 1e-12))
+                .collect()
+        };
+        if d1 < d0 {
+            ticks.reverse();
+        }
+        ticks
+    }
+
+    fn tick_label(&self, value: f64, _count: usize) -> String {
+        // Shortest exact representation: 0.01, 2, 5000
+        let label = format!("{value}");
+        if value.abs() >= 1e6 || (value != 0.0 && value.abs() < 1e-4) {
+            format!("{value:e}")
+        } else {
+            label
+        }
+    }
+
+    /// Widen the domain to whole powers of the base
+    fn nice(&self, _count: usize) -> Self {
+        let (d0, d1) = self.domain;
+        let floor = |v: f64| self.base.powf(v.log(self.base).floor());
+        let ceil = |v: f64| self.base.powf(v.log(self.base).ceil());
+        let domain = if d1 < d0 {
+            (ceil(d0), floor(d1))
+        } else {
+            (floor(d0), ceil(d1))
+        };
+        Self { domain, ..*self }
+    }
+}
+
+/// Power scale: maps `sign(x)·|x|^exponent` linearly onto the range
+///
+/// Exponents below 1 compress large values, above 1 spread them. Ticks
+/// and niceing are linear, as for `LinearScale`. With `with_clamp(true)`
+/// values outside the domain map to the nearest end of the range.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowScale {
+    domain: (f64, f64),
+    range: (f64, f64),
+    exponent: f64,
+    clamp: bool,
+}
+
+impl PowScale {
+    /// Create a new power scale
+    pub fn new(domain: (f64, f64), range: (f64, f64), exponent: f64) -> Self {
+        assert!(
+            exponent.is_finite() && exponent != 0.0,
+            "Pow scale exponent must be finite and != 0"
+        );
+        Self {
+            domain,
+            range,
+            exponent,
+            clamp: false,
+        }
+    }
+
+    /// Clamp mapped values to the range (and inverted values to the domain)
+    pub fn with_clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// The exponent
+    pub fn exponent(&self) -> f64 {
+        self.exponent
+    }
+
+    fn transform(&self, v: f64) -> f64 {
+        v.signum() * v.abs().powf(self.exponent)
+    }
+
+    fn untransform(&self, t: f64) -> f64 {
+        t.signum() * t.abs().powf(1.0 / self.exponent)
+    }
+}
+
+impl Scale for PowScale {
+    fn map(&self, value: f64) -> f64 {
+        let (t0, t1) = (self.transform(self.domain.0), self.transform(self.domain.1));
+        map_transformed(self.transform(value), (t0, t1), self.range, self.clamp)
+    }
+
+    fn inverse(&self, mapped: f64) -> f64 {
+        let (t0, t1) = (self.transform(self.domain.0), self.transform(self.domain.1));
+        self.untransform(invert_transformed(mapped, (t0, t1), self.range, self.clamp))
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        self.domain
+    }
+
+    fn range(&self) -> (f64, f64) {
+        self.range
+    }
+
+    fn nice(&self, count: usize) -> Self {
+        Self {
+            domain: nice_domain(self.domain, count),
+            ..*self
+        }
+    }
+}
+
+/// Square-root scale: a `PowScale` with exponent ½
+///
+/// Use it for bubble sizes, so that circle *area* (not radius) grows with
+/// the value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SqrtScale(PowScale);
+
+impl SqrtScale {
+    /// Create a new square-root scale
+    pub fn new(domain: (f64, f64), range: (f64, f64)) -> Self {
+        Self(PowScale::new(domain, range, 0.5))
+    }
+
+    /// Clamp mapped values to the range (and inverted values to the domain)
+    pub fn with_clamp(self, clamp: bool) -> Self {
+        Self(self.0.with_clamp(clamp))
+    }
+}
+
+impl Scale for SqrtScale {
+    fn map(&self, value: f64) -> f64 {
+        self.0.map(value)
+    }
+
+    fn inverse(&self, mapped: f64) -> f64 {
+        self.0.inverse(mapped)
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        self.0.domain()
+    }
+
+    fn range(&self) -> (f64, f64) {
+        self.0.range()
+    }
+
+    fn nice(&self, count: usize) -> Self {
+        Self(self.0.nice(count))
+    }
+}
+
+/// Symmetric log scale: `sign(x)·ln(1 + |x| / constant)`
+///
+/// Logarithmic for `|x|` well above `constant`, nearly linear around zero,
+/// so unlike `LogScale` it accepts zero and negative values (profit and
+/// loss, deltas). Ticks are zero plus signed powers of ten.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymlogScale {
+    domain: (f64, f64),
+    range: (f64, f64),
+    constant: f64,
+    clamp: bool,
+}
+
+impl SymlogScale {
+    /// Create a new symlog scale with constant 1
+    pub fn new(domain: (f64, f64), range: (f64, f64)) -> Self {
+        Self::with_constant(domain, range, 1.0)
+    }
+
+    /// Create a new symlog scale whose linear region spans about `±constant`
+    pub fn with_constant(domain: (f64, f64), range: (f64, f64), constant: f64) -> Self {
+        assert!(
+            constant.is_finite() && constant > 0.0,
+            "Symlog constant must be > 0"
+        );
+        Self {
+            domain,
+            range,
+            constant,
+            clamp: false,
+        }
+    }
+
+    /// Clamp mapped values to the range (and inverted values to the domain)
+    pub fn with_clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// The constant
+    pub fn constant(&self) -> f64 {
+        self.constant
+    }
+
+    fn transform(&self, v: f64) -> f64 {
+        v.signum() * (v.abs() / self.constant).ln_1p()
+    }
+
+    fn untransform(&self, t: f64) -> f64 {
+        t.signum() * t.abs().exp_m1() * self.constant
+    }
+
+    /// `v` moved away from (`up` for the upper end) to a round value:
+    /// a multiple of the constant inside `±constant`, else a power of ten
+    fn nice_end(&self, v: f64, up: bool) -> f64 {
+        let c = self.constant;
+        if v.abs() <= c {
+            return if up {
+                (v / c).ceil() * c
+            } else {
+                (v / c).floor() * c
+            };
+        }
+        let e = v.abs().log10();
+        let e = if (v > 0.0) == up { e.ceil() } else { e.floor() };
+        v.signum() * pow10(e as i32)
+    }
+}
+
+impl Scale for SymlogScale {
+    fn map(&self, value: f64) -> f64 {
+        let (t0, t1) = (self.transform(self.domain.0), self.transform(self.domain.1));
+        map_transformed(self.transform(value), (t0, t1), self.range, self.clamp)
+    }
+
+    fn inverse(&self, mapped: f64) -> f64 {
+        let (t0, t1) = (self.transform(self.domain.0), self.transform(self.domain.1));
+        self.untransform(invert_transformed(mapped, (t0, t1), self.range, self.clamp))
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        self.domain
+    }
+
+    fn range(&self) -> (f64, f64) {
+        self.range
+    }
+
+    /// Zero and `±10^k` for `10^k ≥ constant`, every n-th power when there
+    /// are many; linear ticks when the domain stays near the linear region
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        let (d0, d1) = self.domain;
+        let (lo, hi) = (d0.min(d1), d0.max(d1));
+        let max_abs = lo.abs().max(hi.abs());
+        if count == 0 || !max_abs.is_finite() || max_abs <= self.constant * 10.0 {
+            return linear_ticks(d0, d1, count);
+        }
+        let (e0, e1) = (self.constant.log10().ceil(), max_abs.log10().floor());
+        // Both signs share the tick budget
+        let sides = usize::from(lo < 0.0) + usize::from(hi > 0.0);
+        let powers: Vec<f64> = linear_ticks(e0, e1, (count / sides.max(1)).max(1))
+            .into_iter()
+            .filter(|e| e.fract() == 0.0)
+            .map(|e| pow10(e as i32))
+            .collect();
+        let mut ticks: Vec<f64> = powers.iter().rev().map(|p| -p).collect();
+        ticks.push(0.0);
+        ticks.extend(&powers);
+        ticks.retain(|&t| t >= lo && t <= hi);
+        if d1 < d0 {
+            ticks.reverse();
+        }
+        ticks
+    }
+
+    fn tick_label(&self, value: f64, count: usize) -> String {
+        let (d0, d1) = self.domain;
+        if d0.abs().max(d1.abs()) <= self.constant * 10.0 {
+            return LinearScale::new(self.domain, self.range).tick_label(value, count);
+        }
+        if value.abs() >= 1e6 || (value != 0.0 && value.abs() < 1e-4) {
+            format!("{value:e}")
+        } else {
+            format!("{value}")
+        }
+    }
+
+    /// Widen each end to a power of ten (or a multiple of the constant
+    /// inside the linear region)
+    fn nice(&self, _count: usize) -> Self {
+        let (d0, d1) = self.domain;
+        let domain = if d1 < d0 {
+            (self.nice_end(d0, true), self.nice_end(d1, false))
+        } else {
+            (self.nice_end(d0, false), self.nice_end(d1, true))
+        };
+        Self { domain, ..*self }
+    }
+}
+
+/// `10^e`, dividing for negative exponents so `1e-2` is exactly `0.01`
+fn pow10(e: i32) -> f64 {
+    if e >= 0 {
+        10f64.powi(e)
+    } else {
+        1.0 / 10f64.powi(-e)
+    }
 }
 
-/// Time scale for temporal data (simplified version using f64 timestamps)
+/// Linear map of an already transformed value between transformed domain
+/// ends `(t0, t1)` and `range`, optionally clamped to the range
+fn map_transformed(t: f64, (t0, t1): (f64, f64), (r0, r1): (f64, f64), clamp: bool) -> f64 {
+    if (t1 - t0).abs() < f64::EPSILON {
+        return r0;
+    }
+    let normalized = (t - t0) / (t1 - t0);
+    let normalized = if clamp {
+        normalized.clamp(0.0, 1.0)
+    } else {
+        normalized
+    };
+    r0 + normalized * (r1 - r0)
+}
+
+/// Inverse of `map_transformed`, in transformed units
+fn invert_transformed(mapped: f64, (t0, t1): (f64, f64), (r0, r1): (f64, f64), clamp: bool) -> f64 {
+    if (r1 - r0).abs() < f64::EPSILON {
+        return t0;
+    }
+    let normalized = (mapped - r0) / (r1 - r0);
+    let normalized = if clamp {
+        normalized.clamp(0.0, 1.0)
+    } else {
+        normalized
+    };
+    t0 + normalized * (t1 - t0)
+}
+
+/// Time scale for temporal data (Unix millisecond timestamps)
+///
+/// Maps linearly like `LinearScale`, but ticks fall on calendar boundaries
+/// (every 15 minutes, each month, ...) read in its `TimeZone`, and tick
+/// labels are multi-scale: `Mar`, `2024`, `12:00`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct TimeScale {
-    /// Domain as Unix timestamps (seconds since epoch)
+    /// Domain as Unix timestamps (milliseconds since epoch)
     domain: (f64, f64),
     range: (f64, f64),
+    timezone: TimeZone,
 }
 
 impl TimeScale {
-    /// Create a new time scale
+    /// Create a new time scale (UTC)
     pub fn new(domain: (f64, f64), range: (f64, f64)) -> Self {
-        Self { domain, range }
+        Self {
+            domain,
+            range,
+            timezone: TimeZone::Utc,
+        }
+    }
+
+    /// Place ticks and read labels in `timezone`
+    pub fn with_timezone(mut self, timezone: TimeZone) -> Self {
+        self.timezone = timezone;
+        self
+    }
+
+    /// Time zone used for ticks and labels
+    pub fn timezone(&self) -> TimeZone {
+        self.timezone
     }
 }
 
@@ -192,6 +769,175 @@ impl Scale for TimeScale {
     fn range(&self) -> (f64, f64) {
         self.range
     }
+
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        time_ticks(self.domain.0, self.domain.1, count, self.timezone)
+    }
+
+    fn tick_label(&self, value: f64, _count: usize) -> String {
+        time_label(value, self.timezone)
+    }
+
+    fn nice(&self, count: usize) -> Self {
+        let (d0, d1) = self.domain;
+        let Some(interval) = time_interval(d0, d1, count) else {
+            return Self {
+                domain: nice_domain(self.domain, count),
+                ..*self
+            };
+        };
+        let tz = self.timezone;
+        let (lo, hi) = (
+            interval.floor(d0.min(d1), tz),
+            interval.ceil(d0.max(d1), tz),
+        );
+        Self {
+            domain: if d1 < d0 { (hi, lo) } else { (lo, hi) },
+            ..*self
+        }
+    }
+}
+
+/// Kind of continuous scale for a chart axis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScaleType {
+    /// `LinearScale`
+    #[default]
+    Linear,
+    /// `LogScale` (base 10, positive values only)
+    Log,
+    /// `SqrtScale`
+    Sqrt,
+    /// `SymlogScale` (constant 1)
+    Symlog,
+    /// `TimeScale` on Unix ms timestamps, with calendar ticks and labels
+    /// read in the zone
+    Time(TimeZone),
+}
+
+impl ScaleType {
+    /// `domain` made usable by this kind of scale: log domains get
+    /// positive, distinct ends (a non-positive end becomes a thousandth of
+    /// the other one). Other kinds are unchanged.
+    pub fn valid_domain(self, domain: (f64, f64)) -> (f64, f64) {
+        if self != Self::Log {
+            return domain;
+        }
+        let (d0, d1) = domain;
+        let (mut lo, mut hi) = (d0.min(d1), d0.max(d1));
+        if hi <= 0.0 || !hi.is_finite() {
+            (lo, hi) = (1.0, 10.0);
+        } else if lo <= 0.0 || !lo.is_finite() {
+            lo = hi / 1_000.0;
+        }
+        if lo == hi {
+            (lo, hi) = (lo / 10.0, hi * 10.0);
+        }
+        if d1 < d0 {
+            (hi, lo)
+        } else {
+            (lo, hi)
+        }
+    }
+
+    /// `domain` widened to round values for this kind: 1-2-5 steps,
+    /// powers of ten for log and symlog, calendar boundaries for time
+    pub fn nice_domain(self, domain: (f64, f64), count: usize) -> (f64, f64) {
+        AxisScale::new(self, domain, (0.0, 1.0))
+            .nice(count)
+            .domain()
+    }
+}
+
+/// A continuous axis scale of any `ScaleType`, so charts can hold one
+/// concrete type whatever the configured kind
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AxisScale {
+    /// Linear axis
+    Linear(LinearScale),
+    /// Logarithmic axis
+    Log(LogScale),
+    /// Square-root axis
+    Sqrt(SqrtScale),
+    /// Symmetric log axis
+    Symlog(SymlogScale),
+    /// Calendar time axis
+    Time(TimeScale),
+}
+
+impl AxisScale {
+    /// Build a scale of `kind` (the domain passes through
+    /// `ScaleType::valid_domain` first)
+    pub fn new(kind: ScaleType, domain: (f64, f64), range: (f64, f64)) -> Self {
+        let domain = kind.valid_domain(domain);
+        match kind {
+            ScaleType::Linear => Self::Linear(LinearScale::new(domain, range)),
+            ScaleType::Log => Self::Log(LogScale::new(domain, range)),
+            ScaleType::Sqrt => Self::Sqrt(SqrtScale::new(domain, range)),
+            ScaleType::Symlog => Self::Symlog(SymlogScale::new(domain, range)),
+            ScaleType::Time(timezone) => {
+                Self::Time(TimeScale::new(domain, range).with_timezone(timezone))
+            }
+        }
+    }
+
+    /// The kind of this scale
+    pub fn kind(&self) -> ScaleType {
+        match self {
+            Self::Linear(_) => ScaleType::Linear,
+            Self::Log(_) => ScaleType::Log,
+            Self::Sqrt(_) => ScaleType::Sqrt,
+            Self::Symlog(_) => ScaleType::Symlog,
+            Self::Time(s) => ScaleType::Time(s.timezone()),
+        }
+    }
+
+    fn inner(&self) -> &dyn Scale {
+        match self {
+            Self::Linear(s) => s,
+            Self::Log(s) => s,
+            Self::Sqrt(s) => s,
+            Self::Symlog(s) => s,
+            Self::Time(s) => s,
+        }
+    }
+}
+
+impl Scale for AxisScale {
+    fn map(&self, value: f64) -> f64 {
+        self.inner().map(value)
+    }
+
+    fn inverse(&self, mapped: f64) -> f64 {
+        self.inner().inverse(mapped)
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        self.inner().domain()
+    }
+
+    fn range(&self) -> (f64, f64) {
+        self.inner().range()
+    }
+
+    fn ticks(&self, count: usize) -> Vec<f64> {
+        self.inner().ticks(count)
+    }
+
+    fn tick_label(&self, value: f64, count: usize) -> String {
+        self.inner().tick_label(value, count)
+    }
+
+    fn nice(&self, count: usize) -> Self {
+        match self {
+            Self::Linear(s) => Self::Linear(s.nice(count)),
+            Self::Log(s) => Self::Log(s.nice(count)),
+            Self::Sqrt(s) => Self::Sqrt(s.nice(count)),
+            Self::Symlog(s) => Self::Symlog(s.nice(count)),
+            Self::Time(s) => Self::Time(s.nice(count)),
+        }
+    }
 }
 
 /// Band scale for categorical/ordinal data
@@ -275,6 +1021,252 @@ impl BandScale {
     }
 }
 
+/// Point scale for dot plots: categories at evenly spaced positions
+///
+/// Like a `BandScale` with zero-width bands. `padding` is the space before
+/// the first and after the last point, in steps; points are centered in
+/// the range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointScale {
+    categories: Vec<String>,
+    range: (f64, f64),
+    padding: f64,
+}
+
+impl PointScale {
+    /// Create a new point scale (`padding` ≥ 0, in steps)
+    pub fn new(categories: Vec<String>, range: (f64, f64), padding: f64) -> Self {
+        Self {
+            categories,
+            range,
+            padding: padding.max(0.0),
+        }
+    }
+
+    /// Distance between neighbouring points
+    pub fn step(&self) -> f64 {
+        let slots = (self.categories.len() as f64 - 1.0 + 2.0 * self.padding).max(1.0);
+        (self.range.1 - self.range.0) / slots
+    }
+
+    /// Position of the point at `idx`
+    pub fn map_index(&self, idx: usize) -> f64 {
+        let (r0, r1) = self.range;
+        let step = self.step();
+        let used = step * (self.categories.len() as f64 - 1.0).max(0.0);
+        let start = r0 + ((r1 - r0) - used) / 2.0;
+        start + idx as f64 * step
+    }
+
+    /// Position of `name`, `None` if it is not a category
+    pub fn map_category(&self, name: &str) -> Option<f64> {
+        let idx = self.categories.iter().position(|c| c == name)?;
+        Some(self.map_index(idx))
+    }
+
+    /// Get all categories
+    pub fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    /// Get the range
+    pub fn range(&self) -> (f64, f64) {
+        self.range
+    }
+}
+
+// --- Discrete output scales ---
+//
+// These map values onto a list of outputs (colors, shapes, labels) rather
+// than pixels, so they are generic over the output type instead of
+// implementing `Scale`.
+
+/// Ordinal scale: the n-th category gets the n-th output
+///
+/// Outputs repeat when there are more categories than outputs. Values
+/// outside the domain get the `unknown` output, if one is set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrdinalScale<T> {
+    domain: Vec<String>,
+    range: Vec<T>,
+    unknown: Option<T>,
+}
+
+impl<T> OrdinalScale<T> {
+    /// Create a new ordinal scale
+    pub fn new(domain: Vec<String>, range: Vec<T>) -> Self {
+        Self {
+            domain,
+            range,
+            unknown: None,
+        }
+    }
+
+    /// Output for values not in the domain
+    pub fn with_unknown(mut self, unknown: T) -> Self {
+        self.unknown = Some(unknown);
+        self
+    }
+
+    /// Output for `category`
+    pub fn map(&self, category: &str) -> Option<&T> {
+        match self.domain.iter().position(|c| c == category) {
+            Some(idx) if !self.range.is_empty() => self.range.get(idx % self.range.len()),
+            _ => self.unknown.as_ref(),
+        }
+    }
+
+    /// Get the categories
+    pub fn domain(&self) -> &[String] {
+        &self.domain
+    }
+
+    /// Get the outputs
+    pub fn range(&self) -> &[T] {
+        &self.range
+    }
+}
+
+/// Threshold scale: `thresholds` split the number line into
+/// `thresholds.len() + 1` bins, each with one output
+///
+/// A value equal to a threshold falls in the bin above it. E.g.
+/// thresholds `[50, 80]` with `["red", "amber", "green"]` gives
+/// traffic-light KPI colors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThresholdScale<T> {
+    thresholds: Vec<f64>,
+    range: Vec<T>,
+}
+
+impl<T> ThresholdScale<T> {
+    /// Create a new threshold scale (`thresholds` ascending)
+    pub fn new(thresholds: Vec<f64>, range: Vec<T>) -> Self {
+        Self { thresholds, range }
+    }
+
+    /// Bin index of `value`: the number of thresholds at or below it
+    pub fn bin(&self, value: f64) -> usize {
+        self.thresholds.partition_point(|&t| t <= value)
+    }
+
+    /// Output for `value`; `None` for NaN or a missing output
+    pub fn map(&self, value: f64) -> Option<&T> {
+        if value.is_nan() {
+            return None;
+        }
+        self.range.get(self.bin(value))
+    }
+
+    /// Bin boundaries, ascending
+    pub fn thresholds(&self) -> &[f64] {
+        &self.thresholds
+    }
+
+    /// Get the outputs
+    pub fn range(&self) -> &[T] {
+        &self.range
+    }
+}
+
+/// Quantize scale: splits a continuous domain into equal-width bins, one
+/// per output (e.g. a 5-class choropleth from `ColorMap::palette(5)`)
+///
+/// Values outside the domain fall in the first or last bin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizeScale<T> {
+    domain: (f64, f64),
+    inner: ThresholdScale<T>,
+}
+
+impl<T> QuantizeScale<T> {
+    /// Create a new quantize scale over `domain` (either order)
+    pub fn new(domain: (f64, f64), range: Vec<T>) -> Self {
+        let (lo, hi) = (domain.0.min(domain.1), domain.0.max(domain.1));
+        let n = range.len();
+        let thresholds = (1..n)
+            .map(|i| lo + (hi - lo) * i as f64 / n as f64)
+            .collect();
+        Self {
+            domain,
+            inner: ThresholdScale::new(thresholds, range),
+        }
+    }
+
+    /// Output for `value`
+    pub fn map(&self, value: f64) -> Option<&T> {
+        self.inner.map(value)
+    }
+
+    /// Get the domain
+    pub fn domain(&self) -> (f64, f64) {
+        self.domain
+    }
+
+    /// Inner bin boundaries, ascending
+    pub fn thresholds(&self) -> &[f64] {
+        self.inner.thresholds()
+    }
+
+    /// Get the outputs
+    pub fn range(&self) -> &[T] {
+        self.inner.range()
+    }
+}
+
+impl<T> From<QuantizeScale<T>> for ThresholdScale<T> {
+    fn from(scale: QuantizeScale<T>) -> Self {
+        scale.inner
+    }
+}
+
+/// Quantile scale: bins holding equal numbers of sample values, one per
+/// output, so every class is equally common in the data
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantileScale<T> {
+    inner: ThresholdScale<T>,
+}
+
+impl<T> QuantileScale<T> {
+    /// Create a new quantile scale from sample values (non-finite ones are
+    /// ignored)
+    pub fn new(sample: &[f64], range: Vec<T>) -> Self {
+        let mut sorted: Vec<f64> = sample.iter().copied().filter(|v| v.is_finite()).collect();
+        let n = range.len();
+        let thresholds = if sorted.is_empty() {
+            Vec::new()
+        } else {
+            (1..n)
+                .filter_map(|i| quantile(&mut sorted, i as f64 / n as f64))
+                .collect()
+        };
+        Self {
+            inner: ThresholdScale::new(thresholds, range),
+        }
+    }
+
+    /// Output for `value`
+    pub fn map(&self, value: f64) -> Option<&T> {
+        self.inner.map(value)
+    }
+
+    /// The sample quantiles separating the bins, ascending
+    pub fn quantiles(&self) -> &[f64] {
+        self.inner.thresholds()
+    }
+
+    /// Get the outputs
+    pub fn range(&self) -> &[T] {
+        self.inner.range()
+    }
+}
+
+impl<T> From<QuantileScale<T>> for ThresholdScale<T> {
+    fn from(scale: QuantileScale<T>) -> Self {
+        scale.inner
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -294,6 +1286,27 @@ mod tests {
         assert!(approx_eq(scale.map(100.0), 500.0));
     }
 
+    #[test]
+    fn test_default_nice_keeps_domain() {
+        #[derive(Debug, Clone)]
+        struct Identity;
+        impl Scale for Identity {
+            fn map(&self, value: f64) -> f64 {
+                value
+            }
+            fn inverse(&self, mapped: f64) -> f64 {
+                mapped
+            }
+            fn domain(&self) -> (f64, f64) {
+                (0.3, 9.7)
+            }
+            fn range(&self) -> (f64, f64) {
+                (0.3, 9.7)
+            }
+        }
+        assert_eq!(Identity.nice(NICE_TICKS).domain(), (0.3, 9.7));
+    }
+
     #[test]
     fn test_linear_scale_inverse() {
         let scale = LinearScale::new((0.0, 100.0), (0.0, 500.0));
@@ -384,6 +1397,278 @@ mod tests {
         assert!(approx_eq(scale.inverse(1000.0), year_end));
     }
 
+    // === Ticks and nice domains ===
+
+    #[test]
+    fn test_tick_step_1_2_5() {
+        assert_eq!(tick_step(0.0, 10.0, 10), 1.0);
+        assert_eq!(tick_step(0.0, 133.7, 5), 20.0);
+        assert_eq!(tick_step(0.0, 1.0, 3), 0.5);
+        assert_eq!(tick_step(10.0, 0.0, 5), -2.0);
+        assert_eq!(tick_step(1.0, 1.0, 5), 0.0);
+        assert_eq!(tick_step(0.0, 1.0, 0), 0.0);
+    }
+
+    #[test]
+    fn test_linear_ticks_round_values() {
+        let scale = LinearScale::new((0.0, 133.7), (0.0, 500.0));
+        assert_eq!(
+            scale.ticks(5),
+            vec![0.0, 20.0, 40.0, 60.0, 80.0, 100.0, 120.0]
+        );
+
+        let ticks = linear_ticks(-0.25, 0.35, 5);
+        assert_eq!(ticks, vec![-0.2, -0.1, 0.0, 0.1, 0.2, 0.3]);
+
+        assert_eq!(linear_ticks(10.0, 0.0, 2), vec![10.0, 5.0, 0.0]);
+        assert_eq!(linear_ticks(3.0, 3.0, 5), vec![3.0]);
+    }
+
+    #[test]
+    fn test_tick_labels() {
+        let scale = LinearScale::new((0.0, 1.0), (0.0, 100.0));
+        let labels: Vec<String> = scale
+            .ticks(5)
+            .iter()
+            .map(|&t| scale.tick_label(t, 5))
+            .collect();
+        assert_eq!(labels, vec!["0.0", "0.2", "0.4", "0.6", "0.8", "1.0"]);
+
+        let wide = LinearScale::new((-1000.0, 1000.0), (0.0, 100.0));
+        assert_eq!(wide.tick_label(-500.0, 4), "-500");
+        assert_eq!(wide.tick_label(-0.0, 4), "0");
+    }
+
+    #[test]
+    fn test_nice_domain() {
+        assert_eq!(nice_domain((13.37, 96.2), 5), (0.0, 100.0));
+        assert_eq!(nice_domain((13.37, 96.2), 10), (10.0, 100.0));
+        assert_eq!(nice_domain((0.13, 0.87), 10), (0.1, 0.9));
+        assert_eq!(nice_domain((96.2, 13.37), 5), (100.0, 0.0));
+        assert_eq!(nice_domain((5.0, 5.0), 5), (5.0, 5.0));
+
+        let scale = LinearScale::new((13.37, 96.2), (0.0, 100.0)).nice(10);
+        assert_eq!(scale.domain(), (10.0, 100.0));
+        assert_eq!(scale.range(), (0.0, 100.0));
+    }
+
+    #[test]
+    fn test_log_ticks() {
+        let scale = LogScale::new((1.0, 100.0), (0.0, 100.0));
+        assert_eq!(
+            scale.ticks(10),
+            vec![
+                1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0,
+                70.0, 80.0, 90.0, 100.0
+            ]
+        );
+
+        let small = LogScale::new((0.1, 0.5), (0.0, 100.0));
+        assert_eq!(small.ticks(10), vec![0.1, 0.2, 0.3, 0.4, 0.5]);
+
+        let wide = LogScale::new((1.0, 1e12), (0.0, 100.0));
+        assert_eq!(wide.ticks(4), vec![1.0, 1e2, 1e4, 1e6, 1e8, 1e10, 1e12]);
+        assert_eq!(wide.tick_label(1e10, 4), "1e10");
+        assert_eq!(small.tick_label(0.3, 10), "0.3");
+    }
+
+    #[test]
+    fn test_log_nice() {
+        let scale = LogScale::new((3.0, 420.0), (0.0, 100.0)).nice(5);
+        assert!(approx_eq(scale.domain().0, 1.0));
+        assert!(approx_eq(scale.domain().1, 1000.0));
+    }
+
+    #[test]
+    fn test_time_scale_nice() {
+        let scale = TimeScale::new((3.0, 97.0), (0.0, 10.0)).nice(10);
+        assert_eq!(scale.domain(), (0.0, 100.0));
+    }
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> f64 {
+        use crate::core::datetime::DateTimeParts;
+        DateTimeParts {
+            hour: h,
+            minute: mi,
+            ..DateTimeParts::date(y, mo, d)
+        }
+        .to_timestamp_ms()
+    }
+
+    #[test]
+    fn test_time_interval_by_span() {
+        let at = |span_ms: f64| time_interval(0.0, span_ms, 10).unwrap();
+        assert_eq!(at(60_000.0), TimeInterval::new(TimeUnit::Second, 5));
+        assert_eq!(at(3_600_000.0), TimeInterval::new(TimeUnit::Minute, 5));
+        assert_eq!(at(86_400_000.0), TimeInterval::new(TimeUnit::Hour, 3));
+        assert_eq!(at(30.0 * 86_400_000.0), TimeInterval::new(TimeUnit::Day, 2));
+        assert_eq!(
+            at(365.0 * 86_400_000.0),
+            TimeInterval::new(TimeUnit::Month, 1)
+        );
+        assert_eq!(
+            at(100.0 * 365.0 * 86_400_000.0),
+            TimeInterval::new(TimeUnit::Year, 10)
+        );
+        assert_eq!(time_interval(0.0, 500.0, 10), None);
+    }
+
+    #[test]
+    fn test_time_ticks_calendar_boundaries() {
+        let scale = TimeScale::new(
+            (utc(2024, 1, 15, 0, 0), utc(2024, 12, 20, 0, 0)),
+            (0.0, 800.0),
+        );
+        let ticks = scale.ticks(4);
+        let expected: Vec<f64> = [4, 7, 10].iter().map(|&m| utc(2024, m, 1, 0, 0)).collect();
+        assert_eq!(ticks, expected);
+        let labels: Vec<String> = ticks.iter().map(|&t| scale.tick_label(t, 4)).collect();
+        assert_eq!(labels, vec!["Apr", "Jul", "Oct"]);
+
+        let day = TimeScale::new((utc(2024, 3, 4, 0, 0), utc(2024, 3, 5, 0, 0)), (0.0, 800.0));
+        let labels: Vec<String> = day.ticks(4).iter().map(|&t| day.tick_label(t, 4)).collect();
+        assert_eq!(labels, vec!["Mar 4", "06:00", "12:00", "18:00", "Mar 5"]);
+
+        let years = TimeScale::new((utc(2019, 6, 1, 0, 0), utc(2024, 6, 1, 0, 0)), (0.0, 1.0));
+        let labels: Vec<String> = years
+            .ticks(5)
+            .iter()
+            .map(|&t| years.tick_label(t, 5))
+            .collect();
+        assert_eq!(labels, vec!["2020", "2021", "2022", "2023", "2024"]);
+    }
+
+    #[test]
+    fn test_time_ticks_fixed_offset() {
+        // 00:00–12:00 UTC read at UTC+05:30: ticks on local 3-hour marks
+        let scale = TimeScale::new((utc(2024, 3, 4, 0, 0), utc(2024, 3, 4, 12, 0)), (0.0, 1.0))
+            .with_timezone(TimeZone::FixedOffset(330));
+        let ticks = scale.ticks(4);
+        assert_eq!(ticks[0], utc(2024, 3, 4, 0, 30));
+        let labels: Vec<String> = ticks.iter().map(|&t| scale.tick_label(t, 4)).collect();
+        assert_eq!(labels, vec!["06:00", "09:00", "12:00", "15:00"]);
+    }
+
+    #[test]
+    fn test_time_scale_nice_calendar() {
+        let scale = TimeScale::new(
+            (utc(2024, 3, 4, 9, 17), utc(2024, 3, 4, 17, 42)),
+            (0.0, 1.0),
+        )
+        .nice(5);
+        assert_eq!(
+            scale.domain(),
+            (utc(2024, 3, 4, 9, 0), utc(2024, 3, 4, 18, 0))
+        );
+
+        let reversed = TimeScale::new(
+            (utc(2024, 11, 20, 0, 0), utc(2023, 2, 10, 0, 0)),
+            (0.0, 1.0),
+        )
+        .nice(5);
+        assert_eq!(
+            reversed.domain(),
+            (utc(2025, 1, 1, 0, 0), utc(2023, 1, 1, 0, 0))
+        );
+    }
+
+    // === Pow / sqrt / symlog ===
+
+    #[test]
+    fn test_pow_scale() {
+        let scale = PowScale::new((0.0, 10.0), (0.0, 100.0), 2.0);
+        assert!(approx_eq(scale.map(5.0), 25.0));
+        assert!(approx_eq(scale.inverse(25.0), 5.0));
+
+        // Odd-symmetric for negative inputs
+        let signed = PowScale::new((-10.0, 10.0), (-100.0, 100.0), 2.0);
+        assert!(approx_eq(signed.map(-5.0), -25.0));
+        assert!(approx_eq(signed.inverse(-25.0), -5.0));
+        assert_eq!(scale.ticks(5), vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+    }
+
+    #[test]
+    fn test_sqrt_scale_and_clamp() {
+        let scale = SqrtScale::new((0.0, 100.0), (0.0, 10.0));
+        assert!(approx_eq(scale.map(25.0), 5.0));
+        assert!(approx_eq(scale.inverse(5.0), 25.0));
+        assert!(approx_eq(scale.map(400.0), 20.0));
+
+        let clamped = scale.with_clamp(true);
+        assert!(approx_eq(clamped.map(400.0), 10.0));
+        assert!(approx_eq(clamped.map(-4.0), 0.0));
+        assert!(approx_eq(clamped.inverse(20.0), 100.0));
+        assert_eq!(
+            SqrtScale::new((3.0, 97.0), (0.0, 1.0)).nice(10).domain(),
+            (0.0, 100.0)
+        );
+    }
+
+    #[test]
+    fn test_symlog_scale_signed_magnitudes() {
+        let scale = SymlogScale::new((-1e6, 1e6), (0.0, 100.0));
+        assert!(approx_eq(scale.map(0.0), 50.0));
+        assert!(approx_eq(scale.map(-1e3), 100.0 - scale.map(1e3)));
+        // Logarithmic far from zero: equal steps per decade
+        let decade = scale.map(1e4) - scale.map(1e3);
+        assert!((scale.map(1e5) - scale.map(1e4) - decade).abs() < 0.1);
+        assert!((scale.inverse(scale.map(-12_345.0)) + 12_345.0).abs() < 1e-6);
+
+        let clamped = SymlogScale::new((0.0, 100.0), (0.0, 1.0)).with_clamp(true);
+        assert!(approx_eq(clamped.map(-50.0), 0.0));
+    }
+
+    #[test]
+    fn test_symlog_ticks_and_nice() {
+        let scale = SymlogScale::new((-1e4, 1e6), (0.0, 100.0));
+        assert_eq!(
+            scale.ticks(4),
+            vec![-1e4, -100.0, -1.0, 0.0, 1.0, 100.0, 1e4, 1e6]
+        );
+        assert_eq!(scale.tick_label(1e6, 4), "1e6");
+        assert_eq!(scale.tick_label(-100.0, 4), "-100");
+
+        // Near the linear region ticks are plain linear ones
+        let small = SymlogScale::new((-5.0, 5.0), (0.0, 1.0));
+        assert_eq!(small.ticks(5), vec![-4.0, -2.0, 0.0, 2.0, 4.0]);
+
+        let nice = SymlogScale::new((-3_700.0, 52_000.0), (0.0, 1.0)).nice(5);
+        assert_eq!(nice.domain(), (-10_000.0, 100_000.0));
+        let nice = SymlogScale::new((250.0, 0.4), (0.0, 1.0)).nice(5);
+        assert_eq!(nice.domain(), (1_000.0, 0.0));
+    }
+
+    // === Axis scales ===
+
+    #[test]
+    fn test_axis_scale_dispatch() {
+        let log = AxisScale::new(ScaleType::Log, (1.0, 1000.0), (0.0, 300.0));
+        assert_eq!(log.kind(), ScaleType::Log);
+        assert!(approx_eq(log.map(10.0), 100.0));
+        assert!(approx_eq(log.inverse(200.0), 100.0));
+        assert_eq!(log.ticks(10).first(), Some(&1.0));
+
+        let kind = ScaleType::Time(TimeZone::FixedOffset(60));
+        let time = AxisScale::new(kind, (0.0, 86_400_000.0), (0.0, 1.0));
+        assert_eq!(time.kind(), kind);
+        assert_eq!(time.tick_label(0.0, 4), "01:00");
+
+        let linear = AxisScale::new(ScaleType::Linear, (3.0, 97.0), (0.0, 1.0)).nice(10);
+        assert_eq!(linear.domain(), (0.0, 100.0));
+    }
+
+    #[test]
+    fn test_log_valid_domain() {
+        assert_eq!(ScaleType::Log.valid_domain((0.0, 500.0)), (0.5, 500.0));
+        assert_eq!(ScaleType::Log.valid_domain((-5.0, -1.0)), (1.0, 10.0));
+        assert_eq!(ScaleType::Log.valid_domain((100.0, 100.0)), (10.0, 1000.0));
+        assert_eq!(ScaleType::Log.valid_domain((500.0, 0.0)), (500.0, 0.5));
+        assert_eq!(ScaleType::Linear.valid_domain((0.0, 5.0)), (0.0, 5.0));
+        // A zero-based log axis no longer panics
+        let nice = ScaleType::Log.nice_domain((0.0, 420.0), NICE_TICKS);
+        assert_eq!(nice, (0.1, 1000.0));
+    }
+
     // === BandScale tests ===
 
     fn cat(names: &[&str]) -> Vec<String> {
@@ -461,4 +1746,73 @@ mod tests {
         assert!(approx_eq(scale.map_index(0), 25.0));
         assert!(approx_eq(scale.map_index(1), 125.0));
     }
+
+    // === Point and discrete scales ===
+
+    #[test]
+    fn test_point_scale() {
+        let scale = PointScale::new(cat(&["a", "b", "c"]), (0.0, 100.0), 0.5);
+        assert!(approx_eq(scale.step(), 100.0 / 3.0));
+        assert!(approx_eq(scale.map_category("a").unwrap(), 100.0 / 6.0));
+        assert!(approx_eq(scale.map_category("c").unwrap(), 500.0 / 6.0));
+        assert_eq!(scale.map_category("z"), None);
+
+        let edges = PointScale::new(cat(&["a", "b", "c"]), (0.0, 100.0), 0.0);
+        assert_eq!(edges.map_index(0), 0.0);
+        assert_eq!(edges.map_index(2), 100.0);
+        let single = PointScale::new(cat(&["a"]), (0.0, 100.0), 0.0);
+        assert_eq!(single.map_index(0), 50.0);
+    }
+
+    #[test]
+    fn test_ordinal_scale_cycles_and_unknown() {
+        let scale = OrdinalScale::new(cat(&["a", "b", "c"]), vec!["red", "blue"]);
+        assert_eq!(scale.map("a"), Some(&"red"));
+        assert_eq!(scale.map("c"), Some(&"red"));
+        assert_eq!(scale.map("z"), None);
+        let scale = scale.with_unknown("gray");
+        assert_eq!(scale.map("z"), Some(&"gray"));
+    }
+
+    #[test]
+    fn test_threshold_scale_traffic_light() {
+        let kpi = ThresholdScale::new(vec![50.0, 80.0], vec!["red", "amber", "green"]);
+        assert_eq!(kpi.map(12.0), Some(&"red"));
+        assert_eq!(kpi.map(50.0), Some(&"amber"));
+        assert_eq!(kpi.map(79.9), Some(&"amber"));
+        assert_eq!(kpi.map(120.0), Some(&"green"));
+        assert_eq!(kpi.map(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_quantize_scale() {
+        let scale = QuantizeScale::new((0.0, 100.0), vec!['a', 'b', 'c', 'd']);
+        assert_eq!(scale.thresholds(), &[25.0, 50.0, 75.0]);
+        assert_eq!(scale.map(-10.0), Some(&'a'));
+        assert_eq!(scale.map(60.0), Some(&'c'));
+        assert_eq!(scale.map(100.0), Some(&'d'));
+
+        let bins: ThresholdScale<char> = scale.into();
+        assert_eq!(bins.bin(30.0), 1);
+    }
+
+    #[test]
+    fn test_quantile_scale_equal_counts() {
+        let sample = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 100.0, f64::NAN];
+        let scale = QuantileScale::new(&sample, vec!["low", "mid", "high"]);
+        assert_eq!(scale.quantiles().len(), 2);
+        let counts = sample
+            .iter()
+            .filter_map(|&v| scale.map(v))
+            .fold([0; 3], |mut acc, class| {
+                let idx = ["low", "mid", "high"]
+                    .iter()
+                    .position(|c| c == class)
+                    .unwrap();
+                acc[idx] += 1;
+                acc
+            });
+        assert_eq!(counts, [3, 2, 3]);
+        assert_eq!(QuantileScale::new(&[], vec![1, 2]).map(5.0), Some(&1));
+    }
 }
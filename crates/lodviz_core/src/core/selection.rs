@@ -1,10 +1,14 @@
 /// Selection model for interactive chart features
 ///
 /// Represents user selections on charts, inspired by Vega-Lite selection types.
+/// Serializes internally tagged, e.g. `{"type": "Interval", "x": [0.0, 5.0], "y": null}`.
+use serde::{Deserialize, Serialize};
+
 use crate::core::data::DataPoint;
 
 /// A selection on chart data
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
 pub enum Selection {
     /// Selection of specific data points by index
     Point {
@@ -185,4 +189,20 @@ mod tests {
         let filtered = filter_by_selection(&data, &sel);
         assert!(filtered.is_empty());
     }
+
+    #[test]
+    fn test_serde_shape() {
+        let sel = Selection::Multi {
+            selections: vec![
+                Selection::point(vec![1, 2]),
+                Selection::interval_x(5.0, 0.0),
+            ],
+        };
+        let json = serde_json::to_string(&sel).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"Multi","selections":[{"type":"Point","indices":[1,2]},{"type":"Interval","x":[0.0,5.0],"y":null}]}"#
+        );
+        assert_eq!(serde_json::from_str::<Selection>(&json).unwrap(), sel);
+    }
 }
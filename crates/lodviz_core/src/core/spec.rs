@@ -2,11 +2,14 @@
 ///
 /// Uses zero-sized type markers to enforce required fields at compile time.
 /// `build()` is only available when all required fields (data, mark, x) are set.
-use super::data::{BarDataset, DataPoint, Dataset, Series};
-use super::encoding::Field;
+use super::data::{
+    BarDataset, ChordData, DataPoint, Dataset, GridData, OhlcBar, SankeyData, Series, StripGroup,
+};
+use super::encoding::{Encoding, Field};
 use super::field_value::DataTable;
 use super::mark::Mark;
 use super::theme::{ChartConfig, GridStyle};
+use super::transform::Transform;
 
 // --- Typestate markers ---
 
@@ -23,7 +26,9 @@ pub struct Set<T>(pub T);
 /// Allows passing pre-built datasets, categorical bar data,
 /// or a raw `DataTable` that gets converted at render time
 /// based on the `Mark` type and `Encoding` fields in `ChartSpec`.
-#[derive(Debug, Clone)]
+/// Non-exhaustive like [`Mark`], since new marks bring new data shapes.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum ChartData {
     /// Continuous time-series or scatter data (`Dataset`)
     TimeSeries(Dataset),
@@ -31,6 +36,16 @@ pub enum ChartData {
     Categorical(BarDataset),
     /// Raw tidy table — converted lazily by the chart facade
     Table(DataTable),
+    /// Matrix of cell values for heatmaps (`Rect`)
+    Grid(GridData),
+    /// Values per category for box and strip plots (`Boxplot`, `Tick`)
+    Groups(Vec<StripGroup>),
+    /// Price bars for candlestick charts (`Candlestick`)
+    Ohlc(Vec<OhlcBar>),
+    /// Node/link flows for Sankey diagrams (`Flow`)
+    Flow(SankeyData),
+    /// Flow matrix for chord diagrams (`Flow`)
+    Chord(ChordData),
 }
 
 impl ChartData {
@@ -57,12 +72,29 @@ impl ChartData {
             _ => None,
         }
     }
+
+    /// True when there is nothing to draw
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::TimeSeries(ds) => ds.series.iter().all(|s| s.data.is_empty()),
+            Self::Categorical(bd) => bd.categories.is_empty(),
+            Self::Table(t) => t.is_empty(),
+            Self::Grid(g) => g.values.iter().all(Vec::is_empty),
+            Self::Groups(groups) => groups.iter().all(|g| g.values.is_empty()),
+            Self::Ohlc(bars) => bars.is_empty(),
+            Self::Flow(sankey) => sankey.links.is_empty(),
+            Self::Chord(chord) => chord.matrix.is_empty(),
+        }
+    }
 }
 
 // --- Final spec ---
 
-/// Immutable chart specification with all fields resolved
+/// Immutable chart specification with all fields resolved.
+///
+/// Non-exhaustive: build it with [`ChartSpec::builder`].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ChartSpec {
     /// The data source driving the chart
     pub data: ChartData,
@@ -76,6 +108,22 @@ pub struct ChartSpec {
     pub color: Option<Field>,
     /// The optional field encoding for the size channel
     pub size: Option<Field>,
+    /// The optional field encoding for per-point opacity
+    pub opacity: Option<Field>,
+    /// The optional field encoding for the point shape channel
+    pub shape: Option<Field>,
+    /// The optional field encoding for the line dash channel
+    pub stroke_dash: Option<Field>,
+    /// The optional field encoding for text labels
+    pub text: Option<Field>,
+    /// Extra fields shown in the tooltip
+    pub tooltip: Vec<Field>,
+    /// The optional field that splits series without changing their color
+    pub detail: Option<Field>,
+    /// The optional field that orders points along each path
+    pub order: Option<Field>,
+    /// Transforms applied to a `ChartData::Table` before encoding
+    pub transforms: Vec<Transform>,
     /// Global layout and styling configuration
     pub config: ChartConfig,
 }
@@ -90,9 +138,40 @@ impl ChartSpec {
             y: None,
             color: None,
             size: None,
+            opacity: None,
+            shape: None,
+            stroke_dash: None,
+            text: None,
+            tooltip: Vec::new(),
+            detail: None,
+            order: None,
+            transforms: Vec::new(),
             config: ChartConfig::default(),
         }
     }
+
+    /// Every encoding channel as an `Encoding`, or `None` without a `y`
+    pub fn encoding(&self) -> Option<Encoding> {
+        Some(Encoding {
+            x: self.x.clone(),
+            y: self.y.clone()?,
+            color: self.color.clone(),
+            size: self.size.clone(),
+            opacity: self.opacity.clone(),
+            shape: self.shape.clone(),
+            stroke_dash: self.stroke_dash.clone(),
+            text: self.text.clone(),
+            tooltip: self.tooltip.clone(),
+            detail: self.detail.clone(),
+            order: self.order.clone(),
+        })
+    }
+
+    /// The table source after applying `transforms`, or `None` for
+    /// pre-built datasets.
+    pub fn transformed_table(&self) -> Option<DataTable> {
+        self.data.as_table().map(|t| t.transform(&self.transforms))
+    }
 }
 
 // --- Builder ---
@@ -105,25 +184,47 @@ pub struct ChartSpecBuilder<D, M, X> {
     y: Option<Field>,
     color: Option<Field>,
     size: Option<Field>,
+    opacity: Option<Field>,
+    shape: Option<Field>,
+    stroke_dash: Option<Field>,
+    text: Option<Field>,
+    tooltip: Vec<Field>,
+    detail: Option<Field>,
+    order: Option<Field>,
+    transforms: Vec<Transform>,
     config: ChartConfig,
 }
 
 // Required setters: each consumes self and changes one type parameter
 
 impl<M, X> ChartSpecBuilder<Missing, M, X> {
-    /// Set the dataset directly (required)
-    pub fn data(self, dataset: Dataset) -> ChartSpecBuilder<Set<ChartData>, M, X> {
+    /// Set any `ChartData` as the data source (required), e.g. the output
+    /// of another spec's `resolve()`
+    pub fn chart_data(self, data: ChartData) -> ChartSpecBuilder<Set<ChartData>, M, X> {
         ChartSpecBuilder {
-            data: Set(ChartData::TimeSeries(dataset)),
+            data: Set(data),
             mark: self.mark,
             x: self.x,
             y: self.y,
             color: self.color,
             size: self.size,
+            opacity: self.opacity,
+            shape: self.shape,
+            stroke_dash: self.stroke_dash,
+            text: self.text,
+            tooltip: self.tooltip,
+            detail: self.detail,
+            order: self.order,
+            transforms: self.transforms,
             config: self.config,
         }
     }
 
+    /// Set the dataset directly (required)
+    pub fn data(self, dataset: Dataset) -> ChartSpecBuilder<Set<ChartData>, M, X> {
+        self.chart_data(ChartData::TimeSeries(dataset))
+    }
+
     /// Convenience: wrap a `Vec<DataPoint>` into a single-series `Dataset`
     pub fn data_points(self, points: Vec<DataPoint>) -> ChartSpecBuilder<Set<ChartData>, M, X> {
         let series = Series::new("default", points);
@@ -133,15 +234,7 @@ impl<M, X> ChartSpecBuilder<Missing, M, X> {
 
     /// Set a raw `BarDataset` for categorical bar charts (required)
     pub fn bar_data(self, bar_dataset: BarDataset) -> ChartSpecBuilder<Set<ChartData>, M, X> {
-        ChartSpecBuilder {
-            data: Set(ChartData::Categorical(bar_dataset)),
-            mark: self.mark,
-            x: self.x,
-            y: self.y,
-            color: self.color,
-            size: self.size,
-            config: self.config,
-        }
+        self.chart_data(ChartData::Categorical(bar_dataset))
     }
 
     /// Set a `DataTable` as the data source — converted lazily at render time.
@@ -150,15 +243,7 @@ impl<M, X> ChartSpecBuilder<Missing, M, X> {
     /// to use. The `SmartChart` facade will convert to the appropriate dataset
     /// type based on the `Mark`.
     pub fn from_table(self, table: DataTable) -> ChartSpecBuilder<Set<ChartData>, M, X> {
-        ChartSpecBuilder {
-            data: Set(ChartData::Table(table)),
-            mark: self.mark,
-            x: self.x,
-            y: self.y,
-            color: self.color,
-            size: self.size,
-            config: self.config,
-        }
+        self.chart_data(ChartData::Table(table))
     }
 }
 
@@ -172,6 +257,14 @@ impl<D, X> ChartSpecBuilder<D, Missing, X> {
             y: self.y,
             color: self.color,
             size: self.size,
+            opacity: self.opacity,
+            shape: self.shape,
+            stroke_dash: self.stroke_dash,
+            text: self.text,
+            tooltip: self.tooltip,
+            detail: self.detail,
+            order: self.order,
+            transforms: self.transforms,
             config: self.config,
         }
     }
@@ -187,6 +280,14 @@ impl<D, M> ChartSpecBuilder<D, M, Missing> {
             y: self.y,
             color: self.color,
             size: self.size,
+            opacity: self.opacity,
+            shape: self.shape,
+            stroke_dash: self.stroke_dash,
+            text: self.text,
+            tooltip: self.tooltip,
+            detail: self.detail,
+            order: self.order,
+            transforms: self.transforms,
             config: self.config,
         }
     }
@@ -213,6 +314,54 @@ impl<D, M, X> ChartSpecBuilder<D, M, X> {
         self
     }
 
+    /// Set the per-point opacity encoding field (optional)
+    pub fn opacity(mut self, field: Field) -> Self {
+        self.opacity = Some(field);
+        self
+    }
+
+    /// Set the point shape encoding field (optional)
+    pub fn shape(mut self, field: Field) -> Self {
+        self.shape = Some(field);
+        self
+    }
+
+    /// Set the line dash encoding field (optional)
+    pub fn stroke_dash(mut self, field: Field) -> Self {
+        self.stroke_dash = Some(field);
+        self
+    }
+
+    /// Set the text label encoding field (optional)
+    pub fn text(mut self, field: Field) -> Self {
+        self.text = Some(field);
+        self
+    }
+
+    /// Add a field to the tooltip (optional, repeatable)
+    pub fn tooltip(mut self, field: Field) -> Self {
+        self.tooltip.push(field);
+        self
+    }
+
+    /// Set the detail (grouping without color) field (optional)
+    pub fn detail(mut self, field: Field) -> Self {
+        self.detail = Some(field);
+        self
+    }
+
+    /// Set the path order field (optional)
+    pub fn order(mut self, field: Field) -> Self {
+        self.order = Some(field);
+        self
+    }
+
+    /// Append a data transform, applied in order to a table source (optional)
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.transforms.push(transform);
+        self
+    }
+
     /// Set the chart title (optional)
     pub fn title(mut self, title: impl Into<String>) -> Self {
         self.config.title = Some(title.into());
@@ -248,6 +397,14 @@ impl ChartSpecBuilder<Set<ChartData>, Set<Mark>, Set<Field>> {
             y: self.y,
             color: self.color,
             size: self.size,
+            opacity: self.opacity,
+            shape: self.shape,
+            stroke_dash: self.stroke_dash,
+            text: self.text,
+            tooltip: self.tooltip,
+            detail: self.detail,
+            order: self.order,
+            transforms: self.transforms,
             config: self.config,
         }
     }
@@ -389,6 +546,34 @@ mod tests {
         assert_eq!(spec.data.as_table().unwrap().len(), 1);
     }
 
+    #[test]
+    fn test_transforms_apply_to_table() {
+        use crate::core::field_value::DataTable;
+        use crate::core::transform::{AggregateField, AggregateOp};
+        use crate::data_row;
+
+        let table = DataTable::from_rows(vec![
+            data_row! { "cat" => "a", "v" => 1.0_f64 },
+            data_row! { "cat" => "a", "v" => 2.0_f64 },
+            data_row! { "cat" => "b", "v" => 4.0_f64 },
+        ]);
+        let spec = ChartSpec::builder()
+            .from_table(table)
+            .mark(Mark::Bar)
+            .x(Field::nominal("cat"))
+            .y(Field::quantitative("total"))
+            .transform(Transform::aggregate(
+                &["cat"],
+                vec![AggregateField::new(AggregateOp::Sum, "v", "total")],
+            ))
+            .build();
+
+        let t = spec.transformed_table().unwrap();
+        assert_eq!(t.extract_numeric("total"), vec![3.0, 4.0]);
+        // Source table is untouched
+        assert_eq!(spec.data.as_table().unwrap().len(), 3);
+    }
+
     #[test]
     fn test_bar_data_categorical() {
         let mut bd = BarDataset::new(vec!["Q1".into(), "Q2".into()]);
@@ -403,4 +588,18 @@ mod tests {
         assert!(spec.data.as_bar_dataset().is_some());
         assert_eq!(spec.data.as_bar_dataset().unwrap().series.len(), 1);
     }
+
+    #[test]
+    fn test_chart_data_any_variant() {
+        let grid = ChartData::Grid(GridData {
+            values: vec![vec![1.0, 2.0]],
+            ..GridData::default()
+        });
+        let spec = ChartSpec::builder()
+            .chart_data(grid.clone())
+            .mark(Mark::Rect)
+            .x(Field::nominal("col"))
+            .build();
+        assert_eq!(spec.data, grid);
+    }
 }
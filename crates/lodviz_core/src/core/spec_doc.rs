@@ -0,0 +1,697 @@
+/// Serializable chart spec documents
+///
+/// A [`SpecDocument`] is the stored form of a [`ChartSpec`]: a data source,
+/// a mark, field encodings, declarative transforms and a `ChartConfig`. It
+/// derives serde, so JSON is the primary format but any serde format (e.g.
+/// TOML through the `toml` crate) works the same way:
+///
+/// ```json
+/// {
+///   "data": {"values": [{"region": "EU", "sales": 10}, {"region": "US", "sales": 20}]},
+///   "mark": "bar",
+///   "encoding": {
+///     "x": {"field": "region", "type": "nominal"},
+///     "y": {"field": "total", "type": "quantitative"}
+///   },
+///   "transform": [
+///     {"type": "filter", "expr": "sales > 0"},
+///     {"type": "aggregate", "groupby": ["region"], "fields": [{"op": "sum", "field": "sales", "as": "total"}]}
+///   ],
+///   "config": {"title": "Sales by region"}
+/// }
+/// ```
+///
+/// Loading checks the document itself: unknown keys, unparsable expressions
+/// and unresolved data names are reported as errors. Transforms are only
+/// parsed; they run when the spec is resolved, so encodings referring to
+/// missing columns are reported by [`ChartSpec::resolve`] (or
+/// [`ChartSpec::validate`]) instead of rendering an empty chart.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::algorithms::statistics::BinRule;
+use crate::core::data::{
+    BarDataset, ChordData, Dataset, GridData, OhlcBar, SankeyData, StripGroup,
+};
+use crate::core::encoding::Field;
+use crate::core::expr::{BinaryOp, Expr, UnaryOp};
+use crate::core::field_value::DataTable;
+use crate::core::json::{from_json_value, JsonOptions};
+use crate::core::mark::Mark;
+use crate::core::spec::{ChartData, ChartSpec};
+use crate::core::theme::ChartConfig;
+use crate::core::transform::{
+    AggregateField, AggregateOp, Predicate, SortField, Transform, WindowField, WindowFrame,
+};
+
+// --- Document ---
+
+/// Where a spec document gets its data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataSource {
+    /// Inline rows (`[{..}, ..]`) or a column-oriented object, as accepted
+    /// by [`crate::core::json::parse_json`]
+    Values(Value),
+    /// A table supplied by the caller at load time (see
+    /// [`SpecDocument::into_spec_with`])
+    Name(String),
+    /// An inline `DataTable` in its column-oriented serde shape
+    Table(DataTable),
+    /// A pre-built `Dataset`
+    Dataset(Dataset),
+    /// A pre-built `BarDataset`
+    Bars(BarDataset),
+    /// A pre-built `GridData` (heatmaps)
+    Grid(GridData),
+    /// Pre-built value groups (box and strip plots)
+    Groups(Vec<StripGroup>),
+    /// Pre-built OHLC bars (candlesticks)
+    Ohlc(Vec<OhlcBar>),
+    /// Pre-built `SankeyData`
+    Flow(SankeyData),
+    /// Pre-built `ChordData`
+    Chord(ChordData),
+}
+
+/// Encoding channels of a spec document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpecEncoding {
+    /// X channel (required)
+    pub x: Field,
+    /// Y channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<Field>,
+    /// Color channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<Field>,
+    /// Size channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<Field>,
+    /// Opacity channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opacity: Option<Field>,
+    /// Shape channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<Field>,
+    /// Stroke dash channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stroke_dash: Option<Field>,
+    /// Text channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<Field>,
+    /// Tooltip fields
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tooltip: Vec<Field>,
+    /// Detail (grouping) channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<Field>,
+    /// Order channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<Field>,
+}
+
+/// Serializable form of a [`Transform`]
+///
+/// Filters and calculations are written in the expression language
+/// (see [`crate::core::expr`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TransformDoc {
+    /// Keep rows where `expr` is truthy
+    Filter {
+        /// Predicate expression
+        expr: String,
+    },
+    /// Add a column computed by `expr`
+    Calculate {
+        /// Value expression
+        expr: String,
+        /// Output column name
+        #[serde(rename = "as")]
+        as_name: String,
+    },
+    /// See [`Transform::Aggregate`]
+    Aggregate {
+        /// Grouping columns
+        #[serde(default)]
+        groupby: Vec<String>,
+        /// Summary columns
+        fields: Vec<AggregateField>,
+    },
+    /// See [`Transform::Fold`]
+    Fold {
+        /// Columns to fold
+        fields: Vec<String>,
+        /// Output key column (default `"key"`)
+        #[serde(default = "default_key")]
+        key_as: String,
+        /// Output value column (default `"value"`)
+        #[serde(default = "default_value")]
+        value_as: String,
+    },
+    /// See [`Transform::Pivot`]
+    Pivot {
+        /// Column whose values become column names
+        pivot: String,
+        /// Column providing the cell values
+        value: String,
+        /// Columns identifying an output row
+        #[serde(default)]
+        groupby: Vec<String>,
+        /// Combines values sharing a cell (default `sum`)
+        #[serde(default = "default_pivot_op")]
+        op: AggregateOp,
+    },
+    /// See [`Transform::Sort`]
+    Sort {
+        /// Sort keys
+        fields: Vec<SortField>,
+    },
+    /// See [`Transform::Window`]
+    Window {
+        /// Output columns
+        fields: Vec<WindowField>,
+        /// Partition columns
+        #[serde(default)]
+        groupby: Vec<String>,
+        /// Ordering within a partition
+        #[serde(default)]
+        sort: Vec<SortField>,
+        /// Frame for value and aggregate ops
+        #[serde(default)]
+        frame: WindowFrame,
+    },
+    /// See [`Transform::Bin`]
+    Bin {
+        /// Numeric input column
+        field: String,
+        /// Bin count rule (default Freedman-Diaconis)
+        #[serde(default)]
+        rule: BinRule,
+        /// Output column for the bin start (default `bin_<field>`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        as_start: Option<String>,
+        /// Output column for the bin end (default `bin_<field>_end`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        as_end: Option<String>,
+    },
+}
+
+fn default_key() -> String {
+    "key".to_owned()
+}
+
+fn default_value() -> String {
+    "value".to_owned()
+}
+
+fn default_pivot_op() -> AggregateOp {
+    AggregateOp::Sum
+}
+
+/// A complete, serializable chart definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpecDocument {
+    /// Data source
+    pub data: DataSource,
+    /// Visual mark
+    pub mark: Mark,
+    /// Field encodings
+    pub encoding: SpecEncoding,
+    /// Transforms applied in order to a table source
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transform: Vec<TransformDoc>,
+    /// Layout and styling
+    #[serde(default)]
+    pub config: ChartConfig,
+}
+
+impl SpecDocument {
+    /// Parse a document from JSON text
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|e| format!("invalid chart spec: {e}"))
+    }
+
+    /// Pretty-printed JSON text of the document
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    /// Convert into a [`ChartSpec`] without running its transforms.
+    ///
+    /// Fails on `DataSource::Name`, which needs [`Self::into_spec_with`].
+    pub fn into_spec(self) -> Result<ChartSpec, String> {
+        self.into_spec_with(|_| None)
+    }
+
+    /// Convert into a [`ChartSpec`], resolving named data sources with
+    /// `resolve`.
+    pub fn into_spec_with(
+        self,
+        resolve: impl Fn(&str) -> Option<DataTable>,
+    ) -> Result<ChartSpec, String> {
+        let data = match self.data {
+            DataSource::Values(value) => {
+                let table = from_json_value(value, &JsonOptions::default())
+                    .map_err(|e| format!("data.values: {e}"))?;
+                ChartData::Table(table)
+            }
+            DataSource::Name(name) => ChartData::Table(
+                resolve(&name).ok_or_else(|| format!("data.name: unknown data source '{name}'"))?,
+            ),
+            DataSource::Table(table) => ChartData::Table(table),
+            DataSource::Dataset(ds) => ChartData::TimeSeries(ds),
+            DataSource::Bars(bars) => ChartData::Categorical(bars),
+            DataSource::Grid(grid) => ChartData::Grid(grid),
+            DataSource::Groups(groups) => ChartData::Groups(groups),
+            DataSource::Ohlc(bars) => ChartData::Ohlc(bars),
+            DataSource::Flow(sankey) => ChartData::Flow(sankey),
+            DataSource::Chord(chord) => ChartData::Chord(chord),
+        };
+        let transforms = self
+            .transform
+            .iter()
+            .enumerate()
+            .map(|(i, t)| t.to_transform().map_err(|e| format!("transform[{i}]: {e}")))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ChartSpec {
+            data,
+            mark: self.mark,
+            x: self.encoding.x,
+            y: self.encoding.y,
+            color: self.encoding.color,
+            size: self.encoding.size,
+            opacity: self.encoding.opacity,
+            shape: self.encoding.shape,
+            stroke_dash: self.encoding.stroke_dash,
+            text: self.encoding.text,
+            tooltip: self.encoding.tooltip,
+            detail: self.encoding.detail,
+            order: self.encoding.order,
+            transforms,
+            config: self.config,
+        })
+    }
+
+    /// Document form of `spec`.
+    ///
+    /// Fails when a transform has no declarative form (closure-based
+    /// calculations and `FilterOp` predicates).
+    pub fn from_spec(spec: &ChartSpec) -> Result<Self, String> {
+        let data = match &spec.data {
+            ChartData::TimeSeries(ds) => DataSource::Dataset(ds.clone()),
+            ChartData::Categorical(bars) => DataSource::Bars(bars.clone()),
+            ChartData::Table(table) => DataSource::Table(table.clone()),
+            ChartData::Grid(grid) => DataSource::Grid(grid.clone()),
+            ChartData::Groups(groups) => DataSource::Groups(groups.clone()),
+            ChartData::Ohlc(bars) => DataSource::Ohlc(bars.clone()),
+            ChartData::Flow(sankey) => DataSource::Flow(sankey.clone()),
+            ChartData::Chord(chord) => DataSource::Chord(chord.clone()),
+        };
+        let transform = spec
+            .transforms
+            .iter()
+            .enumerate()
+            .map(|(i, t)| {
+                TransformDoc::from_transform(t).map_err(|e| format!("transform[{i}]: {e}"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            data,
+            mark: spec.mark,
+            encoding: SpecEncoding {
+                x: spec.x.clone(),
+                y: spec.y.clone(),
+                color: spec.color.clone(),
+                size: spec.size.clone(),
+                opacity: spec.opacity.clone(),
+                shape: spec.shape.clone(),
+                stroke_dash: spec.stroke_dash.clone(),
+                text: spec.text.clone(),
+                tooltip: spec.tooltip.clone(),
+                detail: spec.detail.clone(),
+                order: spec.order.clone(),
+            },
+            transform,
+            config: spec.config.clone(),
+        })
+    }
+}
+
+impl TransformDoc {
+    /// Build the executable transform, parsing any expressions
+    pub fn to_transform(&self) -> Result<Transform, String> {
+        let t = match self {
+            Self::Filter { expr } => Transform::Filter(Predicate::expr(expr)?),
+            Self::Calculate { expr, as_name } => Transform::calculate_expr(as_name, expr)?,
+            Self::Aggregate { groupby, fields } => Transform::Aggregate {
+                groupby: groupby.clone(),
+                fields: fields.clone(),
+            },
+            Self::Fold {
+                fields,
+                key_as,
+                value_as,
+            } => Transform::Fold {
+                fields: fields.clone(),
+                key_as: key_as.clone(),
+                value_as: value_as.clone(),
+            },
+            Self::Pivot {
+                pivot,
+                value,
+                groupby,
+                op,
+            } => Transform::Pivot {
+                pivot: pivot.clone(),
+                value: value.clone(),
+                groupby: groupby.clone(),
+                op: *op,
+            },
+            Self::Sort { fields } => Transform::Sort(fields.clone()),
+            Self::Window {
+                fields,
+                groupby,
+                sort,
+                frame,
+            } => Transform::Window {
+                fields: fields.clone(),
+                groupby: groupby.clone(),
+                sort: sort.clone(),
+                frame: *frame,
+            },
+            Self::Bin {
+                field,
+                rule,
+                as_start,
+                as_end,
+            } => {
+                let mut t = Transform::bin(field, *rule);
+                if let Transform::Bin {
+                    as_start: s,
+                    as_end: e,
+                    ..
+                } = &mut t
+                {
+                    if let Some(name) = as_start {
+                        s.clone_from(name);
+                    }
+                    if let Some(name) = as_end {
+                        e.clone_from(name);
+                    }
+                }
+                t
+            }
+        };
+        Ok(t)
+    }
+
+    /// Declarative form of `transform`
+    pub fn from_transform(transform: &Transform) -> Result<Self, String> {
+        let doc = match transform {
+            Transform::Filter(pred) => Self::Filter {
+                expr: predicate_expr(pred)?.to_string(),
+            },
+            Transform::Calculate { calc, as_name } => Self::Calculate {
+                expr: calc
+                    .expr()
+                    .ok_or("closure calculations cannot be serialized")?
+                    .to_string(),
+                as_name: as_name.clone(),
+            },
+            Transform::Aggregate { groupby, fields } => Self::Aggregate {
+                groupby: groupby.clone(),
+                fields: fields.clone(),
+            },
+            Transform::Fold {
+                fields,
+                key_as,
+                value_as,
+            } => Self::Fold {
+                fields: fields.clone(),
+                key_as: key_as.clone(),
+                value_as: value_as.clone(),
+            },
+            Transform::Pivot {
+                pivot,
+                value,
+                groupby,
+                op,
+            } => Self::Pivot {
+                pivot: pivot.clone(),
+                value: value.clone(),
+                groupby: groupby.clone(),
+                op: *op,
+            },
+            Transform::Sort(fields) => Self::Sort {
+                fields: fields.clone(),
+            },
+            Transform::Window {
+                fields,
+                groupby,
+                sort,
+                frame,
+            } => Self::Window {
+                fields: fields.clone(),
+                groupby: groupby.clone(),
+                sort: sort.clone(),
+                frame: *frame,
+            },
+            Transform::Bin {
+                field,
+                rule,
+                as_start,
+                as_end,
+            } => Self::Bin {
+                field: field.clone(),
+                rule: *rule,
+                as_start: Some(as_start.clone()),
+                as_end: Some(as_end.clone()),
+            },
+        };
+        Ok(doc)
+    }
+}
+
+/// Expression equivalent of a predicate tree
+fn predicate_expr(pred: &Predicate) -> Result<Expr, String> {
+    let combine = |preds: &[Predicate], op: BinaryOp, empty: bool| -> Result<Expr, String> {
+        let mut exprs = preds.iter().map(predicate_expr);
+        let Some(first) = exprs.next() else {
+            return Ok(Expr::Literal(empty.into()));
+        };
+        exprs.try_fold(first?, |acc, e| {
+            Ok(Expr::Binary(op, Box::new(acc), Box::new(e?)))
+        })
+    };
+    match pred {
+        Predicate::Expr(e) => Ok(e.clone()),
+        Predicate::And(preds) => combine(preds, BinaryOp::And, true),
+        Predicate::Or(preds) => combine(preds, BinaryOp::Or, false),
+        Predicate::Not(p) => Ok(Expr::Unary(UnaryOp::Not, Box::new(predicate_expr(p)?))),
+        Predicate::Field { field, .. } => Err(format!(
+            "field predicate on '{field}' cannot be serialized; use an expression"
+        )),
+    }
+}
+
+// --- ChartSpec shortcuts ---
+
+impl ChartSpec {
+    /// Load and validate a spec from JSON text (see [`SpecDocument`])
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        SpecDocument::from_json(text)?.into_spec()
+    }
+
+    /// Load a spec from JSON text, resolving `{"data": {"name": ..}}`
+    /// references with `resolve`
+    pub fn from_json_with(
+        text: &str,
+        resolve: impl Fn(&str) -> Option<DataTable>,
+    ) -> Result<Self, String> {
+        SpecDocument::from_json(text)?.into_spec_with(resolve)
+    }
+
+    /// Pretty-printed JSON document for this spec
+    pub fn to_json(&self) -> Result<String, String> {
+        SpecDocument::from_spec(self)?.to_json()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::data::DataType;
+    use crate::core::field_value::FieldValue;
+    use crate::data_row;
+
+    const BAR_SPEC: &str = r#"{
+        "data": {"values": [
+            {"region": "EU", "sales": 10},
+            {"region": "US", "sales": 20},
+            {"region": "EU", "sales": 5},
+            {"region": "US", "sales": -1}
+        ]},
+        "mark": "bar",
+        "encoding": {
+            "x": {"field": "region", "type": "nominal"},
+            "y": {"field": "total", "type": "quantitative"}
+        },
+        "transform": [
+            {"type": "filter", "expr": "sales > 0"},
+            {"type": "aggregate", "groupby": ["region"],
+             "fields": [{"op": "sum", "field": "sales", "as": "total"}]}
+        ],
+        "config": {"title": "Sales by region"}
+    }"#;
+
+    #[test]
+    fn test_load_inline_values() {
+        let spec = ChartSpec::from_json(BAR_SPEC).unwrap();
+        assert_eq!(spec.mark, Mark::Bar);
+        assert_eq!(spec.x.data_type, DataType::Nominal);
+        assert_eq!(spec.config.title.as_deref(), Some("Sales by region"));
+        let t = spec.transformed_table().unwrap();
+        assert_eq!(t.extract_numeric("total"), vec![15.0, 20.0]);
+    }
+
+    #[test]
+    fn test_load_toml_document() {
+        let text = r#"
+            mark = "bar"
+            transform = [
+                { type = "filter", expr = "sales > 0" },
+                { type = "aggregate", groupby = ["region"], fields = [{ op = "sum", field = "sales", as = "total" }] },
+            ]
+
+            [data]
+            values = [
+                { region = "EU", sales = 10 },
+                { region = "US", sales = 20 },
+                { region = "EU", sales = 5 },
+            ]
+
+            [encoding]
+            x = { field = "region", type = "nominal" }
+            y = { field = "total", type = "quantitative" }
+
+            [config]
+            title = "Sales by region"
+        "#;
+        let doc: SpecDocument = toml::from_str(text).unwrap();
+        let spec = doc.into_spec().unwrap();
+        assert_eq!(spec.mark, Mark::Bar);
+        assert_eq!(spec.config.title.as_deref(), Some("Sales by region"));
+        let ChartData::Categorical(bars) = spec.resolve().unwrap() else {
+            panic!("expected bars");
+        };
+        assert_eq!(bars.categories, vec!["EU", "US"]);
+        assert_eq!(bars.series[0].values, vec![15.0, 20.0]);
+
+        let typo = text.replace("[encoding]", "[encodings]");
+        assert!(toml::from_str::<SpecDocument>(&typo).is_err());
+    }
+
+    #[test]
+    fn test_transforms_run_at_resolve() {
+        let spec = ChartSpec::from_json(BAR_SPEC).unwrap();
+        let table = spec.data.as_table().unwrap();
+        assert_eq!(table.len(), 4);
+        assert!(table.column("total").is_none());
+        assert_eq!(spec.transforms.len(), 2);
+    }
+
+    #[test]
+    fn test_named_data_source() {
+        let text = r#"{"data": {"name": "sales"}, "mark": "line",
+            "encoding": {"x": {"field": "x", "type": "quantitative"}}}"#;
+        let table = DataTable::from_rows(vec![data_row! { "x" => 1.0_f64 }]);
+        let spec = ChartSpec::from_json_with(text, |name| (name == "sales").then(|| table.clone()))
+            .unwrap();
+        assert_eq!(spec.data.as_table().unwrap().len(), 1);
+
+        let err = ChartSpec::from_json(text).unwrap_err();
+        assert!(err.contains("unknown data source 'sales'"), "{err}");
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let unknown_field = BAR_SPEC.replace(r#""field": "total""#, r#""field": "totl""#);
+        let spec = ChartSpec::from_json(&unknown_field).unwrap();
+        assert_eq!(
+            spec.resolve().unwrap_err().to_string(),
+            "encoding.y: unknown field 'totl' (did you mean 'total'?)"
+        );
+
+        let bad_expr = BAR_SPEC.replace("sales > 0", "sales >");
+        let err = ChartSpec::from_json(&bad_expr).unwrap_err();
+        assert!(err.starts_with("transform[0]:"), "{err}");
+
+        let typo = BAR_SPEC.replace(r#""mark""#, r#""marks""#);
+        assert!(ChartSpec::from_json(&typo).is_err());
+
+        let bad_mark = BAR_SPEC.replace(r#""bar""#, r#""pie""#);
+        assert!(ChartSpec::from_json(&bad_mark).is_err());
+    }
+
+    #[test]
+    fn test_roundtrip_from_builder() {
+        let table = DataTable::from_rows(vec![
+            data_row! { "day" => FieldValue::Timestamp(0.0), "v" => 1.0_f64 },
+            data_row! { "day" => FieldValue::Timestamp(86_400_000.0), "v" => 2.0_f64 },
+        ]);
+        let spec = ChartSpec::builder()
+            .from_table(table)
+            .mark(Mark::Line)
+            .x(Field::temporal("day"))
+            .y(Field::quantitative("v2"))
+            .transform(Transform::calculate_expr("v2", "v * 2").unwrap())
+            .transform(Transform::filter(Predicate::Not(Box::new(
+                Predicate::expr("v > 5").unwrap(),
+            ))))
+            .title("Daily")
+            .build();
+
+        let json = spec.to_json().unwrap();
+        let back = ChartSpec::from_json(&json).unwrap();
+        assert_eq!(back.mark, Mark::Line);
+        assert_eq!(back.x.data_type, DataType::Temporal);
+        assert_eq!(back.config.title.as_deref(), Some("Daily"));
+        assert_eq!(
+            back.transformed_table().unwrap().extract_numeric("v2"),
+            vec![2.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn test_closure_calculation_is_not_serializable() {
+        let spec = ChartSpec::builder()
+            .data_points(vec![])
+            .mark(Mark::Line)
+            .x(Field::quantitative("x"))
+            .transform(Transform::calculate("c", |_, _| FieldValue::Null))
+            .build();
+        assert!(spec.to_json().unwrap_err().contains("transform[0]"));
+    }
+
+    #[test]
+    fn test_bin_defaults_and_overrides() {
+        let doc: TransformDoc =
+            serde_json::from_str(r#"{"type": "bin", "field": "v", "rule": {"fixed": 4}}"#).unwrap();
+        let Transform::Bin {
+            rule,
+            as_start,
+            as_end,
+            ..
+        } = doc.to_transform().unwrap()
+        else {
+            panic!("expected bin");
+        };
+        assert_eq!(rule, BinRule::Fixed(4));
+        assert_eq!((as_start.as_str(), as_end.as_str()), ("bin_v", "bin_v_end"));
+    }
+}
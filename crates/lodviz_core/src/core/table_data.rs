@@ -6,7 +6,10 @@
 /// filtering, and conditional formatting.
 use std::cmp::Ordering;
 
+use serde::{Deserialize, Serialize};
+
 use crate::core::field_value::FieldValue;
+use crate::core::format::{FormatSpec, NumberFormat};
 
 // --- TableData ---
 
@@ -80,6 +83,8 @@ pub struct ColumnDef {
     pub alignment: Alignment,
     /// Optional conditional formatting rule.
     pub conditional: Option<ConditionalRule>,
+    /// Optional number format for numeric cells (e.g. `"$,.2f"`).
+    pub number_format: Option<NumberFormat>,
 }
 
 impl ColumnDef {
@@ -94,6 +99,7 @@ impl ColumnDef {
             width: None,
             alignment: Alignment::Left,
             conditional: None,
+            number_format: None,
         }
     }
 
@@ -111,6 +117,19 @@ impl ColumnDef {
         self
     }
 
+    /// Format numeric cells with `format` (builder pattern).
+    #[must_use]
+    pub fn number_format(mut self, format: NumberFormat) -> Self {
+        self.number_format = Some(format);
+        self
+    }
+
+    /// Format a cell of this column: numbers through `number_format` when
+    /// set, everything else as [`format_cell_value`].
+    pub fn format_value(&self, val: &FieldValue) -> String {
+        format_cell_value_with(val, self.number_format.as_ref())
+    }
+
     /// Disable sorting for this column (builder pattern).
     #[must_use]
     pub fn no_sort(mut self) -> Self {
@@ -285,7 +304,8 @@ pub struct SortKey {
 }
 
 /// Sort direction.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SortDir {
     Asc,
     Desc,
@@ -362,23 +382,29 @@ fn sortable_string(val: &FieldValue) -> String {
 pub fn format_cell_value(val: &FieldValue) -> String {
     match val {
         FieldValue::Text(s) => s.clone(),
-        FieldValue::Numeric(n) => {
-            if n.fract() == 0.0 && n.abs() < 1e15 {
-                format!("{:.0}", n)
-            } else {
-                // Up to 4 decimal places, trailing zeros stripped
-                format!("{:.4}", n)
-                    .trim_end_matches('0')
-                    .trim_end_matches('.')
-                    .to_owned()
-            }
-        }
+        // Up to 4 decimal places, trailing zeros stripped
+        FieldValue::Numeric(n) => NumberFormat::from_spec(FormatSpec {
+            precision: Some(4),
+            trim: true,
+            kind: Some('f'),
+            ..FormatSpec::default()
+        })
+        .format(*n),
         FieldValue::Timestamp(t) => format!("{t}"),
         FieldValue::Bool(b) => if *b { "Yes" } else { "No" }.to_owned(),
         FieldValue::Null => String::new(),
     }
 }
 
+/// Format a `FieldValue` like [`format_cell_value`], numbers through
+/// `format` when given.
+pub fn format_cell_value_with(val: &FieldValue, format: Option<&NumberFormat>) -> String {
+    match (val, format) {
+        (FieldValue::Numeric(n), Some(format)) => format.format(*n),
+        _ => format_cell_value(val),
+    }
+}
+
 /// Compute the 0–1 normalised position of `val` within the column's numeric range.
 /// Returns `0.0` for non-numeric values or when all values are equal.
 pub fn data_bar_pct(val: &FieldValue, rows: &[Vec<FieldValue>], col_idx: usize) -> f64 {
@@ -562,6 +588,15 @@ mod tests {
         assert_eq!(format_cell_value(&FieldValue::Numeric(3.1234)), "3.1234");
     }
 
+    #[test]
+    fn column_number_format_applies_to_numbers_only() {
+        let col = ColumnDef::new("price", "Price", ColumnType::Number)
+            .number_format(NumberFormat::new("$,.2f").unwrap());
+        assert_eq!(col.format_value(&FieldValue::Numeric(1234.5)), "$1,234.50");
+        assert_eq!(col.format_value(&FieldValue::Text("n/a".into())), "n/a");
+        assert_eq!(col.format_value(&FieldValue::Null), "");
+    }
+
     #[test]
     fn data_bar_pct_normalises() {
         let rows = vec![
@@ -1,4 +1,6 @@
 /// Theme configuration for charts
+use crate::core::format::{Locale, NumberFormat};
+use crate::core::scale::{AxisScale, ScaleType};
 use serde::{Deserialize, Serialize};
 
 /// Grid line styling configuration
@@ -586,6 +588,24 @@ pub struct ChartConfig {
     pub show_legend: Option<bool>,
     /// Legend placement. true = outside the plot area (right margin). None/false = overlay inside plot.
     pub legend_outside: Option<bool>,
+    /// Fixed x domain `(min, max)` instead of the data extent (e.g. shared across facets)
+    pub x_domain: Option<(f64, f64)>,
+    /// Fixed y / value domain `(min, max)` instead of the data extent
+    pub y_domain: Option<(f64, f64)>,
+    /// X axis scale kind. None = linear; `ScaleType::Time` reads x as Unix
+    /// ms timestamps with calendar ticks and labels in its zone. A log axis
+    /// cannot show zero or negatives: a non-positive low end is replaced by
+    /// `max / 1000`, an all non-positive domain by `(1, 10)`
+    pub x_scale: Option<ScaleType>,
+    /// Y / value axis scale kind. None = linear; log domains are adjusted
+    /// like `x_scale` (e.g. a bar chart's `0..500` is drawn as `0.5..500`)
+    pub y_scale: Option<ScaleType>,
+    /// d3-format specifier for x values (axis ticks, tooltip header)
+    pub x_format: Option<String>,
+    /// d3-format specifier for y / values (axis ticks, tooltips, labels, colorbar)
+    pub y_format: Option<String>,
+    /// Locale for `x_format` / `y_format`. None = en-US
+    pub locale: Option<Locale>,
 }
 
 /// Margin configuration around the chart area
@@ -657,6 +677,83 @@ impl ChartConfig {
         self.legend_outside = Some(outside);
         self
     }
+
+    /// Fix the x domain
+    pub fn with_x_domain(mut self, min: f64, max: f64) -> Self {
+        self.x_domain = Some((min, max));
+        self
+    }
+
+    /// Fix the y (value) domain
+    pub fn with_y_domain(mut self, min: f64, max: f64) -> Self {
+        self.y_domain = Some((min, max));
+        self
+    }
+
+    /// Set the x axis scale kind
+    pub fn with_x_scale(mut self, kind: ScaleType) -> Self {
+        self.x_scale = Some(kind);
+        self
+    }
+
+    /// Set the y (value) axis scale kind
+    pub fn with_y_scale(mut self, kind: ScaleType) -> Self {
+        self.y_scale = Some(kind);
+        self
+    }
+
+    /// Format x values with a d3-format specifier (e.g. `",.0f"`)
+    pub fn with_x_format(mut self, specifier: impl Into<String>) -> Self {
+        self.x_format = Some(specifier.into());
+        self
+    }
+
+    /// Format y values with a d3-format specifier (e.g. `"$.2s"`)
+    pub fn with_y_format(mut self, specifier: impl Into<String>) -> Self {
+        self.y_format = Some(specifier.into());
+        self
+    }
+
+    /// Set the locale used by the number formats
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Parsed x number format; None when unset or not a valid specifier
+    pub fn x_number_format(&self) -> Option<NumberFormat> {
+        self.number_format(self.x_format.as_deref()?)
+    }
+
+    /// Parsed y number format; None when unset or not a valid specifier
+    pub fn y_number_format(&self) -> Option<NumberFormat> {
+        self.number_format(self.y_format.as_deref()?)
+    }
+
+    fn number_format(&self, specifier: &str) -> Option<NumberFormat> {
+        let format = NumberFormat::new(specifier).ok()?;
+        Some(format.with_locale(self.locale.clone().unwrap_or_default()))
+    }
+
+    /// Effective x axis scale kind
+    pub fn x_scale_type(&self) -> ScaleType {
+        self.x_scale.unwrap_or_default()
+    }
+
+    /// Effective y axis scale kind
+    pub fn y_scale_type(&self) -> ScaleType {
+        self.y_scale.unwrap_or_default()
+    }
+
+    /// X axis scale over `domain` → `range`, with the configured kind
+    pub fn x_axis_scale(&self, domain: (f64, f64), range: (f64, f64)) -> AxisScale {
+        AxisScale::new(self.x_scale_type(), domain, range)
+    }
+
+    /// Y axis scale over `domain` → `range`, with the configured kind
+    pub fn y_axis_scale(&self, domain: (f64, f64), range: (f64, f64)) -> AxisScale {
+        AxisScale::new(self.y_scale_type(), domain, range)
+    }
 }
 
 /// Parse a hex color string (#RGB, #RRGGBB, or #RRGGBBAA) into (r, g, b) as 0.0..1.0
@@ -972,4 +1069,34 @@ mod tests {
         assert!(generate_palette(&["#fff"], 5).is_empty());
         assert!(generate_palette(&["#000", "#fff"], 0).is_empty());
     }
+
+    #[test]
+    fn test_number_formats_use_locale() {
+        let cfg = ChartConfig::new()
+            .with_y_format("$,.2f")
+            .with_x_format("not a format")
+            .with_locale(Locale::de_de());
+        assert_eq!(
+            cfg.y_number_format().unwrap().format(1234.5),
+            "1.234,50\u{a0}€"
+        );
+        assert!(cfg.x_number_format().is_none());
+        assert!(ChartConfig::new().y_number_format().is_none());
+    }
+
+    #[test]
+    fn test_log_axis_replaces_non_positive_domain() {
+        use crate::core::scale::Scale;
+
+        let cfg = ChartConfig::new()
+            .with_x_scale(ScaleType::Log)
+            .with_y_scale(ScaleType::Log);
+        let y = cfg.y_axis_scale((0.0, 500.0), (300.0, 0.0));
+        assert_eq!(y.domain(), (0.5, 500.0));
+        assert!(y.map(0.5).is_finite());
+        let x = cfg.x_axis_scale((-5.0, -1.0), (0.0, 400.0));
+        assert_eq!(x.domain(), (1.0, 10.0));
+        let linear = ChartConfig::new().y_axis_scale((0.0, 500.0), (300.0, 0.0));
+        assert_eq!(linear.domain(), (0.0, 500.0));
+    }
 }
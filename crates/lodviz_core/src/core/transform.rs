@@ -0,0 +1,948 @@
+/// Declarative data transforms on `DataTable` (Vega-Lite style)
+///
+/// A `Vec<Transform>` describes how raw rows are reshaped before they reach
+/// a chart: filter, calculate, aggregate, fold, pivot, sort, window and bin.
+/// Transforms run in order, each producing a new `DataTable`.
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::statistics::{self, BinRule};
+use crate::core::column::{Column, GroupKey};
+use crate::core::expr::Expr;
+use crate::core::field_value::{DataTable, FieldValue};
+use crate::core::table_data::{compare_field_values, FilterOp, SortDir};
+
+// --- Predicate ---
+
+/// Row predicate used by [`Transform::Filter`]
+#[derive(Debug, Clone)]
+pub enum Predicate {
+    /// Test a single field with a [`FilterOp`]
+    Field {
+        /// Column name
+        field: String,
+        /// Test applied to the cell value
+        op: FilterOp,
+    },
+    /// All predicates must hold
+    And(Vec<Predicate>),
+    /// At least one predicate must hold
+    Or(Vec<Predicate>),
+    /// Negation
+    Not(Box<Predicate>),
+    /// Expression evaluated for truthiness (see [`crate::core::expr::truthy`])
+    Expr(Expr),
+}
+
+impl Predicate {
+    /// Field predicate shorthand
+    pub fn field(field: impl Into<String>, op: FilterOp) -> Self {
+        Self::Field {
+            field: field.into(),
+            op,
+        }
+    }
+
+    /// Parse an expression predicate, e.g. `amount > 100 && region != "EU"`
+    pub fn expr(source: &str) -> Result<Self, String> {
+        Expr::parse(source).map(Self::Expr)
+    }
+
+    /// Returns `true` if row `row` of `table` passes the predicate
+    pub fn test(&self, table: &DataTable, row: usize) -> bool {
+        match self {
+            Self::Field { field, op } => op.matches(&table.value(row, field)),
+            Self::And(preds) => preds.iter().all(|p| p.test(table, row)),
+            Self::Or(preds) => preds.iter().any(|p| p.test(table, row)),
+            Self::Not(pred) => !pred.test(table, row),
+            Self::Expr(expr) => expr.test(table, row),
+        }
+    }
+}
+
+// --- Calculation ---
+
+type RowFn = dyn Fn(&DataTable, usize) -> FieldValue + Send + Sync;
+
+/// Per-row function producing a derived value for [`Transform::Calculate`]
+#[derive(Clone)]
+pub struct Calculation {
+    f: Arc<RowFn>,
+    expr: Option<Expr>,
+}
+
+impl Calculation {
+    /// Wrap a closure `(table, row) → value`
+    pub fn new(f: impl Fn(&DataTable, usize) -> FieldValue + Send + Sync + 'static) -> Self {
+        Self {
+            f: Arc::new(f),
+            expr: None,
+        }
+    }
+
+    /// Evaluate a parsed expression per row
+    pub fn from_expr(expr: Expr) -> Self {
+        let source = expr.clone();
+        Self {
+            f: Arc::new(move |table, row| expr.eval(table, row)),
+            expr: Some(source),
+        }
+    }
+
+    /// The source expression, when built with [`Calculation::from_expr`]
+    pub fn expr(&self) -> Option<&Expr> {
+        self.expr.as_ref()
+    }
+
+    /// Evaluate for one row
+    pub fn eval(&self, table: &DataTable, row: usize) -> FieldValue {
+        (self.f)(table, row)
+    }
+}
+
+impl fmt::Debug for Calculation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.expr {
+            Some(expr) => write!(f, "Calculation({expr})"),
+            None => f.write_str("Calculation(..)"),
+        }
+    }
+}
+
+// --- Aggregation ---
+
+/// Summary operation applied to the values of a group
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregateOp {
+    /// Number of rows in the group
+    Count,
+    /// Number of distinct non-null values
+    Distinct,
+    /// Sum of numeric values
+    Sum,
+    /// Arithmetic mean
+    Mean,
+    /// Median (50th percentile)
+    Median,
+    /// Minimum value
+    Min,
+    /// Maximum value
+    Max,
+    /// The given quantile (0–1) with linear interpolation, e.g. `Quantile(0.9)`
+    Quantile(f64),
+}
+
+impl AggregateOp {
+    /// Short lowercase name (`"sum"`, `"q90"`, `"q50.1"`), used for derived
+    /// column names. Quantiles keep their percentage to nine decimals, so
+    /// distinct quantiles of one field get distinct columns
+    pub fn name(self) -> String {
+        match self {
+            Self::Count => "count".into(),
+            Self::Distinct => "distinct".into(),
+            Self::Sum => "sum".into(),
+            Self::Mean => "mean".into(),
+            Self::Median => "median".into(),
+            Self::Min => "min".into(),
+            Self::Max => "max".into(),
+            // Rounded at 1e-9 % so 0.07 reads "q7", not "q7.000000000000001"
+            Self::Quantile(p) => format!("q{}", (p * 1e11).round() / 1e9),
+        }
+    }
+
+    /// Apply the operation to the rows `rows` of `col`.
+    ///
+    /// `Count` counts rows (and works without a column); the other ops skip
+    /// nulls and return `Null` when no numeric value is left (`Sum` → 0).
+    pub fn apply(self, col: Option<&Column>, rows: &[usize]) -> FieldValue {
+        if self == Self::Count {
+            return FieldValue::Numeric(rows.len() as f64);
+        }
+        let Some(col) = col else {
+            return match self {
+                Self::Sum | Self::Distinct => FieldValue::Numeric(0.0),
+                _ => FieldValue::Null,
+            };
+        };
+        if self == Self::Distinct {
+            let mut seen = std::collections::HashSet::new();
+            for &i in rows {
+                if col.is_valid(i) {
+                    seen.insert(col.group_key(i));
+                }
+            }
+            return FieldValue::Numeric(seen.len() as f64);
+        }
+        let mut values: Vec<f64> = rows.iter().filter_map(|&i| col.get_f64(i)).collect();
+        let result = match self {
+            Self::Sum => Some(statistics::sum(&values)),
+            Self::Mean => statistics::mean(&values),
+            Self::Median => statistics::median(&mut values),
+            Self::Min => statistics::extent(&values).map(|(lo, _)| lo),
+            Self::Max => statistics::extent(&values).map(|(_, hi)| hi),
+            Self::Quantile(p) => statistics::quantile(&mut values, p),
+            Self::Count | Self::Distinct => unreachable!("handled above"),
+        };
+        result.map_or(FieldValue::Null, FieldValue::Numeric)
+    }
+}
+
+/// One output column of [`Transform::Aggregate`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateField {
+    /// Operation to apply
+    pub op: AggregateOp,
+    /// Input column (`None` is only meaningful for `Count`)
+    #[serde(default)]
+    pub field: Option<String>,
+    /// Output column name
+    #[serde(rename = "as")]
+    pub as_name: String,
+}
+
+impl AggregateField {
+    /// Aggregate `field` with `op` into `as_name`
+    pub fn new(op: AggregateOp, field: impl Into<String>, as_name: impl Into<String>) -> Self {
+        Self {
+            op,
+            field: Some(field.into()),
+            as_name: as_name.into(),
+        }
+    }
+
+    /// Row count into `as_name`
+    pub fn count(as_name: impl Into<String>) -> Self {
+        Self {
+            op: AggregateOp::Count,
+            field: None,
+            as_name: as_name.into(),
+        }
+    }
+}
+
+// --- Sorting ---
+
+/// A sort criterion: column name and direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SortField {
+    /// Column name
+    pub field: String,
+    /// Ascending or descending
+    #[serde(default = "default_order")]
+    pub order: SortDir,
+}
+
+fn default_order() -> SortDir {
+    SortDir::Asc
+}
+
+impl SortField {
+    /// Ascending sort on `field`
+    pub fn asc(field: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            order: SortDir::Asc,
+        }
+    }
+
+    /// Descending sort on `field`
+    pub fn desc(field: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            order: SortDir::Desc,
+        }
+    }
+}
+
+// --- Window ---
+
+/// Operation computed by [`Transform::Window`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowOp {
+    /// 1-based position within the partition
+    RowNumber,
+    /// Rank with gaps for ties (1, 1, 3)
+    Rank,
+    /// Rank without gaps for ties (1, 1, 2)
+    DenseRank,
+    /// Value `n` rows before the current row
+    Lag(usize),
+    /// Value `n` rows after the current row
+    Lead(usize),
+    /// First value of the frame
+    FirstValue,
+    /// Last value of the frame
+    LastValue,
+    /// Aggregate over the frame (e.g. running sum)
+    Aggregate(AggregateOp),
+}
+
+/// One output column of [`Transform::Window`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowField {
+    /// Operation to apply
+    pub op: WindowOp,
+    /// Input column (unused by the ranking ops)
+    #[serde(default)]
+    pub field: Option<String>,
+    /// Output column name
+    #[serde(rename = "as")]
+    pub as_name: String,
+}
+
+impl WindowField {
+    /// Window operation on `field` into `as_name`
+    pub fn new(op: WindowOp, field: impl Into<String>, as_name: impl Into<String>) -> Self {
+        Self {
+            op,
+            field: Some(field.into()),
+            as_name: as_name.into(),
+        }
+    }
+
+    /// Ranking operation (row number, rank, dense rank) into `as_name`
+    pub fn ranking(op: WindowOp, as_name: impl Into<String>) -> Self {
+        Self {
+            op,
+            field: None,
+            as_name: as_name.into(),
+        }
+    }
+}
+
+/// Rows around the current row included in a window aggregate.
+///
+/// `None` means unbounded. The default (unbounded preceding, current row)
+/// yields cumulative values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowFrame {
+    /// Rows before the current row
+    pub preceding: Option<usize>,
+    /// Rows after the current row
+    pub following: Option<usize>,
+}
+
+impl Default for WindowFrame {
+    fn default() -> Self {
+        Self {
+            preceding: None,
+            following: Some(0),
+        }
+    }
+}
+
+// --- Transform ---
+
+/// A single data transform step
+#[derive(Debug, Clone)]
+pub enum Transform {
+    /// Keep rows matching the predicate
+    Filter(Predicate),
+    /// Add (or replace) a column computed per row
+    Calculate {
+        /// Per-row function
+        calc: Calculation,
+        /// Output column name
+        as_name: String,
+    },
+    /// Group rows and summarize each group into one row
+    Aggregate {
+        /// Grouping columns (empty = one group for the whole table)
+        groupby: Vec<String>,
+        /// Summary columns
+        fields: Vec<AggregateField>,
+    },
+    /// Wide → long: one output row per (input row, folded field)
+    Fold {
+        /// Columns to fold
+        fields: Vec<String>,
+        /// Output column holding the field name
+        key_as: String,
+        /// Output column holding the field value
+        value_as: String,
+    },
+    /// Long → wide: one output column per distinct value of `pivot`
+    Pivot {
+        /// Column whose values become new column names
+        pivot: String,
+        /// Column providing the cell values
+        value: String,
+        /// Columns identifying an output row
+        groupby: Vec<String>,
+        /// Combines multiple values landing in the same cell
+        op: AggregateOp,
+    },
+    /// Stable multi-key sort (nulls last)
+    Sort(Vec<SortField>),
+    /// Ranking, offset and running aggregates over ordered partitions
+    Window {
+        /// Output columns
+        fields: Vec<WindowField>,
+        /// Partition columns
+        groupby: Vec<String>,
+        /// Ordering within a partition (empty = table order)
+        sort: Vec<SortField>,
+        /// Frame for `FirstValue`, `LastValue` and aggregates
+        frame: WindowFrame,
+    },
+    /// Discretize a numeric column into `[start, end)` bins
+    Bin {
+        /// Numeric input column
+        field: String,
+        /// Bin count rule
+        rule: BinRule,
+        /// Output column for the bin start
+        as_start: String,
+        /// Output column for the bin end
+        as_end: String,
+    },
+}
+
+impl Transform {
+    /// Filter shorthand
+    pub fn filter(predicate: Predicate) -> Self {
+        Self::Filter(predicate)
+    }
+
+    /// Calculate shorthand
+    pub fn calculate(
+        as_name: impl Into<String>,
+        f: impl Fn(&DataTable, usize) -> FieldValue + Send + Sync + 'static,
+    ) -> Self {
+        Self::Calculate {
+            calc: Calculation::new(f),
+            as_name: as_name.into(),
+        }
+    }
+
+    /// Calculate from expression source, e.g. `price * qty`
+    pub fn calculate_expr(as_name: impl Into<String>, source: &str) -> Result<Self, String> {
+        Ok(Self::Calculate {
+            calc: Calculation::from_expr(Expr::parse(source)?),
+            as_name: as_name.into(),
+        })
+    }
+
+    /// Aggregate shorthand
+    pub fn aggregate(groupby: &[&str], fields: Vec<AggregateField>) -> Self {
+        Self::Aggregate {
+            groupby: to_strings(groupby),
+            fields,
+        }
+    }
+
+    /// Fold shorthand with the default `"key"` / `"value"` output names
+    pub fn fold(fields: &[&str]) -> Self {
+        Self::Fold {
+            fields: to_strings(fields),
+            key_as: "key".to_owned(),
+            value_as: "value".to_owned(),
+        }
+    }
+
+    /// Pivot shorthand summing values that share a cell
+    pub fn pivot(pivot: impl Into<String>, value: impl Into<String>, groupby: &[&str]) -> Self {
+        Self::Pivot {
+            pivot: pivot.into(),
+            value: value.into(),
+            groupby: to_strings(groupby),
+            op: AggregateOp::Sum,
+        }
+    }
+
+    /// Sort shorthand
+    pub fn sort(fields: Vec<SortField>) -> Self {
+        Self::Sort(fields)
+    }
+
+    /// Window shorthand with the default (cumulative) frame
+    pub fn window(fields: Vec<WindowField>, groupby: &[&str], sort: Vec<SortField>) -> Self {
+        Self::Window {
+            fields,
+            groupby: to_strings(groupby),
+            sort,
+            frame: WindowFrame::default(),
+        }
+    }
+
+    /// Bin shorthand with outputs `bin_<field>` / `bin_<field>_end`
+    pub fn bin(field: impl Into<String>, rule: BinRule) -> Self {
+        let field = field.into();
+        Self::Bin {
+            as_start: format!("bin_{field}"),
+            as_end: format!("bin_{field}_end"),
+            field,
+            rule,
+        }
+    }
+
+    /// Apply this step, returning the transformed table
+    pub fn apply(&self, table: &DataTable) -> DataTable {
+        match self {
+            Self::Filter(pred) => {
+                let keep: Vec<usize> = (0..table.len()).filter(|&i| pred.test(table, i)).collect();
+                table.select_rows(&keep)
+            }
+            Self::Calculate { calc, as_name } => {
+                let mut out = table.clone();
+                let values = (0..table.len()).map(|i| calc.eval(table, i));
+                out.insert_column(Column::from_values(as_name.as_str(), values));
+                out
+            }
+            Self::Aggregate { groupby, fields } => aggregate(table, groupby, fields),
+            Self::Fold {
+                fields,
+                key_as,
+                value_as,
+            } => fold(table, fields, key_as, value_as),
+            Self::Pivot {
+                pivot: pivot_col,
+                value,
+                groupby,
+                op,
+            } => pivot(table, pivot_col, value, groupby, *op),
+            Self::Sort(fields) => {
+                let mut indices: Vec<usize> = (0..table.len()).collect();
+                sort_indices(table, &mut indices, fields);
+                table.select_rows(&indices)
+            }
+            Self::Window {
+                fields,
+                groupby,
+                sort,
+                frame,
+            } => window(table, fields, groupby, sort, *frame),
+            Self::Bin {
+                field,
+                rule,
+                as_start,
+                as_end,
+            } => bin(table, field, *rule, as_start, as_end),
+        }
+    }
+}
+
+impl DataTable {
+    /// Run a transform pipeline, returning the resulting table
+    pub fn transform(&self, transforms: &[Transform]) -> DataTable {
+        let mut iter = transforms.iter();
+        let Some(first) = iter.next() else {
+            return self.clone();
+        };
+        iter.fold(first.apply(self), |table, t| t.apply(&table))
+    }
+}
+
+// --- Implementations ---
+
+fn to_strings(names: &[&str]) -> Vec<String> {
+    names.iter().map(|s| (*s).to_owned()).collect()
+}
+
+/// Row indices per distinct combination of `keys`, in first-occurrence order.
+///
+/// With no keys every row lands in a single group.
+fn group_rows(table: &DataTable, keys: &[String]) -> Vec<Vec<usize>> {
+    if keys.is_empty() {
+        return vec![(0..table.len()).collect()];
+    }
+    let cols: Vec<Option<&Column>> = keys.iter().map(|k| table.column(k)).collect();
+    let mut lookup: HashMap<Vec<GroupKey>, usize> = HashMap::new();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for i in 0..table.len() {
+        let key: Vec<GroupKey> = cols
+            .iter()
+            .map(|c| c.map_or(GroupKey::Null, |c| c.group_key(i)))
+            .collect();
+        let g = *lookup.entry(key).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[g].push(i);
+    }
+    groups
+}
+
+/// One column per key holding the value of each group's first row
+fn key_columns(table: &DataTable, keys: &[String], groups: &[Vec<usize>]) -> Vec<Column> {
+    let firsts: Vec<usize> = groups
+        .iter()
+        .map(|g| g.first().copied().unwrap_or(usize::MAX))
+        .collect();
+    keys.iter()
+        .map(|k| match table.column(k) {
+            Some(c) => c.take(&firsts),
+            None => Column::nulls(k.as_str(), groups.len()),
+        })
+        .collect()
+}
+
+fn aggregate(table: &DataTable, groupby: &[String], fields: &[AggregateField]) -> DataTable {
+    let groups = group_rows(table, groupby);
+    let mut columns = key_columns(table, groupby, &groups);
+    for f in fields {
+        let col = f.field.as_deref().and_then(|name| table.column(name));
+        let values = groups.iter().map(|rows| f.op.apply(col, rows));
+        columns.push(Column::from_values(f.as_name.as_str(), values));
+    }
+    DataTable::from_columns(columns)
+}
+
+fn fold(table: &DataTable, fields: &[String], key_as: &str, value_as: &str) -> DataTable {
+    let n = fields.len();
+    let repeated: Vec<usize> = (0..table.len())
+        .flat_map(|i| std::iter::repeat_n(i, n))
+        .collect();
+    let mut out = table.select_rows(&repeated);
+    let keys = (0..repeated.len()).map(|j| FieldValue::Text(fields[j % n].clone()));
+    let values = repeated
+        .iter()
+        .enumerate()
+        .map(|(j, &i)| table.value(i, &fields[j % n]));
+    out.insert_column(Column::from_values(key_as, keys));
+    out.insert_column(Column::from_values(value_as, values));
+    out
+}
+
+fn pivot(
+    table: &DataTable,
+    pivot_col: &str,
+    value_col: &str,
+    groupby: &[String],
+    op: AggregateOp,
+) -> DataTable {
+    let groups = group_rows(table, groupby);
+    let pivots = table.partition(pivot_col);
+    let value = table.column(value_col);
+
+    let mut columns = key_columns(table, groupby, &groups);
+    for (name, pivot_rows) in &pivots {
+        let in_pivot: std::collections::HashSet<usize> = pivot_rows.iter().copied().collect();
+        let values = groups.iter().map(|rows| {
+            let cell: Vec<usize> = rows
+                .iter()
+                .copied()
+                .filter(|i| in_pivot.contains(i))
+                .collect();
+            if cell.is_empty() {
+                FieldValue::Null
+            } else {
+                op.apply(value, &cell)
+            }
+        });
+        columns.push(Column::from_values(name.as_str(), values));
+    }
+    DataTable::from_columns(columns)
+}
+
+fn compare_rows(table: &DataTable, a: usize, b: usize, fields: &[SortField]) -> Ordering {
+    for f in fields {
+        let va = table.value(a, &f.field);
+        let vb = table.value(b, &f.field);
+        // Nulls sort last in either direction; only values are reversed
+        let cmp = match (va.is_null(), vb.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => {
+                let cmp = compare_field_values(Some(&va), Some(&vb));
+                if f.order == SortDir::Desc {
+                    cmp.reverse()
+                } else {
+                    cmp
+                }
+            }
+        };
+        if cmp != Ordering::Equal {
+            return cmp;
+        }
+    }
+    Ordering::Equal
+}
+
+fn sort_indices(table: &DataTable, indices: &mut [usize], fields: &[SortField]) {
+    if !fields.is_empty() {
+        indices.sort_by(|&a, &b| compare_rows(table, a, b, fields));
+    }
+}
+
+fn window(
+    table: &DataTable,
+    fields: &[WindowField],
+    groupby: &[String],
+    sort: &[SortField],
+    frame: WindowFrame,
+) -> DataTable {
+    let len = table.len();
+    let mut outputs: Vec<Vec<FieldValue>> = vec![vec![FieldValue::Null; len]; fields.len()];
+
+    for mut part in group_rows(table, groupby) {
+        sort_indices(table, &mut part, sort);
+        let n = part.len();
+
+        // Rank of each position within the sorted partition
+        let mut ranks = Vec::with_capacity(n);
+        let mut dense_ranks: Vec<usize> = Vec::with_capacity(n);
+        for pos in 0..n {
+            let tied = pos > 0 && compare_rows(table, part[pos - 1], part[pos], sort).is_eq();
+            if tied {
+                ranks.push(ranks[pos - 1]);
+                dense_ranks.push(dense_ranks[pos - 1]);
+            } else {
+                ranks.push(pos + 1);
+                dense_ranks.push(dense_ranks.last().map_or(1, |r| r + 1));
+            }
+        }
+
+        for (out, f) in outputs.iter_mut().zip(fields) {
+            let col = f.field.as_deref().and_then(|name| table.column(name));
+            let value_at = |pos: usize| col.map_or(FieldValue::Null, |c| c.get(part[pos]));
+            for pos in 0..n {
+                let lo = frame.preceding.map_or(0, |p| pos.saturating_sub(p));
+                let hi = frame.following.map_or(n - 1, |f| (pos + f).min(n - 1));
+                out[part[pos]] = match f.op {
+                    WindowOp::RowNumber => FieldValue::Numeric((pos + 1) as f64),
+                    WindowOp::Rank => FieldValue::Numeric(ranks[pos] as f64),
+                    WindowOp::DenseRank => FieldValue::Numeric(dense_ranks[pos] as f64),
+                    WindowOp::Lag(k) => pos.checked_sub(k).map_or(FieldValue::Null, value_at),
+                    WindowOp::Lead(k) => {
+                        if pos + k < n {
+                            value_at(pos + k)
+                        } else {
+                            FieldValue::Null
+                        }
+                    }
+                    WindowOp::FirstValue => value_at(lo),
+                    WindowOp::LastValue => value_at(hi),
+                    WindowOp::Aggregate(op) => op.apply(col, &part[lo..=hi]),
+                };
+            }
+        }
+    }
+
+    let mut out = table.clone();
+    for (f, values) in fields.iter().zip(outputs) {
+        out.insert_column(Column::from_values(f.as_name.as_str(), values));
+    }
+    out
+}
+
+fn bin(table: &DataTable, field: &str, rule: BinRule, as_start: &str, as_end: &str) -> DataTable {
+    let bins = statistics::histogram_bins(&table.extract_numeric(field), rule);
+    let col = table.column(field);
+    let bin_of = |i: usize| -> Option<(f64, f64)> {
+        let v = col?.get_f64(i)?;
+        let idx = bins.iter().position(|b| v < b.x1).unwrap_or(bins.len() - 1);
+        bins.get(idx).map(|b| (b.x0, b.x1))
+    };
+    let edges: Vec<Option<(f64, f64)>> = (0..table.len()).map(bin_of).collect();
+
+    let mut out = table.clone();
+    let starts = edges
+        .iter()
+        .map(|e| e.map_or(FieldValue::Null, |(x0, _)| FieldValue::Numeric(x0)));
+    out.insert_column(Column::from_values(as_start, starts));
+    let ends = edges
+        .iter()
+        .map(|e| e.map_or(FieldValue::Null, |(_, x1)| FieldValue::Numeric(x1)));
+    out.insert_column(Column::from_values(as_end, ends));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::table_data::CompareOp;
+    use crate::data_row;
+
+    fn sales() -> DataTable {
+        DataTable::from_rows(vec![
+            data_row! { "region" => "EU", "quarter" => "Q1", "amount" => 10.0_f64 },
+            data_row! { "region" => "US", "quarter" => "Q1", "amount" => 20.0_f64 },
+            data_row! { "region" => "EU", "quarter" => "Q2", "amount" => 30.0_f64 },
+            data_row! { "region" => "US", "quarter" => "Q2", "amount" => 5.0_f64 },
+            data_row! { "region" => "EU", "quarter" => "Q2", "amount" => 2.0_f64 },
+        ])
+    }
+
+    #[test]
+    fn test_filter() {
+        let t = sales().transform(&[Transform::filter(Predicate::field(
+            "amount",
+            FilterOp::NumberCompare {
+                operator: CompareOp::GreaterEq,
+                value: 10.0,
+            },
+        ))]);
+        assert_eq!(t.extract_numeric("amount"), vec![10.0, 20.0, 30.0]);
+    }
+
+    #[test]
+    fn test_calculate() {
+        let t = sales().transform(&[Transform::calculate("double", |t, i| {
+            t.value(i, "amount")
+                .as_f64()
+                .map_or(FieldValue::Null, |v| FieldValue::Numeric(v * 2.0))
+        })]);
+        assert_eq!(
+            t.extract_numeric("double"),
+            vec![20.0, 40.0, 60.0, 10.0, 4.0]
+        );
+    }
+
+    #[test]
+    fn test_expression_calculate_and_filter() {
+        let t = sales().transform(&[
+            Transform::calculate_expr("adjusted", r#"if(region == "EU", amount * 2, amount)"#)
+                .unwrap(),
+            Transform::filter(Predicate::expr("adjusted >= 20").unwrap()),
+        ]);
+        assert_eq!(t.extract_numeric("adjusted"), vec![20.0, 20.0, 60.0]);
+    }
+
+    #[test]
+    fn test_aggregate_by_group() {
+        let t = sales().transform(&[Transform::aggregate(
+            &["region"],
+            vec![
+                AggregateField::new(AggregateOp::Sum, "amount", "total"),
+                AggregateField::new(AggregateOp::Max, "amount", "max"),
+                AggregateField::new(AggregateOp::Distinct, "quarter", "quarters"),
+                AggregateField::count("n"),
+            ],
+        )]);
+        assert_eq!(t.extract_text("region"), vec!["EU", "US"]);
+        assert_eq!(t.extract_numeric("total"), vec![42.0, 25.0]);
+        assert_eq!(t.extract_numeric("max"), vec![30.0, 20.0]);
+        assert_eq!(t.extract_numeric("quarters"), vec![2.0, 2.0]);
+        assert_eq!(t.extract_numeric("n"), vec![3.0, 2.0]);
+    }
+
+    #[test]
+    fn test_aggregate_median_and_mean() {
+        let t = sales().transform(&[Transform::aggregate(
+            &[],
+            vec![
+                AggregateField::new(AggregateOp::Median, "amount", "median"),
+                AggregateField::new(AggregateOp::Mean, "amount", "mean"),
+            ],
+        )]);
+        assert_eq!(t.len(), 1);
+        assert_eq!(t.extract_numeric("median"), vec![10.0]);
+        assert_eq!(t.extract_numeric("mean"), vec![13.4]);
+    }
+
+    #[test]
+    fn test_fold() {
+        let t = DataTable::from_rows(vec![
+            data_row! { "id" => "a", "x" => 1.0_f64, "y" => 2.0_f64 },
+        ])
+        .transform(&[Transform::fold(&["x", "y"])]);
+        assert_eq!(t.len(), 2);
+        assert_eq!(t.extract_text("key"), vec!["x", "y"]);
+        assert_eq!(t.extract_numeric("value"), vec![1.0, 2.0]);
+        assert_eq!(t.extract_text("id"), vec!["a", "a"]);
+    }
+
+    #[test]
+    fn test_pivot() {
+        let t = sales().transform(&[Transform::pivot("quarter", "amount", &["region"])]);
+        assert_eq!(t.column_names(), vec!["region", "Q1", "Q2"]);
+        assert_eq!(t.extract_numeric("Q1"), vec![10.0, 20.0]);
+        assert_eq!(t.extract_numeric("Q2"), vec![32.0, 5.0]);
+    }
+
+    #[test]
+    fn test_sort_multi_key() {
+        let t = sales().transform(&[Transform::sort(vec![
+            SortField::asc("region"),
+            SortField::desc("amount"),
+        ])]);
+        assert_eq!(
+            t.extract_numeric("amount"),
+            vec![30.0, 10.0, 2.0, 20.0, 5.0]
+        );
+    }
+
+    #[test]
+    fn test_sort_keeps_nulls_last() {
+        let t = DataTable::from_columns(vec![Column::from_values(
+            "v",
+            [
+                FieldValue::Numeric(1.0),
+                FieldValue::Null,
+                FieldValue::Numeric(3.0),
+            ],
+        )]);
+        let desc = t.transform(&[Transform::sort(vec![SortField::desc("v")])]);
+        assert_eq!(desc.value(0, "v"), FieldValue::Numeric(3.0));
+        assert_eq!(desc.value(1, "v"), FieldValue::Numeric(1.0));
+        assert_eq!(desc.value(2, "v"), FieldValue::Null);
+        let asc = t.transform(&[Transform::sort(vec![SortField::asc("v")])]);
+        assert_eq!(asc.extract_numeric("v"), vec![1.0, 3.0]);
+        assert_eq!(asc.value(2, "v"), FieldValue::Null);
+    }
+
+    #[test]
+    fn test_window_running_sum_and_rank() {
+        let t = sales().transform(&[Transform::window(
+            vec![
+                WindowField::new(WindowOp::Aggregate(AggregateOp::Sum), "amount", "running"),
+                WindowField::ranking(WindowOp::RowNumber, "n"),
+                WindowField::new(WindowOp::Lag(1), "amount", "prev"),
+            ],
+            &["region"],
+            vec![SortField::asc("amount")],
+        )]);
+        // Original row order is preserved
+        assert_eq!(
+            t.extract_numeric("running"),
+            vec![12.0, 25.0, 42.0, 5.0, 2.0]
+        );
+        assert_eq!(t.extract_numeric("n"), vec![2.0, 2.0, 3.0, 1.0, 1.0]);
+        assert_eq!(t.value(4, "prev"), FieldValue::Null);
+        assert_eq!(t.value(0, "prev"), FieldValue::Numeric(2.0));
+    }
+
+    #[test]
+    fn test_window_rank_ties() {
+        let t = DataTable::from_columns(vec![Column::numeric("v", vec![5.0, 3.0, 5.0, 1.0])])
+            .transform(&[Transform::window(
+                vec![
+                    WindowField::ranking(WindowOp::Rank, "rank"),
+                    WindowField::ranking(WindowOp::DenseRank, "dense"),
+                ],
+                &[],
+                vec![SortField::desc("v")],
+            )]);
+        assert_eq!(t.extract_numeric("rank"), vec![1.0, 3.0, 1.0, 4.0]);
+        assert_eq!(t.extract_numeric("dense"), vec![1.0, 2.0, 1.0, 3.0]);
+    }
+
+    #[test]
+    fn test_bin() {
+        let t = DataTable::from_columns(vec![Column::numeric("v", vec![0.0, 1.0, 2.5, 4.0])])
+            .transform(&[Transform::bin("v", BinRule::Fixed(2))]);
+        assert_eq!(t.extract_numeric("bin_v"), vec![0.0, 0.0, 2.0, 2.0]);
+        assert_eq!(t.extract_numeric("bin_v_end"), vec![2.0, 2.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn test_pipeline_composes() {
+        let pipeline = vec![
+            Transform::aggregate(
+                &["region"],
+                vec![AggregateField::new(AggregateOp::Sum, "amount", "total")],
+            ),
+            Transform::sort(vec![SortField::asc("total")]),
+        ];
+        let t = sales().transform(&pipeline);
+        assert_eq!(t.extract_text("region"), vec!["US", "EU"]);
+    }
+}
@@ -0,0 +1,791 @@
+/// Validation and resolution of a [`ChartSpec`] with typed errors
+///
+/// `ChartSpec::validate()` checks a spec against its data before anything
+/// is drawn; `ChartSpec::resolve()` additionally converts a table source to
+/// the dataset the mark needs. Both report *why* a chart would come out
+/// blank — a misspelled column, text in a quantitative field, an encoding
+/// the mark cannot draw, or no rows — instead of yielding an empty dataset.
+use std::fmt;
+
+use crate::core::column::{Column, ColumnKind};
+use crate::core::data::DataType;
+use crate::core::encoding::{Encoding, Field};
+use crate::core::field_value::{DataTable, FieldValue};
+use crate::core::mark::Mark;
+use crate::core::spec::{ChartData, ChartSpec};
+use crate::core::transform::AggregateOp;
+
+/// Maximum number of "did you mean" suggestions
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Why a spec cannot be rendered
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpecError {
+    /// An encoded field is not a column of the (transformed) table
+    UnknownField {
+        /// Encoding channel (`"x"`, `"y"`, ...)
+        channel: &'static str,
+        /// The missing field name
+        field: String,
+        /// Closest existing column names, best first
+        suggestions: Vec<String>,
+    },
+    /// A column's values do not fit the field's declared type
+    TypeMismatch {
+        /// Encoding channel
+        channel: &'static str,
+        /// Field name
+        field: String,
+        /// Declared type
+        expected: DataType,
+        /// Actual column type
+        found: ColumnKind,
+    },
+    /// The mark needs a channel the spec does not encode
+    MissingChannel {
+        /// The spec's mark
+        mark: Mark,
+        /// The missing channel
+        channel: &'static str,
+    },
+    /// The mark cannot draw this channel's encoding
+    IncompatibleEncoding {
+        /// The spec's mark
+        mark: Mark,
+        /// Offending channel
+        channel: &'static str,
+        /// What the mark expects
+        reason: String,
+    },
+    /// A pre-built dataset of the wrong shape for the mark
+    IncompatibleData {
+        /// The spec's mark
+        mark: Mark,
+        /// Kind of data supplied
+        data: &'static str,
+    },
+    /// The mark cannot be drawn in this context
+    UnsupportedMark {
+        /// The spec's mark
+        mark: Mark,
+        /// Where it can be used instead
+        reason: String,
+    },
+    /// An error in one layer of a `LayerSpec`
+    Layer {
+        /// Index of the layer
+        index: usize,
+        /// The layer's error
+        error: Box<SpecError>,
+    },
+    /// Nothing to draw
+    EmptyData {
+        /// True when the source had rows but the transforms removed them all
+        after_transforms: bool,
+    },
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField {
+                channel,
+                field,
+                suggestions,
+            } => {
+                write!(f, "encoding.{channel}: unknown field '{field}'")?;
+                match suggestions.as_slice() {
+                    [] => Ok(()),
+                    [one] => write!(f, " (did you mean '{one}'?)"),
+                    many => write!(f, " (did you mean one of: {}?)", many.join(", ")),
+                }
+            }
+            Self::TypeMismatch {
+                channel,
+                field,
+                expected,
+                found,
+            } => write!(
+                f,
+                "encoding.{channel}: field '{field}' is declared {} but holds {} values",
+                type_name(*expected),
+                kind_name(*found)
+            ),
+            Self::MissingChannel { mark, channel } => {
+                write!(f, "{mark:?} charts need an encoding for '{channel}'")
+            }
+            Self::IncompatibleEncoding {
+                mark,
+                channel,
+                reason,
+            } => write!(f, "encoding.{channel}: {mark:?} charts {reason}"),
+            Self::IncompatibleData { mark, data } => {
+                write!(f, "{mark:?} charts cannot draw a {data}")
+            }
+            Self::UnsupportedMark { mark, reason } => write!(f, "{mark:?} marks {reason}"),
+            Self::Layer { index, error } => write!(f, "layer[{index}]: {error}"),
+            Self::EmptyData { after_transforms } => {
+                if *after_transforms {
+                    f.write_str("no rows left after applying the transforms")
+                } else {
+                    f.write_str("the data is empty")
+                }
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+fn type_name(t: DataType) -> &'static str {
+    match t {
+        DataType::Quantitative => "quantitative",
+        DataType::Temporal => "temporal",
+        DataType::Nominal => "nominal",
+        DataType::Ordinal => "ordinal",
+    }
+}
+
+fn kind_name(k: ColumnKind) -> &'static str {
+    match k {
+        ColumnKind::Null => "only null",
+        ColumnKind::Numeric => "numeric",
+        ColumnKind::Timestamp => "timestamp",
+        ColumnKind::Bool => "boolean",
+        ColumnKind::Text => "text",
+        ColumnKind::Mixed => "mixed",
+    }
+}
+
+impl ChartSpec {
+    /// Check the spec against its data without converting it
+    pub fn validate(&self) -> Result<(), SpecError> {
+        self.checked_table().map(|_| ())
+    }
+
+    /// Validate, then convert the data to the form the mark draws:
+    ///
+    /// | Mark | Data |
+    /// |------|------|
+    /// | `Bar`, `Arc` | `ChartData::Categorical` |
+    /// | `Rect` | `ChartData::Grid` (rows from y, columns from x, values from color) |
+    /// | `Boxplot`, `Tick` | `ChartData::Groups` (groups from x, values from y) |
+    /// | `Candlestick` | `ChartData::Ohlc` (one bar per x, prices from y) |
+    /// | `Flow` | `ChartData::Flow` (links x → y weighted by size), or `ChartData::Chord` when the links form a cycle |
+    /// | others | `ChartData::TimeSeries` |
+    pub fn resolve(&self) -> Result<ChartData, SpecError> {
+        let resolved = match self.checked_table()? {
+            Some(table) => self.convert(&table),
+            None => self.data.clone(),
+        };
+        if resolved.is_empty() {
+            return Err(SpecError::EmptyData {
+                after_transforms: false,
+            });
+        }
+        Ok(resolved)
+    }
+
+    /// Convert a checked table for the mark
+    pub(crate) fn convert(&self, table: &DataTable) -> ChartData {
+        // checked_table guarantees y is set
+        let enc = self
+            .encoding()
+            .unwrap_or_else(|| Encoding::new(self.x.clone(), self.x.clone()));
+        // Flags used as categories become "true" / "false" labels
+        let mut table = table.clone();
+        let categories: &[&Field] = match self.mark {
+            Mark::Bar | Mark::Arc | Mark::Boxplot | Mark::Tick => &[&enc.x],
+            Mark::Flow | Mark::Rect => &[&enc.x, &enc.y],
+            _ => &[],
+        };
+        for field in categories {
+            if let Some(col) = table.column(&field.name) {
+                if col.kind() == ColumnKind::Bool {
+                    let labels = col.iter().map(|v| match v {
+                        FieldValue::Bool(b) => FieldValue::Text(b.to_string()),
+                        other => other,
+                    });
+                    table.insert_column(Column::from_values(field.name.as_str(), labels));
+                }
+            }
+        }
+        let table = &table;
+        match self.mark {
+            Mark::Bar | Mark::Arc => ChartData::Categorical(table.to_bar_dataset(&enc)),
+            Mark::Line | Mark::Area | Mark::Point | Mark::Circle | Mark::Rule | Mark::Text => {
+                ChartData::TimeSeries(table.to_dataset(&enc))
+            }
+            _ => {
+                let categorical = matches!(self.mark, Mark::Boxplot | Mark::Tick | Mark::Rect);
+                let (table, enc) = enc
+                    .derive(table, categorical)
+                    .unwrap_or_else(|| (table.clone(), enc));
+                let color = enc.color.as_ref().map_or("", |f| f.name.as_str());
+                let size = enc.size.as_ref().map_or("", |f| f.name.as_str());
+                let (x, y) = (enc.x.name.as_str(), enc.y.name.as_str());
+                match self.mark {
+                    Mark::Rect => ChartData::Grid(table.to_grid_long(y, x, color, f64::NAN)),
+                    Mark::Candlestick => ChartData::Ohlc(table.to_ohlc(x, y)),
+                    Mark::Flow => {
+                        let flows = table.to_sankey(x, y, size, None);
+                        if flows.has_cycle() {
+                            ChartData::Chord(flows.to_chord())
+                        } else {
+                            ChartData::Flow(flows)
+                        }
+                    }
+                    _ => ChartData::Groups(table.to_strip_groups(x, y)),
+                }
+            }
+        }
+    }
+
+    /// Run every check; returns the transformed table for table sources
+    pub(crate) fn checked_table(&self) -> Result<Option<DataTable>, SpecError> {
+        let mark = self.mark;
+        if matches!(mark, Mark::Rule | Mark::Text) {
+            return Err(SpecError::UnsupportedMark {
+                mark,
+                reason: "are only drawn as layers of a LayerSpec".to_owned(),
+            });
+        }
+        let source = match &self.data {
+            ChartData::Table(t) => t,
+            prebuilt => {
+                if let Some(hint) = prebuilt_mismatch(mark, prebuilt) {
+                    return Err(SpecError::IncompatibleData { mark, data: hint });
+                }
+                if prebuilt.is_empty() {
+                    return Err(SpecError::EmptyData {
+                        after_transforms: false,
+                    });
+                }
+                return Ok(None);
+            }
+        };
+
+        let table = source.transform(&self.transforms);
+        let channels = [
+            ("x", Some(&self.x)),
+            ("y", self.y.as_ref()),
+            ("color", self.color.as_ref()),
+            ("size", self.size.as_ref()),
+            ("opacity", self.opacity.as_ref()),
+            ("shape", self.shape.as_ref()),
+            ("stroke_dash", self.stroke_dash.as_ref()),
+            ("text", self.text.as_ref()),
+            ("detail", self.detail.as_ref()),
+            ("order", self.order.as_ref()),
+        ];
+        let tooltip = self.tooltip.iter().map(|f| ("tooltip", Some(f)));
+        for (channel, field) in channels.into_iter().chain(tooltip) {
+            if let Some(field) = field {
+                check_field(&table, channel, field)?;
+            }
+        }
+        let missing = |channel| SpecError::MissingChannel { mark, channel };
+        let Some(y) = &self.y else {
+            return Err(missing("y"));
+        };
+
+        // Aggregates, bins and time units always yield continuous values
+        let continuous = |f: &Field| {
+            f.is_derived() || matches!(f.data_type, DataType::Quantitative | DataType::Temporal)
+        };
+        // Binned and time-unit fields are relabelled as categories; flags are
+        // two categories
+        let categorical = |f: &Field| {
+            let kind = table.column(&f.name).map(|c| c.kind());
+            f.bin.is_some()
+                || f.time_unit.is_some()
+                || matches!(
+                    kind,
+                    Some(ColumnKind::Text | ColumnKind::Bool | ColumnKind::Mixed)
+                )
+        };
+        let incompatible = |channel, reason: &str| SpecError::IncompatibleEncoding {
+            mark,
+            channel,
+            reason: reason.to_owned(),
+        };
+        match mark {
+            Mark::Bar | Mark::Arc | Mark::Boxplot | Mark::Tick => {
+                if !categorical(&self.x) {
+                    return Err(incompatible(
+                        "x",
+                        "need a text (nominal) category field on x",
+                    ));
+                }
+            }
+            Mark::Flow => {
+                if !categorical(&self.x) || !categorical(y) {
+                    let channel = if categorical(&self.x) { "y" } else { "x" };
+                    return Err(incompatible(
+                        channel,
+                        "need text (nominal) source and target fields on x and y",
+                    ));
+                }
+                match &self.size {
+                    None => return Err(missing("size")),
+                    Some(size) if !continuous(size) => {
+                        return Err(incompatible("size", "need a quantitative link weight"))
+                    }
+                    Some(_) => {}
+                }
+            }
+            Mark::Rect => match &self.color {
+                None => return Err(missing("color")),
+                Some(color) if !continuous(color) => {
+                    return Err(incompatible("color", "need a quantitative cell value"))
+                }
+                Some(_) => {}
+            },
+            _ => {
+                if !continuous(&self.x) {
+                    return Err(incompatible(
+                        "x",
+                        "need a quantitative or temporal field on x",
+                    ));
+                }
+            }
+        }
+        if !matches!(mark, Mark::Flow | Mark::Rect) && !continuous(y) {
+            return Err(incompatible("y", "need a quantitative field on y"));
+        }
+
+        if table.is_empty() {
+            return Err(SpecError::EmptyData {
+                after_transforms: !source.is_empty(),
+            });
+        }
+        Ok(Some(table))
+    }
+}
+
+/// Why `data` cannot be drawn by `mark`, or `None` when it can
+fn prebuilt_mismatch(mark: Mark, data: &ChartData) -> Option<&'static str> {
+    let (fits, hint) = match data {
+        ChartData::Table(_) => (true, ""),
+        ChartData::TimeSeries(_) => (
+            matches!(mark, Mark::Line | Mark::Area | Mark::Point | Mark::Circle),
+            "Dataset (use a BarDataset or a table)",
+        ),
+        ChartData::Categorical(_) => (
+            matches!(mark, Mark::Bar | Mark::Arc),
+            "BarDataset (use a Dataset or a table)",
+        ),
+        ChartData::Grid(_) => (mark == Mark::Rect, "GridData (use Rect or a table)"),
+        ChartData::Groups(_) => (
+            matches!(mark, Mark::Boxplot | Mark::Tick),
+            "group list (use Boxplot, Tick or a table)",
+        ),
+        ChartData::Ohlc(_) => (
+            mark == Mark::Candlestick,
+            "list of OHLC bars (use Candlestick or a table)",
+        ),
+        ChartData::Flow(_) | ChartData::Chord(_) => {
+            (mark == Mark::Flow, "flow diagram (use Flow or a table)")
+        }
+    };
+    (!fits).then_some(hint)
+}
+
+/// The field exists and its column fits the declared type
+pub(crate) fn check_field(
+    table: &DataTable,
+    channel: &'static str,
+    field: &Field,
+) -> Result<(), SpecError> {
+    let Some(col) = table.column(&field.name) else {
+        return Err(SpecError::UnknownField {
+            channel,
+            field: field.name.clone(),
+            suggestions: suggest(&field.name, table.column_names()),
+        });
+    };
+    let kind = col.kind();
+    let holds_text = match kind {
+        ColumnKind::Text => true,
+        ColumnKind::Mixed => (0..col.len()).any(|i| col.get_str(i).is_some()),
+        _ => false,
+    };
+    // Counting works on any column; time units need dates, bins and the
+    // other aggregates need numbers
+    let expected = match (field.aggregate, field.time_unit, field.bin) {
+        (Some(AggregateOp::Count | AggregateOp::Distinct), ..) => None,
+        (_, Some(_), _) => Some(DataType::Temporal),
+        (Some(_), _, _) | (_, _, Some(_)) => Some(DataType::Quantitative),
+        _ => matches!(field.data_type, DataType::Quantitative | DataType::Temporal)
+            .then_some(field.data_type),
+    };
+    if let Some(expected) = expected.filter(|_| holds_text) {
+        return Err(SpecError::TypeMismatch {
+            channel,
+            field: field.name.clone(),
+            expected,
+            found: kind,
+        });
+    }
+    Ok(())
+}
+
+/// Column names close to `name`: case-insensitive matches first, then by
+/// edit distance (at most a third of the name's length, minimum 2)
+fn suggest(name: &str, candidates: Vec<&str>) -> Vec<String> {
+    let lower = name.to_lowercase();
+    let limit = (name.chars().count() / 3).max(2);
+    let mut scored: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter_map(|c| {
+            let d = if c.to_lowercase() == lower {
+                0
+            } else {
+                edit_distance(&lower, &c.to_lowercase())
+            };
+            (d <= limit).then_some((d, c))
+        })
+        .collect();
+    scored.sort_by_key(|&(d, _)| d);
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, c)| c.to_owned())
+        .collect()
+}
+
+/// Levenshtein distance over chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::statistics::BinRule;
+    use crate::core::data::{BarDataset, DataPoint};
+    use crate::core::datetime::TimeUnit;
+    use crate::core::field_value::FieldValue;
+    use crate::core::transform::{Predicate, Transform};
+    use crate::data_row;
+
+    fn table() -> DataTable {
+        DataTable::from_rows(vec![
+            data_row! { "region" => "EU", "revenue" => 10.0_f64, "note" => "n/a" },
+            data_row! { "region" => "US", "revenue" => 20.0_f64, "note" => "ok" },
+        ])
+    }
+
+    fn spec(mark: Mark, x: Field, y: Field) -> ChartSpec {
+        ChartSpec::builder()
+            .from_table(table())
+            .mark(mark)
+            .x(x)
+            .y(y)
+            .build()
+    }
+
+    #[test]
+    fn test_rect_with_timestamp_axis_uses_categories() {
+        let day = 86_400_000.0;
+        let rows = (0..3)
+            .flat_map(|d| {
+                (0..2).map(move |h| {
+                    data_row! {
+                        "date" => FieldValue::Timestamp(1_700_006_400_000.0 + d as f64 * day),
+                        "hour" => h as f64,
+                        "n" => (d * 2 + h) as f64,
+                    }
+                })
+            })
+            .collect();
+        let heat = ChartSpec::builder()
+            .from_table(DataTable::from_rows(rows))
+            .mark(Mark::Rect)
+            .x(Field::temporal("date"))
+            .y(Field::quantitative("hour"))
+            .color(Field::quantitative("n"))
+            .build();
+        let Ok(ChartData::Grid(grid)) = heat.resolve() else {
+            panic!("expected a grid");
+        };
+        assert_eq!(grid.values.len(), 2);
+        assert_eq!(grid.values[0].len(), 3);
+        assert_eq!(grid.col_labels.map(|l| l.len()), Some(3));
+        assert_eq!(grid.values[1][2], 5.0);
+    }
+
+    #[test]
+    fn test_valid_spec_resolves() {
+        let s = spec(
+            Mark::Bar,
+            Field::nominal("region"),
+            Field::quantitative("revenue"),
+        );
+        assert_eq!(s.validate(), Ok(()));
+        let ChartData::Categorical(bd) = s.resolve().unwrap() else {
+            panic!("expected bars");
+        };
+        assert_eq!(bd.categories, vec!["EU", "US"]);
+    }
+
+    #[test]
+    fn test_unknown_field_suggests() {
+        let s = spec(
+            Mark::Bar,
+            Field::nominal("region"),
+            Field::quantitative("Revenu"),
+        );
+        let err = s.validate().unwrap_err();
+        assert_eq!(
+            err,
+            SpecError::UnknownField {
+                channel: "y",
+                field: "Revenu".into(),
+                suggestions: vec!["revenue".into()],
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "encoding.y: unknown field 'Revenu' (did you mean 'revenue'?)"
+        );
+    }
+
+    #[test]
+    fn test_type_mismatch() {
+        let s = spec(
+            Mark::Bar,
+            Field::nominal("region"),
+            Field::quantitative("note"),
+        );
+        assert!(matches!(
+            s.validate(),
+            Err(SpecError::TypeMismatch {
+                channel: "y",
+                found: ColumnKind::Text,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_mark_encoding_incompatibility() {
+        let line = spec(
+            Mark::Line,
+            Field::nominal("region"),
+            Field::quantitative("revenue"),
+        );
+        assert!(matches!(
+            line.validate(),
+            Err(SpecError::IncompatibleEncoding { channel: "x", .. })
+        ));
+
+        let no_y = ChartSpec::builder()
+            .from_table(table())
+            .mark(Mark::Area)
+            .x(Field::quantitative("revenue"))
+            .build();
+        assert_eq!(
+            no_y.validate(),
+            Err(SpecError::MissingChannel {
+                mark: Mark::Area,
+                channel: "y"
+            })
+        );
+
+        let bars_from_points = ChartSpec::builder()
+            .data_points(vec![DataPoint::new(0.0, 1.0)])
+            .mark(Mark::Bar)
+            .x(Field::nominal("x"))
+            .build();
+        assert!(matches!(
+            bars_from_points.validate(),
+            Err(SpecError::IncompatibleData { .. })
+        ));
+    }
+
+    #[test]
+    fn test_empty_data() {
+        let filtered = ChartSpec::builder()
+            .from_table(table())
+            .mark(Mark::Point)
+            .x(Field::quantitative("revenue"))
+            .y(Field::quantitative("revenue"))
+            .transform(Transform::filter(Predicate::expr("revenue > 100").unwrap()))
+            .build();
+        assert_eq!(
+            filtered.validate(),
+            Err(SpecError::EmptyData {
+                after_transforms: true
+            })
+        );
+
+        let empty_bars = ChartSpec::builder()
+            .bar_data(BarDataset::new(vec![]))
+            .mark(Mark::Bar)
+            .x(Field::nominal("c"))
+            .build();
+        assert_eq!(
+            empty_bars.validate(),
+            Err(SpecError::EmptyData {
+                after_transforms: false
+            })
+        );
+
+        // Rows exist but none has a numeric y
+        let nulls =
+            DataTable::from_rows(vec![data_row! { "x" => 1.0_f64, "y" => FieldValue::Null }]);
+        let s = ChartSpec::builder()
+            .from_table(nulls)
+            .mark(Mark::Line)
+            .x(Field::quantitative("x"))
+            .y(Field::quantitative("y"))
+            .build();
+        assert!(s.validate().is_ok());
+        assert!(matches!(s.resolve(), Err(SpecError::EmptyData { .. })));
+    }
+
+    #[test]
+    fn test_derived_fields() {
+        let t = DataTable::from_rows(vec![
+            data_row! { "at" => FieldValue::Timestamp(0.0), "region" => "EU", "v" => 1.0_f64 },
+        ]);
+        let monthly = ChartSpec::builder()
+            .from_table(t.clone())
+            .mark(Mark::Bar)
+            .x(Field::temporal("at").with_time_unit(TimeUnit::Month))
+            .y(Field::nominal("region").with_aggregate(AggregateOp::Count))
+            .build();
+        let ChartData::Categorical(bd) = monthly.resolve().unwrap() else {
+            panic!("expected bars");
+        };
+        assert_eq!(bd.categories, vec!["1970-01"]);
+
+        let binned_text = ChartSpec::builder()
+            .from_table(t)
+            .mark(Mark::Bar)
+            .x(Field::nominal("region").with_bin(BinRule::Sturges))
+            .y(Field::quantitative("v"))
+            .build();
+        assert!(matches!(
+            binned_text.validate(),
+            Err(SpecError::TypeMismatch {
+                expected: DataType::Quantitative,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_suggestions_ranked() {
+        let names = vec!["amount", "Amount_usd", "mount", "zzz"];
+        assert_eq!(suggest("amount", names.clone())[0], "amount");
+        assert_eq!(suggest("amout", names), vec!["amount", "mount"]);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_chart_family_marks() {
+        let t = DataTable::from_rows(vec![
+            data_row! { "from" => "Coal", "to" => "Power", "day" => 1.0_f64, "v" => 4.0_f64 },
+            data_row! { "from" => "Gas", "to" => "Power", "day" => 1.0_f64, "v" => 6.0_f64 },
+            data_row! { "from" => "Power", "to" => "Homes", "day" => 2.0_f64, "v" => 8.0_f64 },
+        ]);
+        let with = |mark, x: &str, y: Field| {
+            ChartSpec::builder()
+                .from_table(t.clone())
+                .mark(mark)
+                .x(Field::nominal(x))
+                .y(y)
+        };
+
+        let pie = with(Mark::Arc, "from", Field::quantitative("v")).build();
+        assert!(matches!(pie.resolve(), Ok(ChartData::Categorical(_))));
+
+        let boxes = with(Mark::Boxplot, "to", Field::quantitative("v")).build();
+        let Ok(ChartData::Groups(groups)) = boxes.resolve() else {
+            panic!("expected groups");
+        };
+        assert_eq!(groups[0].values, vec![4.0, 6.0]);
+
+        let heat = with(Mark::Rect, "from", Field::nominal("to"))
+            .color(Field::quantitative("v"))
+            .build();
+        let Ok(ChartData::Grid(grid)) = heat.resolve() else {
+            panic!("expected a grid");
+        };
+        assert_eq!(grid.values.len(), 2);
+        assert_eq!(grid.col_labels.unwrap(), vec!["Coal", "Gas", "Power"]);
+
+        let candles = ChartSpec::builder()
+            .from_table(t.clone())
+            .mark(Mark::Candlestick)
+            .x(Field::quantitative("day"))
+            .y(Field::quantitative("v"))
+            .build();
+        let Ok(ChartData::Ohlc(bars)) = candles.resolve() else {
+            panic!("expected OHLC bars");
+        };
+        assert_eq!((bars[0].open, bars[0].close), (4.0, 6.0));
+
+        let mut flow = with(Mark::Flow, "from", Field::nominal("to")).build();
+        assert_eq!(
+            flow.validate(),
+            Err(SpecError::MissingChannel {
+                mark: Mark::Flow,
+                channel: "size"
+            })
+        );
+        flow.size = Some(Field::quantitative("v"));
+        let Ok(ChartData::Flow(sankey)) = flow.resolve() else {
+            panic!("expected a Sankey flow");
+        };
+        assert_eq!(sankey.nodes.len(), 4);
+    }
+
+    #[test]
+    fn test_cyclic_flow_and_prebuilt_data() {
+        let t = DataTable::from_rows(vec![
+            data_row! { "from" => "EU", "to" => "US", "v" => 3.0_f64 },
+            data_row! { "from" => "US", "to" => "EU", "v" => 2.0_f64 },
+        ]);
+        let flow = ChartSpec::builder()
+            .from_table(t)
+            .mark(Mark::Flow)
+            .x(Field::nominal("from"))
+            .y(Field::nominal("to"))
+            .size(Field::quantitative("v"))
+            .build();
+        let Ok(ChartData::Chord(chord)) = flow.resolve() else {
+            panic!("expected a chord matrix");
+        };
+        assert_eq!(chord.matrix, vec![vec![0.0, 3.0], vec![2.0, 0.0]]);
+
+        let mut heat = flow.clone();
+        heat.mark = Mark::Rect;
+        heat.data = ChartData::Chord(chord);
+        assert_eq!(
+            heat.validate().unwrap_err().to_string(),
+            "Rect charts cannot draw a flow diagram (use Flow or a table)"
+        );
+        heat.color = None;
+        heat.data = flow.data.clone();
+        assert!(matches!(
+            heat.validate(),
+            Err(SpecError::MissingChannel {
+                channel: "color",
+                ..
+            })
+        ));
+    }
+}
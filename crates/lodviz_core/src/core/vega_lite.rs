@@ -0,0 +1,1128 @@
+/// Vega-Lite importer: a common subset of Vega-Lite JSON → [`ChartSpec`]
+///
+/// Supported: `data.values` (or `data.name` through a resolver), `mark`
+/// (string or `{"type": ..}`), `encoding.{x,y,color,size}` with `field`,
+/// `type`, `aggregate`, `bin` and `timeUnit`, top-level `title`, `width`,
+/// `height`, and `filter` / `calculate` transforms. Encoding-level
+/// aggregation and time units become [`Transform`] steps whose output
+/// columns follow Vega-Lite naming (`sum_sales`, `yearmonth_date`). A
+/// `bin` is set on the field itself ([`crate::core::encoding::Field::bin`]),
+/// so the spec resolves to labelled bins, and aggregates next to a binned
+/// channel stay on their fields to be grouped per bin.
+///
+/// Anything else is skipped and reported as an [`ImportWarning`], so a
+/// chart prototyped in the Vega editor still loads and the caller can show
+/// what was left out. Composite views (`layer`, `facet`, `concat`, ...) and
+/// marks without a lodviz equivalent are errors.
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::algorithms::statistics::BinRule;
+use crate::core::column::ColumnKind;
+use crate::core::data::DataType;
+use crate::core::datetime::{parse_iso8601, TimeZone};
+use crate::core::encoding::Field;
+use crate::core::expr::{BinaryOp, Expr, Function, UnaryOp};
+use crate::core::field_value::{DataTable, FieldValue};
+use crate::core::format::NumberFormat;
+use crate::core::json::{from_json_value, JsonOptions};
+use crate::core::mark::Mark;
+use crate::core::scale::ScaleType;
+use crate::core::spec::{ChartData, ChartSpec};
+use crate::core::theme::ChartConfig;
+use crate::core::transform::{AggregateField, AggregateOp, Calculation, Predicate, Transform};
+
+/// Vega-Lite's default `maxbins`
+const DEFAULT_MAX_BINS: usize = 10;
+
+// --- Warnings ---
+
+/// How an imported property was handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarningKind {
+    /// The property was ignored
+    Unsupported,
+    /// The property was imported with different semantics
+    Approximated,
+}
+
+/// A Vega-Lite property that was not imported faithfully
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportWarning {
+    /// Dotted path of the property, e.g. `encoding.x.scale`
+    pub path: String,
+    /// Ignored or approximated
+    pub kind: WarningKind,
+    /// Human-readable detail
+    pub message: String,
+}
+
+impl fmt::Display for ImportWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            WarningKind::Unsupported => "ignored",
+            WarningKind::Approximated => "approximated",
+        };
+        write!(f, "{}: {kind}: {}", self.path, self.message)
+    }
+}
+
+/// Result of a Vega-Lite import
+#[derive(Debug, Clone)]
+pub struct VegaLiteImport {
+    /// The converted spec
+    pub spec: ChartSpec,
+    /// Properties that were dropped or approximated
+    pub warnings: Vec<ImportWarning>,
+}
+
+// --- Entry points ---
+
+/// Convert a Vega-Lite JSON document into a [`ChartSpec`]
+pub fn import_vega_lite(text: &str) -> Result<VegaLiteImport, String> {
+    import_vega_lite_with(text, |_| None)
+}
+
+/// Convert a Vega-Lite document, resolving `{"data": {"name": ..}}` with `resolve`
+pub fn import_vega_lite_with(
+    text: &str,
+    resolve: impl Fn(&str) -> Option<DataTable>,
+) -> Result<VegaLiteImport, String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| format!("invalid JSON: {e}"))?;
+    let Value::Object(doc) = value else {
+        return Err("a Vega-Lite spec must be a JSON object".to_owned());
+    };
+    Importer::default().run(doc, &resolve)
+}
+
+// --- Importer ---
+
+#[derive(Default)]
+struct Importer {
+    warnings: Vec<ImportWarning>,
+}
+
+/// An encoding channel after import, before aggregation is resolved
+struct Channel {
+    name: &'static str,
+    field: Field,
+    /// Aggregate op with its Vega-Lite name (`"average"`, `"q1"`, ..)
+    aggregate: Option<(AggregateOp, String)>,
+    scale: Option<ScaleType>,
+}
+
+impl Importer {
+    fn warn(&mut self, path: impl Into<String>, kind: WarningKind, message: impl Into<String>) {
+        self.warnings.push(ImportWarning {
+            path: path.into(),
+            kind,
+            message: message.into(),
+        });
+    }
+
+    fn run(
+        mut self,
+        doc: Map<String, Value>,
+        resolve: &dyn Fn(&str) -> Option<DataTable>,
+    ) -> Result<VegaLiteImport, String> {
+        for key in ["layer", "facet", "repeat", "concat", "hconcat", "vconcat"] {
+            if doc.contains_key(key) {
+                return Err(format!("composite view '{key}' is not supported"));
+            }
+        }
+
+        let table = self.data(doc.get("data"), resolve)?;
+        let mark = self.mark(doc.get("mark"))?;
+        let mut config = ChartConfig::default();
+        let mut transforms = Vec::new();
+        let mut encoding = None;
+        for (key, value) in &doc {
+            match key.as_str() {
+                "data" | "mark" | "$schema" | "description" | "name" => {}
+                "title" => {
+                    config.title = match value {
+                        Value::String(s) => Some(s.clone()),
+                        Value::Object(o) => {
+                            o.get("text").and_then(Value::as_str).map(str::to_owned)
+                        }
+                        _ => None,
+                    }
+                }
+                "width" | "height" => match value.as_u64() {
+                    Some(px) => {
+                        let px = u32::try_from(px).unwrap_or(u32::MAX);
+                        if key == "width" {
+                            config.width = Some(px);
+                        } else {
+                            config.height = Some(px);
+                        }
+                    }
+                    None => self.warn(
+                        key,
+                        WarningKind::Unsupported,
+                        "only pixel sizes are supported",
+                    ),
+                },
+                "transform" => transforms = self.transforms(value, &table),
+                "encoding" => encoding = Some(value),
+                _ => self.warn(key, WarningKind::Unsupported, "property not supported"),
+            }
+        }
+
+        let Some(Value::Object(encoding)) = encoding else {
+            return Err("missing 'encoding'".to_owned());
+        };
+        let table_after = table.transform(&transforms);
+        let mut channels = Vec::new();
+        for (name, def) in encoding {
+            let channel: &'static str = match name.as_str() {
+                "x" => "x",
+                "y" => "y",
+                "color" => "color",
+                "size" => "size",
+                "opacity" => "opacity",
+                "shape" => "shape",
+                "strokeDash" => "stroke_dash",
+                "text" => "text",
+                "detail" => "detail",
+                "order" => "order",
+                "tooltip" => {
+                    // A single definition or a list of them
+                    let defs = match def {
+                        Value::Array(defs) => defs.iter().collect(),
+                        def => vec![def],
+                    };
+                    for def in defs {
+                        if let Some(ch) =
+                            self.channel("tooltip", def, &table_after, &mut transforms)
+                        {
+                            channels.push(ch);
+                        }
+                    }
+                    continue;
+                }
+                _ => {
+                    self.warn(
+                        format!("encoding.{name}"),
+                        WarningKind::Unsupported,
+                        "channel not supported",
+                    );
+                    continue;
+                }
+            };
+            if let Some(ch) = self.channel(channel, def, &table_after, &mut transforms) {
+                channels.push(ch);
+            }
+        }
+        self.aggregate(&mut channels, &mut transforms);
+
+        let scale_of = |name: &str| channels.iter().find(|c| c.name == name)?.scale;
+        let (mut x_scale, mut y_scale) = (scale_of("x"), scale_of("y"));
+        let mut take = |name: &str| {
+            channels
+                .iter()
+                .position(|c| c.name == name)
+                .map(|i| channels.remove(i).field)
+        };
+        let (mut x, mut y) = (take("x"), take("y"));
+        let (color, size) = (take("color"), take("size"));
+        let (opacity, shape, stroke_dash) = (take("opacity"), take("shape"), take("stroke_dash"));
+        let (text, detail, order) = (take("text"), take("detail"), take("order"));
+        let tooltip: Vec<Field> = std::iter::from_fn(|| take("tooltip")).collect();
+        let horizontal = mark == Mark::Bar
+            && matches!(&x, Some(f) if f.data_type == DataType::Quantitative)
+            && matches!(&y, Some(f) if matches!(f.data_type, DataType::Nominal | DataType::Ordinal));
+        if horizontal {
+            self.warn(
+                "encoding",
+                WarningKind::Approximated,
+                "horizontal bars are drawn vertically (x and y swapped)",
+            );
+            std::mem::swap(&mut x, &mut y);
+            std::mem::swap(&mut x_scale, &mut y_scale);
+        }
+        config.x_scale = x_scale;
+        config.y_scale = y_scale;
+        config.x_format = x.as_ref().and_then(|f| f.format.clone());
+        config.y_format = y.as_ref().and_then(|f| f.format.clone());
+        let x = x.ok_or("missing 'encoding.x' with a field")?;
+
+        let spec = ChartSpec {
+            data: ChartData::Table(table),
+            mark,
+            x,
+            y,
+            color,
+            size,
+            opacity,
+            shape,
+            stroke_dash,
+            text,
+            tooltip,
+            detail,
+            order,
+            transforms,
+            config,
+        };
+        Ok(VegaLiteImport {
+            spec,
+            warnings: self.warnings,
+        })
+    }
+
+    fn data(
+        &mut self,
+        data: Option<&Value>,
+        resolve: &dyn Fn(&str) -> Option<DataTable>,
+    ) -> Result<DataTable, String> {
+        let Some(Value::Object(data)) = data else {
+            return Err("missing 'data'".to_owned());
+        };
+        let mut table = None;
+        for (key, value) in data {
+            match (key.as_str(), value) {
+                ("values", values) => {
+                    let t = from_json_value(values.clone(), &JsonOptions::default())
+                        .map_err(|e| format!("data.values: {e}"))?;
+                    table = Some(t);
+                }
+                ("name", Value::String(name)) => {
+                    let t = resolve(name)
+                        .ok_or_else(|| format!("data.name: unknown data source '{name}'"))?;
+                    table = Some(t);
+                }
+                ("url", _) => {
+                    return Err(
+                        "data.url is not supported: load the data and pass it by data.name"
+                            .to_owned(),
+                    )
+                }
+                _ => self.warn(
+                    format!("data.{key}"),
+                    WarningKind::Unsupported,
+                    "property not supported",
+                ),
+            }
+        }
+        table.ok_or_else(|| "data needs 'values' or 'name'".to_owned())
+    }
+
+    fn mark(&mut self, mark: Option<&Value>) -> Result<Mark, String> {
+        let name = match mark {
+            Some(Value::String(s)) => s.as_str(),
+            Some(Value::Object(o)) => {
+                for key in o.keys().filter(|k| *k != "type") {
+                    self.warn(
+                        format!("mark.{key}"),
+                        WarningKind::Unsupported,
+                        "mark property not supported",
+                    );
+                }
+                o.get("type")
+                    .and_then(Value::as_str)
+                    .ok_or("mark object needs a 'type'")?
+            }
+            _ => return Err("missing 'mark'".to_owned()),
+        };
+        match name {
+            "line" | "trail" => {
+                if name == "trail" {
+                    self.warn("mark", WarningKind::Approximated, "trail drawn as line");
+                }
+                Ok(Mark::Line)
+            }
+            "area" => Ok(Mark::Area),
+            "bar" => Ok(Mark::Bar),
+            "point" => Ok(Mark::Point),
+            "circle" => Ok(Mark::Circle),
+            "rect" => Ok(Mark::Rect),
+            "boxplot" => Ok(Mark::Boxplot),
+            "tick" => Ok(Mark::Tick),
+            "square" => {
+                self.warn("mark", WarningKind::Approximated, "square drawn as point");
+                Ok(Mark::Point)
+            }
+            other => Err(format!("mark '{other}' is not supported")),
+        }
+    }
+
+    fn transforms(&mut self, value: &Value, table: &DataTable) -> Vec<Transform> {
+        let Value::Array(steps) = value else {
+            self.warn("transform", WarningKind::Unsupported, "expected an array");
+            return Vec::new();
+        };
+        let mut out = Vec::new();
+        for (i, step) in steps.iter().enumerate() {
+            let path = format!("transform[{i}]");
+            let Value::Object(step) = step else {
+                self.warn(path, WarningKind::Unsupported, "expected an object");
+                continue;
+            };
+            let parsed = if let Some(filter) = step.get("filter") {
+                self.filter(filter, table).map(Transform::Filter)
+            } else if let (Some(Value::String(src)), Some(Value::String(as_name))) =
+                (step.get("calculate"), step.get("as"))
+            {
+                Expr::parse(&translate_expr(src)).map(|e| Transform::Calculate {
+                    calc: Calculation::from_expr(e),
+                    as_name: as_name.clone(),
+                })
+            } else {
+                let kind = step.keys().next().map_or("empty", String::as_str);
+                Err(format!("'{kind}' transform not supported"))
+            };
+            match parsed {
+                Ok(t) => out.push(t),
+                Err(e) => self.warn(path, WarningKind::Unsupported, e),
+            }
+        }
+        out
+    }
+
+    /// Filter as an expression string or a field predicate object
+    fn filter(&mut self, filter: &Value, table: &DataTable) -> Result<Predicate, String> {
+        let obj = match filter {
+            Value::String(src) => return Predicate::expr(&translate_expr(src)),
+            Value::Object(obj) => obj,
+            _ => return Err("filter must be an expression or a field predicate".to_owned()),
+        };
+        let Some(Value::String(name)) = obj.get("field") else {
+            return Err("filter predicate needs a 'field'".to_owned());
+        };
+        let temporal = table
+            .column(name)
+            .is_some_and(|c| c.kind() == ColumnKind::Timestamp);
+        let field = || Box::new(Expr::Field(name.clone()));
+        let cmp = |op: BinaryOp, v: &Value| -> Result<Expr, String> {
+            Ok(Expr::Binary(op, field(), Box::new(literal(v, temporal)?)))
+        };
+        let mut tests = Vec::new();
+        for (key, v) in obj.iter().filter(|(k, _)| *k != "field") {
+            let test = match key.as_str() {
+                "equal" => cmp(BinaryOp::Eq, v)?,
+                "lt" => cmp(BinaryOp::Lt, v)?,
+                "lte" => cmp(BinaryOp::Le, v)?,
+                "gt" => cmp(BinaryOp::Gt, v)?,
+                "gte" => cmp(BinaryOp::Ge, v)?,
+                "range" => {
+                    let [lo, hi] = v.as_array().map(Vec::as_slice).unwrap_or_default() else {
+                        return Err("range needs [min, max]".to_owned());
+                    };
+                    and(cmp(BinaryOp::Ge, lo)?, cmp(BinaryOp::Le, hi)?)
+                }
+                "oneOf" => {
+                    let options = v.as_array().ok_or("oneOf needs an array")?;
+                    let mut any = Expr::Literal(FieldValue::Bool(false));
+                    for o in options {
+                        any = Expr::Binary(
+                            BinaryOp::Or,
+                            Box::new(any),
+                            Box::new(cmp(BinaryOp::Eq, o)?),
+                        );
+                    }
+                    any
+                }
+                "valid" => {
+                    let is_null = Expr::Call(Function::IsNull, vec![Expr::Field(name.clone())]);
+                    if v.as_bool() == Some(false) {
+                        is_null
+                    } else {
+                        Expr::Unary(UnaryOp::Not, Box::new(is_null))
+                    }
+                }
+                other => return Err(format!("filter predicate '{other}' not supported")),
+            };
+            tests.push(test);
+        }
+        let combined = tests
+            .into_iter()
+            .reduce(and)
+            .ok_or("filter predicate needs a test")?;
+        Ok(Predicate::Expr(combined))
+    }
+
+    fn channel(
+        &mut self,
+        name: &'static str,
+        def: &Value,
+        table: &DataTable,
+        transforms: &mut Vec<Transform>,
+    ) -> Option<Channel> {
+        let path = format!("encoding.{name}");
+        let Value::Object(def) = def else {
+            self.warn(path, WarningKind::Unsupported, "expected an object");
+            return None;
+        };
+        let mut aggregate = None;
+        if let Some(op) = def.get("aggregate") {
+            match op
+                .as_str()
+                .and_then(|name| Some((aggregate_op(name)?, name.to_owned())))
+            {
+                Some(op) => aggregate = Some(op),
+                None => self.warn(
+                    format!("{path}.aggregate"),
+                    WarningKind::Unsupported,
+                    format!("aggregate {op} not supported"),
+                ),
+            }
+        }
+        let field = match def.get("field").and_then(Value::as_str) {
+            Some(f) => f.to_owned(),
+            None if matches!(aggregate, Some((AggregateOp::Count, _))) => String::new(),
+            None => {
+                let what = if def.contains_key("value") {
+                    "constant values are not supported"
+                } else {
+                    "channel without a field"
+                };
+                self.warn(path, WarningKind::Unsupported, what);
+                return None;
+            }
+        };
+
+        let mut data_type = match def.get("type").and_then(Value::as_str) {
+            Some("quantitative") => Some(DataType::Quantitative),
+            Some("temporal") => Some(DataType::Temporal),
+            Some("nominal") => Some(DataType::Nominal),
+            Some("ordinal") => Some(DataType::Ordinal),
+            Some(other) => {
+                self.warn(
+                    format!("{path}.type"),
+                    WarningKind::Unsupported,
+                    format!("type '{other}' not supported"),
+                );
+                None
+            }
+            None => None,
+        };
+        let mut column = field.clone();
+        let mut scale = None;
+        let mut format = None;
+        let mut bin = None;
+
+        for (key, value) in def {
+            match key.as_str() {
+                "field" | "type" | "aggregate" => {}
+                "scale" if matches!(name, "x" | "y") => scale = self.scale(&path, value),
+                "format" => match value.as_str() {
+                    Some(spec) => match NumberFormat::new(spec) {
+                        Ok(_) => format = Some(spec.to_owned()),
+                        Err(e) => self.warn(
+                            format!("{path}.format"),
+                            WarningKind::Unsupported,
+                            e.to_string(),
+                        ),
+                    },
+                    None => self.warn(
+                        format!("{path}.format"),
+                        WarningKind::Unsupported,
+                        "expected a d3-format string",
+                    ),
+                },
+                "bin" => match value {
+                    Value::Bool(false) => {}
+                    Value::Bool(true) | Value::Object(_) => {
+                        let max_bins = value
+                            .get("maxbins")
+                            .and_then(Value::as_u64)
+                            .map_or(DEFAULT_MAX_BINS, |n| n as usize);
+                        if let Value::Object(o) = value {
+                            for k in o.keys().filter(|k| *k != "maxbins") {
+                                self.warn(
+                                    format!("{path}.bin.{k}"),
+                                    WarningKind::Unsupported,
+                                    "bin property not supported",
+                                );
+                            }
+                        }
+                        // Binned on the field, so the spec resolves to labelled bins
+                        bin = Some(BinRule::Fixed(max_bins.max(1)));
+                    }
+                    _ => self.warn(
+                        format!("{path}.bin"),
+                        WarningKind::Unsupported,
+                        "expected true or an object",
+                    ),
+                },
+                "timeUnit" => {
+                    let unit = value.as_str().unwrap_or_default();
+                    match time_unit_expr(unit, &column) {
+                        Some((expr, kind)) => {
+                            let as_name = format!("{}_{column}", unit.trim_start_matches("utc"));
+                            transforms.push(Transform::Calculate {
+                                calc: Calculation::from_expr(expr),
+                                as_name: as_name.clone(),
+                            });
+                            column = as_name;
+                            if kind == DataType::Ordinal {
+                                self.warn(
+                                    format!("{path}.timeUnit"),
+                                    WarningKind::Approximated,
+                                    format!("'{unit}' is shown as a number"),
+                                );
+                            }
+                            data_type = Some(kind);
+                        }
+                        None => self.warn(
+                            format!("{path}.timeUnit"),
+                            WarningKind::Unsupported,
+                            format!("time unit '{value}' not supported"),
+                        ),
+                    }
+                }
+                _ => self.warn(
+                    format!("{path}.{key}"),
+                    WarningKind::Unsupported,
+                    "property not supported",
+                ),
+            }
+        }
+
+        let data_type = match (data_type, &aggregate) {
+            (Some(t), _) => t,
+            (None, Some(_)) => DataType::Quantitative,
+            (None, None) => {
+                let inferred = match table.column(&field).map(|c| c.kind()) {
+                    Some(ColumnKind::Numeric) => DataType::Quantitative,
+                    Some(ColumnKind::Timestamp) => DataType::Temporal,
+                    _ => DataType::Nominal,
+                };
+                self.warn(
+                    format!("{path}.type"),
+                    WarningKind::Approximated,
+                    format!("missing type, inferred {inferred:?} from the data"),
+                );
+                inferred
+            }
+        };
+        let mut field = Field::new(column, data_type);
+        field.format = format;
+        field.bin = bin;
+        Some(Channel {
+            name,
+            field,
+            aggregate,
+            scale,
+        })
+    }
+
+    /// `scale.type` of a positional channel; other scale properties are ignored
+    fn scale(&mut self, path: &str, scale: &Value) -> Option<ScaleType> {
+        let Value::Object(scale) = scale else {
+            self.warn(
+                format!("{path}.scale"),
+                WarningKind::Unsupported,
+                "expected an object",
+            );
+            return None;
+        };
+        for key in scale.keys().filter(|k| *k != "type") {
+            self.warn(
+                format!("{path}.scale.{key}"),
+                WarningKind::Unsupported,
+                "scale property not supported",
+            );
+        }
+        let kind = scale.get("type").and_then(Value::as_str)?;
+        match kind {
+            "linear" => Some(ScaleType::Linear),
+            "log" => Some(ScaleType::Log),
+            "sqrt" => Some(ScaleType::Sqrt),
+            "symlog" => Some(ScaleType::Symlog),
+            "time" | "utc" => Some(ScaleType::Time(TimeZone::Utc)),
+            _ => {
+                self.warn(
+                    format!("{path}.scale.type"),
+                    WarningKind::Unsupported,
+                    format!("scale type '{kind}' not supported"),
+                );
+                None
+            }
+        }
+    }
+
+    /// Turn encoding-level aggregates into one aggregate transform grouped by
+    /// the other channels, renaming aggregated fields `<op>_<field>` with the
+    /// op named as in the source (`average_price`, like Vega-Lite).
+    ///
+    /// With a binned channel the aggregates stay on the fields instead, so
+    /// they are grouped by bin when the spec resolves.
+    fn aggregate(&mut self, channels: &mut [Channel], transforms: &mut Vec<Transform>) {
+        if channels.iter().all(|c| c.aggregate.is_none()) {
+            return;
+        }
+        if let Some(binned) = channels.iter().find(|c| c.field.bin.is_some()) {
+            // A field-less count counts the rows of the binned column
+            let rows = binned.field.name.clone();
+            for ch in channels.iter_mut() {
+                ch.field.aggregate = ch.aggregate.take().map(|(op, _)| op);
+                if ch.field.name.is_empty() {
+                    ch.field.name.clone_from(&rows);
+                }
+            }
+            return;
+        }
+        let mut groupby: Vec<String> = Vec::new();
+        let mut fields = Vec::new();
+        for ch in channels.iter_mut() {
+            match &ch.aggregate {
+                None => {
+                    if !groupby.contains(&ch.field.name) {
+                        groupby.push(ch.field.name.clone());
+                    }
+                }
+                Some((AggregateOp::Count, _)) => {
+                    ch.field.name = "count".to_owned();
+                    fields.push(AggregateField::count("count"));
+                }
+                Some((op, op_name)) => {
+                    let as_name = format!("{op_name}_{}", ch.field.name);
+                    fields.push(AggregateField::new(*op, &ch.field.name, &as_name));
+                    ch.field.name = as_name;
+                }
+            }
+        }
+        transforms.push(Transform::Aggregate { groupby, fields });
+    }
+}
+
+fn and(a: Expr, b: Expr) -> Expr {
+    Expr::Binary(BinaryOp::And, Box::new(a), Box::new(b))
+}
+
+/// Literal for a filter value; ISO date strings become timestamps on
+/// temporal columns
+fn literal(value: &Value, temporal: bool) -> Result<Expr, String> {
+    let v = match value {
+        Value::Number(n) => FieldValue::Numeric(n.as_f64().unwrap_or(f64::NAN)),
+        Value::Bool(b) => FieldValue::Bool(*b),
+        Value::Null => FieldValue::Null,
+        Value::String(s) => match parse_iso8601(s).filter(|_| temporal) {
+            Some(ts) => FieldValue::Timestamp(ts),
+            None => FieldValue::Text(s.clone()),
+        },
+        _ => return Err(format!("unsupported filter value {value}")),
+    };
+    Ok(Expr::Literal(v))
+}
+
+fn aggregate_op(name: &str) -> Option<AggregateOp> {
+    Some(match name {
+        "count" => AggregateOp::Count,
+        "distinct" => AggregateOp::Distinct,
+        "sum" => AggregateOp::Sum,
+        "mean" | "average" => AggregateOp::Mean,
+        "median" => AggregateOp::Median,
+        "min" => AggregateOp::Min,
+        "max" => AggregateOp::Max,
+        "q1" => AggregateOp::Quantile(0.25),
+        "q3" => AggregateOp::Quantile(0.75),
+        _ => return None,
+    })
+}
+
+/// Expression deriving a time unit from `field`, with the resulting type:
+/// truncating units stay temporal, single parts become ordinal numbers.
+fn time_unit_expr(unit: &str, field: &str) -> Option<(Expr, DataType)> {
+    let part = |f: Function| Expr::Call(f, vec![Expr::Field(field.to_owned())]);
+    let num = |n: f64| Expr::Literal(FieldValue::Numeric(n));
+    let date = |y: Expr, m: Expr, d: Expr| Expr::Call(Function::Date, vec![y, m, d]);
+    let unit = unit.strip_prefix("utc").unwrap_or(unit);
+    let truncated = match unit {
+        "year" => date(part(Function::Year), num(1.0), num(1.0)),
+        "yearquarter" => {
+            // First month of the quarter: (quarter - 1) * 3 + 1
+            let q = Expr::Binary(
+                BinaryOp::Sub,
+                Box::new(part(Function::Quarter)),
+                Box::new(num(1.0)),
+            );
+            let m = Expr::Binary(
+                BinaryOp::Add,
+                Box::new(Expr::Binary(BinaryOp::Mul, Box::new(q), Box::new(num(3.0)))),
+                Box::new(num(1.0)),
+            );
+            date(part(Function::Year), m, num(1.0))
+        }
+        "yearmonth" => date(part(Function::Year), part(Function::Month), num(1.0)),
+        "yearmonthdate" => date(
+            part(Function::Year),
+            part(Function::Month),
+            part(Function::Day),
+        ),
+        _ => {
+            let f = match unit {
+                "quarter" => Function::Quarter,
+                "month" => Function::Month,
+                "date" => Function::Day,
+                "day" => Function::Weekday,
+                "hours" => Function::Hour,
+                "minutes" => Function::Minute,
+                "seconds" => Function::Second,
+                _ => return None,
+            };
+            return Some((part(f), DataType::Ordinal));
+        }
+    };
+    Some((truncated, DataType::Temporal))
+}
+
+/// Rewrite a Vega expression into lodviz syntax: `datum.x` → `x`,
+/// `datum["a b"]` → `` `a b` ``, `===` / `!==` → `==` / `!=`. String
+/// literals are decoded with JavaScript escapes and re-quoted.
+fn translate_expr(src: &str) -> String {
+    let mut out = String::with_capacity(src.len());
+    let mut rest = src;
+    while let Some(c) = rest.chars().next() {
+        if c == '"' || c == '\'' {
+            match js_string(rest) {
+                Some((value, len)) => {
+                    out.push_str(&quote_str(&value));
+                    rest = &rest[len..];
+                }
+                // Unterminated: left for the parser to report
+                None => {
+                    out.push_str(rest);
+                    rest = "";
+                }
+            }
+        } else if let Some(tail) = rest.strip_prefix("datum.") {
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("datum[") {
+            match js_string(tail).filter(|(_, len)| tail[*len..].starts_with(']')) {
+                Some((name, len)) => {
+                    out.push('`');
+                    out.push_str(&name.replace('`', "``"));
+                    out.push('`');
+                    rest = &tail[len + 1..];
+                }
+                None => {
+                    out.push_str("datum[");
+                    rest = tail;
+                }
+            }
+        } else if let Some(tail) = rest.strip_prefix("===") {
+            out.push_str("==");
+            rest = tail;
+        } else if let Some(tail) = rest.strip_prefix("!==") {
+            out.push_str("!=");
+            rest = tail;
+        } else {
+            out.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    out
+}
+
+/// Value and byte length of the JavaScript string literal opening `src`,
+/// or `None` when `src` does not start with a complete one
+fn js_string(src: &str) -> Option<(String, usize)> {
+    let mut chars = src.char_indices();
+    let quote = chars
+        .next()
+        .map(|(_, q)| q)
+        .filter(|q| *q == '"' || *q == '\'')?;
+    let mut value = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next()?.1 {
+                'n' => value.push('\n'),
+                't' => value.push('\t'),
+                'r' => value.push('\r'),
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).map(|(_, h)| h).collect();
+                    value.push(
+                        u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)?,
+                    );
+                }
+                other => value.push(other),
+            },
+            c if c == quote => return Some((value, i + 1)),
+            c => value.push(c),
+        }
+    }
+    None
+}
+
+/// `value` as a single-quoted lodviz string literal
+fn quote_str(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('\'');
+    for c in value.chars() {
+        match c {
+            '\'' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(text: &str) -> VegaLiteImport {
+        import_vega_lite(text).unwrap()
+    }
+
+    /// The imported spec's drawable data; every import must resolve
+    fn resolved(r: &VegaLiteImport) -> ChartData {
+        r.spec.resolve().unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    #[test]
+    fn test_simple_bar() {
+        let r = import(
+            r#"{
+            "$schema": "https://vega.github.io/schema/vega-lite/v5.json",
+            "title": "Sales",
+            "data": {"values": [{"a": "A", "b": 28}, {"a": "B", "b": 55}]},
+            "mark": "bar",
+            "encoding": {
+                "x": {"field": "a", "type": "nominal"},
+                "y": {"field": "b", "type": "quantitative"}
+            }
+        }"#,
+        );
+        assert!(r.warnings.is_empty(), "{:?}", r.warnings);
+        assert_eq!(r.spec.mark, Mark::Bar);
+        assert_eq!(r.spec.x.name, "a");
+        assert_eq!(r.spec.y.as_ref().unwrap().data_type, DataType::Quantitative);
+        assert_eq!(r.spec.config.title.as_deref(), Some("Sales"));
+        assert!(matches!(resolved(&r), ChartData::Categorical(b) if b.categories == ["A", "B"]));
+    }
+
+    #[test]
+    fn test_distribution_marks() {
+        let r = import(
+            r#"{
+            "data": {"values": [{"g": "A", "v": 1}, {"g": "A", "v": 3}, {"g": "B", "v": 2}]},
+            "mark": {"type": "boxplot"},
+            "encoding": {
+                "x": {"field": "g", "type": "nominal"},
+                "y": {"field": "v", "type": "quantitative"}
+            }
+        }"#,
+        );
+        assert_eq!(r.spec.mark, Mark::Boxplot);
+        assert!(matches!(resolved(&r), ChartData::Groups(g) if g.len() == 2));
+    }
+
+    #[test]
+    fn test_encoding_aggregate_groups_by_other_channels() {
+        let r = import(
+            r#"{
+            "data": {"values": [
+                {"cat": "A", "g": "x", "v": 1}, {"cat": "A", "g": "x", "v": 2},
+                {"cat": "B", "g": "y", "v": 5}
+            ]},
+            "mark": {"type": "bar", "tooltip": true},
+            "encoding": {
+                "x": {"field": "cat", "type": "nominal", "axis": {"labelAngle": 0}},
+                "y": {"field": "v", "aggregate": "sum", "type": "quantitative"},
+                "color": {"field": "g", "type": "nominal"}
+            }
+        }"#,
+        );
+        assert_eq!(r.spec.y.as_ref().unwrap().name, "sum_v");
+        let t = r.spec.transformed_table().unwrap();
+        assert_eq!(t.extract_numeric("sum_v"), vec![3.0, 5.0]);
+        assert!(matches!(resolved(&r), ChartData::Categorical(b) if b.series.len() == 2));
+        let paths: Vec<&str> = r.warnings.iter().map(|w| w.path.as_str()).collect();
+        assert_eq!(paths, vec!["mark.tooltip", "encoding.x.axis"]);
+        assert!(r
+            .warnings
+            .iter()
+            .all(|w| w.kind == WarningKind::Unsupported));
+    }
+
+    #[test]
+    fn test_aggregate_keeps_source_op_name() {
+        let r = import(
+            r#"{
+            "data": {"values": [{"c": "A", "v": 1}, {"c": "A", "v": 3}]},
+            "mark": "bar",
+            "encoding": {
+                "x": {"field": "c", "type": "nominal"},
+                "y": {"field": "v", "aggregate": "average", "type": "quantitative"}
+            }
+        }"#,
+        );
+        assert_eq!(r.spec.y.as_ref().unwrap().name, "average_v");
+        let t = r.spec.transformed_table().unwrap();
+        assert_eq!(t.extract_numeric("average_v"), vec![2.0]);
+        assert!(matches!(resolved(&r), ChartData::Categorical(b) if b.series[0].values == [2.0]));
+    }
+
+    #[test]
+    fn test_count_bin_and_time_unit() {
+        let r = import(
+            r#"{
+            "data": {"values": [
+                {"d": "2024-01-15", "v": 1}, {"d": "2024-01-20", "v": 9},
+                {"d": "2024-02-03", "v": 4}
+            ]},
+            "mark": "line",
+            "encoding": {
+                "x": {"field": "d", "timeUnit": "yearmonth", "type": "temporal"},
+                "y": {"aggregate": "count", "type": "quantitative"}
+            }
+        }"#,
+        );
+        assert_eq!(r.spec.x.name, "yearmonth_d");
+        let t = r.spec.transformed_table().unwrap();
+        assert_eq!(t.extract_numeric("count"), vec![2.0, 1.0]);
+        assert_eq!(
+            t.value(1, "yearmonth_d"),
+            FieldValue::Timestamp(parse_iso8601("2024-02-01").unwrap())
+        );
+        assert!(matches!(resolved(&r), ChartData::TimeSeries(ds) if ds.series[0].data.len() == 2));
+
+        let r = import(
+            r#"{"data": {"values": [{"v": 1}, {"v": 2}, {"v": 10}]},
+                "mark": "bar",
+                "encoding": {"x": {"field": "v", "bin": {"maxbins": 2}},
+                             "y": {"aggregate": "count"}}}"#,
+        );
+        assert_eq!(r.spec.x.bin, Some(BinRule::Fixed(2)));
+        let ChartData::Categorical(bars) = resolved(&r) else {
+            panic!("expected bars");
+        };
+        assert_eq!(bars.categories, vec!["1–5.5", "5.5–10"]);
+        assert_eq!(bars.series[0].values, vec![2.0, 1.0]);
+        // Missing type on the binned field is inferred and reported
+        assert_eq!(r.warnings.len(), 1);
+        assert_eq!(r.warnings[0].kind, WarningKind::Approximated);
+
+        // The standard histogram: `bin: true` with a count
+        let r = import(
+            r#"{"data": {"values": [{"v": 1}, {"v": 2}, {"v": 10}]},
+                "mark": "bar",
+                "encoding": {"x": {"field": "v", "bin": true, "type": "quantitative"},
+                             "y": {"aggregate": "count", "type": "quantitative"}}}"#,
+        );
+        assert!(r.warnings.is_empty(), "{:?}", r.warnings);
+        let ChartData::Categorical(bars) = resolved(&r) else {
+            panic!("expected bars");
+        };
+        assert_eq!(bars.series[0].values.iter().sum::<f64>(), 3.0);
+    }
+
+    #[test]
+    fn test_filter_and_calculate_transforms() {
+        let r = import(
+            r#"{
+            "data": {"values": [{"a": 1, "b c": "x"}, {"a": 5, "b c": "y"}, {"a": 9, "b c": "y"}]},
+            "transform": [
+                {"filter": "datum.a > 2 && datum['b c'] === 'y'"},
+                {"filter": {"field": "a", "range": [0, 6]}},
+                {"calculate": "datum.a * 10", "as": "a10"},
+                {"fold": ["a"]}
+            ],
+            "mark": "point",
+            "encoding": {"x": {"field": "a10", "type": "quantitative"},
+                         "y": {"field": "a", "type": "quantitative"}}
+        }"#,
+        );
+        let t = r.spec.transformed_table().unwrap();
+        assert_eq!(t.extract_numeric("a10"), vec![50.0]);
+        assert!(matches!(resolved(&r), ChartData::TimeSeries(_)));
+        assert_eq!(r.warnings.len(), 1);
+        assert_eq!(r.warnings[0].path, "transform[3]");
+    }
+
+    #[test]
+    fn test_horizontal_bar_is_swapped() {
+        let r = import(
+            r#"{"data": {"values": [{"a": "A", "b": 1}]}, "mark": "bar",
+                "encoding": {"y": {"field": "a", "type": "nominal"},
+                             "x": {"field": "b", "type": "quantitative"}}}"#,
+        );
+        assert_eq!(r.spec.x.name, "a");
+        assert!(matches!(resolved(&r), ChartData::Categorical(_)));
+        assert_eq!(r.warnings[0].kind, WarningKind::Approximated);
+    }
+
+    #[test]
+    fn test_scale_types() {
+        let r = import(
+            r#"{"data": {"values": [{"a": 1, "b": 10}]}, "mark": "point",
+                "encoding": {"x": {"field": "a", "type": "quantitative", "scale": {"type": "sqrt"}},
+                             "y": {"field": "b", "type": "quantitative",
+                                   "scale": {"type": "log", "domain": [1, 100]}},
+                             "color": {"field": "a", "type": "quantitative", "scale": {"type": "log"}}}}"#,
+        );
+        assert_eq!(r.spec.config.x_scale, Some(ScaleType::Sqrt));
+        assert_eq!(r.spec.config.y_scale, Some(ScaleType::Log));
+        assert!(matches!(resolved(&r), ChartData::TimeSeries(_)));
+        let paths: Vec<&str> = r.warnings.iter().map(|w| w.path.as_str()).collect();
+        assert!(paths.contains(&"encoding.y.scale.domain"));
+        assert!(paths.contains(&"encoding.color.scale"));
+    }
+
+    #[test]
+    fn test_formats() {
+        let r = import(
+            r#"{"data": {"values": [{"a": 1, "b": 1500}]}, "mark": "point",
+                "encoding": {"x": {"field": "a", "type": "quantitative", "format": ".1%"},
+                             "y": {"field": "b", "type": "quantitative", "format": "$,.0f"},
+                             "text": {"field": "b", "type": "quantitative", "format": "~s"},
+                             "tooltip": {"field": "a", "type": "quantitative", "format": "?"}}}"#,
+        );
+        assert_eq!(r.spec.config.x_format.as_deref(), Some(".1%"));
+        assert_eq!(r.spec.config.y_format.as_deref(), Some("$,.0f"));
+        assert!(matches!(resolved(&r), ChartData::TimeSeries(_)));
+        assert_eq!(r.spec.text.unwrap().format.as_deref(), Some("~s"));
+        assert_eq!(r.warnings.len(), 1);
+        assert_eq!(r.warnings[0].path, "encoding.tooltip.format");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(import_vega_lite("[]").is_err());
+        let layered = r#"{"data": {"values": []}, "layer": []}"#;
+        assert!(import_vega_lite(layered).unwrap_err().contains("layer"));
+        let arc = r#"{"data": {"values": []}, "mark": "arc", "encoding": {}}"#;
+        assert!(import_vega_lite(arc).unwrap_err().contains("arc"));
+        let url = r#"{"data": {"url": "cars.json"}, "mark": "bar", "encoding": {}}"#;
+        assert!(import_vega_lite(url).unwrap_err().contains("data.url"));
+    }
+
+    #[test]
+    fn test_translate_expr() {
+        assert_eq!(translate_expr("datum.a === 'x'"), "a == 'x'");
+        assert_eq!(translate_expr(r#"datum["a b"] !== 1"#), "`a b` != 1");
+        assert_eq!(translate_expr("'datum.a'"), "'datum.a'");
+        assert_eq!(translate_expr(r"datum.s == 'it\'s'"), r"s == 'it\'s'");
+        assert_eq!(
+            translate_expr(r#"datum.s == "say \"hi\"""#),
+            r#"s == 'say "hi"'"#
+        );
+        assert_eq!(translate_expr(r#"datum["it's"] > 0"#), "`it's` > 0");
+        assert_eq!(translate_expr(r"'caf\u00e9'"), "'café'");
+
+        let r = import(
+            r#"{
+            "data": {"values": [{"s": "it's"}, {"s": "its"}]},
+            "transform": [{"filter": "datum.s === 'it\\'s'"}],
+            "mark": "bar",
+            "encoding": {"x": {"field": "s", "type": "nominal"},
+                         "y": {"aggregate": "count", "type": "quantitative"}}
+        }"#,
+        );
+        assert!(r.warnings.is_empty(), "{:?}", r.warnings);
+        assert_eq!(r.spec.transformed_table().unwrap().len(), 1);
+        assert!(matches!(resolved(&r), ChartData::Categorical(b) if b.categories == ["it's"]));
+    }
+}